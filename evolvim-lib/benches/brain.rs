@@ -27,7 +27,16 @@ mod benches {
         let mut board = get_test_board();
         let creature: &mut SoftBody = &mut board.creatures[0].borrow_mut();
         let brain = &mut creature.brain;
-        let env = Environment::new(&board.terrain, &creature.base);
+        let env = Environment::new(
+            &board.terrain,
+            &creature.base,
+            board.get_time(),
+            &board.climate,
+            &board.soft_bodies_in_positions,
+            &board.pheromones,
+            &board.get_config().vision,
+            board.creatures[0].clone(),
+        );
 
         b.iter(|| {
             brain.run_with(&env);
@@ -39,7 +48,16 @@ mod benches {
         let mut board = get_test_board();
         let creature: &mut SoftBody = &mut board.creatures[0].borrow_mut();
         let brain = &mut creature.brain;
-        let env = Environment::new(&board.terrain, &creature.base);
+        let env = Environment::new(
+            &board.terrain,
+            &creature.base,
+            board.get_time(),
+            &board.climate,
+            &board.soft_bodies_in_positions,
+            &board.pheromones,
+            &board.get_config().vision,
+            board.creatures[0].clone(),
+        );
 
         b.iter(|| brain.load_input(&env));
     }
@@ -49,7 +67,16 @@ mod benches {
         let mut board = get_test_board();
         let creature: &mut SoftBody = &mut board.creatures[0].borrow_mut();
         let brain = &mut creature.brain;
-        let env = Environment::new(&board.terrain, &creature.base);
+        let env = Environment::new(
+            &board.terrain,
+            &creature.base,
+            board.get_time(),
+            &board.climate,
+            &board.soft_bodies_in_positions,
+            &board.pheromones,
+            &board.get_config().vision,
+            board.creatures[0].clone(),
+        );
 
         brain.load_input(&env);
 
@@ -33,7 +33,8 @@ mod benches {
             let time = board.get_time();
 
             for c_rc in &board.creatures {
-                c_rc.borrow_mut().metabolize(TIME_STEP, time);
+                c_rc.borrow_mut()
+                    .metabolize(TIME_STEP, time, board.get_config());
             }
         });
     }
@@ -10,18 +10,36 @@ extern crate rand;
 #[cfg(multithreading)]
 extern crate rayon;
 
+use crate::board_history::BoardHistory;
+use crate::brain::sensors::SensorReadings;
+use crate::brain::vision::Vision;
 use crate::brain::{Brain, GenerateRandom, NeuralNet, RecombinationInfinite};
-use crate::climate::Climate;
+use crate::climate::{Climate, ClimateZones};
+use crate::config::SimConfig;
 use crate::constants::*;
+use crate::disaster::{self, Disaster};
+use crate::egg::{Egg, HLEgg};
+use crate::energy_flow::{Compartment, EnergyFlows};
+use crate::error::EvolvimError;
+use crate::events::{DeathCause, EventHooks, ExtinctionCause};
+use crate::experiment::{HalfStatistics, SplitExperiment};
+use crate::pheromone::PheromoneField;
+use crate::phylogeny::{Phylogeny, PhylogenyRecord};
+use crate::repair::Inconsistency;
+use crate::rng::{self, Stream};
+use crate::run::{RunOptions, RunSummary};
 use crate::sbip::SoftBodiesInPositions;
+use crate::serde_structs::{BoardSerde, BoardSerdeRef, SaveFormat, Version};
+use crate::snapshot::CreatureView;
 use crate::softbody::{HLSoftBody, SoftBody};
-use crate::terrain::Terrain;
+use crate::species::SpeciesTracker;
+use crate::stats::BoardStats;
+use crate::terrain::edit::Brush;
+use crate::terrain::generator::PerlinGenerator;
+use crate::terrain::{Terrain, TerrainAlgorithm, TerrainFill, TerrainGenerator};
+use crate::weather::WeatherEvent;
 
-/// The amount of times a year an object is updated.
-///
-/// TODO: eliminate this variable because it's not needed.
-const OBJECT_TIMESTEPS_PER_YEAR: f64 = 100.0;
-const _POPULATION_HISTORY_LENGTH: usize = 200;
+const SEASONS: [&str; 4] = ["Winter", "Spring", "Summer", "Autumn"];
 
 pub type BoardSize = (usize, usize);
 pub type BoardCoordinate = (usize, usize);
@@ -42,36 +60,135 @@ impl From<BoardPreciseCoordinate> for BoardCoordinate {
     }
 }
 
-pub struct SelectedCreature<B: NeuralNet>(pub Option<HLSoftBody<B>>);
+/// The creatures the user is looking at, remembered by their id.
+///
+/// Any amount of creatures can be selected at once to compare them, the one selected last is the primary one which gets
+/// the most attention (e.g. its details are drawn). Ids stay the same when a board is saved and loaded, handles don't.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SelectedCreature {
+    /// The selected ids in the order they were selected, the primary one last.
+    ids: Vec<usize>,
+}
 
-impl<B: NeuralNet> Default for SelectedCreature<B> {
-    fn default() -> Self {
-        SelectedCreature(None)
+impl SelectedCreature {
+    /// Removes the creature with the given id from the selection, call this when it dies.
+    pub fn unselect_if_dead(&mut self, id: usize) {
+        self.ids.retain(|&i| i != id);
+    }
+
+    /// Selects only the creature with the given id.
+    pub fn select(&mut self, id: usize) {
+        self.ids.clear();
+        self.ids.push(id);
+    }
+
+    /// Adds the creature with the given id to the selection and makes it the primary one.
+    pub fn add(&mut self, id: usize) {
+        self.ids.retain(|&i| i != id);
+        self.ids.push(id);
+    }
+
+    /// Adds the creature with the given id to the selection, or removes it if it was already selected.
+    pub fn toggle(&mut self, id: usize) {
+        if self.is_selected(id) {
+            self.unselect_if_dead(id);
+        } else {
+            self.ids.push(id);
+        }
+    }
+
+    pub fn deselect(&mut self) {
+        self.ids.clear();
+    }
+
+    pub fn is_selected(&self, id: usize) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Returns the id of the creature that was selected last.
+    pub fn get_primary(&self) -> Option<usize> {
+        self.ids.last().cloned()
+    }
+
+    /// Returns the ids of all selected creatures, the primary one last.
+    pub fn get_ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
     }
 }
 
-impl<B: NeuralNet> SelectedCreature<B> {
-    /// Checks if the given creature was selected and if so, removes it by setting `self.0` to `None`.
-    pub fn unselect_if_dead(&mut self, creature: HLSoftBody<B>) {
-        if let Some(sel_creature) = &self.0 {
-            // If `creature` isn't the same as `self.selected_creature`.
-            if *sel_creature != creature {
-                // Then don't change to `None`.
-                return;
-            }
+/// A way of comparing creatures, used to sort them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CreatureMetric {
+    Age,
+    Energy,
+    Offspring,
+    /// The distance (in tiles) a creature moved since it was born.
+    Distance,
+}
 
-            // Else go on
+impl CreatureMetric {
+    /// Returns the value of this metric for the given creature, higher is "better".
+    pub fn get_value<B>(&self, creature: &SoftBody<B>, time: f64) -> f64 {
+        match self {
+            CreatureMetric::Age => creature.get_age(time),
+            CreatureMetric::Energy => creature.get_energy(),
+            CreatureMetric::Offspring => creature.get_offspring_count() as f64,
+            CreatureMetric::Distance => creature.get_distance_traveled(),
         }
+    }
 
-        self.0 = None;
+    /// Returns the metric after this one, cycling back to the first.
+    pub fn next(&self) -> Self {
+        match self {
+            CreatureMetric::Age => CreatureMetric::Energy,
+            CreatureMetric::Energy => CreatureMetric::Offspring,
+            CreatureMetric::Offspring => CreatureMetric::Distance,
+            CreatureMetric::Distance => CreatureMetric::Age,
+        }
     }
+}
 
-    pub fn select(&mut self, creature: HLSoftBody<B>) {
-        self.0 = Some(creature);
+/// Where the brains of the creatures spawned to keep up the creature minimum come from.
+///
+/// Whenever there is nothing to pick from (no survivors or an empty seed bank) creatures get a random brain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpawnPolicy {
+    /// A random brain, or one from the brain factory if the board has one.
+    Random,
+    /// An exact copy of the brain of a random living creature.
+    CloneRandomSurvivor,
+    /// The brain of a random living creature with the mutations it would give a baby.
+    MutatedClone,
+    /// A copy of a random brain from the seed bank, see `Board::add_to_seed_bank`.
+    FromSeedBank,
+}
+
+impl Default for SpawnPolicy {
+    fn default() -> Self {
+        SpawnPolicy::Random
     }
+}
 
-    pub fn deselect(&mut self) {
-        self.0 = None;
+/// What a `Board` does when its population dies out, see `ExtinctionCause`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExtinctionPolicy {
+    /// Nothing special, the creature minimum is kept up as usual.
+    Continue,
+    /// Replaces all creatures by creatures with brains from the seed bank, see `Board::add_to_seed_bank`.
+    ReseedFromSeedBank,
+    /// Generates new terrain and replaces all creatures by random ones.
+    RestartTerrain,
+    /// Pauses the board until `Board::resume` is called.
+    Halt,
+}
+
+impl Default for ExtinctionPolicy {
+    fn default() -> Self {
+        ExtinctionPolicy::Continue
     }
 }
 
@@ -80,60 +197,127 @@ pub struct Board<B: NeuralNet = Brain> {
     board_width: usize,
     board_height: usize,
     pub terrain: Terrain,
+    config: SimConfig,
 
     // Fields relevant for the creatures.
-    creature_minimum: usize,
     pub soft_bodies_in_positions: SoftBodiesInPositions<B>,
     pub creatures: Vec<HLSoftBody<B>>,
     creature_id_up_to: usize,
-    // _creature_rank_metric: usize,
+    phylogeny: Phylogeny,
+    species: SpeciesTracker,
+    eggs: Vec<HLEgg<B>>,
 
     // Fields relevant for time or history
     year: f64,
 
     // Fields relevant for temperature
     pub climate: Climate,
+    split_experiment: Option<SplitExperiment>,
+
+    // Fields relevant for communication
+    pub pheromones: PheromoneField,
 
     // Miscelanious
-    pub selected_creature: SelectedCreature<B>,
+    pub selected_creature: SelectedCreature,
+
+    // Statistics, not saved.
+    births: usize,
+    deaths_by_starvation: usize,
+    deaths_by_predation: usize,
+    deaths_by_old_age: usize,
+    eggs_eaten: usize,
+    energy_flows: EnergyFlows,
+    tile_food: f64,
+    history: BoardHistory,
+
+    // Callbacks for events, not saved.
+    hooks: EventHooks<B>,
+
+    // Creates the brains of random creatures, not saved.
+    brain_factory: Option<Box<Fn(&Vision) -> B>>,
+    // Where the brains of creatures spawned to keep up the creature minimum come from, not saved.
+    spawn_policy: SpawnPolicy,
+    seed_bank: Vec<B>,
+
+    // What happens when the population dies out, not saved.
+    extinction_policy: ExtinctionPolicy,
+    had_creatures: bool,
+    had_descendants: bool,
+
+    // Whether `update` is ignored, not saved.
+    paused: bool,
+
+    // The random number generator of a seeded board, not saved.
+    rng: Option<rng::Streams>,
 }
 
 impl<B: NeuralNet + GenerateRandom> Default for Board<B> {
     fn default() -> Self {
-        let board_size = DEFAULT_BOARD_SIZE;
-        let noise_step_size = DEFAULT_NOISE_STEP_SIZE;
-        let creature_minimum = DEFAULT_CREATURE_MINIMUM;
-        let min_temp = DEFAULT_MIN_TEMP;
-        let max_temp = DEFAULT_MAX_TEMP;
-
-        return Board::new_random(
-            board_size,
-            noise_step_size,
-            creature_minimum,
-            min_temp,
-            max_temp,
-        );
+        Self::generate_random(DEFAULT_BOARD_SIZE, SimConfig::default(), None)
     }
 }
 
 impl<B: NeuralNet> Board<B> {
-    pub fn new(board_width: usize, board_height: usize, terrain: Terrain, creature_minimum: usize, soft_bodies_in_positions: SoftBodiesInPositions<B>,
-    creatures: Vec<HLSoftBody<B>>, creature_id_up_to: usize, year: f64, climate: Climate, selected_creature: SelectedCreature<B>) -> Board<B>{
+    pub fn new(board_width: usize, board_height: usize, terrain: Terrain, config: SimConfig, soft_bodies_in_positions: SoftBodiesInPositions<B>,
+    creatures: Vec<HLSoftBody<B>>, creature_id_up_to: usize, year: f64, climate: Climate, pheromones: PheromoneField, selected_creature: SelectedCreature) -> Board<B>{
         Board {
             board_width,
             board_height,
+            tile_food: terrain.get_total_food(),
             terrain,
+            config,
 
-            creature_minimum,
             soft_bodies_in_positions,
             creatures,
             creature_id_up_to,
+            phylogeny: Phylogeny::new(),
+            species: SpeciesTracker::new(),
+            eggs: Vec::new(),
 
             year,
 
             climate,
+            split_experiment: None,
+
+            pheromones,
 
             selected_creature,
+
+            births: 0,
+            deaths_by_starvation: 0,
+            deaths_by_predation: 0,
+            deaths_by_old_age: 0,
+            eggs_eaten: 0,
+            energy_flows: EnergyFlows::new(),
+            history: BoardHistory::default(),
+
+            hooks: EventHooks::default(),
+
+            brain_factory: None,
+            spawn_policy: SpawnPolicy::default(),
+            seed_bank: Vec::new(),
+
+            extinction_policy: ExtinctionPolicy::default(),
+            had_creatures: false,
+            had_descendants: false,
+
+            paused: false,
+
+            rng: None,
+        }
+    }
+
+    /// Calls `f` with the random numbers of this board if it's seeded.
+    pub(crate) fn with_own_rng<T, F: FnOnce(&mut Self) -> T>(&mut self, f: F) -> T {
+        match self.rng.take() {
+            Some(own_rng) => {
+                // Use the random numbers of this board only.
+                let previous = rng::replace(own_rng);
+                let result = f(self);
+                self.rng = Some(rng::replace(previous));
+                result
+            }
+            None => f(self),
         }
     }
 }
@@ -147,28 +331,147 @@ impl<B: NeuralNet + GenerateRandom> Board<B> {
         min_temp: f64,
         max_temp: f64,
     ) -> Self {
-        let creatures = Vec::with_capacity(creature_minimum);
+        Self::generate_random(
+            board_size,
+            SimConfig::from_parameters(noise_step_size, creature_minimum, min_temp, max_temp),
+            None,
+        )
+    }
+
+    /// Randomly generates a new `Board` with the given settings.
+    pub fn new_random_with_config(
+        board_size: BoardSize,
+        config: SimConfig,
+    ) -> Result<Self, EvolvimError> {
+        config.validate()?;
+
+        Ok(Self::generate_random(board_size, config, None))
+    }
+
+    /// Randomly generates a new `Board` where all random creatures get their brain from `brain_factory`.
+    ///
+    /// This is how a `Board<AnyBrain>` can start out with different kinds of brains.
+    pub fn new_random_with_brain_factory(
+        board_size: BoardSize,
+        noise_step_size: f64,
+        creature_minimum: usize,
+        min_temp: f64,
+        max_temp: f64,
+        brain_factory: Box<Fn(&Vision) -> B>,
+    ) -> Self {
+        Self::generate_random(
+            board_size,
+            SimConfig::from_parameters(noise_step_size, creature_minimum, min_temp, max_temp),
+            Some(brain_factory),
+        )
+    }
+
+    /// Randomly generates a new `Board` that only uses random numbers generated from `seed`.
+    ///
+    /// Two boards with the same seed and settings are exactly the same and stay that way as long as they're updated in
+    /// the same way.
+    pub fn new_random_seeded(
+        board_size: BoardSize,
+        noise_step_size: f64,
+        creature_minimum: usize,
+        min_temp: f64,
+        max_temp: f64,
+        seed: u64,
+    ) -> Self {
+        Self::generate_random_seeded(
+            board_size,
+            SimConfig::from_parameters(noise_step_size, creature_minimum, min_temp, max_temp),
+            seed,
+        )
+    }
+
+    /// Randomly generates a new `Board` with the given settings that only uses random numbers generated from `seed`,
+    /// see `new_random_seeded`.
+    pub fn new_random_with_config_seeded(
+        board_size: BoardSize,
+        config: SimConfig,
+        seed: u64,
+    ) -> Result<Self, EvolvimError> {
+        config.validate()?;
+
+        Ok(Self::generate_random_seeded(board_size, config, seed))
+    }
+
+    fn generate_random_seeded(board_size: BoardSize, config: SimConfig, seed: u64) -> Self {
+        let previous = rng::replace(rng::from_seed(seed));
+        let mut board = Self::generate_random(board_size, config, None);
+        board.rng = Some(rng::replace(previous));
+
+        board
+    }
+
+    /// From now on only use random numbers generated from `seed`, e.g. to continue a loaded board reproducibly.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Some(rng::from_seed(seed));
+    }
+
+    fn generate_random(
+        board_size: BoardSize,
+        config: SimConfig,
+        brain_factory: Option<Box<Fn(&Vision) -> B>>,
+    ) -> Self {
+        let creatures = Vec::with_capacity(config.creature_minimum);
 
         // Initialize climate.
-        let mut climate = Climate::new(min_temp, max_temp);
+        let mut climate = Climate::new(config.min_temp, config.max_temp);
+        climate.set_day_length(config.day_length);
+        climate.set_latitude_cooling(config.latitude_cooling);
+        climate.set_drift(config.drift.clone());
+        climate.set_oscillation(config.oscillation.clone());
         climate.update(0.0);
 
         let mut board = Board {
             board_width: board_size.0,
             board_height: board_size.1,
-            terrain: Terrain::generate_perlin(board_size, noise_step_size),
+            terrain: generate_terrain(&config, board_size),
+            config,
 
-            creature_minimum,
             soft_bodies_in_positions: SoftBodiesInPositions::new_allocated(board_size),
             creatures,
             creature_id_up_to: 0,
+            phylogeny: Phylogeny::new(),
+            species: SpeciesTracker::new(),
+            eggs: Vec::new(),
 
             year: 0.0,
 
             climate,
+            split_experiment: None,
+
+            pheromones: PheromoneField::new(board_size),
 
             selected_creature: SelectedCreature::default(),
+
+            births: 0,
+            deaths_by_starvation: 0,
+            deaths_by_predation: 0,
+            deaths_by_old_age: 0,
+            eggs_eaten: 0,
+            energy_flows: EnergyFlows::new(),
+            tile_food: 0.0,
+            history: BoardHistory::default(),
+
+            hooks: EventHooks::default(),
+
+            brain_factory,
+            spawn_policy: SpawnPolicy::default(),
+            seed_bank: Vec::new(),
+
+            extinction_policy: ExtinctionPolicy::default(),
+            had_creatures: false,
+            had_descendants: false,
+
+            paused: false,
+
+            rng: None,
         };
+        board.apply_climate_zones();
+        board.tile_food = board.terrain.get_total_food();
 
         // Initialize creatures.
         board.maintain_creature_minimum();
@@ -176,54 +479,369 @@ impl<B: NeuralNet + GenerateRandom> Board<B> {
         return board;
     }
 
-    /// Maintains the creature minimum by adding random creatures until there are at least `self.creature_minimum` creatures.
+    /// Makes the random creatures added to keep up the creature minimum get their brain from `factory`.
+    ///
+    /// The factory is given the `Vision` of the board, the brains it makes should fit it. Without a factory
+    /// `GenerateRandom::new_random_with_vision` is used. Creatures that are already alive keep their brain, use
+    /// `new_random_with_brain_factory` to also control the brains of the first creatures.
+    pub fn set_brain_factory(&mut self, factory: Box<Fn(&Vision) -> B>) {
+        self.brain_factory = Some(factory);
+    }
+
+    /// Maintains the creature minimum by adding random creatures until there are at least `config.creature_minimum` creatures.
     ///
     /// # Processing equivalent
     /// This function is the equivalent of *Board.pde/maintainCreatureMinimum* with *choosePreexisting* set to false.
     fn maintain_creature_minimum(&mut self) {
-        while self.creatures.len() < self.creature_minimum {
-            let board_size = self.get_board_size();
-            let creature = HLSoftBody::from(SoftBody::new_random(board_size, self.year));
+        self.maintain_creature_minimum_with(|_| None);
+    }
+
+    /// Like `maintain_creature_minimum` but the new creatures get the brain `spawn_brain` returns, if any.
+    ///
+    /// Without one they get a brain from the brain factory or a random brain.
+    fn maintain_creature_minimum_with<F: FnMut(&Self) -> Option<B>>(&mut self, mut spawn_brain: F) {
+        rng::with_stream(Stream::Spawning, || {
+            while self.creatures.len() < self.config.creature_minimum {
+                let board_size = self.get_board_size();
+                let creature = HLSoftBody::from(match (spawn_brain(self), &self.brain_factory) {
+                    (Some(brain), _) => {
+                        SoftBody::new_random_with_brain(board_size, self.year, brain)
+                    }
+                    (None, Some(factory)) => SoftBody::new_random_with_brain(
+                        board_size,
+                        self.year,
+                        factory(&self.config.vision),
+                    ),
+                    (None, None) => SoftBody::new_random_with_brain(
+                        board_size,
+                        self.year,
+                        B::new_random_with_vision(&self.config.vision),
+                    ),
+                });
+
+                if self.config.spawn_on_land {
+                    let mut c = creature.borrow_mut();
+                    let pos = c.get_position().into();
+                    if let Some((x, y)) = self.terrain.nearest_land(pos) {
+                        if (x, y) != pos {
+                            c.set_body_x(x as f64 + 0.5, board_size.0);
+                            c.set_body_y(y as f64 + 0.5, board_size.1);
+                        }
+                    }
+                }
+
+                // Initialize in `SoftBodiesInPositions` as well.
+                creature.add_to_sbip(&mut self.soft_bodies_in_positions, board_size);
+
+                let mut c = creature.borrow_mut();
+                c.set_id(self.creature_id_up_to);
+                self.energy_flows.add(
+                    Compartment::Immigration,
+                    c.get_compartment(),
+                    c.get_energy(),
+                );
+                self.phylogeny.record_birth(&c);
+                drop(c);
 
-            // Initialize in `SoftBodiesInPositions` as well.
-            creature.set_sbip(&mut self.soft_bodies_in_positions, board_size);
-            // Just to set the prevSBIP variables.
-            creature.set_sbip(&mut self.soft_bodies_in_positions, board_size);
+                self.creatures.push(creature);
+                self.creature_id_up_to += 1;
+            }
+        });
+    }
 
-            self.creatures.push(creature);
-            self.creature_id_up_to += 1;
+    /// Removes all creatures and adds new ones with the given brains, see `Generations`.
+    ///
+    /// There will be `config.creature_minimum` new creatures, if there are too few brains the rest get a random one.
+    pub(crate) fn replace_creatures(&mut self, brains: Vec<B>) {
+        for c in &self.creatures {
+            let id = c.borrow().get_id();
+            self.phylogeny.record_death(id, self.year);
+            self.species.remove_creature(id);
         }
+        self.creatures.clear();
+        self.eggs.clear();
+        self.soft_bodies_in_positions = SoftBodiesInPositions::new_allocated(self.get_board_size());
+        self.selected_creature.deselect();
+
+        let mut brains = brains.into_iter();
+        self.maintain_creature_minimum_with(|_| brains.next());
     }
 }
 
-impl<B: NeuralNet + RecombinationInfinite + GenerateRandom> Board<B> {
+impl<B: NeuralNet + RecombinationInfinite + GenerateRandom + Clone> Board<B> {
+    /// Advances the simulation by `time_step` years, unless the board is paused.
     pub fn update(&mut self, time_step: f64) {
+        if !self.paused {
+            self.step_once(time_step);
+        }
+    }
+
+    /// Advances the simulation by `time_step` years, even if the board is paused.
+    ///
+    /// Call this on a paused board to step through the simulation one update at a time.
+    pub fn step_once(&mut self, time_step: f64) {
+        self.with_own_rng(|board| board.step(time_step));
+    }
+
+    /// Makes `update` and `run_for` do nothing until `resume` is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Lets a paused board run again.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns true if the board is paused, by `pause` or by `ExtinctionPolicy::Halt`.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Advances the simulation by `years` years as fast as possible and returns what happened.
+    ///
+    /// Every update takes `options.time_step` years, `years` is rounded up to a whole amount of updates. Nothing is
+    /// prepared for drawing, call `prepare_for_drawing` afterwards if the board will be drawn again.
+    pub fn run_for(&mut self, years: f64, options: &RunOptions) -> RunSummary {
+        assert!(options.time_step > 0.0, "The time step has to be positive.");
+
+        let start = std::time::Instant::now();
+        let population = self.get_population_size();
+        let deaths = self.get_deaths();
+        let mut summary = RunSummary {
+            start_time: self.year,
+            start_population: population,
+            min_population: population,
+            max_population: population,
+            ..RunSummary::default()
+        };
+        let births = self.births;
+        // Don't let rounding errors add an update.
+        let updates = (years / options.time_step - 1e-9).ceil().max(0.0) as usize;
+        let mut total_population = 0;
+
+        // Swap in the random numbers of a seeded board once instead of every update.
+        let own_rng = self.rng.take();
+        let previous = own_rng.map(rng::replace);
+
+        for _ in 0..updates {
+            if options.should_abort() {
+                summary.aborted = true;
+                break;
+            }
+            if self.paused {
+                summary.paused = true;
+                break;
+            }
+
+            self.step(options.time_step);
+
+            let population = self.get_population_size();
+            summary.updates += 1;
+            summary.min_population = summary.min_population.min(population);
+            summary.max_population = summary.max_population.max(population);
+            total_population += population;
+        }
+
+        if let Some(previous) = previous {
+            self.rng = Some(rng::replace(previous));
+        }
+
+        summary.end_time = self.year;
+        summary.end_population = self.get_population_size();
+        if summary.updates > 0 {
+            summary.mean_population = total_population as f64 / summary.updates as f64;
+        }
+        summary.births = self.births - births;
+        summary.deaths = self.get_deaths() - deaths;
+        summary.creature_energy = self.creatures.iter().map(|c| c.borrow().get_energy()).sum();
+        summary.duration = start.elapsed();
+
+        summary
+    }
+
+    fn step(&mut self, time_step: f64) {
+        self.energy_flows = EnergyFlows::new();
+        let previous_season = get_season_index(self.year);
         self.year += time_step;
         self.climate.update(self.year);
 
+        let season = get_season_index(self.year);
+        if season != previous_season {
+            self.hooks.season_change(self.year, SEASONS[season]);
+        }
+
+        // Weather changes how fast the food grows, so the tiles are updated whenever it starts or ends.
+        let weather = self
+            .climate
+            .update_weather(self.year, time_step, &self.config.weather);
+        if self
+            .climate
+            .has_weather_changed(self.year, self.year - time_step)
+        {
+            self.terrain.update_all_lazily(self.year);
+        }
+        if let Some(weather) = weather {
+            self.hooks.weather(self.year, &weather);
+        }
+
+        self.terrain.heal_scars(self.year);
+        let disaster = self
+            .config
+            .disasters
+            .roll(self.year, time_step, self.get_board_size());
+        if let Some(disaster) = disaster {
+            self.strike(&disaster);
+            self.hooks.disaster(self.year, &disaster);
+        }
+
+        // Days and nights are left out, the food only needs an update when the season turns around.
+        let season_temperature = self.climate.get_growth_rate(self.year);
         let temp_change_into_frame =
-            self.climate.get_temperature() - self.climate.get_growth_rate(self.year - time_step);
+            season_temperature - self.climate.get_growth_rate(self.year - time_step);
         let temp_change_out_of_frame =
-            self.climate.get_growth_rate(self.year + time_step) - self.climate.get_temperature();
+            self.climate.get_growth_rate(self.year + time_step) - season_temperature;
 
         if temp_change_into_frame * temp_change_out_of_frame < 0.0 {
             // Temperature change flipped direction
-            self.terrain.update_all(self.year, &self.climate);
+            self.terrain.update_all_lazily(self.year);
+        }
+
+        // The tiles creatures eat from have to be up to date.
+        for c in &self.creatures {
+            let c = c.borrow();
+            self.terrain
+                .catch_up_at(c.current_x_range(), c.current_y_range(), &self.climate);
         }
 
         self.update_creatures(time_step);
+        self.creatures_eat_eggs();
+
+        // Let the pheromones spread out and fade away.
+        self.pheromones.update(time_step);
 
         // Kill weak creatures.
         self.remove_dead_creatures();
 
         // Let creatures reproduce
         self.creatures_reproduce();
+        self.hatch_eggs();
+
+        self.handle_extinction();
 
         // Experimental: this was moved from above to always keep the creature minimum.
-        self.maintain_creature_minimum();
+        self.maintain_creature_minimum_with(Self::get_spawn_brain);
+        self.had_creatures = !self.creatures.is_empty();
 
         // Move the creatures around on the board
         self.move_creatures(time_step);
+
+        // Swimming costs energy so this has to come after moving.
+        self.collect_energy_flows();
+
+        self.species
+            .tick(&self.creatures, self.year, &self.config.species);
+
+        let mut history = std::mem::replace(&mut self.history, BoardHistory::default());
+        // If spilling to disk fails the samples stay in memory and it's tried again next time.
+        let _ = history.tick(self);
+        self.history = history;
+    }
+}
+
+impl<B: NeuralNet + RecombinationInfinite + GenerateRandom + Clone> Board<B> {
+    /// Replaces all creatures by creatures with random brains from the seed bank, see `Board::add_to_seed_bank`.
+    ///
+    /// There will be `config.creature_minimum` new creatures, they all get a random brain if the seed bank is empty.
+    pub fn reseed_from_seed_bank(&mut self) {
+        self.with_own_rng(|board| {
+            let bank = &board.seed_bank;
+            let brains = if bank.is_empty() {
+                Vec::new()
+            } else {
+                rng::with_stream(Stream::Spawning, || {
+                    (0..board.config.creature_minimum)
+                        .map(|_| bank[rng::gen_range(0, bank.len())].clone())
+                        .collect()
+                })
+            };
+
+            board.replace_creatures(brains);
+        });
+    }
+
+    /// Finds out if the population died out since the last update and acts according to `self.extinction_policy`.
+    fn handle_extinction(&mut self) {
+        let has_creatures = !self.creatures.is_empty();
+        let has_descendants = self
+            .creatures
+            .iter()
+            .any(|c| !c.borrow().get_parent_ids().is_empty());
+
+        let cause = if self.had_creatures && !has_creatures {
+            Some(ExtinctionCause::NoCreatures)
+        } else if self.had_descendants && !has_descendants {
+            Some(ExtinctionCause::NoDescendants)
+        } else {
+            None
+        };
+        self.had_descendants = has_descendants;
+
+        let cause = match cause {
+            Some(cause) => cause,
+            None => return,
+        };
+        self.hooks.extinction(self.year, cause);
+
+        match self.extinction_policy {
+            ExtinctionPolicy::Continue => {}
+            ExtinctionPolicy::ReseedFromSeedBank => self.reseed_from_seed_bank(),
+            ExtinctionPolicy::RestartTerrain => {
+                let board_size = self.get_board_size();
+                self.terrain = generate_terrain(&self.config, board_size);
+                self.apply_climate_zones();
+                self.tile_food = self.terrain.get_total_food();
+                self.pheromones = PheromoneField::new(board_size);
+                if let Some(split) = self.split_experiment.clone() {
+                    split.apply_to(self);
+                }
+
+                self.replace_creatures(Vec::new());
+            }
+            ExtinctionPolicy::Halt => self.paused = true,
+        }
+    }
+}
+
+impl<B: NeuralNet + RecombinationInfinite + Clone> Board<B> {
+    /// Returns the brain of a creature spawned to keep up the creature minimum according to `self.spawn_policy`.
+    ///
+    /// Returns `None` if it should get a random brain, which is also the fallback if there is nothing to pick from.
+    fn get_spawn_brain(&self) -> Option<B> {
+        let random_survivor = || {
+            if self.creatures.is_empty() {
+                None
+            } else {
+                Some(&self.creatures[rng::gen_range(0, self.creatures.len())])
+            }
+        };
+
+        match self.spawn_policy {
+            SpawnPolicy::Random => None,
+            SpawnPolicy::CloneRandomSurvivor => random_survivor().map(|c| c.borrow().brain.clone()),
+            SpawnPolicy::MutatedClone => random_survivor().map(|c| {
+                let c = c.borrow();
+                B::recombination_infinite_brains(&[&c.brain])
+            }),
+            SpawnPolicy::FromSeedBank => {
+                if self.seed_bank.is_empty() {
+                    None
+                } else {
+                    let i = rng::gen_range(0, self.seed_bank.len());
+                    Some(self.seed_bank[i].clone())
+                }
+            }
+        }
     }
 }
 
@@ -235,68 +853,343 @@ impl<B: NeuralNet + RecombinationInfinite> Board<B> {
         {
             let time = self.get_time();
             let board_size = self.get_board_size();
+            let mode = self.config.reproduction;
+            let incubation = self.config.egg_incubation;
+            let eggs = &mut self.eggs;
             let sbip = &mut self.soft_bodies_in_positions;
 
-            for c in &mut self.creatures {
-                let maybe_baby = c.try_reproduce(time, sbip, board_size);
-                if let Some(baby) = maybe_baby {
-                    babies.push(baby);
+            let creatures = &mut self.creatures;
+            rng::with_stream(Stream::Mutation, || {
+                for c in creatures {
+                    let maybe_baby = c.try_reproduce(time, sbip, board_size, mode);
+                    match maybe_baby {
+                        Some(mut baby) if incubation > 0.0 => {
+                            // It stays in the egg until it hatches.
+                            baby.remove_from_sbip(sbip);
+                            let egg = HLEgg::from(Egg::new(baby.into_inner(), time, incubation));
+                            egg.add_to_sbip(sbip);
+                            eggs.push(egg);
+                        }
+                        Some(baby) => babies.push(baby),
+                        None => {}
+                    }
+                }
+            });
+        }
+
+        for c in babies {
+            self.add_baby(c);
+        }
+    }
+}
+
+impl<B: NeuralNet> Board<B> {
+    /// Gives a newborn (or newly hatched) baby its id and puts it on the board, it should already be in
+    /// `SoftBodiesInPositions`.
+    fn add_baby(&mut self, c: HLSoftBody<B>) {
+        c.borrow_mut().set_id(self.creature_id_up_to);
+        self.creature_id_up_to += 1;
+        self.births += 1;
+        self.phylogeny.record_birth(&c.borrow());
+        if let Some(&parent) = c.borrow().get_parent_ids().first() {
+            self.species.add_baby(c.borrow().get_id(), parent);
+        }
+        self.hooks.birth(self.year, &c.borrow());
+
+        self.creatures.push(c);
+    }
+
+    /// Puts the babies of all eggs that are ready on the board.
+    fn hatch_eggs(&mut self) {
+        let time = self.year;
+        let board_size = self.get_board_size();
+
+        let (ready, waiting) = std::mem::replace(&mut self.eggs, Vec::new())
+            .into_iter()
+            .partition(|egg: &HLEgg<B>| egg.is_ready_to_hatch(time));
+        self.eggs = waiting;
+
+        for egg in ready {
+            egg.remove_from_sbip(&mut self.soft_bodies_in_positions);
+            let baby = HLSoftBody::from(egg.into_inner().hatch(time));
+            baby.add_to_sbip(&mut self.soft_bodies_in_positions, board_size);
+            self.add_baby(baby);
+        }
+    }
+
+    /// Lets creatures that are eating eat the eggs on the tiles they cover, except for their own.
+    fn creatures_eat_eggs(&mut self) {
+        let sbip = &mut self.soft_bodies_in_positions;
+        let mut eaten = Vec::new();
+
+        for c_rc in &self.creatures {
+            let mut c = c_rc.borrow_mut();
+            if c.get_energy_eaten() <= 0.0 {
+                continue;
+            }
+
+            for egg in sbip.get_eggs_in(c.current_x_range(), c.current_y_range()) {
+                if !egg.get_parent_ids().contains(&c.get_id()) {
+                    c.eat_egg(egg.get_energy());
+                    egg.remove_from_sbip(sbip);
+                    eaten.push(egg);
                 }
             }
         }
 
-        babies.into_iter().for_each(|c| self.creatures.push(c));
+        self.eggs.retain(|egg| !eaten.contains(egg));
+        self.eggs_eaten += eaten.len();
     }
 }
 
 impl<B: NeuralNet> Board<B> {
     /// Selects the oldest creature still alive.
     pub fn select_oldest(&mut self) {
-        let oldest = self.creatures.iter().fold(&self.creatures[0], |c_old, c| {
-            if c.borrow().get_birth_time() < c_old.borrow().get_birth_time() {
-                &c
-            } else {
-                c_old
-            }
-        });
-
-        self.selected_creature.select(oldest.clone());
+        self.select_best(CreatureMetric::Age);
     }
 
     /// Selects the biggest creature.
     pub fn select_biggest(&mut self) {
-        let biggest = self.creatures.iter().fold(&self.creatures[0], |c_old, c| {
-            if c.borrow().get_energy() > c_old.borrow().get_energy() {
-                &c
-            } else {
-                c_old
-            }
-        });
+        self.select_best(CreatureMetric::Energy);
+    }
+
+    /// Selects the creature that scores highest on the given metric, does nothing if there are no creatures.
+    pub fn select_best(&mut self, metric: CreatureMetric) {
+        let best = self.get_leaderboard(metric, 1);
 
-        self.selected_creature.select(biggest.clone());
+        if let Some(c) = best.first() {
+            let id = c.borrow().get_id();
+            self.selected_creature.select(id);
+        }
     }
 
-    #[cfg(not(multithreading))]
-    fn update_brains(&mut self) {
+    /// Returns the living creature with the given id, if there is one.
+    pub fn get_creature_by_id(&self, id: usize) -> Option<HLSoftBody<B>> {
         self.creatures
             .iter()
-            .map(|c| c.borrow_mut())
-            .for_each(|mut c| {
-                let creature: &mut SoftBody<B> = &mut c;
-                let env = crate::brain::Environment::new(&self.terrain, &creature.base);
-                creature.brain.run_with(&env);
-            });
+            .find(|c| c.borrow().get_id() == id)
+            .cloned()
+    }
+
+    /// Returns the living creatures with their center at most `radius` away from `center`, closest first.
+    ///
+    /// Only the tiles around `center` are searched, so this is fast even on a big board.
+    pub fn get_creatures_within(&self, center: (f64, f64), radius: f64) -> Vec<HLSoftBody<B>> {
+        let (width, height) = self.get_board_size();
+        let to_range = |center: f64, size: usize| {
+            let start = (center - radius).floor().max(0.0) as usize;
+            let end = ((center + radius).floor() + 1.0).max(0.0) as usize;
+
+            start.min(size)..end.min(size)
+        };
+
+        let mut found: Vec<(f64, HLSoftBody<B>)> = self
+            .soft_bodies_in_positions
+            .get_soft_bodies_in(to_range(center.0, width), to_range(center.1, height))
+            .into_iter()
+            .filter_map(|c| {
+                let distance = {
+                    let c = c.borrow();
+                    (c.get_px() - center.0).hypot(c.get_py() - center.1)
+                };
+
+                if distance <= radius {
+                    Some((distance, c))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        found.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Returns a read-only view of every living creature, in the order of `Board::creatures`.
+    ///
+    /// Every creature is only borrowed while it's copied, so creatures can be changed while going over the views.
+    pub fn creatures_iter<'a>(&'a self) -> impl Iterator<Item = CreatureView> + 'a {
+        self.creatures
+            .iter()
+            .map(move |c| CreatureView::new(&c.borrow(), self.year))
+    }
+
+    /// Calls `f` with a read-only view of every living creature, see `creatures_iter`.
+    pub fn for_each_creature<F: FnMut(CreatureView)>(&self, f: F) {
+        self.creatures_iter().for_each(f);
+    }
+
+    /// Returns all living creatures for which `predicate` returns true.
+    pub fn find_creatures<F: FnMut(&SoftBody<B>) -> bool>(
+        &self,
+        mut predicate: F,
+    ) -> Vec<HLSoftBody<B>> {
+        self.creatures
+            .iter()
+            .filter(|c| predicate(&c.borrow()))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the primary selected creature, see `SelectedCreature`.
+    pub fn get_selected_creature(&self) -> Option<HLSoftBody<B>> {
+        self.selected_creature
+            .get_primary()
+            .and_then(|id| self.get_creature_by_id(id))
+    }
+
+    /// Returns all selected creatures that are alive, the primary one last.
+    pub fn get_selected_creatures(&self) -> Vec<HLSoftBody<B>> {
+        self.selected_creature
+            .get_ids()
+            .iter()
+            .filter_map(|&id| self.get_creature_by_id(id))
+            .collect()
+    }
+
+    /// Returns all living creatures sorted by the given metric, highest first.
+    pub fn get_creatures_sorted_by(&self, metric: CreatureMetric) -> Vec<HLSoftBody<B>> {
+        let time = self.year;
+        let mut creatures: Vec<(f64, HLSoftBody<B>)> = self
+            .creatures
+            .iter()
+            .map(|c| (metric.get_value(&c.borrow(), time), c.clone()))
+            .collect();
+
+        creatures.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        creatures.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Returns the `amount` creatures that score highest on the given metric, highest first.
+    ///
+    /// Creatures that score the same keep the order they have in `Board::creatures`.
+    pub fn get_leaderboard(&self, metric: CreatureMetric, amount: usize) -> Vec<HLSoftBody<B>> {
+        let mut creatures = self.get_creatures_sorted_by(metric);
+        creatures.truncate(amount);
+
+        creatures
+    }
+
+    /// Checks this board for inconsistencies and for brains that don't fit its config, e.g. after loading it from a
+    /// file. The brains in the seed bank are checked too, see `NeuralNet::check_config`.
+    ///
+    /// Returns a description of every problem found, an empty `Vec` means everything is fine.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let board_size = self.get_board_size();
+
+        let terrain_size = (self.terrain.get_width(), self.terrain.get_height());
+        if terrain_size != board_size {
+            problems.push(format!(
+                "the terrain is {:?} tiles but the board is {:?}",
+                terrain_size, board_size
+            ));
+        }
+
+        if self.pheromones.get_size() != board_size {
+            problems.push(format!(
+                "the pheromone field is {:?} tiles but the board is {:?}",
+                self.pheromones.get_size(),
+                board_size
+            ));
+        }
+
+        if !self.year.is_finite() || self.year < 0.0 {
+            problems.push(format!("the time is {}, which is not a valid year", self.year));
+        }
+
+        if self.climate.get_min_temperature() > self.climate.get_max_temperature() {
+            problems.push(format!(
+                "the minimum temperature ({}) is higher than the maximum temperature ({})",
+                self.climate.get_min_temperature(),
+                self.climate.get_max_temperature()
+            ));
+        }
+
+        // Every creature with the same kind of brain has the same mismatch, only report it once.
+        let mut mismatches: Vec<String> = self
+            .creatures
+            .iter()
+            .filter_map(|c| c.borrow().brain.check_config(&self.config).err())
+            .chain(self.seed_bank.iter().filter_map(|b| b.check_config(&self.config).err()))
+            .collect();
+        mismatches.sort();
+        mismatches.dedup();
+        problems.extend(mismatches);
+
+        problems.extend(self.find_inconsistencies().iter().map(|i| i.to_string()));
+
+        return problems;
+    }
+
+    /// Returns everything that's wrong with the creatures, the collision grid and the selection.
+    ///
+    /// See the [repair] module.
+    pub fn find_inconsistencies(&self) -> Vec<Inconsistency> {
+        crate::repair::find_inconsistencies(self)
+    }
+
+    /// Fixes everything `find_inconsistencies` would find and returns what was fixed.
+    ///
+    /// Creatures outside of the board are moved back onto it, creatures with a broken energy or brain are removed, the
+    /// collision grid is rebuilt and a selected creature that isn't alive is deselected.
+    pub fn repair(&mut self) -> Vec<Inconsistency> {
+        crate::repair::repair(self)
+    }
+
+    /// Returns what the sensors of the given creature currently perceive.
+    pub fn get_sensor_readings(&self, creature: &HLSoftBody<B>) -> SensorReadings {
+        let c = creature.borrow();
+        let env = crate::brain::Environment::new(
+            &self.terrain,
+            &c.base,
+            self.year,
+            &self.climate,
+            &self.soft_bodies_in_positions,
+            &self.pheromones,
+            &self.config.vision,
+            creature.clone(),
+        );
+
+        SensorReadings::read(&env)
+    }
+
+    #[cfg(not(multithreading))]
+    fn update_brains(&mut self) {
+        self.creatures.iter().for_each(|c_rc| {
+            let creature: &mut SoftBody<B> = &mut c_rc.borrow_mut();
+            let env = crate::brain::Environment::new(
+                &self.terrain,
+                &creature.base,
+                self.year,
+                &self.climate,
+                &self.soft_bodies_in_positions,
+                &self.pheromones,
+                &self.config.vision,
+                c_rc.clone(),
+            );
+            creature.brain.run_with(&env);
+        });
     }
 
     #[cfg(multithreading)]
     fn update_brains(&mut self) {
-        self.creatures
-            .map(|c| c.borrow_mut())
-            .par_iter()
-            .for_each(|c| {
-                let env = crate::brain::Environment::new(&self.terrain, &c.base);
-                c.brain.run_with(&env);
-            });
+        self.creatures.par_iter().for_each(|c_rc| {
+            let c = &mut c_rc.borrow_mut();
+            let env = crate::brain::Environment::new(
+                &self.terrain,
+                &c.base,
+                self.year,
+                &self.climate,
+                &self.soft_bodies_in_positions,
+                &self.pheromones,
+                &self.config.vision,
+                c_rc.clone(),
+            );
+            c.brain.run_with(&env);
+        });
     }
 
     pub fn update_creatures(&mut self, time_step: f64) {
@@ -313,7 +1206,7 @@ impl<B: NeuralNet> Board<B> {
 
             c.record_energy();
 
-            c.metabolize(time_step, time);
+            c.metabolize(time_step, time, &self.config);
         }
 
         self.update_brains();
@@ -329,59 +1222,218 @@ impl<B: NeuralNet> Board<B> {
                     time,
                     &self.climate,
                     &self.soft_bodies_in_positions,
+                    &mut self.pheromones,
                     c_rc.clone(),
                 );
                 creature.brain.use_output(&mut env, time_step);
             }
         }
+
+        if self.hooks.wants_eat() {
+            for c in &self.creatures {
+                let c = c.borrow();
+                let eaten = c.get_energy_eaten();
+                if eaten > 0.0 {
+                    self.hooks.eat(time, &c, eaten);
+                }
+            }
+        }
     }
 
     // #[cfg(multithreading)]
     pub fn move_creatures(&mut self, time_step: f64) {
         let board_size = self.get_board_size();
+        let wall_x = self
+            .split_experiment
+            .as_ref()
+            .and_then(|s| s.get_wall_x(board_size.0));
 
         for c in &self.creatures {
-            c.apply_motions(
-                time_step * OBJECT_TIMESTEPS_PER_YEAR,
-                board_size,
-                &self.terrain,
-                &mut self.soft_bodies_in_positions,
-            );
+            if let Some(wall_x) = wall_x {
+                let was_left = c.borrow().get_px() < wall_x;
+                c.borrow_mut().apply_motions(
+                    time_step * self.config.object_timesteps_per_year,
+                    &self.terrain,
+                    board_size,
+                );
+                c.borrow_mut().stay_beside_wall(wall_x, was_left);
+                c.set_sbip(&mut self.soft_bodies_in_positions, board_size);
+            } else {
+                c.apply_motions(
+                    time_step * self.config.object_timesteps_per_year,
+                    board_size,
+                    &self.terrain,
+                    &mut self.soft_bodies_in_positions,
+                );
+            }
+        }
+    }
+
+    /// Gives the tiles the climate zones of `config.climate_zones`, this does nothing for `ClimateZones::Uniform`.
+    fn apply_climate_zones(&mut self) {
+        let zones = self.config.climate_zones;
+        if zones == ClimateZones::Uniform {
+            return;
         }
+
+        self.climate
+            .set_regions(zones.get_regions(self.config.min_temp, self.config.max_temp));
+        let (width, height) = self.get_board_size();
+        for x in 0..width {
+            for y in 0..height {
+                self.terrain
+                    .get_tile_at_mut((x, y))
+                    .set_region(zones.get_region(y, height));
+            }
+        }
+    }
+
+    /// Splits the board into two halves with their own climate and a barrier in between, see `SplitExperiment`.
+    ///
+    /// The climate and terrain are changed right away, the barrier stays in place for as long as this board exists.
+    pub fn set_split_experiment(&mut self, split: SplitExperiment) {
+        split.apply_to(self);
+        self.split_experiment = Some(split);
+    }
+
+    pub fn get_split_experiment(&self) -> Option<&SplitExperiment> {
+        self.split_experiment.as_ref()
     }
 
+    /// Returns the statistics of both halves of the split experiment, A first, or `None` if there is none.
+    pub fn get_half_statistics(&self) -> Option<[HalfStatistics; 2]> {
+        self.split_experiment
+            .as_ref()
+            .map(|s| s.get_statistics(self))
+    }
+
+    /// Brings every tile up to date, so they all have the right color.
+    ///
+    /// Tiles are only updated when a creature eats from them, so on a big board this is a lot of work. Use
+    /// `prepare_for_drawing_at` when only part of the board is drawn.
     pub fn prepare_for_drawing(&mut self) {
         self.terrain.update_all(self.year, &self.climate);
     }
 
+    /// Brings only the tiles in the given ranges up to date, e.g. the ones that are visible.
+    pub fn prepare_for_drawing_at(
+        &mut self,
+        x_range: std::ops::Range<usize>,
+        y_range: std::ops::Range<usize>,
+    ) {
+        let x_range = x_range.start.min(self.board_width)..x_range.end.min(self.board_width);
+        let y_range = y_range.start.min(self.board_height)..y_range.end.min(self.board_height);
+
+        self.terrain
+            .update_all_at(self.year, &self.climate, x_range, y_range);
+    }
+
     /// Checks for all creatures whether they are fit enough to live and kills them off if they're not.
     ///
     /// Utilizes the `should_die` function of `SoftBody`.
     fn remove_dead_creatures(&mut self) {
         let time = self.get_time();
         let board_size = self.get_board_size();
-        let terrain = &mut self.terrain;
-        let climate = &self.climate;
-        let sbip = &mut self.soft_bodies_in_positions;
 
-        // TODO: possibly optimise code
-        let mut i = 0;
-        while i < self.creatures.len() {
-            // let creature = &mut self.creatures[i];
-            if self.creatures[i].borrow().should_die() {
-                self.creatures[i].return_to_earth(time, board_size, terrain, climate, sbip);
+        // Sort out the dead in a single pass, the living keep their order.
+        let (dead, alive): (Vec<HLSoftBody<B>>, Vec<HLSoftBody<B>>) =
+            std::mem::replace(&mut self.creatures, Vec::new())
+                .into_iter()
+                .partition(|c| c.borrow().should_die(time));
+        self.creatures = alive;
+
+        for mut c in dead {
+            let cause = if c.borrow().was_bitten_at(time) {
+                self.deaths_by_predation += 1;
+                DeathCause::Predation
+            } else if c.borrow().is_past_lifespan(time) {
+                self.deaths_by_old_age += 1;
+                DeathCause::OldAge
+            } else {
+                self.deaths_by_starvation += 1;
+                DeathCause::Starvation
+            };
+            self.phylogeny.record_death(c.borrow().get_id(), time);
+            self.species.remove_creature(c.borrow().get_id());
+            self.hooks.death(time, &c.borrow(), cause);
+
+            let mut flows = c.borrow_mut().take_energy_flows();
+            let (compartment, energy, id) = {
+                let c = c.borrow();
+                (c.get_compartment(), c.get_energy(), c.get_id())
+            };
+
+            let returned = c.return_to_earth(
+                time,
+                board_size,
+                &mut self.terrain,
+                &self.climate,
+                &mut self.soft_bodies_in_positions,
+                self.config.carrion,
+            );
+            flows.add(compartment, Compartment::Tiles, returned);
+            flows.add(compartment, Compartment::Decay, energy - returned);
+            self.energy_flows.merge(&flows);
 
-                self.selected_creature
-                    .unselect_if_dead(self.creatures[i].clone());
-                self.creatures.remove(i);
+            self.selected_creature.unselect_if_dead(id);
+        }
+    }
 
-            // println!("Dead!");
-            } else {
-                i += 1;
+    /// Lets `disaster` wipe out the food and change the land it hits until it's over, and hurts the creatures caught in
+    /// it. Creatures that lose all their energy die of starvation.
+    pub fn strike(&mut self, disaster: &Disaster) {
+        let board_size = self.get_board_size();
+        let footprint = Brush {
+            radius: disaster.radius,
+        }
+        .get_footprint(disaster.center, board_size);
+
+        let mut changes = Vec::new();
+        for pos in footprint {
+            let tile = self.terrain.get_tile_at_mut(pos);
+            // The food is gone for good, it doesn't come back with the tile.
+            disaster::wipe_food(tile);
+            if let Some(scarred) = disaster.get_scarred_tile(pos, tile) {
+                changes.push((pos, scarred));
+            }
+        }
+        self.terrain.scar(changes, disaster.end);
+
+        let damage = disaster.kind.get_damage();
+        for c in &self.creatures {
+            let mut c = c.borrow_mut();
+            if damage > 0.0 && disaster.covers(c.get_px(), c.get_py()) {
+                let lost = c.get_energy().max(0.0) * damage;
+                c.lose_energy(lost);
+                self.energy_flows
+                    .add(c.get_compartment(), Compartment::Decay, lost);
             }
         }
     }
 
+    /// Adds the energy flows of all living creatures and the tiles to `self.energy_flows`.
+    ///
+    /// Growth of food can't be tracked tile by tile since tiles are updated lazily,
+    /// instead any change in food that isn't explained by creatures is counted as growth (or rotting).
+    fn collect_energy_flows(&mut self) {
+        for c in &self.creatures {
+            let flows = c.borrow_mut().take_energy_flows();
+            self.energy_flows.merge(&flows);
+        }
+
+        let tile_food = self.terrain.get_total_food();
+        let growth = tile_food - self.tile_food + self.energy_flows.get_outflow(Compartment::Tiles)
+            - self.energy_flows.get_inflow(Compartment::Tiles);
+        if growth >= 0.0 {
+            self.energy_flows
+                .add(Compartment::Sun, Compartment::Tiles, growth);
+        } else {
+            self.energy_flows
+                .add(Compartment::Tiles, Compartment::Decay, -growth);
+        }
+        self.tile_food = tile_food;
+    }
+
     /// Performs the same function on `self.climate`, filling in `self.year`.
     pub fn get_growth_since(&self, last_updated: f64) -> f64 {
         return self
@@ -422,7 +1474,115 @@ impl<B: NeuralNet> Board<B> {
     /// 
     /// When the population drops below this `maintain_creature_minimum()` spawns new creatures to fill the gap.
     pub fn get_creature_minimum(&self) -> usize {
-        self.creature_minimum
+        self.config.creature_minimum
+    }
+
+    pub fn get_config(&self) -> &SimConfig {
+        &self.config
+    }
+
+    /// Changes the settings of this board while it runs.
+    ///
+    /// Returns an error if `config` isn't valid or changes any of the settings that are only used when generating a
+    /// board, see `SimConfig`.
+    pub fn set_config(&mut self, config: SimConfig) -> Result<(), EvolvimError> {
+        config.validate()?;
+        if !config.has_same_generation(&self.config) {
+            return Err(EvolvimError::InvalidConfig(
+                "the temperatures, climate, terrain noise and vision can only be chosen when generating a board"
+                    .to_string(),
+            ));
+        }
+
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Changes the size of the board while it runs, e.g. to give a thriving population more space.
+    ///
+    /// The board grows and shrinks at the right and at the bottom, new tiles are made according to `fill`. Creatures
+    /// that no longer fit are moved back onto the board and a split experiment is applied again for the new width.
+    pub fn resize(&mut self, board_size: BoardSize, fill: TerrainFill) -> Result<(), EvolvimError> {
+        if board_size.0 == 0 || board_size.1 == 0 {
+            return Err(EvolvimError::InvalidConfig(format!(
+                "a board of {:?} tiles has no room for anything",
+                board_size
+            )));
+        }
+
+        let step_size = self.config.noise_step_size;
+        self.with_own_rng(|board| board.terrain.resize(board_size, fill, step_size));
+        self.board_width = board_size.0;
+        self.board_height = board_size.1;
+        self.tile_food = self.terrain.get_total_food();
+        self.pheromones.resize(board_size);
+        self.apply_climate_zones();
+        if let Some(split) = self.split_experiment.clone() {
+            split.apply_to(self);
+        }
+
+        self.soft_bodies_in_positions = SoftBodiesInPositions::new_allocated(board_size);
+        for c in &self.creatures {
+            {
+                let mut c = c.borrow_mut();
+                let (px, py) = (c.get_px(), c.get_py());
+                c.set_body_x(px, board_size.0);
+                c.set_body_y(py, board_size.1);
+            }
+
+            c.add_to_sbip(&mut self.soft_bodies_in_positions, board_size);
+        }
+        // The old `SoftBodiesInPositions` is gone, so the eggs can be moved.
+        for egg in &mut self.eggs {
+            egg.keep_on_board(board_size);
+            egg.add_to_sbip(&mut self.soft_bodies_in_positions);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the terrain, e.g. with one read by `Terrain::from_image`, and resizes the board to fit it.
+    ///
+    /// Creatures that end up outside of the new terrain are moved back onto it, see `resize`.
+    pub fn set_terrain(&mut self, terrain: Terrain) -> Result<(), EvolvimError> {
+        let board_size = (terrain.get_width(), terrain.get_height());
+        self.terrain = terrain;
+
+        // The terrain already has the right size, this only fits the rest of the board to it.
+        self.resize(board_size, TerrainFill::Water)
+    }
+
+    pub fn get_spawn_policy(&self) -> SpawnPolicy {
+        self.spawn_policy
+    }
+
+    /// Changes where the brains of the creatures spawned to keep up the creature minimum come from.
+    pub fn set_spawn_policy(&mut self, policy: SpawnPolicy) {
+        self.spawn_policy = policy;
+    }
+
+    /// Returns the brains `SpawnPolicy::FromSeedBank` picks from.
+    pub fn get_seed_bank(&self) -> &[B] {
+        &self.seed_bank
+    }
+
+    /// Adds a brain to the seed bank, e.g. one of a creature that did well.
+    pub fn add_to_seed_bank(&mut self, brain: B) {
+        self.seed_bank.push(brain);
+    }
+
+    pub fn clear_seed_bank(&mut self) {
+        self.seed_bank.clear();
+    }
+
+    pub fn get_extinction_policy(&self) -> ExtinctionPolicy {
+        self.extinction_policy
+    }
+
+    /// Changes what happens when the population dies out, see `on_extinction` to find out when that happens.
+    pub fn set_extinction_policy(&mut self, policy: ExtinctionPolicy) {
+        self.extinction_policy = policy;
     }
     
     /// Returns `self.creature_id_up_to`
@@ -430,42 +1590,305 @@ impl<B: NeuralNet> Board<B> {
         self.creature_id_up_to
     }
 
+    /// Returns the family tree of all creatures that lived on this board, see `Phylogeny`.
+    pub fn get_phylogeny(&self) -> &Phylogeny {
+        &self.phylogeny
+    }
+
+    pub(crate) fn get_phylogeny_mut(&mut self) -> &mut Phylogeny {
+        &mut self.phylogeny
+    }
+
+    /// Returns the name of the creature with `id`, dead or alive, see `Creature::get_name`.
+    pub fn get_name_of(&self, id: usize) -> Option<String> {
+        self.phylogeny.get(id).map(PhylogenyRecord::get_name)
+    }
+
+    /// Returns the species the living creatures are divided into, see `SpeciesTracker`.
+    pub fn get_species(&self) -> &SpeciesTracker {
+        &self.species
+    }
+
+    pub(crate) fn get_species_mut(&mut self) -> &mut SpeciesTracker {
+        &mut self.species
+    }
+
+    /// Returns the amount of babies born, random creatures added to keep up the creature minimum don't count.
+    ///
+    /// Babies in an egg count once they hatch.
+    pub fn get_births(&self) -> usize {
+        self.births
+    }
+
+    /// Returns the eggs that haven't hatched yet, see `Egg`.
+    pub fn get_eggs(&self) -> &[HLEgg<B>] {
+        &self.eggs
+    }
+
+    /// Puts `egg` on the board, e.g. when loading one.
+    pub(crate) fn add_egg(&mut self, egg: Egg<B>) {
+        let egg = HLEgg::from(egg);
+        egg.add_to_sbip(&mut self.soft_bodies_in_positions);
+        self.eggs.push(egg);
+    }
+
+    /// Returns the amount of eggs that were eaten before they hatched.
+    pub fn get_eggs_eaten(&self) -> usize {
+        self.eggs_eaten
+    }
+
+    /// Returns the amount of creatures that died because they were bitten to death.
+    pub fn get_deaths_by_predation(&self) -> usize {
+        self.deaths_by_predation
+    }
+
+    /// Returns the amount of creatures that died without being bitten, i.e. they ran out of energy on their own.
+    pub fn get_deaths_by_starvation(&self) -> usize {
+        self.deaths_by_starvation
+    }
+
+    /// Returns the amount of creatures that died because they got older than their lifespan, see `Rock::get_lifespan`.
+    pub fn get_deaths_by_old_age(&self) -> usize {
+        self.deaths_by_old_age
+    }
+
+    /// Returns the amount of creatures that died, of any cause.
+    pub fn get_deaths(&self) -> usize {
+        self.deaths_by_predation + self.deaths_by_starvation + self.deaths_by_old_age
+    }
+
+    /// Returns the energy that flowed between compartments during the last update.
+    pub fn get_energy_flows(&self) -> &EnergyFlows {
+        &self.energy_flows
+    }
+
+    /// Returns the samples of the population taken so far.
+    pub fn get_history(&self) -> &BoardHistory {
+        &self.history
+    }
+
+    /// Replaces the history of the population, e.g. by one with another interval or `RetentionPolicy`.
+    pub fn set_history(&mut self, mut history: BoardHistory) {
+        history.start_counting_from(self.births, self.get_deaths());
+        self.history = history;
+    }
+
     /// Gets the size of the current population; i.e. how many creatures are currently alive.
     pub fn get_population_size(&self) -> usize {
         return self.creatures.len();
     }
 
+    /// Returns the food lying on and stored in all tiles as it was after the last update.
+    pub fn get_total_food(&self) -> f64 {
+        self.tile_food
+    }
+
+    /// Returns the population, energy, age, food and temperature of this board in one go.
+    pub fn stats(&self) -> BoardStats {
+        BoardStats::new(self)
+    }
+
     /// Returns a `String` representing the current season.
     ///
     /// Can be either "Winter", "Spring", "Summer" or "Autumn".
     pub fn get_season(&self) -> String {
-        const SEASONS: [&str; 4] = ["Winter", "Spring", "Summer", "Autumn"];
-        let season: usize = ((self.year % 1.0) * 4.0).floor() as usize;
+        return SEASONS[get_season_index(self.year)].to_string();
+    }
 
-        return SEASONS[season].to_string();
+    /// Calls `hook` with every baby that's born, random creatures added to keep up the creature minimum don't count.
+    pub fn on_birth<F: FnMut(f64, &SoftBody<B>) + 'static>(&mut self, hook: F) {
+        self.hooks.add_birth(Box::new(hook));
+    }
+
+    /// Calls `hook` with every creature that dies, right before it's returned to the earth.
+    pub fn on_death<F: FnMut(f64, &SoftBody<B>, DeathCause) + 'static>(&mut self, hook: F) {
+        self.hooks.add_death(Box::new(hook));
+    }
+
+    /// Calls `hook` once an update with every creature that ate and the energy it got out of it.
+    pub fn on_eat<F: FnMut(f64, &SoftBody<B>, f64) + 'static>(&mut self, hook: F) {
+        self.hooks.add_eat(Box::new(hook));
+    }
+
+    /// Calls `hook` with the name of the new season (see `get_season`) whenever the season changes.
+    pub fn on_season_change<F: FnMut(f64, &str) + 'static>(&mut self, hook: F) {
+        self.hooks.add_season_change(Box::new(hook));
+    }
+
+    /// Calls `hook` with the new weather whenever rain, a drought or a storm starts, see `Climate::update_weather`.
+    pub fn on_weather<F: FnMut(f64, &WeatherEvent) + 'static>(&mut self, hook: F) {
+        self.hooks.add_weather(Box::new(hook));
+    }
+
+    /// Calls `hook` with every flood, wildfire or meteor that hits the board, see `DisasterConfig`.
+    pub fn on_disaster<F: FnMut(f64, &Disaster) + 'static>(&mut self, hook: F) {
+        self.hooks.add_disaster(Box::new(hook));
+    }
+
+    /// Calls `hook` with what died out whenever the population goes extinct, see `ExtinctionCause`.
+    ///
+    /// This is called before the `ExtinctionPolicy` is applied.
+    pub fn on_extinction<F: FnMut(f64, ExtinctionCause) + 'static>(&mut self, hook: F) {
+        self.hooks.add_extinction(Box::new(hook));
+    }
+
+    /// Removes all callbacks added with `on_birth`, `on_death`, `on_eat`, `on_season_change`, `on_weather`,
+    /// `on_disaster` and `on_extinction`.
+    pub fn clear_hooks(&mut self) {
+        self.hooks.clear();
     }
 }
 
 impl<B: NeuralNet + serde::de::DeserializeOwned> Board<B> {
-    pub fn load_from<P: AsRef<std::path::Path>>(path: P) -> Result<Board<B>, Box<std::error::Error>> {
-        let file = std::fs::File::open(path)?;
-        Ok({
-            use crate::serde_structs::board::BoardSerde;
-            let ir: BoardSerde<B> = bincode::deserialize_from(file)?;
-            
-            ir.into()
-        })
+    pub fn load_from<P: AsRef<std::path::Path>>(path: P) -> Result<Board<B>, EvolvimError> {
+        Self::load_from_with_format(path, SaveFormat::Bincode)
+    }
+
+    /// Loads a board saved with `save_to_with_format`, use `SaveFormat::from_path` to go by the file extension.
+    pub fn load_from_with_format<P: AsRef<std::path::Path>>(
+        path: P,
+        format: SaveFormat,
+    ) -> Result<Board<B>, EvolvimError> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let ir: BoardSerde<B> = format.deserialize_from(file)?;
+
+        if !ir.get_version().is_compatible_with_current() {
+            return Err(EvolvimError::IncompatibleVersion {
+                found: ir.get_version().to_string(),
+                current: Version::current_version().to_string(),
+            });
+        }
+
+        Ok(ir.into())
+    }
+
+    /// Adds the brains in a seed bank file to the seed bank of this board and returns how many there were.
+    ///
+    /// Call `reseed_from_seed_bank` afterwards to replace the creatures, or use `SpawnPolicy::FromSeedBank`.
+    pub fn load_seed_bank<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<usize, EvolvimError> {
+        let brains: Vec<B> = crate::seed_bank::load(path)?;
+        let amount = brains.len();
+        self.seed_bank.extend(brains);
+
+        Ok(amount)
+    }
+
+    /// Loads the most recent checkpoint in `dir` that isn't broken, see `Checkpoints`.
+    pub fn restore_latest<P: AsRef<std::path::Path>>(dir: P) -> Result<Board<B>, EvolvimError> {
+        crate::checkpoint::restore_latest(dir)
     }
 }
 
 impl<B: NeuralNet + serde::Serialize> Board<B> {
-    pub fn save_to<P: AsRef<std::path::Path>>(
-        self,
+    /// Saves this board to a file, it can be loaded again with `load_from`.
+    ///
+    /// The board can keep running afterwards, so this can be used for checkpoints during a run.
+    pub fn save_to<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), EvolvimError> {
+        self.save_to_with_format(path, SaveFormat::Bincode)
+    }
+
+    /// Saves this board to a file in the given format, it can be loaded again with `load_from_with_format`.
+    ///
+    /// RON and JSON are a lot bigger and slower than bincode but can be read and edited by hand, which is handy for
+    /// small worlds when debugging or teaching.
+    pub fn save_to_with_format<P: AsRef<std::path::Path>>(
+        &self,
         path: P,
-    ) -> Result<(), Box<std::error::Error>> {
-        let file = std::fs::File::create(path)?;
-        bincode::serialize_into(file, &crate::serde_structs::board::BoardSerde::from(self))?;
+        format: SaveFormat,
+    ) -> Result<(), EvolvimError> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        format.serialize_into(&mut file, &BoardSerdeRef::from(self))?;
+
+        // Dropping the `BufWriter` would flush it as well, but it would ignore a failed write.
+        std::io::Write::flush(&mut file)?;
 
         Ok(())
     }
+
+    /// Writes the brains of the `amount` creatures that score highest on `metric` to a seed bank file.
+    ///
+    /// Returns the amount of brains written, which is less than `amount` if there aren't that many creatures.
+    pub fn export_seed_bank<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        metric: CreatureMetric,
+        amount: usize,
+    ) -> Result<usize, EvolvimError> {
+        let best = self.get_leaderboard(metric, amount);
+        let best: Vec<_> = best.iter().map(|c| c.borrow()).collect();
+        let brains: Vec<&B> = best.iter().map(|c| &c.brain).collect();
+
+        crate::seed_bank::save(path, &brains)?;
+
+        Ok(brains.len())
+    }
+
+    /// Writes the seed bank of this board to a file, see `Board::add_to_seed_bank`.
+    pub fn save_seed_bank<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), EvolvimError> {
+        crate::seed_bank::save(path, &self.seed_bank)
+    }
+
+    /// Returns a hash of the whole simulation state: the creatures, terrain, climate, pheromones, time and the random
+    /// number generators of a seeded board.
+    ///
+    /// The hash is the same on every platform, so it can be used to check if two runs diverged.
+    /// Things that don't influence the simulation, like the selected creature, are left out.
+    ///
+    /// NOTE: the generators aren't saved, call `set_seed` on a loaded board and the original to compare them.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = crate::hash::StateHasher::new();
+
+        hasher.write_usize(self.board_width);
+        hasher.write_usize(self.board_height);
+        hasher.write_serialized(&self.config);
+        hasher.write_usize(self.creature_id_up_to);
+        hasher.write_f64(self.year);
+        // A board that isn't seeded takes its random numbers from the thread, those aren't part of its state.
+        if let Some(rng) = &self.rng {
+            rng.write_state(&mut hasher);
+        }
+
+        hasher.write_serialized(&self.terrain);
+        hasher.write_serialized(&self.climate);
+        hasher.write_serialized(&self.pheromones);
+
+        hasher.write_usize(self.creatures.len());
+        for c in &self.creatures {
+            let c = c.borrow();
+
+            hasher.write_usize(c.get_id());
+            c.base.write_state(&mut hasher);
+            hasher.write_serialized(&c.brain);
+        }
+
+        hasher.write_usize(self.eggs.len());
+        for egg in &self.eggs {
+            let baby = egg.get_baby();
+
+            hasher.write_f64(egg.get_hatch_time());
+            baby.base.write_state(&mut hasher);
+            hasher.write_serialized(&baby.brain);
+        }
+
+        hasher.finish()
+    }
+}
+
+/// Generates the terrain of a new board with the generator and noise chosen in `config`.
+fn generate_terrain(config: &SimConfig, board_size: BoardSize) -> Terrain {
+    match config.terrain_generator {
+        TerrainAlgorithm::Perlin => PerlinGenerator {
+            params: config.noise.clone(),
+        }
+        .generate(board_size, config.noise_step_size),
+        algorithm => algorithm.generate(board_size, config.noise_step_size),
+    }
+}
+
+/// Returns the index in `SEASONS` of the season at `year`.
+fn get_season_index(year: f64) -> usize {
+    ((year % 1.0) * 4.0).floor() as usize
 }
@@ -0,0 +1,215 @@
+//! The population of a `Board` over time.
+//!
+//! Every `Board` keeps a `BoardHistory`: every few updates it takes a `PopulationSample` with the size, energy, age and
+//! species of the population and the births and deaths since the last sample. Creatures are counted both by the hue
+//! of their mouth and by the species they're divided into by their genes, see `SpeciesTracker`. By default only the
+//! last `POPULATION_HISTORY_LENGTH` samples are kept, give the board a history with another `RetentionPolicy` through
+//! `Board::set_history` to keep more.
+
+use crate::brain::NeuralNet;
+use crate::error::EvolvimError;
+use crate::history::{History, RetentionPolicy};
+use crate::Board;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// The amount of samples a `BoardHistory` keeps by default.
+pub const POPULATION_HISTORY_LENGTH: usize = 200;
+/// The amount of updates between two samples by default.
+pub const POPULATION_HISTORY_INTERVAL: usize = 50;
+/// Creatures are grouped into this many species by the hue of their mouth.
+pub const SPECIES_AMOUNT: usize = 12;
+
+/// The state of the population at one point in time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PopulationSample {
+    pub population: usize,
+    pub mean_energy: f64,
+    pub median_energy: f64,
+    pub mean_age: f64,
+    /// The amount of creatures of every species, see `get_species_of_hue`.
+    pub species: Vec<usize>,
+    /// The amount of creatures of every species they're divided into by their genes, by species id. See
+    /// `SpeciesTracker`.
+    pub genetic_species: BTreeMap<usize, usize>,
+    /// The amount of creatures born since the previous sample.
+    pub births: usize,
+    /// The amount of creatures that died since the previous sample.
+    pub deaths: usize,
+}
+
+impl PopulationSample {
+    /// Returns the amount of species with at least one living creature.
+    pub fn get_species_amount(&self) -> usize {
+        self.species.iter().filter(|&&n| n > 0).count()
+    }
+}
+
+/// Returns the species of a creature with the given mouth hue.
+pub fn get_species_of_hue(hue: f64) -> usize {
+    let species = (hue.max(0.0) * SPECIES_AMOUNT as f64) as usize;
+
+    species.min(SPECIES_AMOUNT - 1)
+}
+
+/// Samples the population of a `Board` every `interval` updates.
+#[derive(Clone, Debug)]
+pub struct BoardHistory {
+    interval: usize,
+    ticks: usize,
+    /// The total births and deaths of the board when the previous sample was taken.
+    births: usize,
+    deaths: usize,
+    samples: History<PopulationSample>,
+}
+
+impl Default for BoardHistory {
+    fn default() -> Self {
+        BoardHistory::new(
+            POPULATION_HISTORY_INTERVAL,
+            RetentionPolicy::KeepCount {
+                entries: POPULATION_HISTORY_LENGTH,
+            },
+        )
+    }
+}
+
+impl BoardHistory {
+    /// Creates a history which takes a sample every `interval` updates and forgets old samples according to `policy`.
+    pub fn new(interval: usize, policy: RetentionPolicy) -> Self {
+        assert!(interval > 0, "The interval between two samples can't be 0.");
+
+        BoardHistory {
+            interval,
+            ticks: 0,
+            births: 0,
+            deaths: 0,
+            samples: History::new(policy),
+        }
+    }
+
+    pub fn get_interval(&self) -> usize {
+        self.interval
+    }
+
+    /// Counts an update of `board` and takes a sample if it's time, `Board::update` calls this.
+    ///
+    /// This only fails if the policy spills to disk and writing goes wrong, the samples then stay in memory until the
+    /// next sample is taken.
+    pub fn tick<B: NeuralNet>(&mut self, board: &Board<B>) -> Result<(), EvolvimError> {
+        self.ticks += 1;
+
+        if self.ticks % self.interval == 0 {
+            let sample = self.take_sample(board);
+            self.samples.push(board.get_time(), sample)?;
+        }
+
+        Ok(())
+    }
+
+    /// Only counts births and deaths after the board got to these totals.
+    pub(crate) fn start_counting_from(&mut self, births: usize, deaths: usize) {
+        self.births = births;
+        self.deaths = deaths;
+    }
+
+    fn take_sample<B: NeuralNet>(&mut self, board: &Board<B>) -> PopulationSample {
+        let time = board.get_time();
+        let mut energies = Vec::with_capacity(board.creatures.len());
+        let mut species = vec![0; SPECIES_AMOUNT];
+        let mut total_age = 0.0;
+
+        for c in &board.creatures {
+            let c = c.borrow();
+
+            energies.push(c.get_energy());
+            species[get_species_of_hue(c.get_mouth_hue())] += 1;
+            total_age += c.get_age(time);
+        }
+
+        let births = board.get_births();
+        let deaths = board.get_deaths();
+        let population = energies.len();
+        let mut sample = PopulationSample {
+            population,
+            species,
+            genetic_species: board.get_species().get_sizes(),
+            births: births - self.births,
+            deaths: deaths - self.deaths,
+            ..PopulationSample::default()
+        };
+        self.births = births;
+        self.deaths = deaths;
+
+        if population > 0 {
+            energies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            sample.mean_energy = energies.iter().sum::<f64>() / population as f64;
+            sample.median_energy = if population % 2 == 0 {
+                (energies[population / 2 - 1] + energies[population / 2]) / 2.0
+            } else {
+                energies[population / 2]
+            };
+            sample.mean_age = total_age / population as f64;
+        }
+
+        sample
+    }
+
+    /// Returns all `(time, sample)` pairs that are still in memory, oldest first.
+    pub fn get_samples(&self) -> impl Iterator<Item = &(f64, PopulationSample)> {
+        self.samples.iter()
+    }
+
+    /// Returns the samples still in memory that were taken at a time in `from..=to`, oldest first.
+    pub fn get_range(&self, from: f64, to: f64) -> impl Iterator<Item = &(f64, PopulationSample)> {
+        self.samples.range(from, to)
+    }
+
+    /// Returns the most recent sample.
+    pub fn get_last(&self) -> Option<&(f64, PopulationSample)> {
+        self.samples.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Writes all samples that are still in memory with a row per sample and a column for every species by hue. The
+    /// species by genes change all the time, only their amount is written.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<(), EvolvimError> {
+        write!(
+            writer,
+            "time,population,mean_energy,median_energy,mean_age,births,deaths,genetic_species"
+        )?;
+        for i in 0..SPECIES_AMOUNT {
+            write!(writer, ",species_{}", i)?;
+        }
+        writeln!(writer)?;
+
+        for (time, s) in self.get_samples() {
+            write!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                time,
+                s.population,
+                s.mean_energy,
+                s.median_energy,
+                s.mean_age,
+                s.births,
+                s.deaths,
+                s.genetic_species.len()
+            )?;
+            for n in &s.species {
+                write!(writer, ",{}", n)?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! A brain that can be any of the brain implementations, so different kinds of creatures can share a `Board`.
+//!
+//! Use `Board<AnyBrain>` and give it a factory with `Board::new_random_with_brain_factory` or `set_brain_factory` to
+//! decide which kinds of brains the random creatures get, e.g. `AnyBrain::new_random_mixed` lets hand-written and
+//! evolved brains compete.
+//!
+//! `NeatBrain` is left out for now because it doesn't implement `Intentions` yet.
+
+use super::vision::Vision;
+#[cfg(feature = "scripting")]
+use super::ScriptBrain;
+use super::{Brain, GenerateRandom, GreedyBrain, Intentions, NeuralNet, RecombinationInfinite};
+use super::{Environment, EnvironmentMut};
+
+/// The kind of brain inside an `AnyBrain`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BrainKind {
+    FeedForward,
+    Greedy,
+    #[cfg(feature = "scripting")]
+    Script,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum AnyBrain {
+    FeedForward(Brain),
+    Greedy(GreedyBrain),
+    #[cfg(feature = "scripting")]
+    Script(ScriptBrain),
+}
+
+/// Calls `$call` on whatever brain is inside `$any`, the brain is bound to `$b`.
+macro_rules! dispatch {
+    ($any:expr, $b:ident => $call:expr) => {
+        match $any {
+            AnyBrain::FeedForward($b) => $call,
+            AnyBrain::Greedy($b) => $call,
+            #[cfg(feature = "scripting")]
+            AnyBrain::Script($b) => $call,
+        }
+    };
+}
+
+impl AnyBrain {
+    pub fn get_kind(&self) -> BrainKind {
+        match self {
+            AnyBrain::FeedForward(_) => BrainKind::FeedForward,
+            AnyBrain::Greedy(_) => BrainKind::Greedy,
+            #[cfg(feature = "scripting")]
+            AnyBrain::Script(_) => BrainKind::Script,
+        }
+    }
+
+    /// Returns a random brain of the given kind that sees with `vision`.
+    pub fn new_random_of_kind(kind: BrainKind, vision: &Vision) -> Self {
+        match kind {
+            BrainKind::FeedForward => AnyBrain::FeedForward(Brain::new_random_with_vision(vision)),
+            BrainKind::Greedy => AnyBrain::Greedy(GreedyBrain::new_random_with_vision(vision)),
+            #[cfg(feature = "scripting")]
+            BrainKind::Script => AnyBrain::Script(ScriptBrain::new_random_with_vision(vision)),
+        }
+    }
+
+    /// Returns either an evolving `Brain` or a `GreedyBrain`, both are equally likely.
+    pub fn new_random_mixed(vision: &Vision) -> Self {
+        if crate::rng::random() {
+            Self::new_random_of_kind(BrainKind::FeedForward, vision)
+        } else {
+            Self::new_random_of_kind(BrainKind::Greedy, vision)
+        }
+    }
+}
+
+impl NeuralNet for AnyBrain {
+    fn load_input<B>(&mut self, env: &Environment<B>) {
+        dispatch!(self, b => b.load_input(env))
+    }
+
+    fn run(&mut self) {
+        dispatch!(self, b => b.run())
+    }
+
+    fn use_output<B>(&self, env: &mut EnvironmentMut<B>, time_step: f64) {
+        dispatch!(self, b => b.use_output(env, time_step))
+    }
+
+    fn complexity(&self) -> usize {
+        dispatch!(self, b => b.complexity())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        dispatch!(self, b => b.validate())
+    }
+
+    fn check_config(&self, config: &crate::config::SimConfig) -> Result<(), String> {
+        dispatch!(self, b => b.check_config(config))
+    }
+
+    fn write_genome(&self, hasher: &mut crate::hash::StateHasher) {
+        hasher.write_usize(self.get_kind() as usize);
+        dispatch!(self, b => b.write_genome(hasher))
+    }
+
+    /// Brains of different kinds are infinitely far apart.
+    fn genome_distance(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (AnyBrain::FeedForward(a), AnyBrain::FeedForward(b)) => a.genome_distance(b),
+            (AnyBrain::Greedy(a), AnyBrain::Greedy(b)) => a.genome_distance(b),
+            #[cfg(feature = "scripting")]
+            (AnyBrain::Script(a), AnyBrain::Script(b)) => a.genome_distance(b),
+            _ => std::f64::INFINITY,
+        }
+    }
+}
+
+impl Intentions for AnyBrain {
+    fn wants_birth(&self) -> f64 {
+        dispatch!(self, b => b.wants_birth())
+    }
+
+    fn wants_help_birth(&self) -> f64 {
+        dispatch!(self, b => b.wants_help_birth())
+    }
+}
+
+impl GenerateRandom for AnyBrain {
+    /// Returns an evolving `Brain`, use `Board::set_brain_factory` to get other kinds.
+    fn new_random_with_vision(vision: &Vision) -> Self {
+        Self::new_random_of_kind(BrainKind::FeedForward, vision)
+    }
+}
+
+impl RecombinationInfinite for AnyBrain {
+    /// The baby gets the same kind of brain as the parent that started the birth, parents with a different kind of
+    /// brain don't pass on any genes.
+    fn recombination_infinite_brains(parents: &[&Self]) -> Self {
+        let kind = parents
+            .last()
+            .expect("A baby needs at least one parent.")
+            .get_kind();
+
+        match kind {
+            BrainKind::FeedForward => AnyBrain::FeedForward(Brain::recombination_infinite_brains(
+                &parents
+                    .iter()
+                    .filter_map(|p| match p {
+                        AnyBrain::FeedForward(b) => Some(b),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            BrainKind::Greedy => AnyBrain::Greedy(GreedyBrain::recombination_infinite_brains(
+                &parents
+                    .iter()
+                    .filter_map(|p| match p {
+                        AnyBrain::Greedy(b) => Some(b),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            #[cfg(feature = "scripting")]
+            BrainKind::Script => AnyBrain::Script(ScriptBrain::recombination_infinite_brains(
+                &parents
+                    .iter()
+                    .filter_map(|p| match p {
+                        AnyBrain::Script(b) => Some(b),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use super::vision::Vision;
+use crate::constants::{NEARBY_SENSE_RADIUS, PHEROMONE_EMIT_RATE, PHEROMONE_ENERGY};
 use crate::softbody::{HLSoftBody, Rock};
-use crate::{BoardSize, Climate, SoftBodiesInPositions, Terrain};
+use crate::{Biome, BoardSize, Climate, PheromoneField, SoftBodiesInPositions, Terrain};
 
 pub struct EnvironmentMut<'a, B> {
     pub terrain: &'a mut Terrain,
@@ -8,6 +10,7 @@ pub struct EnvironmentMut<'a, B> {
     pub time: f64,
     pub climate: &'a Climate,
     pub sbip: &'a SoftBodiesInPositions<B>,
+    pub pheromones: &'a mut PheromoneField,
     pub self_pointer: HLSoftBody<B>,
 }
 
@@ -19,6 +22,7 @@ impl<'a, B> EnvironmentMut<'a, B> {
         time: f64,
         climate: &'a Climate,
         sbip: &'a SoftBodiesInPositions<B>,
+        pheromones: &'a mut PheromoneField,
         self_pointer: HLSoftBody<B>,
     ) -> Self {
         EnvironmentMut {
@@ -28,10 +32,31 @@ impl<'a, B> EnvironmentMut<'a, B> {
             time,
             climate,
             sbip,
+            pheromones,
             self_pointer,
         }
     }
 
+    /// Leaves pheromone behind at the center of this body, costs energy.
+    pub fn emit_pheromone(&mut self, value: f64, time_step: f64) {
+        let amount = value.max(0.0) * PHEROMONE_EMIT_RATE * time_step;
+
+        if amount > 0.0 {
+            let pos = self.this_body.get_position().into();
+            self.pheromones.deposit(pos, amount);
+            self.this_body.lose_energy(amount * PHEROMONE_ENERGY);
+        }
+    }
+
+    /// Asks to give birth if `willingness` exceeds `threshold`.
+    ///
+    /// The baby is only born after all creatures have acted and only if there's enough energy for it.
+    pub fn give_birth(&mut self, willingness: f64, threshold: f64) {
+        if willingness > threshold {
+            self.this_body.request_birth();
+        }
+    }
+
     pub fn get_colliders(&self) -> crate::sbip::SoftBodiesAt<B> {
         use crate::sbip::SoftBodyBucket;
 
@@ -44,13 +69,153 @@ impl<'a, B> EnvironmentMut<'a, B> {
     }
 }
 
-pub struct Environment<'a> {
+pub struct Environment<'a, B> {
     pub terrain: &'a Terrain,
     pub this_body: &'a Rock,
+    pub time: f64,
+    pub climate: &'a Climate,
+    pub sbip: &'a SoftBodiesInPositions<B>,
+    pub pheromones: &'a PheromoneField,
+    /// The eyes of every creature on the board, see `SimConfig::vision`.
+    pub vision: &'a Vision,
+    pub self_pointer: HLSoftBody<B>,
 }
 
-impl<'a> Environment<'a> {
-    pub fn new(terrain: &'a Terrain, this_body: &'a Rock) -> Self {
-        Environment { terrain, this_body }
+impl<'a, B> Environment<'a, B> {
+    pub fn new(
+        terrain: &'a Terrain,
+        this_body: &'a Rock,
+        time: f64,
+        climate: &'a Climate,
+        sbip: &'a SoftBodiesInPositions<B>,
+        pheromones: &'a PheromoneField,
+        vision: &'a Vision,
+        self_pointer: HLSoftBody<B>,
+    ) -> Self {
+        Environment {
+            terrain,
+            this_body,
+            time,
+            climate,
+            sbip,
+            pheromones,
+            vision,
+            self_pointer,
+        }
+    }
+
+    /// Returns the energy of this body.
+    pub fn get_energy(&self) -> f64 {
+        self.this_body.get_energy()
     }
+
+    /// Returns the age of this body in years.
+    pub fn get_age(&self) -> f64 {
+        self.this_body.get_age(self.time)
+    }
+
+    /// Returns how far this body has declined with age, from 0 to 1 at the end of its lifespan, see
+    /// `Rock::get_senescence`.
+    pub fn get_senescence(&self) -> f64 {
+        self.this_body.get_senescence(self.time)
+    }
+
+    /// Returns the radius of this body.
+    pub fn get_size(&self) -> f64 {
+        self.this_body.get_radius()
+    }
+
+    /// Returns the biome at the center of this body, `None` in the water.
+    pub fn get_biome(&self) -> Option<Biome> {
+        self.terrain
+            .get_tile_at(self.this_body.get_position().into())
+            .get_biome()
+    }
+
+    /// Returns the direction in which the land gets more fertile at the center of this body, see
+    /// `Terrain::fertility_gradient`.
+    pub fn sense_fertility_gradient(&self) -> (f64, f64) {
+        self.terrain
+            .fertility_gradient(self.this_body.get_position().into())
+    }
+
+    /// Returns the food lying on the tiles within `radius` tiles of the center of this body.
+    pub fn sense_food_within(&self, radius: f64) -> f64 {
+        self.terrain
+            .total_food_within(self.this_body.get_position().into(), radius)
+    }
+
+    /// Returns how light it is, from 0 at midnight to 1 at noon, see `Climate::get_light_level`.
+    pub fn sense_light(&self) -> f64 {
+        self.climate.get_light_level(self.time)
+    }
+
+    /// Returns the amount of pheromone at the center of this body.
+    pub fn smell_pheromone(&self) -> f64 {
+        self.pheromones
+            .get_concentration_at(self.this_body.get_position().into())
+    }
+
+    /// Summarizes all other creatures within `NEARBY_SENSE_RADIUS` of this body.
+    pub fn sense_nearby(&self) -> NearbyCreatures {
+        let body = self.this_body;
+        let (px, py) = (body.get_px(), body.get_py());
+        let radius = NEARBY_SENSE_RADIUS;
+
+        let x_range = (px - radius).floor().max(0.0) as usize
+            ..((px + radius).ceil() as usize + 1).min(self.terrain.get_width());
+        let y_range = (py - radius).floor().max(0.0) as usize
+            ..((py + radius).ceil() as usize + 1).min(self.terrain.get_height());
+
+        let mut count = 0;
+        let (mut dir_sin, mut dir_cos) = (0.0, 0.0);
+        let (mut head_sin, mut head_cos) = (0.0, 0.0);
+        let mut hue = 0.0;
+
+        for c in self.sbip.get_soft_bodies_in(x_range, y_range) {
+            // Don't borrow ourselves, we're already borrowed mutably.
+            if c == self.self_pointer {
+                continue;
+            }
+
+            let c = c.borrow();
+            let (dx, dy) = (c.get_px() - px, c.get_py() - py);
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+
+            let direction = dy.atan2(dx) - body.get_rotation();
+            let heading = c.get_rotation() - body.get_rotation();
+
+            count += 1;
+            dir_sin += direction.sin();
+            dir_cos += direction.cos();
+            head_sin += heading.sin();
+            head_cos += heading.cos();
+            hue += c.get_mouth_hue();
+        }
+
+        if count == 0 {
+            return NearbyCreatures::default();
+        }
+
+        NearbyCreatures {
+            count,
+            direction: dir_sin.atan2(dir_cos),
+            heading: head_sin.atan2(head_cos),
+            hue: hue / count as f64,
+        }
+    }
+}
+
+/// A summary of the creatures around a body, see `Environment::sense_nearby`.
+#[derive(Clone, Debug, Default)]
+pub struct NearbyCreatures {
+    pub count: usize,
+    /// The average direction towards the others relative to where this body is facing, ranges from -PI to PI.
+    pub direction: f64,
+    /// The average heading of the others relative to the heading of this body, ranges from -PI to PI.
+    pub heading: f64,
+    /// The average mouth hue of the others.
+    pub hue: f64,
 }
@@ -0,0 +1,181 @@
+//! Exports brain weights as NumPy `.npz` archives so they can be analysed in Python.
+//!
+//! An `.npz` file is an uncompressed zip archive with one `.npy` file per array, load it with
+//! `numpy.load("brain.npz")` and index the result by array name.
+
+use crate::error::EvolvimError;
+use std::io::Write;
+
+/// The values of an array, NumPy reads these as `float64` or `int64`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArrayData {
+    F64(Vec<f64>),
+    I64(Vec<i64>),
+}
+
+impl ArrayData {
+    fn len(&self) -> usize {
+        match self {
+            ArrayData::F64(v) => v.len(),
+            ArrayData::I64(v) => v.len(),
+        }
+    }
+
+    fn get_descr(&self) -> &'static str {
+        match self {
+            ArrayData::F64(_) => "<f8",
+            ArrayData::I64(_) => "<i8",
+        }
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        match self {
+            ArrayData::F64(v) => v
+                .iter()
+                .for_each(|x| bytes.extend(&x.to_bits().to_le_bytes())),
+            ArrayData::I64(v) => v.iter().for_each(|x| bytes.extend(&x.to_le_bytes())),
+        }
+    }
+}
+
+/// A named n-dimensional array, `data` is stored in row-major (C) order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedArray {
+    pub name: String,
+    pub shape: Vec<usize>,
+    pub data: ArrayData,
+}
+
+impl NamedArray {
+    pub fn new(name: &str, shape: Vec<usize>, data: ArrayData) -> Self {
+        assert_eq!(
+            shape.iter().product::<usize>(),
+            data.len(),
+            "The shape of array {} doesn't match the amount of values.",
+            name
+        );
+
+        NamedArray {
+            name: name.to_string(),
+            shape,
+            data,
+        }
+    }
+
+    /// Returns this array as the contents of a `.npy` file (format version 1.0).
+    pub fn to_npy(&self) -> Vec<u8> {
+        let shape = match self.shape.len() {
+            // A 1-tuple needs a trailing comma in Python.
+            1 => format!("({},)", self.shape[0]),
+            _ => format!(
+                "({})",
+                self.shape
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+            self.data.get_descr(),
+            shape
+        );
+
+        // The magic string, version and header length take 10 bytes, the data has to start at a multiple of 64.
+        let unpadded = 10 + header.len() + 1;
+        let padding = (64 - unpadded % 64) % 64;
+        header.extend(std::iter::repeat(' ').take(padding));
+        header.push('\n');
+
+        let mut bytes = Vec::new();
+        bytes.extend(b"\x93NUMPY\x01\x00");
+        bytes.extend(&(header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        self.data.write_le(&mut bytes);
+
+        bytes
+    }
+}
+
+/// Brains that can hand out their parameters as arrays.
+pub trait ExportWeights {
+    fn get_arrays(&self) -> Vec<NamedArray>;
+}
+
+/// Writes `arrays` to `writer` as an `.npz` archive.
+pub fn write_npz<W: Write>(arrays: &[NamedArray], mut writer: W) -> Result<(), EvolvimError> {
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for array in arrays {
+        let name = format!("{}.npy", array.name);
+        let data = array.to_npy();
+        let crc = crc32(&data);
+        let offset = archive.len() as u32;
+
+        // Local file header, the data is stored without compression.
+        archive.extend(&0x0403_4b50u32.to_le_bytes());
+        write_entry_info(&mut archive, crc, data.len() as u32, name.len() as u16);
+        archive.extend(&0u16.to_le_bytes()); // extra field length
+        archive.extend(name.as_bytes());
+        archive.extend(&data);
+
+        central_directory.extend(&0x0201_4b50u32.to_le_bytes());
+        central_directory.extend(&20u16.to_le_bytes()); // version made by
+        write_entry_info(
+            &mut central_directory,
+            crc,
+            data.len() as u32,
+            name.len() as u16,
+        );
+        central_directory.extend(&[0; 12]); // extra, comment, disk, attributes
+        central_directory.extend(&offset.to_le_bytes());
+        central_directory.extend(name.as_bytes());
+    }
+
+    let directory_offset = archive.len() as u32;
+    let directory_size = central_directory.len() as u32;
+    archive.extend(central_directory);
+
+    // End of central directory record
+    archive.extend(&0x0605_4b50u32.to_le_bytes());
+    archive.extend(&[0; 4]); // disk numbers
+    archive.extend(&(arrays.len() as u16).to_le_bytes());
+    archive.extend(&(arrays.len() as u16).to_le_bytes());
+    archive.extend(&directory_size.to_le_bytes());
+    archive.extend(&directory_offset.to_le_bytes());
+    archive.extend(&0u16.to_le_bytes()); // comment length
+
+    writer.write_all(&archive)?;
+
+    Ok(())
+}
+
+/// Writes the part shared by local file headers and central directory entries, up to the file name length.
+fn write_entry_info(bytes: &mut Vec<u8>, crc: u32, size: u32, name_length: u16) {
+    bytes.extend(&20u16.to_le_bytes()); // version needed to extract
+    bytes.extend(&0u16.to_le_bytes()); // flags
+    bytes.extend(&0u16.to_le_bytes()); // compression: stored
+    bytes.extend(&0u16.to_le_bytes()); // modification time
+    bytes.extend(&0x21u16.to_le_bytes()); // modification date: 1980-01-01
+    bytes.extend(&crc.to_le_bytes());
+    bytes.extend(&size.to_le_bytes()); // compressed size
+    bytes.extend(&size.to_le_bytes()); // uncompressed size
+    bytes.extend(&name_length.to_le_bytes());
+}
+
+/// The CRC-32 checksum used by zip.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
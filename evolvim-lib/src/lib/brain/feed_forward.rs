@@ -5,12 +5,11 @@
 #![warn(missing_docs)]
 
 extern crate nalgebra;
-extern crate rand;
 
 use self::allocator::Allocator;
-use self::dimension::DimName;
+use self::dimension::{Dim, DimName};
 use self::nalgebra::*;
-use self::rand::Rng;
+use super::vision::{Vision, INPUTS_PER_RAY};
 use std::f64::consts::PI;
 
 pub type BrainOutput<'a> = &'a [FPN];
@@ -18,10 +17,11 @@ pub type BrainInput = [FPN; 9];
 
 type FPN = f64;
 
-/// The amount of neurons in the input layer.
-type _InputLayerSize = U10;
-/// The amount of neurons in the input layer plus the bias node.
-type InputLayerSizePlusBias = U11;
+/// The amount of input neurons that aren't fed by the eyes, the input layer also has `Vision::input_count` neurons for
+/// the rays and a bias node.
+const BASE_INPUT_AMOUNT: usize = 14;
+/// The index of the first input neuron that's fed by the eyes.
+const FIRST_RAY_INPUT: usize = 6;
 /// The amount of neurons in the hidden layer.
 type HiddenLayerSize = U10;
 /// The amount of neurons in the hidden layer plus the bias node.
@@ -40,22 +40,24 @@ type OutputLayerSize = U10;
 ///
 /// # Processing equivalent
 /// *Brain.pde/Brain*, although this doesn't have an `Axon` class/structure to rely on.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Brain {
-    // This dimension should be equal to InputLayerSize + 1.
-    a_1: RowVectorN<FPN, InputLayerSizePlusBias>,
-    // These dimensions should be equal to InputLayerSize + 1 by HiddenLayerSize.
-    theta_1: MatrixMN<FPN, InputLayerSizePlusBias, HiddenLayerSize>,
+    // This dimension should be equal to the amount of inputs + 1, the amount of inputs depends on the `Vision`.
+    a_1: RowDVector<FPN>,
+    // These dimensions should be equal to the length of `a_1` by HiddenLayerSize.
+    theta_1: MatrixMN<FPN, Dynamic, HiddenLayerSize>,
     // This dimension should be equal to HiddenLayerSize + 1.
     a_2: RowVectorN<FPN, HiddenLayerSizePlusBias>,
     // These dimensions should be equal to HiddenLayerSize + 1 by OutputLayerSize.
     theta_2: MatrixMN<FPN, HiddenLayerSizePlusBias, OutputLayerSize>,
     // This dimension should be equal to OUTPUT_LAYER_SIZE.
     a_3: RowVectorN<FPN, OutputLayerSize>,
+    // The birth output has to exceed this to give birth, evolves like the weights.
+    birth_threshold: FPN,
 }
 
 impl super::NeuralNet for Brain {
-    fn load_input(&mut self, env: &super::Environment) {
+    fn load_input<B>(&mut self, env: &super::Environment<B>) {
         // Load the memory
         self.a_1[0] = self.get_memory();
 
@@ -72,12 +74,40 @@ impl super::NeuralNet for Brain {
         self.a_1[3] = colors[0] as FPN;
         self.a_1[4] = colors[1] as FPN;
         self.a_1[5] = colors[2] as FPN;
+
+        // Look around, a brain made for other eyes only gets the rays it has room for.
+        let ray_inputs = self.get_ray_input_amount();
+        let rays = env.vision.look(env);
+        for (i, hit) in rays.iter().take(ray_inputs / INPUTS_PER_RAY).enumerate() {
+            let first = FIRST_RAY_INPUT + i * INPUTS_PER_RAY;
+            self.a_1[first] = hit.distance;
+            self.a_1[first + 1] = hit.hue;
+            self.a_1[first + 2] = hit.hit_type.as_input();
+        }
+        let after_rays = FIRST_RAY_INPUT + ray_inputs;
+
+        // Smell the pheromones
+        self.a_1[after_rays] = env.smell_pheromone();
+
+        // Feel its own body
+        self.a_1[after_rays + 1] = env.get_age();
+        self.a_1[after_rays + 2] = env.get_size();
+
+        // Sense the creatures around it
+        let nearby = env.sense_nearby();
+        self.a_1[after_rays + 3] = nearby.count as FPN;
+        self.a_1[after_rays + 4] = nearby.direction;
+        self.a_1[after_rays + 5] = nearby.heading;
+        self.a_1[after_rays + 6] = nearby.hue;
+
+        // See whether it's day or night
+        self.a_1[after_rays + 7] = env.sense_light();
     }
 
     /// Performs feed foward propagation on the neural network.
     // TODO: see if I can speed this up a little with clever memory management.
     fn run(&mut self) {
-        let mut z_2 = self.a_1 * self.theta_1;
+        let mut z_2 = &self.a_1 * &self.theta_1;
         // Perform sigmoid function
         Brain::sigmoid(&mut z_2);
         // Add bias.
@@ -91,7 +121,7 @@ impl super::NeuralNet for Brain {
         self.a_3 = z_3;
     }
 
-    fn use_output(&self, env: &mut super::EnvironmentMut<Self>, time_step: f64) {
+    fn use_output<B>(&self, env: &mut super::EnvironmentMut<B>, time_step: f64) {
         let acceleration = self.wants_acceleration();
         env.this_body.accelerate(acceleration, time_step);
 
@@ -107,20 +137,94 @@ impl super::NeuralNet for Brain {
 
         let mouth_hue = self.wants_mouth_hue();
         env.this_body.set_mouth_hue(mouth_hue);
+
+        let pheromone = self.wants_pheromone();
+        env.emit_pheromone(pheromone, time_step);
+
+        let bite = self.wants_to_bite();
+        env.this_body.bite(
+            bite,
+            env.time,
+            time_step,
+            env.sbip,
+            env.self_pointer.clone(),
+        );
+
+        env.give_birth(
+            super::Intentions::wants_birth(self),
+            self.get_birth_threshold(),
+        );
+    }
+
+    fn complexity(&self) -> usize {
+        let nodes = self.a_1.len() + self.a_2.len() + self.a_3.len();
+        let connections = self.theta_1.len() + self.theta_2.len();
+
+        nodes + connections
+    }
+
+    /// Hashes the weights and the birth threshold, the activations change all the time so they're left out.
+    fn write_genome(&self, hasher: &mut crate::hash::StateHasher) {
+        for w in self.theta_1.iter().chain(self.theta_2.iter()) {
+            hasher.write_f64(*w);
+        }
+        hasher.write_f64(self.birth_threshold);
+    }
+
+    /// Returns the mean difference between the weights of both brains.
+    fn genome_distance(&self, other: &Self) -> f64 {
+        let difference: f64 = (&self.theta_1 - &other.theta_1).abs().sum()
+            + (&self.theta_2 - &other.theta_2).abs().sum();
+
+        difference / (self.theta_1.len() + self.theta_2.len()) as f64
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.theta_1.nrows() != self.a_1.len() {
+            return Err(format!(
+                "the brain has {} inputs but weights for {}",
+                self.a_1.len(),
+                self.theta_1.nrows()
+            ));
+        }
+
+        match self.a_1.len().checked_sub(BASE_INPUT_AMOUNT + 1) {
+            Some(rays) if rays % INPUTS_PER_RAY == 0 => Ok(()),
+            _ => Err(format!(
+                "{} inputs don't fit the senses of a brain",
+                self.a_1.len()
+            )),
+        }
+    }
+
+    fn check_config(&self, config: &crate::config::SimConfig) -> Result<(), String> {
+        let rays = self.get_ray_input_amount() / INPUTS_PER_RAY;
+        if rays != config.vision.ray_amount {
+            return Err(format!(
+                "a brain has inputs for {} rays but the vision casts {}",
+                rays, config.vision.ray_amount
+            ));
+        }
+
+        Ok(())
     }
 }
 
 impl super::GenerateRandom for Brain {
-    /// Returns a brain with completely random weights.
-    fn new_random() -> Self {
-        let theta_1 = <MatrixMN<FPN, InputLayerSizePlusBias, HiddenLayerSize>>::new_random()
-            - <MatrixMN<FPN, InputLayerSizePlusBias, HiddenLayerSize>>::from_element(0.5);
-        let theta_2 = <MatrixMN<FPN, HiddenLayerSizePlusBias, OutputLayerSize>>::new_random()
-            - <MatrixMN<FPN, HiddenLayerSizePlusBias, OutputLayerSize>>::from_element(0.5);
+    /// Returns a brain with completely random weights and an input for everything `vision` sees.
+    fn new_random_with_vision(vision: &Vision) -> Self {
+        let inputs = BASE_INPUT_AMOUNT + vision.input_count() + 1;
+        let theta_1 =
+            <MatrixMN<FPN, Dynamic, HiddenLayerSize>>::from_fn(inputs, |_, _| crate::rng::random())
+                - <MatrixMN<FPN, Dynamic, HiddenLayerSize>>::from_element(inputs, 0.5);
+        let theta_2 =
+            <MatrixMN<FPN, HiddenLayerSizePlusBias, OutputLayerSize>>::from_fn(|_, _| {
+                crate::rng::random()
+            }) - <MatrixMN<FPN, HiddenLayerSizePlusBias, OutputLayerSize>>::from_element(0.5);
 
         Brain {
             // Empty input
-            a_1: <RowVectorN<FPN, InputLayerSizePlusBias>>::zeros(),
+            a_1: <RowDVector<FPN>>::zeros(inputs),
             // Initialize random weights between [-0.5, 0.5].
             theta_1,
             // Empty hidden layer
@@ -129,6 +233,8 @@ impl super::GenerateRandom for Brain {
             theta_2,
             // Empty output
             a_3: <RowVectorN<FPN, OutputLayerSize>>::zeros(),
+            // Start out as willing as in the Processing code.
+            birth_threshold: 0.0,
         }
     }
 }
@@ -150,6 +256,11 @@ impl Brain {
         self.a_1.as_slice()
     }
 
+    /// Returns the amount of inputs that are fed by the eyes, `Vision::input_count` of the vision it was made for.
+    pub fn get_ray_input_amount(&self) -> usize {
+        self.a_1.len().saturating_sub(BASE_INPUT_AMOUNT + 1)
+    }
+
     /// Performs the sigmoid function for every element in the matrix.
     fn sigmoid<R: DimName, C: DimName>(matrix: &mut MatrixMN<FPN, R, C>)
     where
@@ -168,23 +279,31 @@ impl super::RecombinationInfinite for Brain {
     ///
     /// TODO: improve performance via vectorization.
     /// TODO: understand formulae and improve them or come up with my own
-    fn recombination_infinite_parents(parents: &Vec<crate::softbody::HLSoftBody<Brain>>) -> Self {
-        let a_1 = <RowVectorN<FPN, InputLayerSizePlusBias>>::zeros();
+    fn recombination_infinite_brains(parents: &[&Brain]) -> Self {
+        // Parents that were made for other eyes can't mix their weights, the baby gets the inputs of the parent that
+        // started the birth and only inherits from the parents with the same inputs.
+        let inputs = parents.last().unwrap().a_1.len();
+        let parents: Vec<&Brain> = parents
+            .iter()
+            .cloned()
+            .filter(|p| p.a_1.len() == inputs)
+            .collect();
+
+        let a_1 = <RowDVector<FPN>>::zeros(inputs);
         let a_2 = <RowVectorN<FPN, HiddenLayerSizePlusBias>>::zeros();
         let a_3 = <RowVectorN<FPN, OutputLayerSize>>::zeros();
 
-        let mut theta_1 = <MatrixMN<FPN, InputLayerSizePlusBias, HiddenLayerSize>>::zeros();
+        let mut theta_1 = <MatrixMN<FPN, Dynamic, HiddenLayerSize>>::zeros(inputs);
         let mut theta_2 = <MatrixMN<FPN, HiddenLayerSizePlusBias, OutputLayerSize>>::zeros();
 
-        let mut rng = rand::thread_rng();
-        let random_rotation: f64 = rng.gen();
+        let random_rotation: f64 = crate::rng::random();
         let amount_parents = parents.len() as f64;
 
         const MUTABILITY: f64 = 0.0005;
         // const MUTATE_MULTI: f64 = 0.5.powi(9);
         const MUTATE_MULTI: f64 = 0.001953125;
 
-        let axon_angles = get_axon_angles(110, 0);
+        let axon_angles = get_axon_angles(theta_1.nrows() + theta_1.ncols(), 0);
         for y in 0..theta_1.nrows() {
             for z in 0..theta_1.ncols() {
                 // BRAIN_HEIGHT = 11; x = 0; BRAIN_WIDTH = 3;
@@ -193,14 +312,14 @@ impl super::RecombinationInfinite for Brain {
                 let parent_id =
                     (((axon_angle + random_rotation) % 1.0) * amount_parents).floor() as usize;
 
-                let r = (rng.gen::<f64>() * 2.0 - 1.0).powi(9);
+                let r = (crate::rng::random::<f64>() * 2.0 - 1.0).powi(9);
 
-                theta_1[(y, z)] = parents[parent_id].borrow().brain.theta_1[(y, z)]
-                    + r * MUTABILITY / MUTATE_MULTI;
+                theta_1[(y, z)] =
+                    parents[parent_id].theta_1[(y, z)] + r * MUTABILITY / MUTATE_MULTI;
             }
         }
 
-        let axon_angles = get_axon_angles(110, 1);
+        let axon_angles = get_axon_angles(theta_2.nrows() + theta_2.ncols(), 1);
         for y in 0..theta_2.nrows() {
             for z in 0..theta_2.ncols() {
                 // BRAIN_HEIGHT = 11; x = 1; BRAIN_WIDTH = 3;
@@ -209,23 +328,71 @@ impl super::RecombinationInfinite for Brain {
                 let parent_id =
                     (((axon_angle + random_rotation) % 1.0) * amount_parents).floor() as usize;
 
-                let r = (rng.gen::<f64>() * 2.0 - 1.0).powi(9);
+                let r = (crate::rng::random::<f64>() * 2.0 - 1.0).powi(9);
 
-                theta_2[(y, z)] = parents[parent_id].borrow().brain.theta_2[(y, z)]
-                    + r * MUTABILITY / MUTATE_MULTI;
+                theta_2[(y, z)] =
+                    parents[parent_id].theta_2[(y, z)] + r * MUTABILITY / MUTATE_MULTI;
             }
         }
 
+        let r = (crate::rng::random::<f64>() * 2.0 - 1.0).powi(9);
+        let birth_threshold = parents.iter().fold(0.0, |acc, p| acc + p.birth_threshold)
+            / amount_parents
+            + r * MUTABILITY / MUTATE_MULTI;
+
         Brain {
             a_1,
             theta_1,
             a_2,
             theta_2,
             a_3,
+            birth_threshold,
         }
     }
 }
 
+impl super::ExportWeights for Brain {
+    /// Returns `theta_1` (inputs by hidden neurons), `theta_2` (hidden neurons by outputs) and `birth_threshold`.
+    ///
+    /// The first row of `theta_2` belongs to the bias of the hidden layer.
+    fn get_arrays(&self) -> Vec<super::export::NamedArray> {
+        use super::export::{ArrayData, NamedArray};
+
+        fn row_major<R: Dim, C: Dim>(matrix: &MatrixMN<FPN, R, C>) -> ArrayData
+        where
+            DefaultAllocator: Allocator<FPN, R, C>,
+        {
+            // nalgebra stores its matrices column by column, NumPy expects them row by row.
+            let mut values = Vec::with_capacity(matrix.len());
+            for r in 0..matrix.nrows() {
+                for c in 0..matrix.ncols() {
+                    values.push(matrix[(r, c)]);
+                }
+            }
+
+            ArrayData::F64(values)
+        }
+
+        vec![
+            NamedArray::new(
+                "theta_1",
+                vec![self.theta_1.nrows(), self.theta_1.ncols()],
+                row_major(&self.theta_1),
+            ),
+            NamedArray::new(
+                "theta_2",
+                vec![self.theta_2.nrows(), self.theta_2.ncols()],
+                row_major(&self.theta_2),
+            ),
+            NamedArray::new(
+                "birth_threshold",
+                vec![],
+                ArrayData::F64(vec![self.birth_threshold]),
+            ),
+        ]
+    }
+}
+
 impl Brain {
     /// # Processing equivalent
     /// Returns *Brain.pde/outputLabels*.
@@ -238,6 +405,8 @@ impl Brain {
             "Birth",
             "Mouth hue",
             "Help birth",
+            "Bite",
+            "Pheromone",
         ];
 
         // Turn it into `String`s
@@ -277,6 +446,19 @@ impl Brain {
     pub fn wants_mouth_hue(&self) -> f64 {
         self.get_output()[5]
     }
+
+    /// Returns the value the birth output has to exceed before this creature gives birth.
+    pub fn get_birth_threshold(&self) -> f64 {
+        self.birth_threshold
+    }
+
+    pub fn wants_to_bite(&self) -> f64 {
+        self.get_output()[7]
+    }
+
+    pub fn wants_pheromone(&self) -> f64 {
+        self.get_output()[8]
+    }
 }
 
 fn get_axon_angles(max: usize, x: usize) -> Vec<f64> {
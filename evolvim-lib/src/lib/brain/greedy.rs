@@ -0,0 +1,150 @@
+//! A hand-written brain that doesn't evolve, useful as a baseline to compare evolved brains against.
+
+use super::{Environment, EnvironmentMut};
+use crate::constants::CREATURE_MAX_ENERGY;
+use crate::softbody::ReproductionMode;
+use std::f64::consts::PI;
+
+/// The amount of directions a `GreedyBrain` looks in.
+const DIRECTIONS: usize = 8;
+/// How far away (in tiles) a `GreedyBrain` looks for food.
+const LOOK_DISTANCE: f64 = 1.5;
+const ACCELERATION: f64 = 0.5;
+
+/// Steers towards the neighbouring tile with the most food and eats whatever it's standing on.
+///
+/// Its children are exact copies; there are no genes so there is nothing to recombine.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GreedyBrain {
+    acceleration: f64,
+    turning: f64,
+    eating: f64,
+    mouth_hue: f64,
+    birth: f64,
+}
+
+impl GreedyBrain {
+    /// Returns the angle `a` wrapped into the range -PI to PI.
+    fn wrap_angle(mut a: f64) -> f64 {
+        while a > PI {
+            a -= 2.0 * PI;
+        }
+        while a < -PI {
+            a += 2.0 * PI;
+        }
+
+        a
+    }
+}
+
+impl super::NeuralNet for GreedyBrain {
+    fn load_input<B>(&mut self, env: &Environment<B>) {
+        let body = env.this_body;
+        let (width, height) = (env.terrain.get_width(), env.terrain.get_height());
+        let here = env.terrain.get_tile_at(body.get_position().into());
+
+        // Find the direction with the most food.
+        let mut best: Option<(f64, f64)> = None;
+        for i in 0..DIRECTIONS {
+            let angle = i as f64 * 2.0 * PI / DIRECTIONS as f64;
+            let x = body.get_px() + angle.cos() * LOOK_DISTANCE;
+            let y = body.get_py() + angle.sin() * LOOK_DISTANCE;
+
+            if x < 0.0 || y < 0.0 || x >= width as f64 || y >= height as f64 {
+                continue;
+            }
+
+            let food = env
+                .terrain
+                .get_tile_at((x.floor() as usize, y.floor() as usize))
+                .get_food_level();
+            if best.map_or(true, |(_, best_food)| food > best_food) {
+                best = Some((angle, food));
+            }
+        }
+
+        match best {
+            Some((angle, food)) if food > here.get_food_level() => {
+                self.turning = Self::wrap_angle(angle - body.get_rotation());
+                self.acceleration = ACCELERATION;
+                self.eating = 0.0;
+            }
+            _ => {
+                self.turning = 0.0;
+                self.acceleration = 0.0;
+                self.eating = 1.0;
+            }
+        }
+
+        self.mouth_hue = here.get_food_type();
+        self.birth = body.get_energy() - CREATURE_MAX_ENERGY;
+    }
+
+    fn run(&mut self) {}
+
+    fn use_output<B>(&self, env: &mut EnvironmentMut<B>, time_step: f64) {
+        env.this_body.accelerate(self.acceleration, time_step);
+        env.this_body.turn(self.turning, time_step);
+        env.this_body.set_mouth_hue(self.mouth_hue);
+
+        let tile_pos = env.this_body.get_random_covered_tile(env.board_size);
+        let tile = env.terrain.get_tile_at_mut(tile_pos);
+        env.this_body
+            .eat(self.eating, time_step, env.time, env.climate, tile);
+
+        env.give_birth(self.birth, 0.0);
+    }
+
+    /// There is no network, count it as a single node.
+    fn complexity(&self) -> usize {
+        1
+    }
+
+    /// Greedy brains never help a birth, so they can't be anyone's mate.
+    fn check_config(&self, config: &crate::config::SimConfig) -> Result<(), String> {
+        if config.reproduction == ReproductionMode::Sexual {
+            return Err(
+                "greedy brains never want to mate, they can't reproduce with sexual reproduction"
+                    .to_string(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl super::Intentions for GreedyBrain {
+    fn wants_birth(&self) -> f64 {
+        self.birth
+    }
+
+    fn wants_help_birth(&self) -> f64 {
+        0.0
+    }
+}
+
+impl super::GenerateRandom for GreedyBrain {
+    /// Greedy brains don't look around, `vision` is ignored.
+    fn new_random_with_vision(_vision: &super::vision::Vision) -> Self {
+        GreedyBrain::default()
+    }
+}
+
+impl super::RecombinationInfinite for GreedyBrain {
+    fn recombination_infinite_brains(_parents: &[&Self]) -> Self {
+        GreedyBrain::default()
+    }
+}
+
+impl super::ProvideInformation for GreedyBrain {
+    fn get_keys(&self) -> Vec<String> {
+        vec!["turning".to_string(), "eating".to_string()]
+    }
+
+    fn get_raw_values(&self) -> Vec<String> {
+        vec![
+            format!("{:.3}", self.turning),
+            format!("{:.3}", self.eating),
+        ]
+    }
+}
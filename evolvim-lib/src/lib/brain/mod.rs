@@ -1,22 +1,74 @@
 pub mod feed_forward;
 pub use feed_forward::Brain;
 
+pub mod any;
 mod environment;
-pub use environment::{Environment, EnvironmentMut};
+pub mod export;
+pub mod greedy;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod sensors;
+pub mod vision;
+pub use any::{AnyBrain, BrainKind};
+pub use environment::{Environment, EnvironmentMut, NearbyCreatures};
+pub use export::ExportWeights;
+pub use greedy::GreedyBrain;
+#[cfg(feature = "scripting")]
+pub use script::ScriptBrain;
+pub use vision::Vision;
 
+/// A brain that drives a creature.
+///
+/// The environment is generic over the brain type of the `Board` so a brain can also live inside another brain,
+/// like in `AnyBrain`.
 pub trait NeuralNet: Intentions {
-    fn load_input(&mut self, env: &Environment);
+    fn load_input<B>(&mut self, env: &Environment<B>);
 
     fn run(&mut self);
 
-    fn run_with(&mut self, env: &Environment) {
+    fn run_with<B>(&mut self, env: &Environment<B>) {
         self.load_input(env);
         self.run();
     }
 
-    fn use_output(&self, env: &mut EnvironmentMut<Self>, time_step: f64)
+    fn use_output<B>(&self, env: &mut EnvironmentMut<B>, time_step: f64);
+
+    /// Returns the size of this network: the amount of nodes plus the amount of (enabled) connections.
+    ///
+    /// Bigger brains cost more energy to keep running.
+    fn complexity(&self) -> usize;
+
+    /// Feeds everything this brain passes on to its babies to `hasher`, see `SoftBody::get_genome_fingerprint`.
+    ///
+    /// By default only the size of the brain counts, brains that evolve should hash their weights too.
+    fn write_genome(&self, hasher: &mut crate::hash::StateHasher) {
+        hasher.write_usize(self.complexity());
+    }
+
+    /// Returns how different the genomes of this brain and `other` are, 0 if they're the same. See `Species`.
+    ///
+    /// By default all brains of a kind are the same, brains that evolve should compare their weights.
+    fn genome_distance(&self, _other: &Self) -> f64
     where
-        Self: std::marker::Sized;
+        Self: std::marker::Sized,
+    {
+        0.0
+    }
+
+    /// Checks whether this brain is internally consistent, e.g. after loading it from a file.
+    ///
+    /// Returns a description of the first problem found.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Checks whether this brain can use what a board with `config` offers it: an input for every sense, e.g. the rays
+    /// of `config.vision`, and the outputs the board relies on, e.g. to find a mate.
+    ///
+    /// Returns a description of the first mismatch found.
+    fn check_config(&self, _config: &crate::config::SimConfig) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 pub trait Intentions {
@@ -25,7 +77,16 @@ pub trait Intentions {
 }
 
 pub trait GenerateRandom {
-    fn new_random() -> Self;
+    /// Returns a random brain that sees with the default `Vision`.
+    fn new_random() -> Self
+    where
+        Self: std::marker::Sized,
+    {
+        Self::new_random_with_vision(&vision::Vision::default())
+    }
+
+    /// Returns a random brain with an input for everything `vision` sees, see `Vision::input_count`.
+    fn new_random_with_vision(vision: &vision::Vision) -> Self;
 }
 
 pub trait RecombinationTwoParents {
@@ -35,9 +96,20 @@ pub trait RecombinationTwoParents {
 }
 
 pub trait RecombinationInfinite {
-    fn recombination_infinite_parents(parents: &Vec<crate::softbody::HLSoftBody<Self>>) -> Self
+    /// Combines the brains of any amount of parents, the last one is the parent that started the birth.
+    fn recombination_infinite_brains(parents: &[&Self]) -> Self
     where
         Self: NeuralNet + std::marker::Sized;
+
+    fn recombination_infinite_parents(parents: &Vec<crate::softbody::HLSoftBody<Self>>) -> Self
+    where
+        Self: NeuralNet + std::marker::Sized,
+    {
+        let creatures: Vec<_> = parents.iter().map(|p| p.borrow()).collect();
+        let brains: Vec<&Self> = creatures.iter().map(|c| &c.brain).collect();
+
+        Self::recombination_infinite_brains(&brains)
+    }
 }
 
 pub trait ProvideInformation {
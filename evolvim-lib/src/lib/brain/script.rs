@@ -0,0 +1,271 @@
+//! A brain whose behaviour is written by hand in Lua instead of evolved.
+//!
+//! This makes it easy to put simple baseline agents in the world and compare them against evolved creatures.
+//!
+//! A script has to define a global function `think(input)`. `input` is a table with the following fields:
+//! `energy`, `age`, `senescence`, `size`, `mouth_hue`, `tile_hue`, `tile_saturation`, `tile_brightness`,
+//! `ray_distance`, `ray_hue`, `ray_hit`, `pheromone`, `nearby_count`, `nearby_direction`, `nearby_heading`,
+//! `nearby_hue` and `light`; apart from `senescence` these are the same values a `Brain` gets. `think` should return a
+//! table with any of the following fields: `accelerate`, `turn`, `eat`, `mouth_hue`, `bite`, `pheromone`, `birth` and
+//! `help_birth`. Missing fields mean "do nothing", except for `mouth_hue` which then stays the same.
+//!
+//! ```lua
+//! function think(input)
+//!     return { accelerate = 0.5, eat = 1.0 - input.tile_hue }
+//! end
+//! ```
+
+extern crate rlua;
+
+use self::rlua::{Function, Lua, Table};
+use super::{Environment, EnvironmentMut};
+use crate::error::EvolvimError;
+
+/// The script used by `ScriptBrain::new_random()`: wander around and eat when standing on food.
+pub const DEFAULT_SCRIPT: &str = r#"
+function think(input)
+    local out = {}
+    out.accelerate = 0.3
+    out.eat = input.tile_saturation
+    if input.ray_hit < 0 and input.ray_distance < 0.3 then
+        -- Water ahead, turn away
+        out.turn = 1.0
+    else
+        out.turn = 0.1
+    end
+    out.birth = input.energy - 1.5
+    return out
+end
+"#;
+
+/// What the script wants to do this tick.
+#[derive(Clone, Debug, Default)]
+struct ScriptOutput {
+    accelerate: f64,
+    turn: f64,
+    eat: f64,
+    mouth_hue: Option<f64>,
+    bite: f64,
+    pheromone: f64,
+    birth: f64,
+    help_birth: f64,
+}
+
+pub struct ScriptBrain {
+    source: String,
+    lua: Lua,
+
+    input: Vec<(&'static str, f64)>,
+    output: ScriptOutput,
+    /// The last error the script ran into, if any.
+    error: Option<String>,
+}
+
+impl ScriptBrain {
+    /// Loads the script and checks that it defines `think`.
+    pub fn new(source: &str) -> Result<Self, EvolvimError> {
+        let lua = Lua::new();
+
+        lua.context(|ctx| -> rlua::Result<()> {
+            ctx.load(source).exec()?;
+            let _think: Function = ctx.globals().get("think")?;
+
+            Ok(())
+        })?;
+
+        Ok(ScriptBrain {
+            source: source.to_string(),
+            lua,
+
+            input: Vec::new(),
+            output: ScriptOutput::default(),
+            error: None,
+        })
+    }
+
+    pub fn load_from<P: AsRef<std::path::Path>>(path: P) -> Result<Self, EvolvimError> {
+        let source = std::fs::read_to_string(path)?;
+
+        ScriptBrain::new(&source)
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the last error thrown by the script, the brain does nothing while its script fails.
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_ref().map(|e| e.as_str())
+    }
+
+    fn think(&self) -> rlua::Result<ScriptOutput> {
+        let input = &self.input;
+
+        self.lua.context(|ctx| {
+            let table = ctx.create_table()?;
+            for (key, value) in input {
+                table.set(*key, *value)?;
+            }
+
+            let think: Function = ctx.globals().get("think")?;
+            let out: Table = think.call(table)?;
+
+            Ok(ScriptOutput {
+                accelerate: out.get::<_, Option<f64>>("accelerate")?.unwrap_or(0.0),
+                turn: out.get::<_, Option<f64>>("turn")?.unwrap_or(0.0),
+                eat: out.get::<_, Option<f64>>("eat")?.unwrap_or(0.0),
+                mouth_hue: out.get("mouth_hue")?,
+                bite: out.get::<_, Option<f64>>("bite")?.unwrap_or(0.0),
+                pheromone: out.get::<_, Option<f64>>("pheromone")?.unwrap_or(0.0),
+                birth: out.get::<_, Option<f64>>("birth")?.unwrap_or(0.0),
+                help_birth: out.get::<_, Option<f64>>("help_birth")?.unwrap_or(0.0),
+            })
+        })
+    }
+}
+
+impl std::fmt::Debug for ScriptBrain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ScriptBrain")
+            .field("source", &self.source)
+            .field("output", &self.output)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl super::NeuralNet for ScriptBrain {
+    fn load_input<B>(&mut self, env: &Environment<B>) {
+        let body = env.this_body;
+        let tile = env.terrain.get_tile_at(body.get_position().into());
+        let colors = tile.get_hsba_color();
+        let hit = super::vision::cast_ray(env, 0.0, env.vision.range);
+        let nearby = env.sense_nearby();
+
+        self.input = vec![
+            ("energy", env.get_energy()),
+            ("age", env.get_age()),
+            ("senescence", env.get_senescence()),
+            ("size", env.get_size()),
+            ("mouth_hue", body.get_mouth_hue()),
+            ("tile_hue", colors[0] as f64),
+            ("tile_saturation", colors[1] as f64),
+            ("tile_brightness", colors[2] as f64),
+            ("ray_distance", hit.distance),
+            ("ray_hue", hit.hue),
+            ("ray_hit", hit.hit_type.as_input()),
+            ("pheromone", env.smell_pheromone()),
+            ("nearby_count", nearby.count as f64),
+            ("nearby_direction", nearby.direction),
+            ("nearby_heading", nearby.heading),
+            ("nearby_hue", nearby.hue),
+            ("light", env.sense_light()),
+        ];
+    }
+
+    fn run(&mut self) {
+        match self.think() {
+            Ok(output) => {
+                self.output = output;
+                self.error = None;
+            }
+            Err(e) => {
+                self.output = ScriptOutput::default();
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn use_output<B>(&self, env: &mut EnvironmentMut<B>, time_step: f64) {
+        let out = &self.output;
+
+        env.this_body.accelerate(out.accelerate, time_step);
+        env.this_body.turn(out.turn, time_step);
+
+        let tile_pos = env.this_body.get_random_covered_tile(env.board_size);
+        let tile = env.terrain.get_tile_at_mut(tile_pos);
+        env.this_body
+            .eat(out.eat, time_step, env.time, env.climate, tile);
+
+        if let Some(hue) = out.mouth_hue {
+            env.this_body.set_mouth_hue(hue);
+        }
+
+        env.emit_pheromone(out.pheromone, time_step);
+
+        env.this_body.bite(
+            out.bite,
+            env.time,
+            time_step,
+            env.sbip,
+            env.self_pointer.clone(),
+        );
+
+        env.give_birth(out.birth, 0.0);
+    }
+
+    /// A script has no network so it's treated as a single node.
+    fn complexity(&self) -> usize {
+        1
+    }
+}
+
+impl super::Intentions for ScriptBrain {
+    fn wants_birth(&self) -> f64 {
+        self.output.birth
+    }
+
+    fn wants_help_birth(&self) -> f64 {
+        self.output.help_birth
+    }
+}
+
+impl Clone for ScriptBrain {
+    /// The copy runs the same script in a fresh Lua state, global variables of the script aren't copied.
+    fn clone(&self) -> Self {
+        ScriptBrain::new(&self.source).expect("A script that loaded before should load again.")
+    }
+}
+
+impl super::GenerateRandom for ScriptBrain {
+    /// Scripts can't be generated randomly, this returns a brain running `DEFAULT_SCRIPT` instead.
+    fn new_random_with_vision(_vision: &super::vision::Vision) -> Self {
+        ScriptBrain::new(DEFAULT_SCRIPT).expect("The default script should always load.")
+    }
+}
+
+impl super::RecombinationInfinite for ScriptBrain {
+    /// Babies run the same script as the parent that started the birth.
+    fn recombination_infinite_brains(parents: &[&Self]) -> Self {
+        let source = &parents
+            .last()
+            .expect("A baby needs at least one parent.")
+            .source;
+
+        ScriptBrain::new(source).expect("A script that loaded before should load again.")
+    }
+}
+
+impl super::ProvideInformation for ScriptBrain {
+    fn get_keys(&self) -> Vec<String> {
+        vec!["script error".to_string()]
+    }
+
+    fn get_raw_values(&self) -> Vec<String> {
+        vec![self.get_error().unwrap_or("none").to_string()]
+    }
+}
+
+impl serde::Serialize for ScriptBrain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ScriptBrain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<ScriptBrain, D::Error> {
+        let source = String::deserialize(deserializer)?;
+
+        ScriptBrain::new(&source).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,106 @@
+//! A readable snapshot of everything a creature's sensors perceive, used for debugging evolved behaviour.
+
+use super::vision::RayHit;
+use super::Environment;
+use crate::terrain::Biome;
+
+/// A single vision ray and what it hit.
+#[derive(Clone, Debug)]
+pub struct RayReading {
+    /// The absolute angle of the ray, in radians.
+    pub angle: f64,
+    /// The range of the ray, `hit.distance` is relative to this.
+    pub range: f64,
+    pub hit: RayHit,
+}
+
+impl RayReading {
+    /// Returns the point where this ray stopped when cast from `origin`.
+    pub fn get_end_point(&self, origin: (f64, f64)) -> (f64, f64) {
+        let length = self.hit.distance * self.range;
+
+        (
+            origin.0 + self.angle.cos() * length,
+            origin.1 + self.angle.sin() * length,
+        )
+    }
+}
+
+/// What a creature perceives at one moment.
+#[derive(Clone, Debug)]
+pub struct SensorReadings {
+    /// The center of the body, this is where the rays come from.
+    pub origin: (f64, f64),
+    pub rays: Vec<RayReading>,
+    /// The amount of pheromone smelled at the center of the body.
+    pub pheromone: f64,
+    /// How light it is, from 0 at midnight to 1 at noon.
+    pub light: f64,
+    /// The color of the tile under the mouth as hsba.
+    pub tile_color: [f32; 4],
+    /// The food level of the tile under the mouth.
+    pub tile_food: f64,
+    pub tile_is_water: bool,
+    /// The biome under the mouth, `None` for water.
+    pub tile_biome: Option<Biome>,
+}
+
+impl SensorReadings {
+    /// Reads all sensors of `env.this_body`, the rays are those of `env.vision`.
+    pub fn read<B>(env: &Environment<B>) -> Self {
+        let body = env.this_body;
+        let vision = env.vision;
+
+        let rays = vision
+            .get_ray_angles()
+            .into_iter()
+            .zip(vision.look(env))
+            .map(|(angle, hit)| RayReading {
+                angle: body.get_rotation() + angle,
+                range: vision.range,
+                hit,
+            })
+            .collect();
+
+        let tile = env.terrain.get_tile_at(body.get_position().into());
+
+        SensorReadings {
+            origin: (body.get_px(), body.get_py()),
+            rays,
+            pheromone: env.smell_pheromone(),
+            light: env.sense_light(),
+            tile_color: tile.get_hsba_color(),
+            tile_food: tile.get_food_level(),
+            tile_is_water: tile.is_water(),
+            tile_biome: tile.get_biome(),
+        }
+    }
+
+    /// Returns a line of text for every reading, ready to be shown in a panel.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for (i, ray) in self.rays.iter().enumerate() {
+            lines.push(format!(
+                "Ray {}: {:?} at {:.2}, hue {:.2}",
+                i, ray.hit.hit_type, ray.hit.distance, ray.hit.hue
+            ));
+        }
+
+        lines.push(format!("Pheromone: {:.3}", self.pheromone));
+        lines.push(format!("Light: {:.2}", self.light));
+
+        if self.tile_is_water {
+            lines.push("Mouth tile: water".to_string());
+        } else {
+            lines.push(format!(
+                "Mouth tile: food {:.2}, hue {:.2}, {:?}",
+                self.tile_food,
+                self.tile_color[0],
+                self.tile_biome.unwrap_or_default()
+            ));
+        }
+
+        return lines;
+    }
+}
@@ -0,0 +1,164 @@
+//! Lets creatures look around by casting rays from their body.
+//!
+//! Every ray travels in a straight line from the edge of the body until it hits another creature or a notable tile
+//! (currently only water) or until it runs out of range.
+
+use super::Environment;
+use crate::constants::*;
+use crate::softbody::distance;
+
+/// The kind of object a ray ran into.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HitType {
+    /// The ray didn't hit anything within its range.
+    Nothing,
+    /// The ray hit another creature.
+    Creature,
+    /// The ray hit a water tile.
+    Water,
+    /// The ray hit a tile creatures can't enter.
+    Obstacle,
+}
+
+impl HitType {
+    /// Returns a number representing this type that can be fed into a brain.
+    pub fn as_input(&self) -> f64 {
+        match self {
+            HitType::Nothing => 0.0,
+            HitType::Creature => 1.0,
+            HitType::Water => -1.0,
+            HitType::Obstacle => -0.5,
+        }
+    }
+}
+
+/// The result of casting a single ray.
+#[derive(Clone, Debug)]
+pub struct RayHit {
+    /// The distance to the object that was hit relative to the range of the ray, ranges from 0 to 1.
+    ///
+    /// This is 1 if nothing was hit.
+    pub distance: f64,
+    /// The hue of the object that was hit, this is the mouth hue for creatures.
+    pub hue: f64,
+    /// What was hit.
+    pub hit_type: HitType,
+}
+
+impl RayHit {
+    fn nothing() -> Self {
+        RayHit {
+            distance: 1.0,
+            hue: 0.0,
+            hit_type: HitType::Nothing,
+        }
+    }
+}
+
+/// The amount of brain inputs every ray takes: the distance, hue and type of what it hit.
+pub const INPUTS_PER_RAY: usize = 3;
+
+/// The eyes of a creature: `ray_amount` rays spread evenly over `field_of_view` radians around the direction it's facing.
+///
+/// Part of a `SimConfig`, the brains of new random creatures get an input for everything these eyes see.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Vision {
+    pub ray_amount: usize,
+    pub field_of_view: f64,
+    pub range: f64,
+}
+
+impl Default for Vision {
+    fn default() -> Self {
+        Vision {
+            ray_amount: VISION_RAY_AMOUNT,
+            field_of_view: VISION_FIELD_OF_VIEW,
+            range: VISION_RANGE,
+        }
+    }
+}
+
+impl Vision {
+    /// Returns the amount of brain inputs these eyes need, every brain type sizes its vision inputs with this.
+    pub fn input_count(&self) -> usize {
+        self.ray_amount * INPUTS_PER_RAY
+    }
+
+    /// Returns the angles of all rays relative to the rotation of the creature.
+    pub fn get_ray_angles(&self) -> Vec<f64> {
+        if self.ray_amount == 1 {
+            return vec![0.0];
+        }
+
+        let step = self.field_of_view / (self.ray_amount - 1) as f64;
+        (0..self.ray_amount)
+            .map(|i| i as f64 * step - self.field_of_view / 2.0)
+            .collect()
+    }
+
+    /// Casts all rays and returns what they hit, in the same order as `get_ray_angles()`.
+    pub fn look<B>(&self, env: &Environment<B>) -> Vec<RayHit> {
+        self.get_ray_angles()
+            .into_iter()
+            .map(|angle| cast_ray(env, angle, self.range))
+            .collect()
+    }
+}
+
+/// Casts a single ray at `relative_angle` (relative to the rotation of the creature) and returns the first thing it hits.
+pub fn cast_ray<B>(env: &Environment<B>, relative_angle: f64, range: f64) -> RayHit {
+    let body = env.this_body;
+    let angle = body.get_rotation() + relative_angle;
+    let (dir_x, dir_y) = (angle.cos(), angle.sin());
+    let (width, height) = (env.terrain.get_width(), env.terrain.get_height());
+
+    // Start at the edge of the body so we don't see ourselves.
+    let mut travelled = body.get_radius();
+    while travelled < range {
+        let x = body.get_px() + dir_x * travelled;
+        let y = body.get_py() + dir_y * travelled;
+
+        if x < 0.0 || y < 0.0 || x >= width as f64 || y >= height as f64 {
+            // The ray left the board.
+            break;
+        }
+
+        let (tile_x, tile_y) = (x.floor() as usize, y.floor() as usize);
+
+        for c in env.sbip.get_soft_bodies_at(tile_x, tile_y) {
+            // Don't borrow ourselves, we're already borrowed mutably.
+            if *c == env.self_pointer {
+                continue;
+            }
+
+            let c = c.borrow();
+            if distance(x, y, c.get_px(), c.get_py()) < c.get_radius() {
+                return RayHit {
+                    distance: travelled / range,
+                    hue: c.get_mouth_hue(),
+                    hit_type: HitType::Creature,
+                };
+            }
+        }
+
+        let tile = env.terrain.get_tile_at((tile_x, tile_y));
+        if tile.is_water() {
+            return RayHit {
+                distance: travelled / range,
+                hue: COLOR_WATER[0] as f64,
+                hit_type: HitType::Water,
+            };
+        } else if tile.is_obstacle() {
+            return RayHit {
+                distance: travelled / range,
+                hue: COLOR_OBSTACLE[0] as f64,
+                hit_type: HitType::Obstacle,
+            };
+        }
+
+        travelled += VISION_RAY_STEP;
+    }
+
+    RayHit::nothing()
+}
@@ -0,0 +1,166 @@
+//! Saving a running `Board` every now and then so a crash doesn't lose everything.
+//!
+//! `Checkpoints` writes the board to a directory every few simulated years and only keeps the most recent files.
+//! `Board::restore_latest` loads the newest checkpoint that can still be read.
+
+use crate::brain::NeuralNet;
+use crate::error::EvolvimError;
+use crate::Board;
+use std::path::{Path, PathBuf};
+
+const PREFIX: &str = "checkpoint-";
+const EXTENSION: &str = "bin";
+
+/// Saves a `Board` to a rotating set of files in a directory.
+#[derive(Clone, Debug)]
+pub struct Checkpoints {
+    dir: PathBuf,
+    /// The amount of simulated years between two checkpoints.
+    interval: f64,
+    /// The amount of checkpoints to keep, older ones are removed.
+    keep: usize,
+    /// The time the next checkpoint is due, set by the first call to `tick`.
+    next_time: Option<f64>,
+}
+
+impl Checkpoints {
+    /// Saves a checkpoint to `dir` every `interval` years and keeps the last `keep` of them.
+    pub fn new<P: Into<PathBuf>>(dir: P, interval: f64, keep: usize) -> Self {
+        assert!(
+            interval > 0.0,
+            "The interval between two checkpoints has to be positive."
+        );
+        assert!(keep > 0, "At least one checkpoint has to be kept.");
+
+        Checkpoints {
+            dir: dir.into(),
+            interval,
+            keep,
+            next_time: None,
+        }
+    }
+
+    pub fn get_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Saves a checkpoint if `interval` years passed since the last one, call this after updating `board`.
+    ///
+    /// The first call only starts the clock. Returns the file that was written, if any.
+    pub fn tick<B: NeuralNet + serde::Serialize>(
+        &mut self,
+        board: &Board<B>,
+    ) -> Result<Option<PathBuf>, EvolvimError> {
+        let time = board.get_time();
+
+        match self.next_time {
+            Some(next_time) if time >= next_time => self.save(board).map(Some),
+            Some(_) => Ok(None),
+            None => {
+                self.next_time = Some(time + self.interval);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Saves a checkpoint right now and removes the ones that are too old.
+    pub fn save<B: NeuralNet + serde::Serialize>(
+        &mut self,
+        board: &Board<B>,
+    ) -> Result<PathBuf, EvolvimError> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let time = board.get_time();
+        let path = self.dir.join(format!(
+            "{}{:012}.{}",
+            PREFIX,
+            (time * 1000.0).round() as u64,
+            EXTENSION
+        ));
+
+        // Write to a temporary file first so a crash while saving can't ruin the last checkpoint.
+        let temporary = path.with_extension("tmp");
+        if let Err(e) = write_synced(board, &temporary) {
+            // A half written file is of no use to anyone.
+            let _ = std::fs::remove_file(&temporary);
+            return Err(e);
+        }
+        std::fs::rename(&temporary, &path)?;
+
+        self.next_time = Some(time + self.interval);
+
+        let checkpoints = list(&self.dir)?;
+        if checkpoints.len() > self.keep {
+            for old in &checkpoints[..checkpoints.len() - self.keep] {
+                std::fs::remove_file(old)?;
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// Saves `board` to `path` and waits until it's really on the disk.
+///
+/// Only after this succeeded may the file replace a checkpoint or may older checkpoints be removed, otherwise a crash
+/// of the whole machine could still leave nothing but a truncated file.
+fn write_synced<B: NeuralNet + serde::Serialize>(
+    board: &Board<B>,
+    path: &Path,
+) -> Result<(), EvolvimError> {
+    board.save_to(path)?;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .sync_all()?;
+
+    Ok(())
+}
+
+/// Returns all checkpoints in `dir`, oldest first.
+pub fn list<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, EvolvimError> {
+    let mut checkpoints: Vec<(u64, PathBuf)> = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+            continue;
+        }
+
+        let time = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .filter(|s| s.starts_with(PREFIX))
+            .and_then(|s| s[PREFIX.len()..].parse().ok());
+        if let Some(time) = time {
+            checkpoints.push((time, path));
+        }
+    }
+
+    checkpoints.sort();
+
+    Ok(checkpoints.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Loads the newest checkpoint in `dir` that can be read, see `Board::restore_latest`.
+pub(crate) fn restore_latest<B, P>(dir: P) -> Result<Board<B>, EvolvimError>
+where
+    B: NeuralNet + serde::de::DeserializeOwned,
+    P: AsRef<Path>,
+{
+    let mut newest_error = None;
+
+    for path in list(&dir)?.iter().rev() {
+        match Board::load_from(path) {
+            Ok(board) => return Ok(board),
+            Err(e) => {
+                newest_error.get_or_insert(e);
+            }
+        }
+    }
+
+    Err(newest_error.unwrap_or_else(|| {
+        let message = format!("there are no checkpoints in {}", dir.as_ref().display());
+        std::io::Error::new(std::io::ErrorKind::NotFound, message).into()
+    }))
+}
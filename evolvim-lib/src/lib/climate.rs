@@ -1,37 +1,451 @@
+use crate::constants::{
+    CLIMATE_HISTORY_LENGTH, CLIMATE_HISTORY_RESOLUTION, CLIMATE_ZONE_BANDS,
+    CLIMATE_ZONE_POLAR_COOLING, DAY_TEMPERATURE_RANGE, DEFAULT_ICE_AGE_PERIOD,
+    DEFAULT_OSCILLATION_PERIOD, ELEVATION_COOLING, WEATHER_MEMORY,
+};
+use crate::history::{History, RetentionPolicy};
+use crate::weather::{WeatherConfig, WeatherEvent};
 use std::f64::consts::PI;
 
+/// A climate that replaces the normal one in part of the board, see `Tile::set_region`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegionalClimate {
+    pub min_temperature: f64,
+    pub max_temperature: f64,
+    /// Food grows this many times as fast as it would with the same temperature elsewhere.
+    pub growth_multiplier: f64,
+    /// How many years the seasons here are ahead of the normal ones, 0.5 swaps summer and winter.
+    pub phase: f64,
+}
+
+impl RegionalClimate {
+    pub fn new(min_temperature: f64, max_temperature: f64, growth_multiplier: f64) -> Self {
+        RegionalClimate {
+            min_temperature,
+            max_temperature,
+            growth_multiplier,
+            phase: 0.0,
+        }
+    }
+}
+
+/// How the board is divided into climate regions, see `SimConfig::climate_zones`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClimateZones {
+    /// The whole board has the same climate.
+    Uniform,
+    /// `CLIMATE_ZONE_BANDS` bands from the top to the bottom of the board. The winters get colder towards the poles
+    /// and the seasons of the bottom half are half a year behind those of the top half, like on two hemispheres.
+    Latitude,
+}
+
+impl Default for ClimateZones {
+    fn default() -> Self {
+        ClimateZones::Uniform
+    }
+}
+
+impl ClimateZones {
+    /// Returns the climates of the zones for a board going from `min_temperature` to `max_temperature`, see
+    /// `Climate::set_regions`.
+    pub fn get_regions(self, min_temperature: f64, max_temperature: f64) -> Vec<RegionalClimate> {
+        match self {
+            ClimateZones::Uniform => Vec::new(),
+            ClimateZones::Latitude => (0..CLIMATE_ZONE_BANDS)
+                .map(|band| {
+                    let middle = (band as f64 + 0.5) / CLIMATE_ZONE_BANDS as f64;
+                    let latitude = (middle * 2.0 - 1.0).abs();
+
+                    RegionalClimate {
+                        phase: if middle > 0.5 { 0.5 } else { 0.0 },
+                        ..RegionalClimate::new(
+                            min_temperature - CLIMATE_ZONE_POLAR_COOLING * latitude,
+                            max_temperature,
+                            1.0,
+                        )
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the climate region of the tiles in row `y` of a board `height` tiles high.
+    pub fn get_region(self, y: usize, height: usize) -> usize {
+        match self {
+            ClimateZones::Uniform => 0,
+            ClimateZones::Latitude => y * CLIMATE_ZONE_BANDS / height.max(1) + 1,
+        }
+    }
+}
+
+/// A slow change of the temperature of the whole board over many years, see `Climate::set_drift`.
+///
+/// The trend and the ice age cycle are added together, both start at 0 when the board is made.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClimateDrift {
+    /// How much warmer it gets every year, negative for a climate that cools down.
+    pub trend: f64,
+    /// How much warmer and colder than normal it gets during an ice age cycle, 0 for no ice ages.
+    pub cycle_amplitude: f64,
+    /// The length of an ice age cycle in years, every cycle starts with a warm period.
+    pub cycle_period: f64,
+}
+
+impl Default for ClimateDrift {
+    fn default() -> Self {
+        ClimateDrift {
+            trend: 0.0,
+            cycle_amplitude: 0.0,
+            cycle_period: DEFAULT_ICE_AGE_PERIOD,
+        }
+    }
+}
+
+impl ClimateDrift {
+    /// Returns how much warmer than normal it is at `time`.
+    pub fn get_offset(&self, time: f64) -> f64 {
+        self.trend * time + sine_wave(self.cycle_amplitude, self.cycle_period, time)
+    }
+
+    /// Returns the growth the drift adds from `last_updated` until `time`, the integral of `get_offset`.
+    pub fn get_growth_over_time_range(&self, time: f64, last_updated: f64) -> f64 {
+        let trend = self.trend * 0.5 * (time * time - last_updated * last_updated);
+        let cycle = sine_wave_integral(self.cycle_amplitude, self.cycle_period, time, last_updated);
+
+        trend + cycle
+    }
+}
+
+/// Good and bad years on top of the seasons, like El Niño, see `Climate::set_oscillation`.
+///
+/// Unlike the drift this is part of the growth rate of the season, see `Climate::get_growth_rate`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClimateOscillation {
+    /// How much higher the growth rate is in the best years and lower in the worst, 0 for no oscillation.
+    pub amplitude: f64,
+    /// How many years it takes to go from good years to bad years and back, the first years are good.
+    pub period: f64,
+}
+
+impl Default for ClimateOscillation {
+    fn default() -> Self {
+        ClimateOscillation {
+            amplitude: 0.0,
+            period: DEFAULT_OSCILLATION_PERIOD,
+        }
+    }
+}
+
+impl ClimateOscillation {
+    /// Returns how much higher than normal the growth rate is at `time`.
+    pub fn get_offset(&self, time: f64) -> f64 {
+        sine_wave(self.amplitude, self.period, time)
+    }
+
+    /// Returns the growth the oscillation adds from `last_updated` until `time`, the integral of `get_offset`.
+    pub fn get_growth_over_time_range(&self, time: f64, last_updated: f64) -> f64 {
+        sine_wave_integral(self.amplitude, self.period, time, last_updated)
+    }
+}
+
+/// The climate at one moment, see `Climate::history`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClimateSample {
+    /// The temperature with the time of day and the drift, see `Climate::get_temperature`.
+    pub temperature: f64,
+    /// The growth rate of the season with the oscillation and the drift, but without the time of day and the weather.
+    pub growth_rate: f64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Climate {
+    /// The time of the last `update`.
+    time: f64,
     temperature: f64,
     min_temperature: f64,
     max_temperature: f64,
+    /// Region 0 uses the climate above, region `i` uses `regions[i - 1]`.
+    regions: Vec<RegionalClimate>,
+    /// The length of a day in years, 0 if there are no days and nights.
+    day_length: f64,
+    /// How much colder it is at the poles than at the equator.
+    latitude_cooling: f64,
+    /// The weather that's going on and the weather of the last `WEATHER_MEMORY` years.
+    weather: Vec<WeatherEvent>,
+    drift: ClimateDrift,
+    oscillation: ClimateOscillation,
+    /// The temperature every `history_resolution` years, see `update`.
+    history: History<ClimateSample>,
+    history_resolution: f64,
 }
 
 impl Climate {
-    /// Returns the growth rate (temperature) for the given time.
+    /// Returns the growth rate (temperature) of the season at the given time including the oscillation, without the
+    /// days and nights.
     pub fn get_growth_rate(&self, time: f64) -> f64 {
-        let temp_range = self.max_temperature - self.min_temperature;
-        return self.min_temperature + temp_range * 0.5
-            - temp_range * 0.5 * ((time % 1.0) * 2.0 * PI).cos();
+        season_temperature(self.min_temperature, self.max_temperature, time)
+            + self.oscillation.get_offset(time)
+    }
+
+    /// Does the same as `get_growth_rate` but for the tiles in the given region.
+    pub fn get_region_growth_rate(&self, region: usize, time: f64) -> f64 {
+        match self.get_region(region) {
+            Some(r) => {
+                season_temperature(r.min_temperature, r.max_temperature, time + r.phase)
+                    + self.oscillation.get_offset(time)
+            }
+            None => self.get_growth_rate(time),
+        }
     }
 
+    /// Returns how much light there is at the given time, from 0 at midnight to 1 at noon.
+    ///
+    /// Without a day cycle it's always 1, see `set_day_length`.
+    pub fn get_light_level(&self, time: f64) -> f64 {
+        if self.day_length > 0.0 {
+            0.5 - 0.5 * (time / self.day_length * 2.0 * PI).cos()
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the growth from `last_updated` until `time`, this includes the warmth of the days and the cold of the
+    /// nights, the oscillation and the drift.
     pub fn get_growth_over_time_range(&self, time: f64, last_updated: f64) -> f64 {
-        let temp_range = self.max_temperature - self.min_temperature;
-        let m = self.min_temperature + temp_range * 0.5;
+        growth_over_time_range(
+            self.min_temperature,
+            self.max_temperature,
+            time,
+            last_updated,
+        ) + self.get_daylight_growth_over_time_range(time, last_updated)
+            + self
+                .oscillation
+                .get_growth_over_time_range(time, last_updated)
+            + self.drift.get_growth_over_time_range(time, last_updated)
+    }
+
+    /// Does the same as `get_growth_over_time_range` but for the tiles in the given region, including the weather in
+    /// that region.
+    pub fn get_region_growth_over_time_range(
+        &self,
+        region: usize,
+        time: f64,
+        last_updated: f64,
+    ) -> f64 {
+        let climate = match self.get_region(region) {
+            Some(r) => {
+                r.growth_multiplier
+                    * growth_over_time_range(
+                        r.min_temperature,
+                        r.max_temperature,
+                        time + r.phase,
+                        last_updated + r.phase,
+                    )
+                    + self.get_daylight_growth_over_time_range(time, last_updated)
+                    + self
+                        .oscillation
+                        .get_growth_over_time_range(time, last_updated)
+                    + self.drift.get_growth_over_time_range(time, last_updated)
+            }
+            None => self.get_growth_over_time_range(time, last_updated),
+        };
+        let weather: f64 = self
+            .weather
+            .iter()
+            .filter(|w| w.region == region)
+            .map(|w| w.get_growth_over_time_range(time, last_updated))
+            .sum();
+
+        climate + weather
+    }
+
+    /// Does the same as `get_region_growth_over_time_range` but for a tile at `elevation` (between 0 and 1), the
+    /// higher the tile the colder it is.
+    pub fn get_growth_at_elevation_over_time_range(
+        &self,
+        region: usize,
+        elevation: f64,
+        time: f64,
+        last_updated: f64,
+    ) -> f64 {
+        self.get_region_growth_over_time_range(region, time, last_updated)
+            - ELEVATION_COOLING * elevation * (time - last_updated)
+    }
+
+    /// Does the same as `get_growth_at_elevation_over_time_range` but also makes it colder the further the tile is
+    /// from the equator, `latitude` goes from 0 at the equator to 1 at the poles.
+    pub fn get_local_growth_over_time_range(
+        &self,
+        region: usize,
+        elevation: f64,
+        latitude: f64,
+        time: f64,
+        last_updated: f64,
+    ) -> f64 {
+        self.get_growth_at_elevation_over_time_range(region, elevation, time, last_updated)
+            - self.latitude_cooling * latitude * (time - last_updated)
+    }
+
+    /// Returns the temperature at the given `elevation` right now, see `get_temperature`.
+    pub fn get_temperature_at_elevation(&self, elevation: f64) -> f64 {
+        self.temperature - ELEVATION_COOLING * elevation
+    }
+
+    /// Returns the temperature in `region` right now, including the warmth of the time of day.
+    pub fn get_region_temperature(&self, region: usize) -> f64 {
+        match self.get_region(region) {
+            Some(_) => {
+                let daylight = self.temperature - self.get_growth_rate(self.time);
+                self.get_region_growth_rate(region, self.time) + daylight
+            }
+            None => self.temperature,
+        }
+    }
+
+    /// Returns the temperature in `region` at the given `elevation` and `latitude` right now, see
+    /// `get_local_growth_over_time_range`.
+    pub fn get_local_temperature(&self, region: usize, elevation: f64, latitude: f64) -> f64 {
+        self.get_region_temperature(region)
+            - ELEVATION_COOLING * elevation
+            - self.latitude_cooling * latitude
+    }
 
-        return (time - last_updated) * m
-            + (temp_range / PI / 4.0) * ((PI * 2.0 * last_updated).sin() - (PI * 2.0 * time).sin());
+    /// Sets the climates of regions 1 and up, tiles in region 0 or in a region without a climate use the normal one.
+    pub fn set_regions(&mut self, regions: Vec<RegionalClimate>) {
+        self.regions = regions;
     }
 
+    /// Gives this climate days and nights of `day_length` years, 0 turns them off.
+    ///
+    /// Days are warmer than nights by `DAY_TEMPERATURE_RANGE`, food grows faster during the day but just as much as
+    /// without days over a whole day.
+    pub fn set_day_length(&mut self, day_length: f64) {
+        self.day_length = day_length.max(0.0);
+    }
+
+    /// Gives the climate good and bad years, see `ClimateOscillation`.
+    pub fn set_oscillation(&mut self, oscillation: ClimateOscillation) {
+        self.oscillation = oscillation;
+    }
+
+    /// Makes the whole board slowly warm up or cool down, see `ClimateDrift`.
+    pub fn set_drift(&mut self, drift: ClimateDrift) {
+        self.drift = drift;
+    }
+
+    /// Starts new weather at random according to `config` and forgets old weather, returns the weather that started.
+    ///
+    /// `time_step` is the time since the last call, see `WeatherConfig::frequency`.
+    pub fn update_weather(
+        &mut self,
+        time: f64,
+        time_step: f64,
+        config: &WeatherConfig,
+    ) -> Option<WeatherEvent> {
+        self.weather.retain(|w| w.end + WEATHER_MEMORY > time);
+
+        let event = config.roll(time, time_step, self.regions.len() + 1)?;
+        self.weather.push(event.clone());
+
+        Some(event)
+    }
+
+    /// Adds weather by hand, e.g. to script a drought.
+    pub fn add_weather(&mut self, event: WeatherEvent) {
+        self.weather.push(event);
+    }
+
+    /// Returns the weather that's going on at `time`.
+    pub fn get_active_weather(&self, time: f64) -> impl Iterator<Item = &WeatherEvent> {
+        self.weather.iter().filter(move |w| w.is_active(time))
+    }
+
+    /// Returns true if any weather started or ended after `last_updated` until `time`.
+    pub fn has_weather_changed(&self, time: f64, last_updated: f64) -> bool {
+        let between = |t: f64| t > last_updated && t <= time;
+
+        self.weather
+            .iter()
+            .any(|w| between(w.start) || between(w.end))
+    }
+
+    /// Makes the poles `latitude_cooling` colder than the equator, 0 gives the whole board the same temperature.
+    pub fn set_latitude_cooling(&mut self, latitude_cooling: f64) {
+        self.latitude_cooling = latitude_cooling.max(0.0);
+    }
+
+    /// The temperature is the one of the season (see `get_growth_rate`) plus the warmth of the time of day and the
+    /// drift.
     pub fn update(&mut self, time: f64) {
-        self.temperature = self.get_growth_rate(time);
+        let daylight = if self.day_length > 0.0 {
+            DAY_TEMPERATURE_RANGE * (self.get_light_level(time) - 0.5)
+        } else {
+            0.0
+        };
+
+        self.time = time;
+        self.temperature = self.get_growth_rate(time) + daylight + self.drift.get_offset(time);
+
+        let due = self
+            .history
+            .last()
+            .map_or(true, |&(last, _)| time - last >= self.history_resolution);
+        if due {
+            let sample = ClimateSample {
+                temperature: self.temperature,
+                growth_rate: self.get_growth_rate(time) + self.drift.get_offset(time),
+            };
+            // If spilling to disk fails the samples stay in memory and it's tried again next time.
+            let _ = self.history.push(time, sample);
+        }
+    }
+
+    /// Records the temperature every `resolution` years from now on, see `history`.
+    pub fn set_history_resolution(&mut self, resolution: f64) {
+        self.history_resolution = resolution.max(0.0);
+    }
+
+    /// Changes how many old temperatures are remembered, by default the last `CLIMATE_HISTORY_LENGTH`.
+    pub fn set_history_policy(&mut self, policy: RetentionPolicy) {
+        self.history.set_policy(policy);
     }
 
     pub fn new(min: f64, max: f64) -> Self {
         Climate {
+            time: 0.0,
             temperature: 0.0,
             min_temperature: min,
             max_temperature: max,
+            regions: Vec::new(),
+            day_length: 0.0,
+            latitude_cooling: 0.0,
+            weather: Vec::new(),
+            drift: ClimateDrift::default(),
+            oscillation: ClimateOscillation::default(),
+            history: History::new(RetentionPolicy::KeepCount {
+                entries: CLIMATE_HISTORY_LENGTH,
+            }),
+            history_resolution: CLIMATE_HISTORY_RESOLUTION,
+        }
+    }
+
+    /// Returns the climate of `region`, `None` for region 0 and regions without their own climate.
+    fn get_region(&self, region: usize) -> Option<&RegionalClimate> {
+        region.checked_sub(1).and_then(|i| self.regions.get(i))
+    }
+
+    /// The integral of the daily temperature swing from `last_updated` until `time`.
+    fn get_daylight_growth_over_time_range(&self, time: f64, last_updated: f64) -> f64 {
+        if self.day_length > 0.0 {
+            let phase = |t: f64| (t / self.day_length * 2.0 * PI).sin();
+
+            -DAY_TEMPERATURE_RANGE * 0.5 * self.day_length / (2.0 * PI)
+                * (phase(time) - phase(last_updated))
+        } else {
+            0.0
         }
     }
 }
@@ -41,4 +455,87 @@ impl Climate {
     pub fn get_temperature(&self) -> f64 {
         return self.temperature;
     }
+
+    pub fn get_min_temperature(&self) -> f64 {
+        return self.min_temperature;
+    }
+
+    pub fn get_max_temperature(&self) -> f64 {
+        return self.max_temperature;
+    }
+
+    pub fn get_regions(&self) -> &[RegionalClimate] {
+        &self.regions
+    }
+
+    pub fn get_day_length(&self) -> f64 {
+        self.day_length
+    }
+
+    pub fn get_latitude_cooling(&self) -> f64 {
+        self.latitude_cooling
+    }
+
+    pub fn get_drift(&self) -> &ClimateDrift {
+        &self.drift
+    }
+
+    pub fn get_oscillation(&self) -> &ClimateOscillation {
+        &self.oscillation
+    }
+
+    /// Returns how much warmer than normal the drift makes it at `time`, see `ClimateDrift::get_offset`.
+    pub fn get_drift_offset(&self, time: f64) -> f64 {
+        self.drift.get_offset(time)
+    }
+
+    /// Returns the weather that's going on and the weather that ended less than `WEATHER_MEMORY` years ago.
+    pub fn get_weather(&self) -> &[WeatherEvent] {
+        &self.weather
+    }
+
+    /// Returns the temperature and growth rate recorded every `get_history_resolution` years by `update`, to plot
+    /// them next to the population.
+    pub fn history(&self) -> &History<ClimateSample> {
+        &self.history
+    }
+
+    pub fn get_history_resolution(&self) -> f64 {
+        self.history_resolution
+    }
+}
+
+/// Returns the temperature of the season at `time` in a climate going from `min_temperature` to `max_temperature`.
+fn season_temperature(min_temperature: f64, max_temperature: f64, time: f64) -> f64 {
+    let temp_range = max_temperature - min_temperature;
+
+    min_temperature + temp_range * 0.5 - temp_range * 0.5 * ((time % 1.0) * 2.0 * PI).cos()
+}
+
+fn growth_over_time_range(
+    min_temperature: f64,
+    max_temperature: f64,
+    time: f64,
+    last_updated: f64,
+) -> f64 {
+    let temp_range = max_temperature - min_temperature;
+    let m = min_temperature + temp_range * 0.5;
+
+    return (time - last_updated) * m
+        + (temp_range / PI / 4.0) * ((PI * 2.0 * last_updated).sin() - (PI * 2.0 * time).sin());
+}
+
+/// A wave going up to `amplitude` and down to `-amplitude` every `period` years, starting at 0 and going up.
+fn sine_wave(amplitude: f64, period: f64, time: f64) -> f64 {
+    amplitude * (time / period * 2.0 * PI).sin()
+}
+
+/// The integral of `sine_wave` from `last_updated` until `time`.
+fn sine_wave_integral(amplitude: f64, period: f64, time: f64, last_updated: f64) -> f64 {
+    if amplitude != 0.0 {
+        let phase = |t: f64| (t / period * 2.0 * PI).cos();
+        -amplitude * period / (2.0 * PI) * (phase(time) - phase(last_updated))
+    } else {
+        0.0
+    }
 }
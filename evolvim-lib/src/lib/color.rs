@@ -0,0 +1,40 @@
+//! Turning the HSBA colors used throughout this crate into RGBA colors for drawing.
+
+/// Converts hsba (Hue, Saturation, Brightness, Alpha) into rgba (Red, Green, Blue, Alpha)
+///
+/// All input values should range from 0 to 1. All output values will range from 0 to 1.
+///
+/// Formulae from [here](https://en.wikipedia.org/wiki/HSL_and_HSV#From_HSV)
+pub fn hsba_to_rgba(hsba: [f32; 4]) -> [f32; 4] {
+    let [hue, sat, bri, alpha] = hsba;
+
+    assert!(hue <= 1.0, "Hue can't be larger than 1.");
+    assert!(sat <= 1.0);
+    assert!(bri <= 1.0);
+    assert!(alpha <= 1.0);
+
+    // Chroma
+    let c = bri * sat;
+    // H' = hue * 360 / 60 = hue * 6
+    let mut h = hue * 6.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+
+    if h == 0.0 {
+        h = 1.0;
+    }
+
+    let (r, g, b): (f32, f32, f32) = match h.ceil() as usize {
+        1 => (c, x, 0.0),
+        2 => (x, c, 0.0),
+        3 => (0.0, c, x),
+        4 => (0.0, x, c),
+        5 => (x, 0.0, c),
+        6 => (c, 0.0, x),
+        // Value should not be larger than 6 --> hue should not be larger than 1
+        _ => unreachable!(),
+    };
+
+    let m = bri - c;
+
+    return [r + m, g + m, b + m, alpha];
+}
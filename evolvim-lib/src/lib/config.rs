@@ -0,0 +1,254 @@
+//! The parameters of a simulation that can be chosen without recompiling.
+//!
+//! A `SimConfig` is stored on every `Board` and saved along with it. Pass one to `Board::new_random_with_config` to
+//! generate a board with it, the defaults are the values in `constants`. The terrain generator, the noise step size and
+//! the temperatures are only used when generating a board, everything else can be changed while it runs with
+//! `Board::set_config`.
+//!
+//! Experiments can be described in a TOML or YAML file and loaded with `SimConfig::from_path`, e.g.
+//!
+//! ```toml
+//! creature_minimum = 100
+//! max_temp = 1.0
+//! terrain_generator = "archipelago"
+//!
+//! [noise]
+//! seed = 42
+//! ```
+
+extern crate serde_yaml;
+extern crate toml;
+
+use crate::brain::vision::Vision;
+use crate::climate::{ClimateDrift, ClimateOscillation, ClimateZones};
+use crate::constants::*;
+use crate::disaster::DisasterConfig;
+use crate::error::EvolvimError;
+use crate::softbody::ReproductionMode;
+use crate::species::SpeciesConfig;
+use crate::terrain::{NoiseParams, TerrainAlgorithm};
+use crate::weather::WeatherConfig;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimConfig {
+    /// New random creatures are spawned if the population drops under this amount.
+    pub creature_minimum: usize,
+    /// The coldest it is going to get, only used when generating a board.
+    pub min_temp: f64,
+    /// The hottest it is going to get, only used when generating a board.
+    pub max_temp: f64,
+    /// The step size of the noise the terrain is generated from, smaller makes bigger continents.
+    pub noise_step_size: f64,
+    /// How the terrain is generated, only used when generating a board.
+    pub terrain_generator: TerrainAlgorithm,
+    /// The seed, octaves, persistence and water threshold of the noise for `TerrainAlgorithm::Perlin`, only used when
+    /// generating a board.
+    pub noise: NoiseParams,
+    /// The amount of times a year creatures move, higher makes them faster.
+    pub object_timesteps_per_year: f64,
+    /// The fraction of their energy creatures lose every year, multiplied by their age.
+    pub metabolism_energy: f64,
+    /// The energy it costs every year to keep a single node or connection of the brain running.
+    pub brain_metabolism_energy: f64,
+    /// Moves creatures spawned in the water to the nearest land, see `Terrain::nearest_land`.
+    pub spawn_on_land: bool,
+    /// The length of a day in years, 0 for no days and nights. Only used when generating a board, see
+    /// `Climate::set_day_length`.
+    pub day_length: f64,
+    /// How much colder the top and bottom of the board are than the middle. Only used when generating a board, see
+    /// `Climate::set_latitude_cooling`.
+    pub latitude_cooling: f64,
+    /// Gives the board climate zones with their own temperatures and seasons, see `ClimateZones`. Only used when
+    /// generating a board.
+    pub climate_zones: ClimateZones,
+    /// Makes the whole board slowly warm up, cool down or go through ice ages, see `ClimateDrift`. Only used when
+    /// generating a board.
+    pub drift: ClimateDrift,
+    /// Gives the board good and bad years, see `ClimateOscillation`. Only used when generating a board.
+    pub oscillation: ClimateOscillation,
+    /// How often there's rain, drought or a storm and how bad it is, see `Climate::update_weather`.
+    pub weather: WeatherConfig,
+    /// How often floods, wildfires and meteors hit the board and how big they are, see `Disaster`.
+    pub disasters: DisasterConfig,
+    /// Dead creatures leave carrion instead of plant food, see `Terrain::add_carrion_or_nothing_at`.
+    pub carrion: bool,
+    /// How different creatures of the same species can be, see `SpeciesTracker`.
+    pub species: SpeciesConfig,
+    /// Whether creatures need a willing mate to give birth, see `ReproductionMode`.
+    pub reproduction: ReproductionMode,
+    /// The years an egg takes to hatch, 0 for babies that are born right away. See `Egg`.
+    pub egg_incubation: f64,
+    /// How far and how wide creatures see, see `Vision`. Brains are made to fit it so it can only be chosen when
+    /// generating a board.
+    pub vision: Vision,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        SimConfig {
+            creature_minimum: DEFAULT_CREATURE_MINIMUM,
+            min_temp: DEFAULT_MIN_TEMP,
+            max_temp: DEFAULT_MAX_TEMP,
+            noise_step_size: DEFAULT_NOISE_STEP_SIZE,
+            terrain_generator: TerrainAlgorithm::default(),
+            noise: NoiseParams::default(),
+            object_timesteps_per_year: DEFAULT_OBJECT_TIMESTEPS_PER_YEAR,
+            metabolism_energy: DEFAULT_METABOLISM_ENERGY,
+            brain_metabolism_energy: DEFAULT_BRAIN_METABOLISM_ENERGY,
+            spawn_on_land: false,
+            day_length: 0.0,
+            latitude_cooling: DEFAULT_LATITUDE_COOLING,
+            climate_zones: ClimateZones::default(),
+            drift: ClimateDrift::default(),
+            oscillation: ClimateOscillation::default(),
+            weather: WeatherConfig::default(),
+            disasters: DisasterConfig::default(),
+            carrion: false,
+            species: SpeciesConfig::default(),
+            reproduction: ReproductionMode::default(),
+            egg_incubation: 0.0,
+            vision: Vision::default(),
+        }
+    }
+}
+
+impl SimConfig {
+    /// Returns the default config with the values `Board::new_random` takes.
+    pub(crate) fn from_parameters(
+        noise_step_size: f64,
+        creature_minimum: usize,
+        min_temp: f64,
+        max_temp: f64,
+    ) -> Self {
+        SimConfig {
+            creature_minimum,
+            min_temp,
+            max_temp,
+            noise_step_size,
+            ..SimConfig::default()
+        }
+    }
+
+    /// Reads a config from a TOML (`.toml`) or YAML (`.yaml` or `.yml`) file and checks it.
+    ///
+    /// Values that are left out get their default, so a file only has to list what it changes.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, EvolvimError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let invalid =
+            |e: &fmt::Display| EvolvimError::InvalidConfig(format!("{}: {}", path.display(), e));
+
+        let config: SimConfig = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&text).map_err(|e| invalid(&e))?,
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&text).map_err(|e| invalid(&e))?,
+            _ => {
+                return Err(invalid(&"a config file should end in .toml, .yaml or .yml"));
+            }
+        };
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Returns an error describing the first value that doesn't make sense, if any.
+    pub fn validate(&self) -> Result<(), EvolvimError> {
+        let invalid = |reason: String| Err(EvolvimError::InvalidConfig(reason));
+
+        if !(self.min_temp.is_finite() && self.max_temp.is_finite())
+            || self.min_temp > self.max_temp
+        {
+            return invalid(format!(
+                "the temperature should go from low to high but goes from {} to {}",
+                self.min_temp, self.max_temp
+            ));
+        }
+
+        if self.noise.octaves == 0 || self.noise.octaves > 16 {
+            return invalid(format!(
+                "noise.octaves should be between 1 and 16 but is {}",
+                self.noise.octaves
+            ));
+        }
+        if !self.noise.water_threshold.is_finite() {
+            return invalid(format!(
+                "noise.water_threshold should be a number but is {}",
+                self.noise.water_threshold
+            ));
+        }
+        if self.species.interval == 0 {
+            return invalid("species.interval should be at least 1 but is 0".to_string());
+        }
+        if self.vision.ray_amount == 0 {
+            return invalid("vision.ray_amount should be at least 1 but is 0".to_string());
+        }
+        if self.vision.field_of_view > 2.0 * std::f64::consts::PI {
+            return invalid(format!(
+                "vision.field_of_view can't be wider than a full circle but is {}",
+                self.vision.field_of_view
+            ));
+        }
+        if !self.drift.trend.is_finite() {
+            return invalid(format!(
+                "drift.trend should be a number but is {}",
+                self.drift.trend
+            ));
+        }
+
+        let positive = [
+            ("noise_step_size", self.noise_step_size),
+            ("noise.persistence", self.noise.persistence),
+            ("weather.duration", self.weather.duration),
+            ("disasters.duration", self.disasters.duration),
+            ("drift.cycle_period", self.drift.cycle_period),
+            ("oscillation.period", self.oscillation.period),
+            ("object_timesteps_per_year", self.object_timesteps_per_year),
+            ("vision.range", self.vision.range),
+        ];
+        for &(name, value) in &positive {
+            if !(value.is_finite() && value > 0.0) {
+                return invalid(format!("{} should be positive but is {}", name, value));
+            }
+        }
+
+        let not_negative = [
+            ("metabolism_energy", self.metabolism_energy),
+            ("brain_metabolism_energy", self.brain_metabolism_energy),
+            ("day_length", self.day_length),
+            ("egg_incubation", self.egg_incubation),
+            ("latitude_cooling", self.latitude_cooling),
+            ("weather.frequency", self.weather.frequency),
+            ("weather.severity", self.weather.severity),
+            ("disasters.frequency", self.disasters.frequency),
+            ("disasters.radius", self.disasters.radius),
+            ("drift.cycle_amplitude", self.drift.cycle_amplitude),
+            ("oscillation.amplitude", self.oscillation.amplitude),
+            ("species.threshold", self.species.threshold),
+            ("vision.field_of_view", self.vision.field_of_view),
+        ];
+        for &(name, value) in &not_negative {
+            if !(value.is_finite() && value >= 0.0) {
+                return invalid(format!("{} should be at least 0 but is {}", name, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if `other` only differs in values that can be changed while a board runs.
+    pub(crate) fn has_same_generation(&self, other: &SimConfig) -> bool {
+        self.min_temp == other.min_temp
+            && self.max_temp == other.max_temp
+            && self.noise_step_size == other.noise_step_size
+            && self.terrain_generator == other.terrain_generator
+            && self.noise == other.noise
+            && self.day_length == other.day_length
+            && self.latitude_cooling == other.latitude_cooling
+            && self.climate_zones == other.climate_zones
+            && self.drift == other.drift
+            && self.oscillation == other.oscillation
+            && self.vision == other.vision
+    }
+}
@@ -6,6 +6,8 @@
 //! You should be able to find a description of what each constant does and estimate it's impact.
 //! Have fun!
 //!
+//! The values starting with `DEFAULT_` can also be changed without recompiling through a `SimConfig`.
+//!
 //! TODO: transport all constants over to this file.
 
 use super::*;
@@ -34,7 +36,7 @@ pub const DEFAULT_BOARD_HEIGHT: usize = 100;
 /// NOTE: Don't change the value of this constant, change `DEFAULT_BOARD_WIDTH` and/or `DEFAULT_BOARD_HEIGHT` instead.
 pub const DEFAULT_BOARD_SIZE: BoardSize = (DEFAULT_BOARD_WIDTH, DEFAULT_BOARD_HEIGHT);
 
-/// The default minimum amount of creatures.
+/// The default minimum amount of creatures, see `SimConfig::creature_minimum`.
 ///
 /// New random creatures will be generated if the population drops under this amount.
 pub const DEFAULT_CREATURE_MINIMUM: usize = 60;
@@ -45,9 +47,66 @@ pub const DEFAULT_MIN_TEMP: f64 = -0.5;
 /// The hottest it is going to get.
 pub const DEFAULT_MAX_TEMP: f64 = 0.7;
 
+/// How much colder the poles are than the equator, see `Climate::set_latitude_cooling`.
+pub const DEFAULT_LATITUDE_COOLING: f64 = 0.3;
+
+/// The length of an ice age cycle in years, see `ClimateDrift`.
+pub const DEFAULT_ICE_AGE_PERIOD: f64 = 200.0;
+
+/// How many years it takes to go from good years to bad years and back, see `ClimateOscillation`.
+pub const DEFAULT_OSCILLATION_PERIOD: f64 = 5.0;
+
+/// How many times a year new weather starts, 0 for no weather. See `WeatherConfig`.
+pub const DEFAULT_WEATHER_FREQUENCY: f64 = 0.0;
+
+/// How strong weather is, see `WeatherConfig`.
+pub const DEFAULT_WEATHER_SEVERITY: f64 = 1.0;
+
+/// How many years weather lasts on average, see `WeatherConfig`.
+pub const DEFAULT_WEATHER_DURATION: f64 = 0.1;
+
+/// How many disasters happen every year, 0 for none. See `DisasterConfig`.
+pub const DEFAULT_DISASTER_FREQUENCY: f64 = 0.0;
+
+/// The radius in tiles of a disaster, see `DisasterConfig`.
+pub const DEFAULT_DISASTER_RADIUS: f64 = 4.0;
+
+/// How many years the tiles a disaster changed stay that way, see `DisasterConfig`.
+pub const DEFAULT_DISASTER_DURATION: f64 = 0.5;
+
 /// Used for terrain generation.
 pub const DEFAULT_NOISE_STEP_SIZE: f64 = 0.1;
 
+/// The amount of layers of noise the land and water of `Terrain::generate_perlin` are made of, see `NoiseParams`.
+pub const DEFAULT_NOISE_OCTAVES: usize = 1;
+
+/// How much weaker every next layer of noise is, see `NoiseParams`.
+pub const DEFAULT_NOISE_PERSISTENCE: f64 = 0.5;
+
+/// Generated tiles with a fertility above this become water, see `NoiseParams`.
+pub const DEFAULT_WATER_THRESHOLD: f64 = 1.0;
+
+/// The amount of layers of noise `FbmGenerator` adds up, more gives rougher coasts.
+pub const DEFAULT_FBM_OCTAVES: usize = 6;
+
+/// How much smaller every next layer of `DiamondSquareGenerator` is, higher gives rougher terrain.
+pub const DEFAULT_DIAMOND_SQUARE_ROUGHNESS: f64 = 0.55;
+
+/// The amount of islands `ArchipelagoGenerator` makes.
+pub const DEFAULT_ARCHIPELAGO_ISLANDS: usize = 7;
+
+/// The default amount of times a year an object is updated, see `SimConfig::object_timesteps_per_year`.
+pub const DEFAULT_OBJECT_TIMESTEPS_PER_YEAR: f64 = 100.0;
+
+/// The amount of years that pass every update when running without graphics, see `Board::run_for`.
+pub const DEFAULT_TIME_STEP: f64 = 0.001;
+
+/// The amount of years that pass every second of real time at a speed of 1, see `Simulation`.
+pub const SIMULATION_YEARS_PER_SECOND: f64 = 0.02;
+
+/// The most updates a `Simulation` does in one go, so one slow frame doesn't make the next ones even slower.
+pub const DEFAULT_MAX_UPDATES_PER_FRAME: usize = 1000;
+
 // ************************* //
 // ******** DRAWING ******** //
 // ************************* //
@@ -55,6 +114,12 @@ pub const DEFAULT_NOISE_STEP_SIZE: f64 = 0.1;
 /// [Hue, Saturation, Brightness, Alpha]
 pub const COLOR_WATER: [f32; 4] = [0., 0., 0., 1.];
 
+/// The water at the shore, it gets darker towards `COLOR_DEEP_WATER` the deeper it is. [Hue, Saturation, Brightness]
+pub const COLOR_SHALLOW_WATER: [f32; 3] = [0.58, 0.6, 0.45];
+
+/// [Hue, Saturation, Brightness]
+pub const COLOR_DEEP_WATER: [f32; 3] = [0.58, 0.6, 0.];
+
 /// [Hue, Saturation, Brightness]
 pub const COLOR_BARREN: [f32; 3] = [0., 0., 1.];
 
@@ -63,6 +128,42 @@ pub const COLOR_FERTILE: [f32; 3] = [0., 0., 0.2];
 
 pub const COLOR_BLACK: [f32; 3] = [0., 1., 0.];
 
+/// The bare ground of a desert, [Hue, Saturation, Brightness]
+pub const COLOR_DESERT: [f32; 3] = [0.12, 0.3, 1.];
+
+/// The bare ground of a forest, [Hue, Saturation, Brightness]
+pub const COLOR_FOREST: [f32; 3] = [0.3, 0.15, 0.85];
+
+/// The bare ground of a swamp, [Hue, Saturation, Brightness]
+pub const COLOR_SWAMP: [f32; 3] = [0.2, 0.2, 0.7];
+
+/// The bare ground of the tundra, [Hue, Saturation, Brightness]
+pub const COLOR_TUNDRA: [f32; 3] = [0.55, 0.1, 1.];
+
+/// A tile creatures can't enter, [Hue, Saturation, Brightness, Alpha]
+pub const COLOR_OBSTACLE: [f32; 4] = [0., 0., 0.45, 1.];
+
+/// The color toxic land is tinted with, [Hue, Saturation, Brightness]
+pub const COLOR_TOXIC: [f32; 3] = [0.8, 0.7, 0.6];
+
+/// How much land with a toxicity of 1 is tinted with `COLOR_TOXIC`.
+pub const TOXIC_TINT: f32 = 0.6;
+
+/// The highest land is drawn this much darker than land at sea level.
+pub const ELEVATION_SHADING: f32 = 0.35;
+
+/// The color of the frozen land and water in the middle of winter, [Hue, Saturation, Brightness]
+pub const COLOR_FROST: [f32; 3] = [0.55, 0.08, 0.95];
+
+/// How much the terrain is tinted with `COLOR_FROST` in the middle of winter.
+pub const FROST_TINT: f32 = 0.5;
+
+/// The color of the withering land in the middle of autumn, [Hue, Saturation, Brightness]
+pub const COLOR_AUTUMN: [f32; 3] = [0.08, 0.65, 0.55];
+
+/// How much the terrain is tinted with `COLOR_AUTUMN` in the middle of autumn.
+pub const AUTUMN_TINT: f32 = 0.35;
+
 // ******************** //
 // ******** UI ******** //
 // ******************** //
@@ -80,12 +181,35 @@ pub const ACCELERATION_ENERGY: f64 = 0.18;
 pub const ACCELERATION_BACK_ENERGY: f64 = 0.24;
 pub const TURN_ENERGY: f64 = 0.06;
 
-pub const METABOLISM_ENERGY: f64 = 0.004;
+/// The default of `SimConfig::metabolism_energy`.
+pub const DEFAULT_METABOLISM_ENERGY: f64 = 0.004;
+/// The default of `SimConfig::brain_metabolism_energy`, the energy it costs every year to keep a single node or
+/// connection of the brain running.
+pub const DEFAULT_BRAIN_METABOLISM_ENERGY: f64 = 0.0001;
 
+/// The energy it costs to be in the water, multiplied by the energy of the creature. It goes up with the depth until
+/// it's twice as much in the deepest water.
 pub const SWIM_ENERGY: f64 = 0.008;
+/// The extra friction of the water, multiplied by its depth. Creatures in water slow down.
+pub const WATER_DRAG: f64 = 0.02;
+/// The energy a creature that can't swim loses in deep water, multiplied by its energy. Enough to drown it in about a
+/// month.
+pub const DROWN_ENERGY: f64 = 0.02;
+/// The energy it costs to move over land, multiplied by the elevation, speed and energy of the creature.
+pub const CLIMB_ENERGY: f64 = 0.1;
 pub const EAT_ENERGY: f64 = 0.05;
 pub const FIGHT_ENERGY: f64 = 0.06;
 pub const INJURED_ENERGY: f64 = 0.25;
+pub const BITE_ENERGY: f64 = 0.06;
+/// How much energy a bite takes from the victim, before scaling for size.
+pub const BITE_DAMAGE: f64 = 0.5;
+/// The fraction of the energy taken from the victim that ends up with the attacker, for a pure meat eater. See
+/// `Rock::get_meat_efficiency`.
+pub const BITE_EFFICIENCY: f64 = 0.8;
+/// Caps how much more damage a big attacker does to a small victim.
+pub const BITE_MAX_SIZE_MULTIPLIER: f64 = 4.0;
+/// The fraction of the energy in an egg that ends up with the creature eating it, for a pure meat eater.
+pub const EGG_EFFICIENCY: f64 = 0.8;
 
 // ********************* //
 // ******* FOOD ******** //
@@ -93,12 +217,143 @@ pub const INJURED_ENERGY: f64 = 0.25;
 
 pub const FOOD_GROWTH_RATE: f64 = 1.0;
 pub const MAX_GROWTH_LEVEL: f64 = 3.0;
+
+/// Land further from the middle of the board than this (1 is the edge) is tundra, see `Biome::classify`.
+pub const BIOME_TUNDRA_LATITUDE: f64 = 0.85;
+/// Land less fertile than this is desert.
+pub const BIOME_DESERT_FERTILITY: f64 = 0.15;
+/// Land more fertile than this is forest.
+pub const BIOME_FOREST_FERTILITY: f64 = 0.55;
+/// Land more fertile than this is swamp.
+pub const BIOME_SWAMP_FERTILITY: f64 = 0.9;
+/// How much colder the highest land is than land at sea level, see `Climate::get_growth_at_elevation_over_time_range`.
+pub const ELEVATION_COOLING: f64 = 0.4;
+/// How much warmer it is at noon than at midnight, see `Climate::set_day_length`.
+pub const DAY_TEMPERATURE_RANGE: f64 = 0.6;
+/// The amount of climate zones `ClimateZones::Latitude` divides the board into from top to bottom.
+pub const CLIMATE_ZONE_BANDS: usize = 4;
+/// How much colder the winters of the outermost climate zones are than the normal ones, see `ClimateZones`.
+pub const CLIMATE_ZONE_POLAR_COOLING: f64 = 0.5;
+/// The growth rain adds every year, see `WeatherKind::get_growth_change`.
+pub const WEATHER_RAIN_GROWTH: f64 = 0.5;
+/// The growth a drought adds every year.
+pub const WEATHER_DROUGHT_GROWTH: f64 = -0.5;
+/// The growth a storm adds every year, storms are short but tear up a lot of food.
+pub const WEATHER_STORM_GROWTH: f64 = -1.5;
+/// How many years a `Climate` remembers weather after it ended, so tiles that are behind can still catch up with it.
+pub const WEATHER_MEMORY: f64 = 1.0;
+/// How many years apart a `Climate` records its temperature, see `Climate::history`.
+pub const CLIMATE_HISTORY_RESOLUTION: f64 = 0.02;
+/// How many temperatures a `Climate` remembers by default, which is 100 years at the default resolution.
+pub const CLIMATE_HISTORY_LENGTH: usize = 5000;
+/// How deep the water of a flood is in its middle, shallow enough for every creature to wade through.
+pub const DISASTER_FLOOD_DEPTH: f64 = 0.3;
+/// The part of their energy creatures caught in a wildfire lose.
+pub const DISASTER_FIRE_DAMAGE: f64 = 0.5;
 pub const FOOD_SENSITIVITY: f64 = 0.3;
+/// Creatures with a mouth hue this far from the toxin hue of a tile aren't adapted to it at all.
+pub const TOXIN_TOLERANCE: f64 = 0.15;
+/// Land where the toxin noise is above this gets toxic food when generating terrain, the noise goes from 0 to 1.
+pub const TOXIC_NOISE_THRESHOLD: f64 = 0.78;
+/// How much deeper water gets for every unit its generated fertility goes over 1, see `Tile::new`.
+pub const WATER_DEPTH_SCALE: f64 = 1.0;
+/// Water deeper than this drowns creatures that can't swim.
+pub const DEEP_WATER_DEPTH: f64 = 0.6;
 
 pub const EAT_WHILE_MOVING_INEFFICIENCY_MULTIPLIER: f64 = 2.0;
 pub const EAT_SPEED: f64 = 0.5;
 
+/// The time in years between two moments a fruiting tile releases its stored food.
+pub const FRUITING_PERIOD: f64 = 0.25;
+/// Land tiles with at least this fertility become fruiting tiles when generating terrain.
+pub const FRUITING_MIN_FERTILITY: f64 = 0.8;
+/// The fraction of the carrion on a tile that rots away every year, see `Resource::carrion`.
+pub const CARRION_ROT_RATE: f64 = 2.0;
+/// How much of the energy in food a creature gets when its mouth isn't specialized in that kind of food.
+pub const UNSPECIALIZED_MOUTH_EFFICIENCY: f64 = 0.5;
+/// The chance a baby's mouth is specialized in a random kind of food instead of the one of its first parent.
+pub const MOUTH_SPECIALIZATION_MUTATION_CHANCE: f64 = 0.01;
+/// The lifespan in years of creatures spawned at random, every one of them gets between half and one and a half times
+/// this. See `Rock::get_lifespan`.
+pub const DEFAULT_LIFESPAN: f64 = 4.0;
+/// A baby's lifespan is the mean of its parents' lifespans, give or take this part of it.
+pub const LIFESPAN_MUTATION: f64 = 0.1;
+/// The part of its lifespan after which a creature starts to decline, see `Rock::get_senescence`.
+pub const SENESCENCE_ONSET: f64 = 0.75;
+/// The part of its energy a creature that reached the end of its lifespan loses every year on top of its metabolism.
+pub const SENESCENCE_ENERGY: f64 = 0.5;
+/// The part of its energy a creature loses every year for every year of its lifespan, living long isn't free.
+pub const LIFESPAN_ENERGY: f64 = 0.002;
+/// The diet of creatures spawned at random, every one of them gets between 0 and twice this. See `Rock::get_diet`.
+pub const DEFAULT_DIET: f64 = 0.15;
+/// A baby's diet is the mean of its parents' diets, give or take this much.
+pub const DIET_MUTATION: f64 = 0.05;
+/// The fraction of the energy in plants a pure meat eater digests, and the other way around.
+pub const DIET_MIN_EFFICIENCY: f64 = 0.25;
+/// The body size of creatures spawned at random, every one of them gets between half and one and a half times this.
+/// See `Rock::get_body_size`.
+pub const DEFAULT_BODY_SIZE: f64 = 1.0;
+/// The smallest body size a creature can have, small enough that it can't have a baby without help.
+pub const MIN_BODY_SIZE: f64 = 0.5;
+/// A baby's body size is the mean of its parents' body sizes, give or take this part of it.
+pub const BODY_SIZE_MUTATION: f64 = 0.05;
+/// The most energy a creature with a body size of 1 can store, see `Rock::get_energy_capacity`.
+pub const BODY_SIZE_ENERGY_CAPACITY: f64 = 5.0;
+/// The energy a creature loses every year for every unit of its body size, a big body is expensive to keep up.
+pub const BODY_SIZE_ENERGY: f64 = 0.05;
+
 // ********************** //
 // **** REPRODUCTION **** //
 // ********************** //
 pub const BABY_SIZE: f64 = SAFE_SIZE + 0.1;
+/// How far a creature reaches for a mate in `ReproductionMode::Sexual`, in its own radius.
+pub const MATING_RANGE: f64 = 2.0;
+/// The lowest "help birth" output of a creature that's willing to mate in `ReproductionMode::Sexual`.
+pub const MATE_WILLINGNESS: f64 = 0.5;
+/// Creatures whose genetic distance to the representative of a species is at most this belong to it, see
+/// `SpeciesConfig`.
+pub const DEFAULT_SPECIES_THRESHOLD: f64 = 0.5;
+/// The amount of updates between two times the population is divided into species.
+pub const DEFAULT_SPECIES_INTERVAL: usize = 50;
+
+// ********************** //
+// ******* VISION ******* //
+// ********************** //
+
+/// The amount of rays every creature casts to look around.
+pub const VISION_RAY_AMOUNT: usize = 3;
+
+/// The angle (in radians) between the outermost rays, the rays are spread evenly over this angle.
+pub const VISION_FIELD_OF_VIEW: f64 = 0.5 * std::f64::consts::PI;
+
+/// How far (in tiles) a creature can see.
+pub const VISION_RANGE: f64 = 5.0;
+
+/// The distance (in tiles) a ray travels between two checks, smaller is more precise but slower.
+pub const VISION_RAY_STEP: f64 = 0.25;
+
+/// Creatures with their center within this distance (in tiles) are sensed as nearby.
+pub const NEARBY_SENSE_RADIUS: f64 = 3.0;
+
+// ************************* //
+// ******* TERRAFORM ******* //
+// ************************* //
+
+/// The maximum amount of terrain edits that can be undone.
+pub const TERRAIN_UNDO_CAPACITY: usize = 100;
+
+/// The width and height in tiles of the chunks a `Terrain` is stored in.
+pub const TERRAIN_CHUNK_SIZE: usize = 32;
+
+// ************************* //
+// ****** PHEROMONES ******* //
+// ************************* //
+
+/// The fraction of pheromone that disappears every year.
+pub const PHEROMONE_DECAY_RATE: f64 = 5.0;
+/// How fast pheromone spreads to neighbouring tiles, per year.
+pub const PHEROMONE_DIFFUSION_RATE: f64 = 10.0;
+/// The amount of pheromone emitted per year when the output is 1.
+pub const PHEROMONE_EMIT_RATE: f64 = 10.0;
+/// The energy it costs to emit one unit of pheromone.
+pub const PHEROMONE_ENERGY: f64 = 0.01;
@@ -0,0 +1,162 @@
+//! Floods, wildfires and meteors that wipe out the food of a part of the board and change its tiles for a while.
+//!
+//! A `Board` strikes its terrain with a new disaster at random while it runs, how often and how big is chosen by the
+//! `DisasterConfig` of its `SimConfig`. The random numbers come from the board, so a seeded board always gets the same
+//! disasters. The changed tiles are remembered by the terrain and come back once the disaster is over, see
+//! `Terrain::scar`. Subscribe to `Board::on_disaster` to log them.
+
+use crate::board::{BoardCoordinate, BoardSize};
+use crate::constants::*;
+use crate::rng;
+use crate::terrain::resource::ResourceKind;
+use crate::terrain::tile::Tile;
+
+/// The kinds of disasters, see `Disaster::get_scarred_tile` for what they do to the tiles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisasterKind {
+    /// Covers the land with shallow water.
+    Flood,
+    /// Burns all food to ashes which don't grow anything, creatures caught in it lose part of their energy.
+    Wildfire,
+    /// Kills every creature it hits and leaves a deep crater lake.
+    Meteor,
+}
+
+impl DisasterKind {
+    pub const fn get_all() -> [Self; 3] {
+        [
+            DisasterKind::Flood,
+            DisasterKind::Wildfire,
+            DisasterKind::Meteor,
+        ]
+    }
+
+    /// Returns the part of their energy creatures hit by this disaster lose.
+    pub fn get_damage(self) -> f64 {
+        match self {
+            DisasterKind::Flood => 0.0,
+            DisasterKind::Wildfire => DISASTER_FIRE_DAMAGE,
+            DisasterKind::Meteor => 1.0,
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            DisasterKind::Flood => "flood",
+            DisasterKind::Wildfire => "wildfire",
+            DisasterKind::Meteor => "meteor",
+        }
+    }
+}
+
+/// A disaster that hits all tiles within `radius` tiles of `center` at `start`, the tiles come back at `end`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Disaster {
+    pub kind: DisasterKind,
+    pub center: BoardCoordinate,
+    pub radius: f64,
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Disaster {
+    /// Returns true if the point `(x, y)` on the board is hit by this disaster.
+    pub fn covers(&self, x: f64, y: f64) -> bool {
+        self.get_distance(x, y) <= self.radius
+    }
+
+    /// Returns what `tile` at `pos` looks like right after this disaster, with all its food gone. Returns `None` if
+    /// the tile doesn't change: floods and wildfires only change land, meteors also dig into the water.
+    pub fn get_scarred_tile(&self, pos: BoardCoordinate, tile: &Tile) -> Option<Tile> {
+        if tile.is_obstacle() || (tile.is_water() && self.kind != DisasterKind::Meteor) {
+            return None;
+        }
+
+        // Water is deepest in the middle.
+        let depth = if self.radius > 0.0 {
+            1.0 - self.get_distance(pos.0 as f64, pos.1 as f64) / self.radius
+        } else {
+            1.0
+        };
+
+        Some(match self.kind {
+            DisasterKind::Flood => Tile::new_water(DISASTER_FLOOD_DEPTH * depth),
+            DisasterKind::Wildfire => {
+                let mut ashes = tile.clone();
+                wipe_food(&mut ashes);
+                ashes.set_fertility(0.0);
+                ashes
+            }
+            DisasterKind::Meteor => Tile::new_water(depth.max(tile.get_water_depth())),
+        })
+    }
+
+    fn get_distance(&self, x: f64, y: f64) -> f64 {
+        ((x - self.center.0 as f64).powi(2) + (y - self.center.1 as f64).powi(2)).sqrt()
+    }
+}
+
+/// Removes all food from `tile`, the plants as well as every other resource.
+pub fn wipe_food(tile: &mut Tile) {
+    for &kind in &ResourceKind::get_all() {
+        let level = tile.get_resource_level(kind);
+        tile.remove_resource(kind, level);
+    }
+}
+
+/// How often disasters happen and how big they are, part of a `SimConfig`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisasterConfig {
+    /// How many disasters happen every year on average, 0 turns them off.
+    pub frequency: f64,
+    /// The radius in tiles of the area a disaster hits, every disaster gets between half and one and a half times
+    /// this.
+    pub radius: f64,
+    /// How many years it takes the tiles to come back on average.
+    pub duration: f64,
+}
+
+impl Default for DisasterConfig {
+    fn default() -> Self {
+        DisasterConfig {
+            frequency: DEFAULT_DISASTER_FREQUENCY,
+            radius: DEFAULT_DISASTER_RADIUS,
+            duration: DEFAULT_DISASTER_DURATION,
+        }
+    }
+}
+
+impl DisasterConfig {
+    /// Returns a new disaster somewhere on a board of `board_size` starting at `time` if it's time for one, the chance
+    /// goes up with `time_step`.
+    ///
+    /// No random numbers are taken without disasters, so turning them off doesn't change anything else.
+    pub(crate) fn roll(
+        &self,
+        time: f64,
+        time_step: f64,
+        board_size: BoardSize,
+    ) -> Option<Disaster> {
+        if self.frequency <= 0.0 || rng::random::<f64>() >= self.frequency * time_step {
+            return None;
+        }
+
+        let kinds = DisasterKind::get_all();
+        let kind = kinds[rng::gen_range(0, kinds.len())];
+        let center = (
+            rng::gen_range(0, board_size.0.max(1)),
+            rng::gen_range(0, board_size.1.max(1)),
+        );
+        let radius = self.radius * (0.5 + rng::random::<f64>());
+        let duration = self.duration * (0.5 + rng::random::<f64>());
+
+        Some(Disaster {
+            kind,
+            center,
+            radius,
+            start: time,
+            end: time + duration,
+        })
+    }
+}
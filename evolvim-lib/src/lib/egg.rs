@@ -0,0 +1,149 @@
+//! Babies that are laid as eggs instead of being born right away.
+//!
+//! With a positive `SimConfig::egg_incubation` a `Board` doesn't put a baby on the board when it's conceived but lays
+//! an `Egg` with it on the tile the baby would have been born on. The egg hatches once it has been incubated for that
+//! long. Until then it lies still and any creature that's eating on its tile, except for the parents, eats it and gets
+//! its energy, see `Rock::eat_egg`. All parents can do is keep others away from their eggs.
+//!
+//! Like bodies, eggs are world objects: the board keeps a list of them and every egg is in the `SoftBodiesInPositions`
+//! of the tile it's lying on, that's where eating creatures find them. Eggs don't move or collide.
+
+use crate::sbip::SoftBodiesInPositions;
+use crate::softbody::{ReferenceCounter, SoftBody};
+use crate::{BoardCoordinate, BoardSize};
+use std::ops::Deref;
+
+/// A baby waiting to hatch, see the module documentation.
+#[derive(Serialize, Deserialize)]
+pub struct Egg<B> {
+    /// The body and brain are made when the egg is laid, the id is given when it hatches.
+    baby: SoftBody<B>,
+    tile: BoardCoordinate,
+    laid: f64,
+    hatch_time: f64,
+}
+
+impl<B> Egg<B> {
+    /// Lays an egg with `baby` in it at `time` that hatches `incubation` years later.
+    pub(crate) fn new(baby: SoftBody<B>, time: f64, incubation: f64) -> Self {
+        let tile = baby.get_position().into();
+
+        Egg {
+            baby,
+            tile,
+            laid: time,
+            hatch_time: time + incubation,
+        }
+    }
+
+    /// Returns the baby inside, with the genes it will hatch with.
+    pub fn get_baby(&self) -> &SoftBody<B> {
+        &self.baby
+    }
+
+    /// Returns the ids of the creatures that laid this egg.
+    pub fn get_parent_ids(&self) -> &[usize] {
+        self.baby.get_parent_ids()
+    }
+
+    /// Returns the tile the egg is lying on.
+    pub fn get_tile(&self) -> BoardCoordinate {
+        self.tile
+    }
+
+    /// Returns the energy a creature would get from eating the egg if it turned all of it into its own.
+    pub fn get_energy(&self) -> f64 {
+        self.baby.get_energy()
+    }
+
+    pub fn get_laid_time(&self) -> f64 {
+        self.laid
+    }
+
+    pub fn get_hatch_time(&self) -> f64 {
+        self.hatch_time
+    }
+
+    pub fn is_ready_to_hatch(&self, time: f64) -> bool {
+        time >= self.hatch_time
+    }
+
+    /// Moves the egg onto a board of `board_size` if it isn't on it, e.g. after the board shrunk.
+    pub(crate) fn keep_on_board(&mut self, board_size: BoardSize) {
+        let (px, py) = (self.baby.get_px(), self.baby.get_py());
+        self.baby.set_body_x(px, board_size.0);
+        self.baby.set_body_y(py, board_size.1);
+        self.tile = self.baby.get_position().into();
+    }
+
+    /// Breaks the egg open at `time`, the baby's age starts counting from here.
+    pub(crate) fn hatch(mut self, time: f64) -> SoftBody<B> {
+        self.baby.set_birth_time(time);
+
+        self.baby
+    }
+}
+
+/// Higher-Level Egg, a handle to an `Egg` that's shared by the board and `SoftBodiesInPositions`.
+///
+/// Eggs don't change until they hatch, so unlike `HLSoftBody` this gives read-only access.
+pub struct HLEgg<B>(ReferenceCounter<Egg<B>>);
+
+impl<B> From<Egg<B>> for HLEgg<B> {
+    fn from(egg: Egg<B>) -> HLEgg<B> {
+        HLEgg(ReferenceCounter::new(egg))
+    }
+}
+
+impl<B> Clone for HLEgg<B> {
+    fn clone(&self) -> Self {
+        HLEgg(ReferenceCounter::clone(&self.0))
+    }
+}
+
+impl<B> PartialEq<HLEgg<B>> for HLEgg<B> {
+    fn eq(&self, rhs: &HLEgg<B>) -> bool {
+        ReferenceCounter::ptr_eq(&self.0, &rhs.0)
+    }
+}
+
+impl<B> Deref for HLEgg<B> {
+    type Target = Egg<B>;
+
+    fn deref(&self) -> &Egg<B> {
+        &self.0
+    }
+}
+
+impl<B> HLEgg<B> {
+    /// Adds this egg to the tile it's lying on.
+    pub fn add_to_sbip(&self, sbip: &mut SoftBodiesInPositions<B>) {
+        let (x, y) = self.get_tile();
+        sbip.add_egg_at(x, y, self.clone());
+    }
+
+    /// Removes this egg from the tile it's lying on.
+    pub fn remove_from_sbip(&self, sbip: &mut SoftBodiesInPositions<B>) {
+        let (x, y) = self.get_tile();
+        sbip.remove_egg_at(x, y, self);
+    }
+
+    /// Returns the egg inside, it should already be removed from `SoftBodiesInPositions`.
+    ///
+    /// Panics if there's another handle to it.
+    pub(crate) fn into_inner(self) -> Egg<B> {
+        match ReferenceCounter::try_unwrap(self.0) {
+            Ok(egg) => egg,
+            Err(_e) => panic!("Could not unwrap the egg, it's still in use."),
+        }
+    }
+
+    /// Moves the egg onto a board of `board_size`, see `Egg::keep_on_board`.
+    ///
+    /// Panics if there's another handle to it, remove it from `SoftBodiesInPositions` first.
+    pub(crate) fn keep_on_board(&mut self, board_size: BoardSize) {
+        ReferenceCounter::get_mut(&mut self.0)
+            .expect("An egg should only be moved when it's not in `SoftBodiesInPositions`.")
+            .keep_on_board(board_size);
+    }
+}
@@ -0,0 +1,222 @@
+//! Bookkeeping of where the energy in the simulation goes.
+//!
+//! Every update the `Board` records how much energy flowed between a few compartments (sunlight, the food on the tiles,
+//! herbivores, predators and decay), this can be used to draw energy-flow (Sankey) diagrams of an ecosystem or to check
+//! that a configuration doesn't create or destroy energy where it shouldn't.
+
+use crate::error::EvolvimError;
+use crate::history::{History, RetentionPolicy};
+use std::io::Write;
+
+/// A place energy can be in or come from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compartment {
+    /// Growth of food on the tiles, powered by the climate.
+    Sun,
+    /// Energy of the random creatures added to keep up the creature minimum.
+    Immigration,
+    /// Food lying on the tiles.
+    Tiles,
+    /// Creatures that got most of their energy from eating food.
+    Herbivores,
+    /// Creatures that got most of their energy from biting others.
+    Predators,
+    /// Energy lost to metabolism, movement, waste and rotting.
+    Decay,
+}
+
+const COMPARTMENT_AMOUNT: usize = 6;
+
+impl Compartment {
+    pub fn get_all() -> [Compartment; COMPARTMENT_AMOUNT] {
+        use Compartment::*;
+
+        [Sun, Immigration, Tiles, Herbivores, Predators, Decay]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        use Compartment::*;
+
+        match self {
+            Sun => "sun",
+            Immigration => "immigration",
+            Tiles => "tiles",
+            Herbivores => "herbivores",
+            Predators => "predators",
+            Decay => "decay",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The energy that flowed between every pair of `Compartment`s, `flows[from][to]`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnergyFlows {
+    flows: [[f64; COMPARTMENT_AMOUNT]; COMPARTMENT_AMOUNT],
+}
+
+impl EnergyFlows {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `amount` of energy went from `from` to `to`.
+    ///
+    /// A negative amount is recorded as a flow in the opposite direction.
+    pub fn add(&mut self, from: Compartment, to: Compartment, amount: f64) {
+        if amount >= 0.0 {
+            self.flows[from.index()][to.index()] += amount;
+        } else {
+            self.flows[to.index()][from.index()] -= amount;
+        }
+    }
+
+    pub fn get(&self, from: Compartment, to: Compartment) -> f64 {
+        self.flows[from.index()][to.index()]
+    }
+
+    /// Returns the total energy that flowed into `c`.
+    pub fn get_inflow(&self, c: Compartment) -> f64 {
+        self.flows.iter().map(|row| row[c.index()]).sum()
+    }
+
+    /// Returns the total energy that flowed out of `c`.
+    pub fn get_outflow(&self, c: Compartment) -> f64 {
+        self.flows[c.index()].iter().sum()
+    }
+
+    /// Returns the inflow minus the outflow of `c`, this should equal the change in energy stored in `c`.
+    pub fn get_net_flow(&self, c: Compartment) -> f64 {
+        self.get_inflow(c) - self.get_outflow(c)
+    }
+
+    /// Adds all flows of `other` to these.
+    pub fn merge(&mut self, other: &EnergyFlows) {
+        for (row, other_row) in self.flows.iter_mut().zip(other.flows.iter()) {
+            for (v, o) in row.iter_mut().zip(other_row.iter()) {
+                *v += o;
+            }
+        }
+    }
+
+    pub fn to_matrix(&self) -> [[f64; COMPARTMENT_AMOUNT]; COMPARTMENT_AMOUNT] {
+        self.flows
+    }
+
+    /// Writes these flows as a matrix with a row for every source and a column for every destination.
+    pub fn write_matrix_csv<W: Write>(&self, mut writer: W) -> Result<(), EvolvimError> {
+        write!(writer, "from")?;
+        for to in Compartment::get_all().iter() {
+            write!(writer, ",{}", to.get_name())?;
+        }
+        writeln!(writer)?;
+
+        for from in Compartment::get_all().iter() {
+            write!(writer, "{}", from.get_name())?;
+            for to in Compartment::get_all().iter() {
+                write!(writer, ",{}", self.get(*from, *to))?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Energy a single creature moved around since it was last collected by the `Board`.
+#[derive(Clone, Debug, Default)]
+pub struct EnergyLedger {
+    /// The flows this creature caused, e.g. from `Tiles` to itself when eating.
+    pub flows: EnergyFlows,
+    /// Energy this creature gained from food and prey.
+    pub gained: f64,
+    /// Energy this creature lost to bites and offspring.
+    pub lost: f64,
+}
+
+/// Adds up the energy flows of a `Board` over a number of updates and remembers them over time.
+pub struct EnergyFlowLog {
+    interval: usize,
+    ticks: usize,
+    pending: EnergyFlows,
+    flows: History<EnergyFlows>,
+}
+
+impl EnergyFlowLog {
+    pub fn new(interval: usize) -> Self {
+        Self::with_policy(interval, RetentionPolicy::KeepAll)
+    }
+
+    /// Creates a log which forgets old flows according to `policy`.
+    pub fn with_policy(interval: usize, policy: RetentionPolicy) -> Self {
+        assert!(interval > 0, "The interval between two samples can't be 0.");
+
+        EnergyFlowLog {
+            interval,
+            ticks: 0,
+            pending: EnergyFlows::new(),
+            flows: History::new(policy),
+        }
+    }
+
+    /// Adds the flows of the last update of `board`, call this after every update.
+    pub fn tick<B: crate::brain::NeuralNet>(
+        &mut self,
+        board: &crate::Board<B>,
+    ) -> Result<(), EvolvimError> {
+        self.ticks += 1;
+        self.pending.merge(board.get_energy_flows());
+
+        if self.ticks % self.interval == 0 {
+            let flows = std::mem::replace(&mut self.pending, EnergyFlows::new());
+            self.flows.push(board.get_time(), flows)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns all recorded `(time, flows)` pairs that are still in memory.
+    pub fn get_flows(&self) -> impl Iterator<Item = &(f64, EnergyFlows)> {
+        self.flows.iter()
+    }
+
+    /// Returns the sum of all flows that are still in memory.
+    pub fn get_total(&self) -> EnergyFlows {
+        let mut total = EnergyFlows::new();
+        for (_, flows) in self.get_flows() {
+            total.merge(flows);
+        }
+
+        total
+    }
+
+    /// Writes all flows that are still in memory with the columns time,from,to,energy.
+    ///
+    /// Flows of zero are left out.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<(), EvolvimError> {
+        writeln!(writer, "time,from,to,energy")?;
+
+        for (time, flows) in self.get_flows() {
+            for from in Compartment::get_all().iter() {
+                for to in Compartment::get_all().iter() {
+                    let energy = flows.get(*from, *to);
+                    if energy != 0.0 {
+                        writeln!(
+                            writer,
+                            "{},{},{},{}",
+                            time,
+                            from.get_name(),
+                            to.get_name(),
+                            energy
+                        )?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! The error type of everything in this crate that can fail.
+//!
+//! Match on `EvolvimError` to find out what went wrong, e.g. to tell a missing save file apart from a broken one.
+
+extern crate bincode;
+extern crate ron;
+extern crate serde_json;
+
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum EvolvimError {
+    /// Reading or writing a file (or anything else) failed.
+    Io(io::Error),
+    /// Something couldn't be turned into bytes or text, or the other way around.
+    Serialization(Box<std::error::Error + Send + Sync>),
+    /// A setting or argument doesn't make sense.
+    InvalidConfig(String),
+    /// A save file, checkpoint or replay could be opened but what's in it is broken.
+    CorruptSave(String),
+    /// A save file or replay was written by a version of this crate that isn't compatible with this one.
+    IncompatibleVersion { found: String, current: String },
+    /// A Lua script couldn't be loaded.
+    #[cfg(feature = "scripting")]
+    Script(String),
+    /// An image couldn't be read or written.
+    #[cfg(feature = "images")]
+    Image(String),
+}
+
+impl EvolvimError {
+    /// Turns errors about the contents of something that was read into `CorruptSave`.
+    pub(crate) fn into_corrupt(self) -> Self {
+        match self {
+            EvolvimError::Serialization(e) => EvolvimError::CorruptSave(e.to_string()),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for EvolvimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use EvolvimError::*;
+
+        match self {
+            Io(e) => write!(f, "{}", e),
+            Serialization(e) => write!(f, "could not serialize: {}", e),
+            InvalidConfig(reason) => write!(f, "invalid configuration: {}", reason),
+            CorruptSave(reason) => write!(f, "the file is corrupt: {}", reason),
+            IncompatibleVersion { found, current } => write!(
+                f,
+                "the file is from version {}, which can not be used with the current version ({})",
+                found, current
+            ),
+            #[cfg(feature = "scripting")]
+            Script(reason) => write!(f, "the script failed: {}", reason),
+            #[cfg(feature = "images")]
+            Image(reason) => write!(f, "the image is broken: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for EvolvimError {
+    fn source(&self) -> Option<&(std::error::Error + 'static)> {
+        match self {
+            EvolvimError::Io(e) => Some(e),
+            EvolvimError::Serialization(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EvolvimError {
+    fn from(e: io::Error) -> Self {
+        EvolvimError::Io(e)
+    }
+}
+
+impl From<bincode::Error> for EvolvimError {
+    fn from(e: bincode::Error) -> Self {
+        match *e {
+            bincode::ErrorKind::Io(e) => EvolvimError::Io(e),
+            e => EvolvimError::Serialization(Box::new(e)),
+        }
+    }
+}
+
+impl From<ron::ser::Error> for EvolvimError {
+    fn from(e: ron::ser::Error) -> Self {
+        EvolvimError::Serialization(Box::new(e))
+    }
+}
+
+impl From<ron::de::Error> for EvolvimError {
+    fn from(e: ron::de::Error) -> Self {
+        EvolvimError::Serialization(Box::new(e))
+    }
+}
+
+impl From<serde_json::Error> for EvolvimError {
+    fn from(e: serde_json::Error) -> Self {
+        if e.is_io() {
+            EvolvimError::Io(e.into())
+        } else {
+            EvolvimError::Serialization(Box::new(e))
+        }
+    }
+}
+
+#[cfg(feature = "scripting")]
+impl From<rlua::Error> for EvolvimError {
+    fn from(e: rlua::Error) -> Self {
+        EvolvimError::Script(e.to_string())
+    }
+}
+
+#[cfg(feature = "images")]
+impl From<image::ImageError> for EvolvimError {
+    fn from(e: image::ImageError) -> Self {
+        match e {
+            image::ImageError::IoError(e) => EvolvimError::Io(e),
+            e => EvolvimError::Image(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Callbacks for things that happen on a `Board`.
+//!
+//! Subscribe with `Board::on_birth`, `Board::on_death`, `Board::on_eat`, `Board::on_season_change`,
+//! `Board::on_weather`, `Board::on_disaster` and `Board::on_extinction` to log events, hand out achievements or feed
+//! an external visualization without touching the simulation itself. Every callback gets the current time first.
+//! Callbacks are called in the order they were added, in the middle of `Board::update`, so they only get to see the
+//! creature involved and not the entire board.
+
+use crate::brain::NeuralNet;
+use crate::disaster::Disaster;
+use crate::softbody::SoftBody;
+use crate::weather::WeatherEvent;
+
+/// Why a creature died.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeathCause {
+    /// It ran out of energy on its own.
+    Starvation,
+    /// It was bitten to death.
+    Predation,
+    /// It got older than its lifespan, see `Rock::get_lifespan`.
+    OldAge,
+}
+
+/// What died out, see `Board::on_extinction`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtinctionCause {
+    /// Every creature died.
+    NoCreatures,
+    /// The last creature that was born on the board died, only creatures spawned to keep up the creature minimum are
+    /// left so nothing that evolved survived.
+    NoDescendants,
+}
+
+pub type CreatureHook<B> = Box<FnMut(f64, &SoftBody<B>)>;
+pub type DeathHook<B> = Box<FnMut(f64, &SoftBody<B>, DeathCause)>;
+pub type EatHook<B> = Box<FnMut(f64, &SoftBody<B>, f64)>;
+pub type SeasonHook = Box<FnMut(f64, &str)>;
+pub type WeatherHook = Box<FnMut(f64, &WeatherEvent)>;
+pub type DisasterHook = Box<FnMut(f64, &Disaster)>;
+pub type ExtinctionHook = Box<FnMut(f64, ExtinctionCause)>;
+
+/// All callbacks subscribed to the events of a `Board`.
+pub struct EventHooks<B: NeuralNet> {
+    birth: Vec<CreatureHook<B>>,
+    death: Vec<DeathHook<B>>,
+    eat: Vec<EatHook<B>>,
+    season_change: Vec<SeasonHook>,
+    weather: Vec<WeatherHook>,
+    disaster: Vec<DisasterHook>,
+    extinction: Vec<ExtinctionHook>,
+}
+
+impl<B: NeuralNet> Default for EventHooks<B> {
+    fn default() -> Self {
+        EventHooks {
+            birth: Vec::new(),
+            death: Vec::new(),
+            eat: Vec::new(),
+            season_change: Vec::new(),
+            weather: Vec::new(),
+            disaster: Vec::new(),
+            extinction: Vec::new(),
+        }
+    }
+}
+
+impl<B: NeuralNet> EventHooks<B> {
+    pub fn add_birth(&mut self, hook: CreatureHook<B>) {
+        self.birth.push(hook);
+    }
+
+    pub fn add_death(&mut self, hook: DeathHook<B>) {
+        self.death.push(hook);
+    }
+
+    pub fn add_eat(&mut self, hook: EatHook<B>) {
+        self.eat.push(hook);
+    }
+
+    pub fn add_season_change(&mut self, hook: SeasonHook) {
+        self.season_change.push(hook);
+    }
+
+    pub fn add_weather(&mut self, hook: WeatherHook) {
+        self.weather.push(hook);
+    }
+
+    pub fn add_disaster(&mut self, hook: DisasterHook) {
+        self.disaster.push(hook);
+    }
+
+    pub fn add_extinction(&mut self, hook: ExtinctionHook) {
+        self.extinction.push(hook);
+    }
+
+    /// Returns true if anything is subscribed to eating, finding out who ate isn't free.
+    pub fn wants_eat(&self) -> bool {
+        !self.eat.is_empty()
+    }
+
+    pub fn birth(&mut self, time: f64, baby: &SoftBody<B>) {
+        for hook in &mut self.birth {
+            hook(time, baby);
+        }
+    }
+
+    pub fn death(&mut self, time: f64, creature: &SoftBody<B>, cause: DeathCause) {
+        for hook in &mut self.death {
+            hook(time, creature, cause);
+        }
+    }
+
+    pub fn eat(&mut self, time: f64, creature: &SoftBody<B>, energy: f64) {
+        for hook in &mut self.eat {
+            hook(time, creature, energy);
+        }
+    }
+
+    pub fn season_change(&mut self, time: f64, season: &str) {
+        for hook in &mut self.season_change {
+            hook(time, season);
+        }
+    }
+
+    pub fn weather(&mut self, time: f64, event: &WeatherEvent) {
+        for hook in &mut self.weather {
+            hook(time, event);
+        }
+    }
+
+    pub fn disaster(&mut self, time: f64, disaster: &Disaster) {
+        for hook in &mut self.disaster {
+            hook(time, disaster);
+        }
+    }
+
+    pub fn extinction(&mut self, time: f64, cause: ExtinctionCause) {
+        for hook in &mut self.extinction {
+            hook(time, cause);
+        }
+    }
+
+    /// Removes all callbacks.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
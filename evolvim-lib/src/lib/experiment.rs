@@ -0,0 +1,176 @@
+//! A/B experiments with two different environments on one board.
+//!
+//! A `SplitExperiment` divides the board into a left half (A) and a right half (B), each with its own climate, and puts
+//! a barrier between them. Run a board with `Board::set_split_experiment` and compare the `HalfStatistics` of both
+//! halves to see how populations diverge in different environments.
+
+use crate::brain::NeuralNet;
+use crate::climate::RegionalClimate;
+use crate::constants::{DEFAULT_MAX_TEMP, DEFAULT_MIN_TEMP};
+use crate::terrain::tile::Tile;
+use crate::Board;
+
+/// One of the two halves of the board.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Half {
+    /// The left half.
+    A,
+    /// The right half.
+    B,
+}
+
+impl Half {
+    /// Returns the climate region the tiles of this half are in.
+    fn get_region(self) -> usize {
+        match self {
+            Half::A => 1,
+            Half::B => 2,
+        }
+    }
+}
+
+/// What separates the two halves.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Barrier {
+    /// Creatures can walk from one half to the other.
+    Permeable,
+    /// A strip of water this many tiles wide, creatures can cross it but swimming costs energy.
+    Water(usize),
+    /// Nothing can cross.
+    Wall,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SplitExperiment {
+    pub climate_a: RegionalClimate,
+    pub climate_b: RegionalClimate,
+    pub barrier: Barrier,
+}
+
+impl Default for SplitExperiment {
+    /// Both halves get the default climate, only the barrier separates them.
+    fn default() -> Self {
+        let climate = RegionalClimate::new(DEFAULT_MIN_TEMP, DEFAULT_MAX_TEMP, 1.0);
+
+        SplitExperiment {
+            climate_a: climate.clone(),
+            climate_b: climate,
+            barrier: Barrier::Wall,
+        }
+    }
+}
+
+impl SplitExperiment {
+    pub fn new(climate_a: RegionalClimate, climate_b: RegionalClimate, barrier: Barrier) -> Self {
+        SplitExperiment {
+            climate_a,
+            climate_b,
+            barrier,
+        }
+    }
+
+    /// Returns the x coordinate of the line between the two halves.
+    pub fn get_split_x(board_width: usize) -> f64 {
+        board_width as f64 / 2.0
+    }
+
+    /// Returns the half the given x coordinate is in.
+    pub fn get_half(x: f64, board_width: usize) -> Half {
+        if x < Self::get_split_x(board_width) {
+            Half::A
+        } else {
+            Half::B
+        }
+    }
+
+    /// Returns the x coordinate of the wall if there is one.
+    pub fn get_wall_x(&self, board_width: usize) -> Option<f64> {
+        match self.barrier {
+            Barrier::Wall => Some(Self::get_split_x(board_width)),
+            _ => None,
+        }
+    }
+
+    /// Changes the climate and terrain of `board` for this experiment, `Board::set_split_experiment` calls this.
+    pub(crate) fn apply_to<B: NeuralNet>(&self, board: &mut Board<B>) {
+        let (width, height) = board.get_board_size();
+
+        board
+            .climate
+            .set_regions(vec![self.climate_a.clone(), self.climate_b.clone()]);
+
+        for x in 0..width {
+            let half = Self::get_half(x as f64 + 0.5, width);
+            for y in 0..height {
+                board
+                    .terrain
+                    .get_tile_at_mut((x, y))
+                    .set_region(half.get_region());
+            }
+        }
+
+        if let Barrier::Water(strip_width) = self.barrier {
+            let start = (width / 2).saturating_sub(strip_width / 2);
+            let end = (start + strip_width).min(width);
+            for x in start..end {
+                for y in 0..height {
+                    board.terrain.set_tile((x, y), Tile::new_water(1.0));
+                }
+            }
+        }
+    }
+
+    /// Returns the statistics of both halves, A first. Creatures count for the half their center is in.
+    pub fn get_statistics<B: NeuralNet>(&self, board: &Board<B>) -> [HalfStatistics; 2] {
+        let (width, height) = board.get_board_size();
+        let time = board.get_time();
+        let mut stats = [HalfStatistics::default(), HalfStatistics::default()];
+        let index = |half| match half {
+            Half::A => 0,
+            Half::B => 1,
+        };
+
+        for x in 0..width {
+            let s = &mut stats[index(Self::get_half(x as f64 + 0.5, width))];
+            for y in 0..height {
+                let tile = board.terrain.get_tile_at((x, y));
+                s.tile_food += tile.get_food_level() + tile.get_stored_food();
+            }
+        }
+
+        for c in &board.creatures {
+            let c = c.borrow();
+            let s = &mut stats[index(Self::get_half(c.get_px(), width))];
+
+            s.population += 1;
+            s.creature_energy += c.get_energy();
+            s.mean_age += c.get_age(time);
+            s.mean_mouth_hue += c.get_mouth_hue();
+            s.mean_complexity += c.brain.complexity() as f64;
+        }
+
+        for s in stats.iter_mut() {
+            if s.population > 0 {
+                let n = s.population as f64;
+                s.mean_age /= n;
+                s.mean_mouth_hue /= n;
+                s.mean_complexity /= n;
+            }
+        }
+
+        stats
+    }
+}
+
+/// A summary of one half of a `SplitExperiment`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HalfStatistics {
+    pub population: usize,
+    /// The energy of all creatures in this half.
+    pub creature_energy: f64,
+    /// The food lying on and stored in all tiles of this half.
+    pub tile_food: f64,
+    pub mean_age: f64,
+    pub mean_mouth_hue: f64,
+    pub mean_complexity: f64,
+}
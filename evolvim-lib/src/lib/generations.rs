@@ -0,0 +1,179 @@
+//! Evolving creatures one generation at a time.
+//!
+//! Normally creatures only evolve by having babies while the world keeps running. For controlled experiments
+//! `Generations` offers an alternative: the board runs for an epoch, the creatures that are still alive are ranked by a
+//! fitness function of your choice and the next generation is bred from the best of them. All creatures are then
+//! replaced by that generation and the next epoch starts. Creatures can still have babies during an epoch.
+//!
+//! Parents are picked with tournament selection and combined with `RecombinationInfinite`, which is NEAT crossover for
+//! a `NeatBrain`.
+
+use crate::brain::{GenerateRandom, NeuralNet, RecombinationInfinite};
+use crate::rng::{self, Stream};
+use crate::run::{RunOptions, RunSummary};
+use crate::softbody::SoftBody;
+use crate::Board;
+use std::cmp::Ordering;
+
+/// Returns how well a creature did, higher is better. Gets the creature and the current time.
+pub type FitnessFunction<B> = Box<Fn(&SoftBody<B>, f64) -> f64>;
+
+/// Rewards creatures for the energy they have at the end of an epoch.
+pub fn energy_fitness<B>(creature: &SoftBody<B>, _time: f64) -> f64 {
+    creature.get_energy()
+}
+
+/// Rewards creatures for staying alive for a long time.
+pub fn age_fitness<B>(creature: &SoftBody<B>, time: f64) -> f64 {
+    creature.get_age(time)
+}
+
+/// How `Generations` should breed.
+#[derive(Clone, Debug)]
+pub struct GenerationOptions {
+    /// The amount of years every generation gets to live.
+    pub epoch: f64,
+    /// The amount of best creatures that are copied into the next generation unchanged.
+    pub elites: usize,
+    /// The amount of creatures that compete for every parent, more means only the best get to breed.
+    pub tournament_size: usize,
+    /// How every epoch is run.
+    pub run: RunOptions,
+}
+
+impl Default for GenerationOptions {
+    fn default() -> Self {
+        GenerationOptions {
+            epoch: 10.0,
+            elites: 2,
+            tournament_size: 3,
+            run: RunOptions::default(),
+        }
+    }
+}
+
+/// What happened during a generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenerationSummary {
+    /// The number of this generation, starting at 0.
+    pub generation: usize,
+    pub run: RunSummary,
+    /// The amount of creatures that were alive at the end of the epoch and got ranked.
+    pub ranked: usize,
+    /// The fitness of the best creature, 0 if there were none.
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+}
+
+/// Runs a `Board` generation by generation.
+pub struct Generations<B> {
+    options: GenerationOptions,
+    fitness: FitnessFunction<B>,
+    generation: usize,
+}
+
+impl<B: NeuralNet + RecombinationInfinite + GenerateRandom + Clone> Generations<B> {
+    pub fn new(fitness: FitnessFunction<B>, options: GenerationOptions) -> Self {
+        assert!(options.epoch > 0.0, "An epoch has to be positive.");
+        assert!(
+            options.tournament_size > 0,
+            "At least one creature has to compete in a tournament."
+        );
+
+        Generations {
+            options,
+            fitness,
+            generation: 0,
+        }
+    }
+
+    pub fn get_options(&self) -> &GenerationOptions {
+        &self.options
+    }
+
+    /// Returns the amount of generations that have been run.
+    pub fn get_generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Runs `board` for an epoch and replaces all creatures by the next generation.
+    ///
+    /// The next generation has as many creatures as the creature minimum of `board`. If no creature survived the epoch
+    /// they all get a random brain.
+    pub fn run_generation(&mut self, board: &mut Board<B>) -> GenerationSummary {
+        let run = board.run_for(self.options.epoch, &self.options.run);
+
+        let time = board.get_time();
+        let mut ranked: Vec<(f64, B)> = board
+            .creatures
+            .iter()
+            .map(|c| {
+                let c = c.borrow();
+                ((self.fitness)(&c, time), c.brain.clone())
+            })
+            .collect();
+        // Best first, creatures with a fitness of NaN end up last.
+        ranked.sort_by(|a, b| match (a.0.is_nan(), b.0.is_nan()) {
+            (false, false) => b.0.partial_cmp(&a.0).unwrap(),
+            (a_nan, b_nan) => a_nan.cmp(&b_nan),
+        });
+
+        let size = board.get_creature_minimum();
+        board.with_own_rng(|board| {
+            let brains = rng::with_stream(Stream::Mutation, || self.breed(&ranked, size));
+            board.replace_creatures(brains)
+        });
+
+        let summary = GenerationSummary {
+            generation: self.generation,
+            run,
+            ranked: ranked.len(),
+            best_fitness: ranked.first().map_or(0.0, |r| r.0),
+            mean_fitness: if ranked.is_empty() {
+                0.0
+            } else {
+                ranked.iter().map(|r| r.0).sum::<f64>() / ranked.len() as f64
+            },
+        };
+        self.generation += 1;
+
+        summary
+    }
+
+    /// Returns `size` brains bred from `ranked`, which has to be sorted best first.
+    fn breed(&self, ranked: &[(f64, B)], size: usize) -> Vec<B> {
+        if ranked.is_empty() {
+            return Vec::new();
+        }
+
+        let mut brains: Vec<B> = ranked
+            .iter()
+            .take(self.options.elites.min(size))
+            .map(|r| r.1.clone())
+            .collect();
+
+        while brains.len() < size {
+            let a = self.tournament(ranked.len());
+            let b = self.tournament(ranked.len());
+
+            // The fittest parent goes first, a single parent is only mutated.
+            brains.push(match a.cmp(&b) {
+                Ordering::Equal => B::recombination_infinite_brains(&[&ranked[a].1]),
+                Ordering::Less => B::recombination_infinite_brains(&[&ranked[a].1, &ranked[b].1]),
+                Ordering::Greater => {
+                    B::recombination_infinite_brains(&[&ranked[b].1, &ranked[a].1])
+                }
+            });
+        }
+
+        brains
+    }
+
+    /// Returns the index of the winner of a tournament between random creatures, the lowest index is the fittest.
+    fn tournament(&self, amount: usize) -> usize {
+        (0..self.options.tournament_size)
+            .map(|_| rng::gen_range(0, amount))
+            .min()
+            .unwrap()
+    }
+}
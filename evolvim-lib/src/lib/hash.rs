@@ -0,0 +1,125 @@
+//! Stable hashing of the simulation state.
+//!
+//! Two runs that should be identical (e.g. a replay, or the same run on another machine) can compare hashes every now
+//! and then to find out if and when they diverged. The hash only depends on the simulation state and not on the
+//! platform or the Rust version, unlike `std::collections::hash_map::DefaultHasher`.
+
+extern crate bincode;
+
+use crate::error::EvolvimError;
+use crate::history::{History, RetentionPolicy};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A 64-bit FNV-1a hasher, values are fed to it in a fixed byte order.
+pub struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        StateHasher(FNV_OFFSET_BASIS)
+    }
+}
+
+impl StateHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    /// Hashes the exact bits of `value`, so `0.0` and `-0.0` hash differently.
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    /// Hashes anything that can be serialized by hashing its serialized form.
+    pub fn write_serialized<T: serde::Serialize + ?Sized>(&mut self, value: &T) {
+        bincode::serialize_into(&mut *self, value)
+            .expect("Could not serialize the state for hashing.");
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl std::io::Write for StateHasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_bytes(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Records `Board::state_hash` every `interval` updates.
+///
+/// Call `tick` after every update of the board.
+#[derive(Serialize, Deserialize)]
+pub struct StateHashLog {
+    interval: usize,
+    ticks: usize,
+    hashes: History<u64>,
+}
+
+impl StateHashLog {
+    pub fn new(interval: usize) -> Self {
+        Self::with_policy(interval, RetentionPolicy::KeepAll)
+    }
+
+    /// Creates a log which forgets old hashes according to `policy`.
+    pub fn with_policy(interval: usize, policy: RetentionPolicy) -> Self {
+        assert!(interval > 0, "The interval between two hashes can't be 0.");
+
+        StateHashLog {
+            interval,
+            ticks: 0,
+            hashes: History::new(policy),
+        }
+    }
+
+    /// Counts an update and hashes the board if it's time to do so.
+    pub fn tick<B>(&mut self, board: &crate::Board<B>) -> Result<(), EvolvimError>
+    where
+        B: crate::brain::NeuralNet + serde::Serialize,
+    {
+        self.ticks += 1;
+
+        if self.ticks % self.interval == 0 {
+            self.hashes.push(board.get_time(), board.state_hash())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns all recorded `(time, hash)` pairs that are still in memory.
+    pub fn get_hashes(&self) -> impl Iterator<Item = &(f64, u64)> {
+        self.hashes.iter()
+    }
+
+    /// Returns the first time at which both logs recorded a different hash.
+    ///
+    /// Only times that were recorded by both logs are compared.
+    pub fn find_divergence(&self, other: &StateHashLog) -> Option<f64> {
+        self.get_hashes()
+            .find(|(time, hash)| other.get_hashes().any(|(t, h)| t == time && h != hash))
+            .map(|(time, _)| *time)
+    }
+}
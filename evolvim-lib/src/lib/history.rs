@@ -0,0 +1,211 @@
+//! Bounded storage for anything that gets recorded over time.
+//!
+//! Long runs produce a lot of history; a `History` keeps its memory usage in check by applying a `RetentionPolicy`
+//! every time something is recorded. Every subsystem that records data owns its own `History` so the policy can be
+//! chosen per subsystem.
+
+extern crate bincode;
+
+use crate::error::EvolvimError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Decides what happens to old entries of a `History`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RetentionPolicy {
+    /// Never forget anything.
+    KeepAll,
+    /// Only keep the entries of the last `years` years.
+    KeepLast { years: f64 },
+    /// Only keep the last `entries` entries.
+    KeepCount { entries: usize },
+    /// Keep the entries of the last `recent` years, older entries are thinned out to at most one every `interval` years.
+    Downsample { recent: f64, interval: f64 },
+    /// Keep the entries of the last `years` years in memory, older entries are appended to the file at `path`.
+    ///
+    /// Use `History::read_spilled` to get them back.
+    SpillToDisk { years: f64, path: PathBuf },
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy::KeepAll
+    }
+}
+
+/// A list of `(time, value)` entries, ordered by time, which forgets old entries according to its `RetentionPolicy`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct History<T> {
+    policy: RetentionPolicy,
+    /// Old entries that survived downsampling.
+    archived: Vec<(f64, T)>,
+    recent: VecDeque<(f64, T)>,
+    /// The amount of entries that were written to disk.
+    spilled: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History::new(RetentionPolicy::default())
+    }
+}
+
+impl<T> History<T> {
+    pub fn new(policy: RetentionPolicy) -> Self {
+        History {
+            policy,
+            archived: Vec::new(),
+            recent: VecDeque::new(),
+            spilled: 0,
+        }
+    }
+
+    pub fn get_policy(&self) -> &RetentionPolicy {
+        &self.policy
+    }
+
+    /// Returns the amount of entries currently held in memory.
+    pub fn len(&self) -> usize {
+        self.archived.len() + self.recent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the amount of entries that were moved to disk.
+    pub fn get_spilled_count(&self) -> usize {
+        self.spilled
+    }
+
+    /// Iterates over all entries in memory, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &(f64, T)> {
+        self.archived.iter().chain(self.recent.iter())
+    }
+
+    pub fn last(&self) -> Option<&(f64, T)> {
+        self.recent.back().or_else(|| self.archived.last())
+    }
+
+    /// Iterates over the entries in memory recorded at a time in `from..=to`, oldest first.
+    pub fn range(&self, from: f64, to: f64) -> impl Iterator<Item = &(f64, T)> {
+        self.iter()
+            .skip_while(move |(t, _)| *t < from)
+            .take_while(move |(t, _)| *t <= to)
+    }
+}
+
+impl<T: Serialize> History<T> {
+    /// Changes the policy, it will be applied the next time something is recorded.
+    pub fn set_policy(&mut self, policy: RetentionPolicy) {
+        self.policy = policy;
+    }
+
+    /// Records `value` for `time` and applies the retention policy.
+    ///
+    /// This only fails if the policy spills to disk and writing goes wrong.
+    pub fn push(&mut self, time: f64, value: T) -> Result<(), EvolvimError> {
+        self.recent.push_back((time, value));
+
+        self.enforce(time)
+    }
+
+    /// Applies the retention policy as if the current time is `now`.
+    pub fn enforce(&mut self, now: f64) -> Result<(), EvolvimError> {
+        match self.policy.clone() {
+            RetentionPolicy::KeepAll => {}
+            RetentionPolicy::KeepLast { years } => {
+                // Downsampled entries are older than anything in `recent`.
+                self.archived.retain(|(t, _)| *t >= now - years);
+
+                while self.is_front_older_than(now - years) {
+                    self.recent.pop_front();
+                }
+            }
+            RetentionPolicy::KeepCount { entries } => {
+                while self.len() > entries {
+                    if self.archived.is_empty() {
+                        self.recent.pop_front();
+                    } else {
+                        self.archived.remove(0);
+                    }
+                }
+            }
+            RetentionPolicy::Downsample { recent, interval } => {
+                while self.is_front_older_than(now - recent) {
+                    let entry = self.recent.pop_front().unwrap();
+
+                    let keep = match self.archived.last() {
+                        Some((t, _)) => entry.0 - t >= interval,
+                        None => true,
+                    };
+                    if keep {
+                        self.archived.push(entry);
+                    }
+                }
+            }
+            RetentionPolicy::SpillToDisk { years, path } => {
+                let old = self
+                    .recent
+                    .iter()
+                    .take_while(|(t, _)| *t < now - years)
+                    .count();
+
+                if !self.archived.is_empty() || old > 0 {
+                    // Entries only leave memory once they're on disk, a failed write loses nothing.
+                    let mut bytes = Vec::new();
+                    for entry in self.archived.iter().chain(self.recent.iter().take(old)) {
+                        bincode::serialize_into(&mut bytes, entry)?;
+                    }
+
+                    std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)?
+                        .write_all(&bytes)?;
+
+                    self.spilled += self.archived.len() + old;
+                    self.archived.clear();
+                    self.recent.drain(..old);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_front_older_than(&self, time: f64) -> bool {
+        match self.recent.front() {
+            Some((t, _)) => *t < time,
+            None => false,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> History<T> {
+    /// Reads back all entries that were spilled to the file at `path`, oldest first.
+    pub fn read_spilled<P: AsRef<Path>>(path: P) -> Result<Vec<(f64, T)>, EvolvimError> {
+        let file = std::fs::File::open(path)?;
+        let mut reader = std::io::BufReader::new(file);
+        let mut entries = Vec::new();
+
+        loop {
+            match bincode::deserialize_from(&mut reader) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => match *e {
+                    bincode::ErrorKind::Io(ref io)
+                        if io.kind() == std::io::ErrorKind::UnexpectedEof =>
+                    {
+                        break
+                    }
+                    _ => return Err(EvolvimError::from(e).into_corrupt()),
+                },
+            }
+        }
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,158 @@
+//! Following a single family through the generations.
+//!
+//! Pin a founder with `LineageTracker::new` and call `update` after every update of the `Board`, the tracker keeps one
+//! living member of the lineage selected and remembers who took over whenever the tracked creature died.
+
+use crate::brain::NeuralNet;
+use crate::softbody::HLSoftBody;
+use crate::Board;
+use std::collections::HashSet;
+
+/// The tracked creature died and `successor` was selected in its place.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuccessionEvent {
+    pub time: f64,
+    pub deceased: usize,
+    /// `None` if the lineage died out.
+    pub successor: Option<usize>,
+}
+
+pub struct LineageTracker {
+    founder: usize,
+    /// The ids of all living members of the lineage.
+    members: HashSet<usize>,
+    tracked: Option<usize>,
+    /// Creatures with an id below this have already been checked.
+    next_id: usize,
+    events: Vec<SuccessionEvent>,
+}
+
+impl LineageTracker {
+    /// Starts following the descendants of the creature with id `founder`.
+    ///
+    /// Only creatures born from now on are counted as descendants, which is fine since the founder is usually alive.
+    pub fn new<B: NeuralNet>(board: &mut Board<B>, founder: usize) -> Self {
+        let mut members = HashSet::new();
+        let mut tracked = None;
+        if board.get_creature_by_id(founder).is_some() {
+            members.insert(founder);
+            tracked = Some(founder);
+            board.selected_creature.select(founder);
+        }
+
+        LineageTracker {
+            founder,
+            members,
+            tracked,
+            next_id: board.get_creature_id_up_to(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Adds newborns to the lineage, forgets dead members and selects a successor if the tracked creature died.
+    ///
+    /// Returns the succession that happened during this call, if any.
+    pub fn update<B: NeuralNet>(&mut self, board: &mut Board<B>) -> Option<SuccessionEvent> {
+        // Babies are only added at the end of `creatures`, in order of their id.
+        for c in board.creatures.iter().rev() {
+            let c = c.borrow();
+            if c.get_id() < self.next_id {
+                break;
+            }
+
+            if c.get_parent_ids().iter().any(|p| self.members.contains(p)) {
+                self.members.insert(c.get_id());
+            }
+        }
+        self.next_id = board.get_creature_id_up_to();
+
+        let living: HashSet<usize> = board
+            .creatures
+            .iter()
+            .map(|c| c.borrow().get_id())
+            .filter(|id| self.members.contains(id))
+            .collect();
+        self.members = living;
+
+        let deceased = match self.tracked {
+            Some(id) if !self.members.contains(&id) => id,
+            Some(id) => {
+                // Keep the tracked creature selected, even if the user clicked something else.
+                board.selected_creature.select(id);
+                return None;
+            }
+            None => return None,
+        };
+
+        let successor = self.find_successor(board, deceased);
+        self.tracked = successor.as_ref().map(|c| c.borrow().get_id());
+        match self.tracked {
+            Some(id) => board.selected_creature.select(id),
+            None => board.selected_creature.deselect(),
+        }
+
+        let event = SuccessionEvent {
+            time: board.get_time(),
+            deceased,
+            successor: self.tracked,
+        };
+        self.events.push(event.clone());
+
+        Some(event)
+    }
+
+    /// Prefers the oldest child of `deceased`, otherwise the oldest member of the lineage.
+    fn find_successor<B: NeuralNet>(
+        &self,
+        board: &Board<B>,
+        deceased: usize,
+    ) -> Option<HLSoftBody<B>> {
+        let members: Vec<&HLSoftBody<B>> = board
+            .creatures
+            .iter()
+            .filter(|c| self.members.contains(&c.borrow().get_id()))
+            .collect();
+
+        let oldest = |candidates: Vec<&HLSoftBody<B>>| {
+            candidates
+                .into_iter()
+                .min_by(|a, b| {
+                    let a = a.borrow().get_birth_time();
+                    let b = b.borrow().get_birth_time();
+                    a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .cloned()
+        };
+
+        let children = members
+            .iter()
+            .filter(|c| c.borrow().get_parent_ids().contains(&deceased))
+            .cloned()
+            .collect();
+
+        oldest(children).or_else(|| oldest(members))
+    }
+
+    pub fn get_founder(&self) -> usize {
+        self.founder
+    }
+
+    /// Returns the id of the creature currently followed, `None` once the lineage died out.
+    pub fn get_tracked(&self) -> Option<usize> {
+        self.tracked
+    }
+
+    pub fn is_member(&self, id: usize) -> bool {
+        self.members.contains(&id)
+    }
+
+    /// Returns the amount of living members of the lineage.
+    pub fn get_member_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns all successions in the order they happened.
+    pub fn get_events(&self) -> &[SuccessionEvent] {
+        &self.events
+    }
+}
@@ -12,18 +12,51 @@ extern crate serde_derive;
 extern crate serde;
 
 pub mod board;
+pub mod board_history;
 pub mod brain;
+pub mod checkpoint;
 pub mod climate;
+pub mod color;
+pub mod config;
 pub mod constants;
+pub mod disaster;
+pub mod egg;
+pub mod energy_flow;
+pub mod error;
+pub mod events;
+pub mod experiment;
+pub mod generations;
+pub mod hash;
+pub mod history;
+pub mod lineage;
+pub mod names;
 pub mod neat;
+pub mod pheromone;
+pub mod phylogeny;
+pub mod repair;
+pub mod replay;
+pub mod rng;
+pub mod run;
 pub mod sbip;
+pub mod seed_bank;
+pub mod simulation;
+pub mod snapshot;
 pub mod softbody;
+pub mod species;
+pub mod stats;
 pub mod terrain;
+pub mod weather;
 pub mod serde_structs;
 
 pub use self::board::*;
 pub use self::brain::*;
 pub use self::climate::Climate;
+pub use self::config::SimConfig;
+pub use self::error::EvolvimError;
+pub use self::history::{History, RetentionPolicy};
+pub use self::pheromone::PheromoneField;
 pub use self::sbip::*;
+pub use self::simulation::Simulation;
 pub use self::softbody::*;
+pub use self::stats::BoardStats;
 pub use self::terrain::*;
@@ -0,0 +1,48 @@
+//! Pronounceable names for creatures, so observers can talk about "Gork's lineage" instead of creature 48213.
+//!
+//! Like in carykh's original, a name is a few syllables stuck together. Here they are picked by the genome fingerprint
+//! of the creature (see `Creature::get_genome_fingerprint`), so a name only depends on the genes: genetically identical
+//! creatures share a name and the `Phylogeny` still knows the name of a creature long after it died.
+
+const CONSONANTS: [&str; 24] = [
+    "b", "d", "f", "g", "h", "j", "k", "l", "m", "n", "p", "r", "s", "t", "v", "w", "z", "br",
+    "dr", "gr", "kr", "sh", "st", "th",
+];
+const VOWELS: [&str; 8] = ["a", "e", "i", "o", "u", "ai", "oo", "ou"];
+/// Most names end on a vowel, the empty endings make that more likely.
+const ENDINGS: [&str; 12] = ["", "", "", "", "k", "m", "n", "r", "s", "x", "ck", "rk"];
+const MIN_SYLLABLES: u64 = 2;
+const MAX_SYLLABLES: u64 = 3;
+
+/// Returns the name that belongs to `fingerprint`, e.g. "Grokar" or "Theboux".
+pub fn name_from_fingerprint(fingerprint: u64) -> String {
+    // Fingerprints of similar genomes can share a lot of bits, mixing them first gives them unrelated names.
+    let mut bits = mix(fingerprint);
+    let mut pick = |amount: u64| {
+        let choice = bits % amount;
+        bits /= amount;
+        choice as usize
+    };
+
+    let syllables = MIN_SYLLABLES + pick(MAX_SYLLABLES - MIN_SYLLABLES + 1) as u64;
+    let mut name = String::new();
+    for _ in 0..syllables {
+        name.push_str(CONSONANTS[pick(CONSONANTS.len() as u64)]);
+        name.push_str(VOWELS[pick(VOWELS.len() as u64)]);
+    }
+    name.push_str(ENDINGS[pick(ENDINGS.len() as u64)]);
+
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+/// The finalizer of SplitMix64, every bit of the result depends on every bit of `x`.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+    x ^ (x >> 31)
+}
@@ -0,0 +1,207 @@
+//! Tracks how often discrete genome features ("alleles") occur in the population over time.
+//!
+//! NEAT genomes don't have fixed loci, so an allele here is any discrete feature that can be recognised across
+//! genomes: a connection gene with a certain innovation number, a hidden node with a certain id or a small structural
+//! motif. Recording the frequencies every now and then gives trajectories that show when alleles reach fixation or get
+//! lost. The samples are kept in a `History`, so long runs can limit how much of it stays in memory.
+
+use super::genome::{Genome, NodeType};
+use crate::error::EvolvimError;
+use crate::history::{History, RetentionPolicy};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+/// A discrete feature of a genome.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Allele {
+    /// An enabled connection gene with this innovation number.
+    Connection(usize),
+    /// A disabled connection gene with this innovation number.
+    DisabledConnection(usize),
+    /// A hidden node with this id.
+    HiddenNode(usize),
+    /// At least one enabled connection going from a node to itself.
+    SelfLoop,
+}
+
+impl Allele {
+    /// Returns a short name that is used when exporting.
+    pub fn get_name(&self) -> String {
+        match self {
+            Allele::Connection(i) => format!("connection_{}", i),
+            Allele::DisabledConnection(i) => format!("disabled_connection_{}", i),
+            Allele::HiddenNode(id) => format!("hidden_node_{}", id),
+            Allele::SelfLoop => "self_loop".to_string(),
+        }
+    }
+}
+
+impl Genome {
+    /// Returns every allele present in this genome, each allele only once.
+    pub fn get_alleles(&self) -> BTreeSet<Allele> {
+        let mut alleles = BTreeSet::new();
+
+        for n in self.get_node_genome() {
+            if let NodeType::Hidden = n.node_type {
+                alleles.insert(Allele::HiddenNode(n.id));
+            }
+        }
+
+        for c in self.get_connection_genome() {
+            if c.enabled {
+                alleles.insert(Allele::Connection(c.innovation_number));
+
+                if c.from == c.to {
+                    alleles.insert(Allele::SelfLoop);
+                }
+            } else {
+                alleles.insert(Allele::DisabledConnection(c.innovation_number));
+            }
+        }
+
+        return alleles;
+    }
+}
+
+/// The allele counts of the population at one moment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Sample {
+    population: usize,
+    counts: BTreeMap<Allele, usize>,
+}
+
+/// Records allele frequencies of a population every time `record` is called.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AlleleTracker {
+    samples: History<Sample>,
+}
+
+impl AlleleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tracker which forgets old samples according to `policy`.
+    pub fn with_policy(policy: RetentionPolicy) -> Self {
+        AlleleTracker {
+            samples: History::new(policy),
+        }
+    }
+
+    pub fn set_retention_policy(&mut self, policy: RetentionPolicy) {
+        self.samples.set_policy(policy);
+    }
+
+    /// Counts the alleles in the given genomes and stores the result for `time`.
+    ///
+    /// This can only fail if the retention policy spills samples to disk.
+    pub fn record<'a, I>(&mut self, time: f64, genomes: I) -> Result<(), EvolvimError>
+    where
+        I: IntoIterator<Item = &'a Genome>,
+    {
+        let mut counts = BTreeMap::new();
+        let mut population = 0;
+
+        for genome in genomes {
+            population += 1;
+
+            for allele in genome.get_alleles() {
+                *counts.entry(allele).or_insert(0) += 1;
+            }
+        }
+
+        self.samples.push(time, Sample { population, counts })
+    }
+
+    /// Records the alleles of all creatures currently living on the board.
+    pub fn record_board(
+        &mut self,
+        board: &crate::Board<super::NeatBrain>,
+    ) -> Result<(), EvolvimError> {
+        let creatures: Vec<_> = board.creatures.iter().map(|c| c.borrow()).collect();
+
+        self.record(
+            board.get_time(),
+            creatures.iter().map(|c| c.brain.get_genome()),
+        )
+    }
+
+    /// Returns the amount of samples currently held in memory.
+    pub fn get_sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns every allele that was seen in at least one sample.
+    pub fn get_alleles(&self) -> BTreeSet<Allele> {
+        self.samples
+            .iter()
+            .flat_map(|(_, s)| s.counts.keys().cloned())
+            .collect()
+    }
+
+    /// Returns `(time, frequency)` for every sample, the frequency ranges from 0 to 1.
+    pub fn get_trajectory(&self, allele: Allele) -> Vec<(f64, f64)> {
+        self.samples
+            .iter()
+            .map(|(time, s)| (*time, Self::get_frequency_in(s, allele)))
+            .collect()
+    }
+
+    /// Returns the alleles that are carried by every member of the population in the last sample.
+    pub fn get_fixed_alleles(&self) -> Vec<Allele> {
+        match self.samples.last() {
+            Some((_, last)) => self
+                .get_alleles()
+                .into_iter()
+                .filter(|a| last.population > 0 && Self::get_frequency_in(last, *a) >= 1.0)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the alleles that were seen before but are absent from the last sample.
+    pub fn get_lost_alleles(&self) -> Vec<Allele> {
+        match self.samples.last() {
+            Some((_, last)) => self
+                .get_alleles()
+                .into_iter()
+                .filter(|a| !last.counts.contains_key(a))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Writes all trajectories as CSV with the columns `time,allele,count,frequency`.
+    ///
+    /// Every allele gets a row for every sample, including the ones where it's absent, so losses show up as zeros.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "time,allele,count,frequency")?;
+
+        let alleles = self.get_alleles();
+        for (time, s) in self.samples.iter() {
+            for allele in &alleles {
+                let count = s.counts.get(allele).cloned().unwrap_or(0);
+
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    time,
+                    allele.get_name(),
+                    count,
+                    Self::get_frequency_in(s, *allele)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_frequency_in(sample: &Sample, allele: Allele) -> f64 {
+        if sample.population == 0 {
+            return 0.0;
+        }
+
+        let count = sample.counts.get(&allele).cloned().unwrap_or(0);
+        count as f64 / sample.population as f64
+    }
+}
@@ -23,7 +23,44 @@ pub struct ConnectionGene {
     pub innovation_number: usize,
 }
 
-impl NodeGene {}
+/// Switches a group of connections on or off depending on the environment.
+///
+/// This lets creatures evolve different behaviour for different circumstances (phenotypic plasticity).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConditionGene {
+    pub condition: crate::neat::input::Condition,
+    pub threshold: f64,
+    /// If this is true the connections are only expressed while the condition is above the threshold, otherwise only
+    /// while it's below.
+    pub when_above: bool,
+    /// The innovation numbers of the connections this gene switches.
+    pub connections: Vec<usize>,
+
+    pub innovation_number: usize,
+}
+
+impl NodeGene {
+    pub fn is_sensor(&self) -> bool {
+        match self.node_type {
+            NodeType::Sensor(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_output(&self) -> bool {
+        match self.node_type {
+            NodeType::Output(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl ConditionGene {
+    /// Returns true if the connections of this gene should be expressed when the condition has the given value.
+    pub fn is_expressed(&self, value: f64) -> bool {
+        (value > self.threshold) == self.when_above
+    }
+}
 
 impl ConnectionGene {
     pub fn disable_and_info(&mut self) -> (Id, Id) {
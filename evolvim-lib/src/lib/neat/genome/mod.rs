@@ -5,16 +5,21 @@ mod mutation;
 mod recombination;
 // mod innovations;
 mod speciation;
+mod svg;
 mod utils;
 
+pub use self::gene::{ConditionGene, Id, NodeType};
 use self::gene::{ConnectionGene, NodeGene};
-pub use self::gene::{Id, NodeType};
-use rand::Rng;
+use crate::brain::vision::{Vision, INPUTS_PER_RAY};
 
-const AMOUNT_INPUT: usize = 6;
-const AMOUNT_OUTPUT: usize = 4;
-static mut INNOVATION_NUMBER: usize = AMOUNT_INPUT * AMOUNT_OUTPUT;
-static mut NODE_NUMBER: Id = AMOUNT_INPUT + AMOUNT_OUTPUT;
+/// The amount of input nodes that aren't rays, there are `Vision::input_count` ray nodes on top of these.
+const BASE_AMOUNT_INPUT: usize = 16;
+const AMOUNT_OUTPUT: usize = 6;
+// Start counting after the fully linked genome of the default vision, `new_fully_linked` skips ahead for bigger ones.
+const DEFAULT_AMOUNT_INPUT: usize =
+    BASE_AMOUNT_INPUT + crate::constants::VISION_RAY_AMOUNT * INPUTS_PER_RAY;
+static mut INNOVATION_NUMBER: usize = DEFAULT_AMOUNT_INPUT * AMOUNT_OUTPUT;
+static mut NODE_NUMBER: Id = DEFAULT_AMOUNT_INPUT + AMOUNT_OUTPUT;
 
 fn get_innovation_number() -> usize {
     unsafe {
@@ -34,6 +39,7 @@ pub fn get_next_node_id() -> Id {
 pub struct Genome {
     node_genome: Vec<NodeGene>,
     connection_genome: Vec<ConnectionGene>,
+    condition_genome: Vec<ConditionGene>,
 }
 
 impl Genome {
@@ -45,25 +51,29 @@ impl Genome {
     pub fn get_connection_genome(&self) -> &Vec<ConnectionGene> {
         &self.connection_genome
     }
+    /// Accessor function to gain readonly access to the `condition_genome`; used for generating a phenotype.
+    pub fn get_condition_genome(&self) -> &Vec<ConditionGene> {
+        &self.condition_genome
+    }
 
     fn get_random_node_id(&self) -> Id {
         self.node_genome[self.get_random_node_place()].id
     }
 
     fn get_random_node_place(&self) -> usize {
-        rand::thread_rng().gen_range(0, self.node_genome.len())
+        crate::rng::gen_range(0, self.node_genome.len())
     }
 
     fn get_random_connection_place(&self) -> usize {
-        rand::thread_rng().gen_range(0, self.connection_genome.len())
+        crate::rng::gen_range(0, self.connection_genome.len())
     }
 
     fn get_random_weight() -> f64 {
-        rand::random::<f64>() * 2.0 - 1.0
+        crate::rng::random::<f64>() * 2.0 - 1.0
     }
 
     fn get_random_weight_multiplier() -> f64 {
-        rand::random::<f64>() * 0.4 + 0.8
+        crate::rng::random::<f64>() * 0.4 + 0.8
     }
 
     fn add_node(&mut self, node_type: NodeType, id: Id) {
@@ -81,43 +91,72 @@ impl Genome {
         });
     }
 
-    pub fn new_fully_linked() -> Self {
+    /// Returns a genome with an input node for every sense, including the rays of `vision`, linked to every output.
+    pub fn new_fully_linked(vision: &Vision) -> Self {
         let mut genome = Genome {
             node_genome: Vec::new(),
             connection_genome: Vec::new(),
+            condition_genome: Vec::new(),
         };
         let mut node_counter = 1;
 
-        use crate::neat::input::Eye;
-        use crate::neat::input::InputType;
-        const EYE: [Eye; 3] = Eye::get_all_three(0.0, 0.0);
-        let input_nodes: [InputType; 6] = [
+        use crate::neat::input::{Eye, InputType, NearbyType, Ray};
+        let mut input_nodes = vec![
             InputType::Bias(1.0),
             InputType::MouthHue,
             InputType::Energy,
-            InputType::Eye(EYE[0]).clone(),
-            InputType::Eye(EYE[1]).clone(),
-            InputType::Eye(EYE[2]).clone(),
+            InputType::Pheromone,
+            InputType::Age,
+            InputType::Size,
+            InputType::Biome,
+            InputType::Light,
+            InputType::Senescence,
         ];
-        for i in 0..AMOUNT_INPUT {
-            genome.add_node(NodeType::Sensor(input_nodes[i].clone()), node_counter);
+        input_nodes.extend(NearbyType::get_all().iter().cloned().map(InputType::Nearby));
+        input_nodes.extend(
+            Eye::get_all_three(0.0, 0.0)
+                .iter()
+                .cloned()
+                .map(InputType::Eye),
+        );
+
+        for angle in vision.get_ray_angles() {
+            input_nodes.extend(
+                Ray::get_all_three(vision.range, angle)
+                    .iter()
+                    .cloned()
+                    .map(InputType::Ray),
+            );
+        }
+        let amount_input = BASE_AMOUNT_INPUT + vision.input_count();
+        assert_eq!(input_nodes.len(), amount_input);
+        // Don't hand out the ids and innovation numbers this genome uses to the nodes and connections of mutations.
+        unsafe {
+            INNOVATION_NUMBER = INNOVATION_NUMBER.max(amount_input * AMOUNT_OUTPUT);
+            NODE_NUMBER = NODE_NUMBER.max(amount_input + AMOUNT_OUTPUT);
+        }
+
+        for input in input_nodes {
+            genome.add_node(NodeType::Sensor(input), node_counter);
             node_counter += 1;
         }
 
         let mut con_counter = 1;
         use crate::neat::output::OutputType;
-        const OUTPUT_NODES: [NodeType; 4] = [
+        const OUTPUT_NODES: [NodeType; AMOUNT_OUTPUT] = [
             NodeType::Output(OutputType::Turning),
             NodeType::Output(OutputType::Accelerating),
             NodeType::Output(OutputType::MouthHue),
             NodeType::Output(OutputType::Eating),
+            NodeType::Output(OutputType::Bite),
+            NodeType::Output(OutputType::Pheromone),
         ];
         for i in 0..AMOUNT_OUTPUT {
             genome.add_node(OUTPUT_NODES[i].clone(), node_counter);
             node_counter += 1;
 
             let to = genome.node_genome.last().unwrap().id;
-            for i in 0..AMOUNT_INPUT {
+            for i in 0..amount_input {
                 let from = genome.node_genome[i].id;
 
                 // Because all creatures start with this basic genome give all the connections the same innovation number
@@ -156,5 +195,16 @@ impl Genome {
                 n.innovation_number, n.from, n.to, n.weight
             );
         }
+
+        for n in &self.condition_genome {
+            println!(
+                "\tinnovation {}: connections {:?} only when {:?} is {} {}",
+                n.innovation_number,
+                n.connections,
+                n.condition,
+                if n.when_above { "above" } else { "below" },
+                n.threshold
+            );
+        }
     }
 }
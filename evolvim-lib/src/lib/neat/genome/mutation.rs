@@ -1,11 +1,15 @@
-use super::gene::{NodeGene, NodeType};
-use super::{get_next_node_id, Genome};
+use super::gene::{ConditionGene, NodeGene, NodeType};
+use super::{get_innovation_number, get_next_node_id, Genome};
+use crate::neat::input::Condition;
 
 const CHANCE_MUTATE_NEW_LINK: f64 = 0.1;
 const CHANCE_MUTATE_LINK_TO_NODE: f64 = 0.05;
 const CHANCE_MUTATE_TWEAK_WEIGHT: f64 = 0.6;
 const CHANCE_MUTATE_RANDOM_WEIGHT: f64 = 0.2;
 const CHANCE_MUTATE_TOGGLE_ENABLED: f64 = 0.05;
+const CHANCE_MUTATE_ADD_CONDITION: f64 = 0.02;
+const CHANCE_MUTATE_EXTEND_CONDITION: f64 = 0.02;
+const CHANCE_MUTATE_TWEAK_CONDITION: f64 = 0.03;
 
 impl Genome {
     pub fn mutate(&mut self) {
@@ -18,41 +22,53 @@ impl Genome {
             TweakWeight,
             RandomizeWeight,
             ToggleEnabled,
+            AddCondition,
+            ExtendCondition,
+            TweakCondition,
         }
 
         impl MutationType {
-            const fn get_choices() -> [Self; 5] {
+            const fn get_choices() -> [Self; 8] {
                 [
                     AddConnection,
                     ConnectionToNode,
                     TweakWeight,
                     RandomizeWeight,
                     ToggleEnabled,
+                    AddCondition,
+                    ExtendCondition,
+                    TweakCondition,
                 ]
             }
 
-            const fn get_weights() -> [f64; 5] {
+            const fn get_weights() -> [f64; 8] {
                 [
                     CHANCE_MUTATE_NEW_LINK,
                     CHANCE_MUTATE_LINK_TO_NODE,
                     CHANCE_MUTATE_TWEAK_WEIGHT,
                     CHANCE_MUTATE_RANDOM_WEIGHT,
                     CHANCE_MUTATE_TOGGLE_ENABLED,
+                    CHANCE_MUTATE_ADD_CONDITION,
+                    CHANCE_MUTATE_EXTEND_CONDITION,
+                    CHANCE_MUTATE_TWEAK_CONDITION,
                 ]
             }
         }
 
         let dist = rand::distributions::WeightedIndex::new(&MutationType::get_weights()).unwrap();
-        let mut rng = rand::thread_rng();
         let times = self.connection_genome.len() / 2;
 
         for _i in 0..times {
-            match MutationType::get_choices()[dist.sample(&mut rng)] {
+            let choice = crate::rng::with_rng(|rng| dist.sample(rng));
+            match MutationType::get_choices()[choice] {
                 AddConnection => self.mutate_add_connection(),
                 ConnectionToNode => self.mutate_connection_to_node(),
                 TweakWeight => self.mutate_tweak_weight(),
                 RandomizeWeight => self.mutate_randomize_weight(),
                 ToggleEnabled => self.mutate_toggle_gene(),
+                AddCondition => self.mutate_add_condition(),
+                ExtendCondition => self.mutate_extend_condition(),
+                TweakCondition => self.mutate_tweak_condition(),
             }
         }
     }
@@ -94,4 +110,46 @@ impl Genome {
         // toggle `enabled`
         self.connection_genome[connection_id].toggle_enabled();
     }
+
+    /// Makes a random connection depend on a random condition of the environment.
+    pub fn mutate_add_condition(&mut self) {
+        let conditions = Condition::get_all();
+        let condition = conditions[crate::rng::gen_range(0, conditions.len())];
+        let connection_id = self.get_random_connection_place();
+
+        self.condition_genome.push(ConditionGene {
+            condition,
+            threshold: condition.get_random_threshold(),
+            when_above: crate::rng::random(),
+            connections: vec![self.connection_genome[connection_id].innovation_number],
+
+            innovation_number: get_innovation_number(),
+        });
+    }
+
+    /// Adds a random connection to the connections switched by a random condition gene, if there is one.
+    pub fn mutate_extend_condition(&mut self) {
+        if self.condition_genome.is_empty() {
+            return;
+        }
+
+        let condition_id = crate::rng::gen_range(0, self.condition_genome.len());
+        let connection_id = self.get_random_connection_place();
+        let innovation_number = self.connection_genome[connection_id].innovation_number;
+
+        let connections = &mut self.condition_genome[condition_id].connections;
+        if !connections.contains(&innovation_number) {
+            connections.push(innovation_number);
+        }
+    }
+
+    /// Moves the threshold of a random condition gene, if there is one.
+    pub fn mutate_tweak_condition(&mut self) {
+        if self.condition_genome.is_empty() {
+            return;
+        }
+
+        let condition_id = crate::rng::gen_range(0, self.condition_genome.len());
+        self.condition_genome[condition_id].threshold *= Self::get_random_weight_multiplier();
+    }
 }
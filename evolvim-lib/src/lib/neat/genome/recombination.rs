@@ -11,6 +11,7 @@ impl Genome {
         let mut genome = Genome {
             node_genome: Vec::new(),
             connection_genome: Vec::new(),
+            condition_genome: parent_a.condition_genome.clone(),
         };
 
         use RecombinationGeneTypes::*;
@@ -30,6 +31,17 @@ impl Genome {
             }
         }
 
+        // Condition genes: take the ones of parent a and add those only parent b has
+        for b in &parent_b.condition_genome {
+            if !genome
+                .condition_genome
+                .iter()
+                .any(|a| a.innovation_number == b.innovation_number)
+            {
+                genome.condition_genome.push(b.clone());
+            }
+        }
+
         // Make the node genome
         genome.generate_nodes_from_connections(&parent_a.node_genome, &parent_b.node_genome);
 
@@ -28,8 +28,17 @@ impl Genome {
         }
 
         let length = (counter_matching + counter_disjoint + counter_excess) as f64;
+        if length == 0.0 {
+            // Neither genome has any connections.
+            return 0.0;
+        }
+        let mean_weight_difference = if counter_matching > 0 {
+            weight_differences / counter_matching as f64
+        } else {
+            0.0
+        };
 
-        return COEFFICIENT_MATCHING * weight_differences / counter_matching as f64
+        return COEFFICIENT_MATCHING * mean_weight_difference
             + COEFFICIENT_DISJOINT * counter_disjoint as f64 / length
             + COEFFICIENT_EXCESS * counter_excess as f64 / length;
     }
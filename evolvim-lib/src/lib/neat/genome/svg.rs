@@ -0,0 +1,170 @@
+use super::gene::{Id, NodeType};
+use super::Genome;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+const COLUMN_WIDTH: f64 = 200.0;
+const ROW_HEIGHT: f64 = 50.0;
+const MARGIN: f64 = 120.0;
+const NODE_RADIUS: f64 = 12.0;
+const MAX_STROKE_WIDTH: f64 = 6.0;
+
+impl Genome {
+    /// Renders this genome as an SVG image.
+    ///
+    /// Sensors are drawn in the leftmost column and outputs in the rightmost column,
+    /// hidden nodes are placed in between according to their depth (longest path from a sensor).
+    /// Positive weights are drawn in blue, negative weights in red and disabled connections are dashed.
+    pub fn to_svg(&self) -> String {
+        let depths = self.get_node_depths();
+        let amount_columns = depths.values().cloned().max().unwrap_or(0) + 1;
+
+        // Place the nodes, every column gets filled from the top.
+        let mut rows_used = vec![0; amount_columns];
+        let mut positions: HashMap<Id, (f64, f64)> = HashMap::new();
+        for node in &self.node_genome {
+            let column = depths[&node.id];
+            let row = rows_used[column];
+            rows_used[column] += 1;
+
+            positions.insert(
+                node.id,
+                (
+                    MARGIN + column as f64 * COLUMN_WIDTH,
+                    MARGIN + row as f64 * ROW_HEIGHT,
+                ),
+            );
+        }
+
+        let width = 2.0 * MARGIN + (amount_columns - 1) as f64 * COLUMN_WIDTH;
+        let height = 2.0 * MARGIN
+            + (rows_used.iter().cloned().max().unwrap_or(1).max(1) - 1) as f64 * ROW_HEIGHT;
+        let max_weight = self
+            .connection_genome
+            .iter()
+            .fold(0.0f64, |acc, c| acc.max(c.weight.abs()))
+            .max(std::f64::EPSILON);
+
+        let mut svg = String::new();
+        // Writing to a `String` never fails so it's safe to ignore the results.
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="0 0 {:.0} {:.0}">"#,
+            width, height, width, height
+        );
+        let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+        for con in &self.connection_genome {
+            let (x1, y1) = positions[&con.from];
+            let (x2, y2) = positions[&con.to];
+            let color = if con.weight >= 0.0 {
+                "#1f5fbf"
+            } else {
+                "#bf1f1f"
+            };
+            let stroke_width = (con.weight.abs() / max_weight * MAX_STROKE_WIDTH).max(0.5);
+            let dashes = if con.enabled {
+                ""
+            } else {
+                r#" stroke-dasharray="4 4" stroke-opacity="0.4""#
+            };
+
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{:.2}"{}><title>innovation {}: {:.3}</title></line>"#,
+                x1, y1, x2, y2, color, stroke_width, dashes, con.innovation_number, con.weight
+            );
+        }
+
+        for node in &self.node_genome {
+            let (x, y) = positions[&node.id];
+            let (fill, label, anchor, label_x) = match &node.node_type {
+                NodeType::Sensor(input) => {
+                    ("#f2c14e", input.get_name(), "end", x - 1.5 * NODE_RADIUS)
+                }
+                NodeType::Hidden => ("#cccccc", format!("{}", node.id), "middle", x),
+                NodeType::Output(output) => {
+                    ("#7ac74f", output.get_name(), "start", x + 1.5 * NODE_RADIUS)
+                }
+            };
+            let label_y = match node.node_type {
+                NodeType::Hidden => y - 1.5 * NODE_RADIUS,
+                _ => y + 4.0,
+            };
+
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}" stroke="black"/>"#,
+                x, y, NODE_RADIUS, fill
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="{}" font-family="sans-serif" font-size="12">{}</text>"#,
+                label_x, label_y, anchor, label
+            );
+        }
+
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+
+    /// Returns the column every node should be drawn in.
+    ///
+    /// Sensors get depth 0, hidden nodes get the length of the longest path from a sensor (ignoring cycles)
+    /// and outputs all share the last column.
+    fn get_node_depths(&self) -> HashMap<Id, usize> {
+        let mut depths: HashMap<Id, usize> = self
+            .node_genome
+            .iter()
+            .map(|n| match n.node_type {
+                NodeType::Hidden => (n.id, 1),
+                _ => (n.id, 0),
+            })
+            .collect();
+        let hidden: HashSet<Id> = self
+            .node_genome
+            .iter()
+            .filter(|n| matches_hidden(&n.node_type))
+            .map(|n| n.id)
+            .collect();
+
+        // A path can't be longer than the amount of nodes, stopping here prevents cycles from looping forever.
+        for _i in 0..self.node_genome.len() {
+            let mut changed = false;
+
+            for con in self.connection_genome.iter().filter(|c| c.enabled) {
+                if !hidden.contains(&con.to) || con.from == con.to {
+                    continue;
+                }
+
+                let candidate = depths[&con.from] + 1;
+                if candidate > depths[&con.to] && candidate < self.node_genome.len() {
+                    depths.insert(con.to, candidate);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let last_column = hidden.iter().map(|id| depths[id]).max().unwrap_or(0) + 1;
+
+        for node in &self.node_genome {
+            if let NodeType::Output(_) = node.node_type {
+                depths.insert(node.id, last_column);
+            }
+        }
+
+        depths
+    }
+}
+
+fn matches_hidden(node_type: &NodeType) -> bool {
+    match node_type {
+        NodeType::Hidden => true,
+        _ => false,
+    }
+}
@@ -3,20 +3,90 @@ use crate::brain::Environment;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum InputType {
     Eye(Eye),
+    Ray(Ray),
     Bias(f64),
     MouthHue,
     Energy,
+    Pheromone,
+    Age,
+    /// How far the body has declined with age, see `Environment::get_senescence`.
+    Senescence,
+    Size,
+    Nearby(NearbyType),
+    /// The biome under the body, see `Biome::get_sensor_value`.
+    Biome,
+    /// How light it is, see `Environment::sense_light`.
+    Light,
+}
+
+/// What part of the summary of nearby creatures to use, see `Environment::sense_nearby`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NearbyType {
+    Count,
+    Direction,
+    Heading,
+    Hue,
+}
+
+impl NearbyType {
+    pub const fn get_all() -> [Self; 4] {
+        [
+            NearbyType::Count,
+            NearbyType::Direction,
+            NearbyType::Heading,
+            NearbyType::Hue,
+        ]
+    }
 }
 
 impl InputType {
-    pub fn get_data(&self, env: &Environment) -> f64 {
+    /// Returns a short human-readable name for this input.
+    pub fn get_name(&self) -> String {
+        use InputType::*;
+
+        match &self {
+            Eye(s) => format!("Eye {:?}", s.what_to_look_for),
+            Ray(r) => format!("Ray {:?} {:.2}", r.what_to_look_for, r.angle),
+            Bias(v) => format!("Bias {}", v),
+            MouthHue => String::from("Mouth hue"),
+            Energy => String::from("Energy"),
+            Pheromone => String::from("Pheromone"),
+            Age => String::from("Age"),
+            Senescence => String::from("Senescence"),
+            Size => String::from("Size"),
+            Biome => String::from("Biome"),
+            Light => String::from("Light"),
+            Nearby(n) => format!("Nearby {:?}", n),
+        }
+    }
+
+    pub fn get_data<B>(&self, env: &Environment<B>) -> f64 {
         use InputType::*;
 
         match &self {
             Bias(v) => *v,
             Eye(s) => s.get_data(env),
+            Ray(r) => r.get_data(env),
             MouthHue => env.this_body.get_mouth_hue(),
             Energy => env.this_body.get_energy(),
+            Pheromone => env.smell_pheromone(),
+            Age => env.get_age(),
+            Senescence => env.get_senescence(),
+            Size => env.get_size(),
+            Biome => env
+                .get_biome()
+                .map_or(0.0, crate::terrain::Biome::get_sensor_value),
+            Light => env.sense_light(),
+            Nearby(n) => {
+                let nearby = env.sense_nearby();
+
+                match n {
+                    NearbyType::Count => nearby.count as f64,
+                    NearbyType::Direction => nearby.direction,
+                    NearbyType::Heading => nearby.heading,
+                    NearbyType::Hue => nearby.hue,
+                }
+            }
         }
     }
 }
@@ -36,7 +106,7 @@ enum EyeType {
 }
 
 impl Eye {
-    pub fn get_data(&self, env: &Environment) -> f64 {
+    pub fn get_data<B>(&self, env: &Environment<B>) -> f64 {
         use crate::board::BoardPreciseCoordinate;
         use EyeType::*;
 
@@ -73,3 +143,90 @@ impl Eye {
         ]
     }
 }
+
+/// A single ray of vision, see `crate::brain::vision`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ray {
+    range: f64,
+    angle: f64,
+    what_to_look_for: RayType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum RayType {
+    Distance,
+    Hue,
+    HitType,
+}
+
+impl Ray {
+    pub fn get_data<B>(&self, env: &Environment<B>) -> f64 {
+        use RayType::*;
+
+        let hit = crate::brain::vision::cast_ray(env, self.angle, self.range);
+        match self.what_to_look_for {
+            Distance => hit.distance,
+            Hue => hit.hue,
+            HitType => hit.hit_type.as_input(),
+        }
+    }
+
+    pub const fn get_all_three(range: f64, angle: f64) -> [Self; 3] {
+        [
+            Ray {
+                range,
+                angle,
+                what_to_look_for: RayType::Distance,
+            },
+            Ray {
+                range,
+                angle,
+                what_to_look_for: RayType::Hue,
+            },
+            Ray {
+                range,
+                angle,
+                what_to_look_for: RayType::HitType,
+            },
+        ]
+    }
+}
+
+/// Something in the environment a condition gene can respond to, see `Genome::get_condition_genome`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// The current temperature of the tile under the creature, see `Terrain::temperature_at`.
+    Temperature,
+    /// The amount of food on the tile under the creature.
+    FoodLevel,
+}
+
+impl Condition {
+    pub const fn get_all() -> [Self; 2] {
+        [Condition::Temperature, Condition::FoodLevel]
+    }
+
+    pub fn get_data<B>(&self, env: &Environment<B>) -> f64 {
+        match self {
+            Condition::Temperature => env
+                .terrain
+                .temperature_at(env.this_body.get_position().into(), env.climate),
+            Condition::FoodLevel => env
+                .terrain
+                .get_tile_at(env.this_body.get_position().into())
+                .get_food_level(),
+        }
+    }
+
+    /// Returns a threshold somewhere in the range of values this condition usually has.
+    pub fn get_random_threshold(&self) -> f64 {
+        use crate::constants::{DEFAULT_MAX_TEMP, DEFAULT_MIN_TEMP, MAX_GROWTH_LEVEL};
+
+        let (min, max) = match self {
+            Condition::Temperature => (DEFAULT_MIN_TEMP, DEFAULT_MAX_TEMP),
+            Condition::FoodLevel => (0.0, MAX_GROWTH_LEVEL),
+        };
+
+        min + crate::rng::random::<f64>() * (max - min)
+    }
+}
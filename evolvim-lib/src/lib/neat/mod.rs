@@ -1,9 +1,12 @@
+pub mod alleles;
 mod genome;
 mod input;
 mod output;
 mod phenotype;
 
-pub use genome::Genome;
+pub use alleles::{Allele, AlleleTracker};
+pub use genome::{ConditionGene, Genome};
+pub use input::Condition;
 pub use phenotype::NeuralNet;
 
 #[derive(Debug)]
@@ -12,6 +15,19 @@ pub struct NeatBrain {
     net: NeuralNet,
 }
 
+impl NeatBrain {
+    /// Returns the genome this brain was generated from.
+    pub fn get_genome(&self) -> &Genome {
+        &self.genome
+    }
+}
+
+impl Clone for NeatBrain {
+    fn clone(&self) -> Self {
+        self.genome.clone().into()
+    }
+}
+
 impl From<Genome> for NeatBrain {
     fn from(genome: Genome) -> Self {
         let net = (&genome).into();
@@ -21,7 +37,7 @@ impl From<Genome> for NeatBrain {
 }
 
 impl crate::brain::NeuralNet for NeatBrain {
-    fn load_input(&mut self, env: &crate::brain::Environment) {
+    fn load_input<B>(&mut self, env: &crate::brain::Environment<B>) {
         self.net.load_input(env);
     }
 
@@ -29,9 +45,57 @@ impl crate::brain::NeuralNet for NeatBrain {
         self.net.run_calculations();
     }
 
-    fn use_output(&self, env: &mut crate::brain::EnvironmentMut<Self>, time_step: f64) {
+    fn use_output<B>(&self, env: &mut crate::brain::EnvironmentMut<B>, time_step: f64) {
         self.net.use_output(env, time_step);
     }
+
+    fn complexity(&self) -> usize {
+        let nodes = self.genome.get_node_genome().len();
+        let connections = self
+            .genome
+            .get_connection_genome()
+            .iter()
+            .filter(|c| c.enabled)
+            .count();
+
+        nodes + connections
+    }
+
+    fn write_genome(&self, hasher: &mut crate::hash::StateHasher) {
+        hasher.write_serialized(&self.genome);
+    }
+
+    fn genome_distance(&self, other: &Self) -> f64 {
+        self.genome.genetical_distance(&other.genome)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let nodes = self.genome.get_node_genome();
+        let has_node = |id| nodes.iter().any(|n| n.id == id);
+
+        if !nodes.iter().any(|n| n.is_sensor()) {
+            return Err("the genome has no sensor nodes".to_string());
+        }
+        if !nodes.iter().any(|n| n.is_output()) {
+            return Err("the genome has no output nodes".to_string());
+        }
+
+        for c in self.genome.get_connection_genome() {
+            if !has_node(c.from) || !has_node(c.to) {
+                return Err(format!(
+                    "connection {} goes from node {} to node {} but one of them doesn't exist",
+                    c.innovation_number, c.from, c.to
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// There are no birth outputs yet, creatures with a `NeatBrain` can't have babies.
+    fn check_config(&self, _config: &crate::config::SimConfig) -> Result<(), String> {
+        Err("NEAT brains have no birth outputs yet, their creatures can't reproduce".to_string())
+    }
 }
 
 impl crate::brain::Intentions for NeatBrain {
@@ -45,8 +109,8 @@ impl crate::brain::Intentions for NeatBrain {
 }
 
 impl crate::brain::GenerateRandom for NeatBrain {
-    fn new_random() -> Self {
-        Genome::new_fully_linked().into()
+    fn new_random_with_vision(vision: &crate::brain::vision::Vision) -> Self {
+        Genome::new_fully_linked(vision).into()
     }
 }
 
@@ -58,28 +122,71 @@ impl crate::brain::RecombinationTwoParents for NeatBrain {
 }
 
 impl crate::brain::RecombinationInfinite for NeatBrain {
-    fn recombination_infinite_parents(parents: &Vec<crate::softbody::HLSoftBody<Self>>) -> Self {
+    fn recombination_infinite_brains(parents: &[&Self]) -> Self {
         use crate::brain::RecombinationTwoParents;
 
         if parents.len() == 1 {
             // Only mutate this genome
 
-            let parent = parents[0].borrow();
             // Make a copy of the parent genome
-            let mut genome = parent.brain.genome.clone();
+            let mut genome = parents[0].genome.clone();
             // Mutate it
             genome.mutate();
             // Generate a phenotype and return a NeatBrain
             genome.into()
         } else {
-            NeatBrain::recombination_two_parents(
-                &parents[0].borrow().brain,
-                &parents[1].borrow().brain,
-            )
+            NeatBrain::recombination_two_parents(parents[0], parents[1])
         }
     }
 }
 
+impl crate::brain::ExportWeights for NeatBrain {
+    /// Returns the connection genome as parallel arrays and the ids of all nodes.
+    fn get_arrays(&self) -> Vec<crate::brain::export::NamedArray> {
+        use crate::brain::export::{ArrayData, NamedArray};
+
+        let connections = self.genome.get_connection_genome();
+        let nodes = self.genome.get_node_genome();
+        let n = connections.len();
+
+        let int_array =
+            |values: Vec<usize>| ArrayData::I64(values.into_iter().map(|v| v as i64).collect());
+
+        vec![
+            NamedArray::new(
+                "connection_from",
+                vec![n],
+                int_array(connections.iter().map(|c| c.from).collect()),
+            ),
+            NamedArray::new(
+                "connection_to",
+                vec![n],
+                int_array(connections.iter().map(|c| c.to).collect()),
+            ),
+            NamedArray::new(
+                "connection_weight",
+                vec![n],
+                ArrayData::F64(connections.iter().map(|c| c.weight).collect()),
+            ),
+            NamedArray::new(
+                "connection_enabled",
+                vec![n],
+                int_array(connections.iter().map(|c| c.enabled as usize).collect()),
+            ),
+            NamedArray::new(
+                "connection_innovation",
+                vec![n],
+                int_array(connections.iter().map(|c| c.innovation_number).collect()),
+            ),
+            NamedArray::new(
+                "node_id",
+                vec![nodes.len()],
+                ArrayData::I64(nodes.iter().map(|n| n.id as i64).collect()),
+            ),
+        ]
+    }
+}
+
 impl crate::brain::ProvideInformation for NeatBrain {
     fn get_keys(&self) -> Vec<String> {
         vec!["nodes".to_string(), "connections".to_string()]
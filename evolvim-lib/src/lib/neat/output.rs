@@ -5,9 +5,16 @@ pub enum OutputType {
     Turning,
     Accelerating,
     Fight,
+    Bite,
+    Pheromone,
 }
 
 impl OutputType {
+    /// Returns a short human-readable name for this output.
+    pub fn get_name(&self) -> String {
+        format!("{:?}", self)
+    }
+
     pub fn use_output<B>(
         &self,
         value: f64,
@@ -33,6 +40,16 @@ impl OutputType {
                 env.sbip,
                 env.self_pointer.clone(),
             ),
+            Bite => {
+                env.this_body.bite(
+                    value,
+                    env.time,
+                    time_step,
+                    env.sbip,
+                    env.self_pointer.clone(),
+                );
+            }
+            Pheromone => env.emit_pheromone(value, time_step),
         };
     }
 }
@@ -1,5 +1,5 @@
 use super::super::genome::{Genome, Id, NodeType};
-use super::{Connection, NeuralNet, Node};
+use super::{Connection, NeuralNet, Node, Switch};
 use std::collections::HashMap;
 
 // TODO: clean this HORRIFIC code up...
@@ -39,7 +39,7 @@ impl From<&Genome> for NeuralNet {
                     let to: *mut f64 = &mut outputs.last_mut().unwrap().value;
                     nodes[counter]
                         .connections
-                        .push(unsafe { Connection::new(to, 1.0) });
+                        .push(unsafe { Connection::new(to, 1.0, Vec::new()) });
                 }
                 _ => {}
             }
@@ -47,19 +47,40 @@ impl From<&Genome> for NeuralNet {
             counter += 1;
         }
 
+        let mut switches = Vec::new();
+        // Maps the innovation number of a connection to the switches it depends on.
+        let mut switch_lookup: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, gene) in genome.get_condition_genome().iter().enumerate() {
+            switches.push(Switch {
+                condition: gene.condition,
+                threshold: gene.threshold,
+                when_above: gene.when_above,
+            });
+
+            for &innovation_number in &gene.connections {
+                switch_lookup.entry(innovation_number).or_default().push(i);
+            }
+        }
+
         for con in genome.get_connection_genome().iter().filter(|c| c.enabled) {
             let from = get_usize_from_id(&lookup, con.from);
             let to = &mut nodes[get_usize_from_id(&lookup, con.to)].value as *mut f64;
+            let con_switches = switch_lookup
+                .get(&con.innovation_number)
+                .cloned()
+                .unwrap_or_default();
 
             nodes[from]
                 .connections
-                .push(unsafe { Connection::new(to, con.weight) });
+                .push(unsafe { Connection::new(to, con.weight, con_switches) });
         }
 
         NeuralNet {
             nodes,
             inputs,
             outputs: outputs.into_boxed_slice(),
+            expressed: vec![true; switches.len()],
+            switches,
         }
     }
 }
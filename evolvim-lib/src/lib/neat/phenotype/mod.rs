@@ -1,6 +1,6 @@
 mod generate;
 
-use super::input::InputType;
+use super::input::{Condition, InputType};
 use super::output::OutputType;
 
 // TODO: use unsafe pointers or something to speed things up
@@ -10,20 +10,24 @@ pub struct NeuralNet {
 
     outputs: Box<[Output]>,
     inputs: Vec<Input>,
+
+    switches: Vec<Switch>,
+    /// Whether the condition of every switch held during the last `load_input`.
+    expressed: Vec<bool>,
 }
 
 impl NeuralNet {
-    pub fn load_input(&mut self, env: &crate::brain::Environment) {
+    pub fn load_input<B>(&mut self, env: &crate::brain::Environment<B>) {
         for input in &self.inputs {
             input.load_into(&mut self.nodes, env);
         }
+
+        for (switch, expressed) in self.switches.iter().zip(self.expressed.iter_mut()) {
+            *expressed = switch.is_expressed(env);
+        }
     }
 
-    pub fn use_output(
-        &self,
-        env: &mut crate::brain::EnvironmentMut<super::NeatBrain>,
-        time_step: f64,
-    ) {
+    pub fn use_output<B>(&self, env: &mut crate::brain::EnvironmentMut<B>, time_step: f64) {
         for output in self.outputs.iter() {
             output.use_output(env, time_step);
         }
@@ -36,7 +40,7 @@ impl NeuralNet {
         }
 
         for n in self.nodes.iter_mut() {
-            n.calc();
+            n.calc(&self.expressed);
         }
     }
 }
@@ -49,7 +53,7 @@ struct Output {
 }
 
 impl Output {
-    fn use_output(&self, env: &mut crate::brain::EnvironmentMut<super::NeatBrain>, time_step: f64) {
+    fn use_output<B>(&self, env: &mut crate::brain::EnvironmentMut<B>, time_step: f64) {
         self.output_type.use_output(self.value, env, time_step);
     }
 
@@ -69,7 +73,7 @@ struct Input {
 }
 
 impl Input {
-    pub fn load_into(&self, nodes: &mut [Node], env: &crate::brain::Environment) {
+    pub fn load_into<B>(&self, nodes: &mut [Node], env: &crate::brain::Environment<B>) {
         let data = self.input_type.get_data(env);
         nodes[self.node_index].add_to_value(data);
     }
@@ -97,12 +101,16 @@ impl Node {
         return sigmoid(self.value);
     }
 
-    pub fn calc(&mut self) {
+    pub fn calc(&mut self, expressed: &[bool]) {
         let sig_value = self.perform_sigmoid();
 
         self.value = 0.0;
 
         for c in &self.connections {
+            if !c.switches.iter().all(|&s| expressed[s]) {
+                continue;
+            }
+
             unsafe {
                 *c.to += c.weight * sig_value;
             }
@@ -121,6 +129,8 @@ impl Node {
 struct Connection {
     to: *mut f64,
     weight: f64,
+    /// The indices of the switches that all need to be expressed for this connection to be used.
+    switches: Vec<usize>,
 }
 
 impl Connection {
@@ -128,8 +138,26 @@ impl Connection {
     ///
     /// This is unsafe, to use this you must manually guarantee that the pointer stays valid
     /// at least until we destroy this Neural Network struct.
-    pub unsafe fn new(to: *mut f64, weight: f64) -> Self {
-        Connection { to, weight }
+    pub unsafe fn new(to: *mut f64, weight: f64, switches: Vec<usize>) -> Self {
+        Connection {
+            to,
+            weight,
+            switches,
+        }
+    }
+}
+
+/// The phenotype of a `ConditionGene`.
+#[derive(Clone, Debug)]
+struct Switch {
+    condition: Condition,
+    threshold: f64,
+    when_above: bool,
+}
+
+impl Switch {
+    fn is_expressed<B>(&self, env: &crate::brain::Environment<B>) -> bool {
+        (self.condition.get_data(env) > self.threshold) == self.when_above
     }
 }
 
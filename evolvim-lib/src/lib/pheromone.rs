@@ -0,0 +1,123 @@
+//! A board-wide chemical field creatures can leave behind and smell.
+//!
+//! Every update the pheromone spreads out to neighbouring tiles and slowly decays,
+//! this allows creatures to communicate indirectly by changing their surroundings.
+
+use crate::board::{BoardCoordinate, BoardSize};
+use crate::constants::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct PheromoneField {
+    width: usize,
+    height: usize,
+    concentrations: Vec<f64>,
+
+    // Reused every update so we don't allocate every frame.
+    #[serde(skip)]
+    buffer: Vec<f64>,
+}
+
+impl PheromoneField {
+    /// Creates a field without any pheromone.
+    pub fn new(board_size: BoardSize) -> Self {
+        let (width, height) = board_size;
+
+        PheromoneField {
+            width,
+            height,
+            concentrations: vec![0.0; width * height],
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the width and height of this field.
+    pub fn get_size(&self) -> BoardSize {
+        (self.width, self.height)
+    }
+
+    /// Changes the size of this field, the pheromone on tiles that are still there stays where it is.
+    pub fn resize(&mut self, board_size: BoardSize) {
+        let mut resized = PheromoneField::new(board_size);
+
+        for x in 0..self.width.min(resized.width) {
+            for y in 0..self.height.min(resized.height) {
+                let i = resized.index(x, y);
+                resized.concentrations[i] = self.get_concentration_at((x, y));
+            }
+        }
+
+        *self = resized;
+    }
+
+    /// Lets the pheromone decay and diffuse for the given amount of time.
+    pub fn update(&mut self, time_step: f64) {
+        let decay = (1.0 - PHEROMONE_DECAY_RATE * time_step).max(0.0);
+        let diffusion = (PHEROMONE_DIFFUSION_RATE * time_step).min(1.0);
+
+        self.buffer.clear();
+        self.buffer.resize(self.concentrations.len(), 0.0);
+
+        for x in 0..self.width {
+            for y in 0..self.height {
+                let mut neighbours = 0.0;
+                let mut amount_neighbours = 0.0;
+
+                if x > 0 {
+                    neighbours += self.concentrations[self.index(x - 1, y)];
+                    amount_neighbours += 1.0;
+                }
+                if x + 1 < self.width {
+                    neighbours += self.concentrations[self.index(x + 1, y)];
+                    amount_neighbours += 1.0;
+                }
+                if y > 0 {
+                    neighbours += self.concentrations[self.index(x, y - 1)];
+                    amount_neighbours += 1.0;
+                }
+                if y + 1 < self.height {
+                    neighbours += self.concentrations[self.index(x, y + 1)];
+                    amount_neighbours += 1.0;
+                }
+
+                let i = self.index(x, y);
+                let own = self.concentrations[i];
+                let average = if amount_neighbours > 0.0 {
+                    neighbours / amount_neighbours
+                } else {
+                    own
+                };
+
+                self.buffer[i] = (own + (average - own) * diffusion) * decay;
+            }
+        }
+
+        std::mem::swap(&mut self.concentrations, &mut self.buffer);
+    }
+
+    /// Adds pheromone at the given tile.
+    pub fn deposit(&mut self, pos: BoardCoordinate, amount: f64) {
+        let i = self.index(pos.0, pos.1);
+        self.concentrations[i] += amount.max(0.0);
+    }
+
+    /// Returns the amount of pheromone at the given tile.
+    pub fn get_concentration_at(&self, pos: BoardCoordinate) -> f64 {
+        self.concentrations[self.index(pos.0, pos.1)]
+    }
+
+    /// Returns the total amount of pheromone on the board.
+    pub fn get_total(&self) -> f64 {
+        self.concentrations.iter().sum()
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "There is no pheromone at ({}, {}).",
+            x,
+            y
+        );
+
+        x * self.height + y
+    }
+}
@@ -0,0 +1,229 @@
+//! The family tree of every creature that ever lived on a board.
+//!
+//! A `Board` adds a `PhylogenyRecord` for every creature it spawns or that is born and fills in the time of death when
+//! it dies, the records are saved together with the board. Ask the `Phylogeny` of a board (see
+//! `Board::get_phylogeny`) who descends from whom, or how every lineage is doing with `get_lineages`.
+//!
+//! Unlike a `LineageTracker` this remembers the dead as well, so it's the place to look when analysing a long run.
+
+use crate::brain::NeuralNet;
+use crate::softbody::SoftBody;
+use std::collections::{BTreeMap, HashSet};
+
+/// What the family tree knows about a single creature.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhylogenyRecord {
+    pub id: usize,
+    /// Empty for a creature that was spawned at random, these are the founders of the lineages.
+    pub parents: Vec<usize>,
+    pub birth_time: f64,
+    /// `None` while it's alive.
+    pub death_time: Option<f64>,
+    /// How many generations it's away from its founder, 0 for a founder.
+    pub generation: usize,
+    /// The id of the founder of its lineage, found by following the first parent back. A founder is its own founder.
+    pub founder: usize,
+    /// See `Creature::get_genome_fingerprint`.
+    pub genome_fingerprint: u64,
+}
+
+impl PhylogenyRecord {
+    pub fn is_alive(&self) -> bool {
+        self.death_time.is_none()
+    }
+
+    /// Returns the name the creature had, see `Creature::get_name`.
+    pub fn get_name(&self) -> String {
+        crate::names::name_from_fingerprint(self.genome_fingerprint)
+    }
+}
+
+/// How a lineage, all descendants of a founder through their first parent, is doing. See `Phylogeny::get_lineages`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LineageStats {
+    pub founder: usize,
+    /// How many creatures ever belonged to it, the founder included.
+    pub members: usize,
+    pub living: usize,
+    /// The highest generation any member reached.
+    pub generations: usize,
+    pub first_birth: f64,
+    pub last_birth: f64,
+    /// When its last member died, `None` while any of them is alive.
+    pub extinct_at: Option<f64>,
+}
+
+/// The records of all creatures of a board, see the module documentation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Phylogeny {
+    /// Sorted by id, which is also the order in which creatures are added to the board.
+    records: Vec<PhylogenyRecord>,
+}
+
+impl Phylogeny {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `creature`, which has just been added to the board at its birth time.
+    pub(crate) fn record_birth<B: NeuralNet>(&mut self, creature: &SoftBody<B>) {
+        let id = creature.get_id();
+        if self.records.last().map_or(false, |r| r.id >= id) {
+            // Ids only go up, this creature is already known.
+            return;
+        }
+
+        let parents = creature.get_parent_ids().to_vec();
+        // A parent from before the records started counts as a founder.
+        let (generation, founder) = match parents.first() {
+            None => (0, id),
+            Some(&parent) => self
+                .get(parent)
+                .map_or((1, parent), |p| (p.generation + 1, p.founder)),
+        };
+
+        self.records.push(PhylogenyRecord {
+            id,
+            parents,
+            birth_time: creature.get_birth_time(),
+            death_time: None,
+            generation,
+            founder,
+            genome_fingerprint: creature.get_genome_fingerprint(),
+        });
+    }
+
+    /// Remembers that the creature with `id` died at `time`.
+    pub(crate) fn record_death(&mut self, id: usize, time: f64) {
+        if let Ok(index) = self.find(id) {
+            self.records[index].death_time.get_or_insert(time);
+        }
+    }
+
+    pub fn get(&self, id: usize) -> Option<&PhylogenyRecord> {
+        self.find(id).ok().map(|index| &self.records[index])
+    }
+
+    /// Returns the records of all creatures, in order of their id.
+    pub fn get_records(&self) -> &[PhylogenyRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the chain of ids from `id` back to the founder of its lineage, following the first parent every time.
+    ///
+    /// Starts with `id` itself, empty if there's no record of it.
+    pub fn get_ancestry(&self, id: usize) -> Vec<usize> {
+        let mut ancestry = Vec::new();
+        let mut current = self.get(id);
+        while let Some(record) = current {
+            ancestry.push(record.id);
+            current = record.parents.first().and_then(|&parent| self.get(parent));
+        }
+
+        ancestry
+    }
+
+    /// Returns the ids of the creatures that have `id` as one of their parents.
+    pub fn get_children(&self, id: usize) -> Vec<usize> {
+        self.records_after(id)
+            .filter(|r| r.parents.contains(&id))
+            .map(|r| r.id)
+            .collect()
+    }
+
+    /// Returns the ids of all creatures that descend from `id` through any of their parents, in order of their id.
+    pub fn get_descendants(&self, id: usize) -> Vec<usize> {
+        let mut family: HashSet<usize> = HashSet::new();
+        family.insert(id);
+
+        // Children always come after their parents, so a single pass finds them all.
+        let mut descendants = Vec::new();
+        for r in self.records_after(id) {
+            if r.parents.iter().any(|p| family.contains(p)) {
+                family.insert(r.id);
+                descendants.push(r.id);
+            }
+        }
+
+        descendants
+    }
+
+    /// Returns the closest creature both `a` and `b` descend from through their first parents, if any. This is `a`
+    /// itself if `b` descends from it.
+    pub fn get_common_ancestor(&self, a: usize, b: usize) -> Option<usize> {
+        let ancestry_a: HashSet<usize> = self.get_ancestry(a).into_iter().collect();
+
+        self.get_ancestry(b)
+            .into_iter()
+            .find(|id| ancestry_a.contains(id))
+    }
+
+    /// Returns the statistics of the lineage founded by `founder`, `None` if there's no such lineage.
+    pub fn get_lineage_stats(&self, founder: usize) -> Option<LineageStats> {
+        self.collect_lineages(|r| r.founder == founder).pop()
+    }
+
+    /// Returns the statistics of every lineage that ever lived, in order of the id of their founder.
+    pub fn get_lineages(&self) -> Vec<LineageStats> {
+        self.collect_lineages(|_| true)
+    }
+
+    fn collect_lineages<F: Fn(&PhylogenyRecord) -> bool>(&self, filter: F) -> Vec<LineageStats> {
+        let mut lineages: BTreeMap<usize, LineageStats> = BTreeMap::new();
+
+        for r in self.records.iter().filter(|r| filter(r)) {
+            let stats = lineages.entry(r.founder).or_insert(LineageStats {
+                founder: r.founder,
+                members: 0,
+                living: 0,
+                generations: 0,
+                first_birth: r.birth_time,
+                last_birth: r.birth_time,
+                extinct_at: None,
+            });
+
+            stats.members += 1;
+            stats.generations = stats.generations.max(r.generation);
+            stats.first_birth = stats.first_birth.min(r.birth_time);
+            stats.last_birth = stats.last_birth.max(r.birth_time);
+            match r.death_time {
+                None => stats.living += 1,
+                Some(time) => {
+                    stats.extinct_at = Some(stats.extinct_at.map_or(time, |t| t.max(time)))
+                }
+            }
+        }
+
+        lineages
+            .into_iter()
+            .map(|(_, mut stats)| {
+                if stats.living > 0 {
+                    stats.extinct_at = None;
+                }
+                stats
+            })
+            .collect()
+    }
+
+    /// Returns the records of the creatures added after the one with `id`.
+    fn records_after(&self, id: usize) -> impl Iterator<Item = &PhylogenyRecord> {
+        let start = match self.find(id) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+
+        self.records[start..].iter()
+    }
+
+    fn find(&self, id: usize) -> Result<usize, usize> {
+        self.records.binary_search_by_key(&id, |r| r.id)
+    }
+}
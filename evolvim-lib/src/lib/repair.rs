@@ -0,0 +1,195 @@
+//! Finding and fixing corrupt state in a `Board`.
+//!
+//! Long runs occasionally end up with state that breaks the assumptions of the simulation, e.g. a creature with an
+//! energy of NaN or a body in `SoftBodiesInPositions` that died long ago. This usually only surfaces as a panic much
+//! later. `Board::find_inconsistencies` lists everything that's wrong and `Board::repair` fixes it, call these right
+//! after loading a save file you don't trust.
+
+use crate::brain::NeuralNet;
+use crate::sbip::SoftBodiesInPositions;
+use crate::Board;
+use std::fmt;
+
+/// Something that's wrong with the state of a `Board`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Inconsistency {
+    /// The center of a creature is outside of the board.
+    OutOfBounds { id: usize, px: f64, py: f64 },
+    /// A creature has an energy that's NaN, infinite or negative.
+    InvalidEnergy { id: usize, energy: f64 },
+    /// The brain of a creature is broken, e.g. it has a weight that's NaN.
+    InvalidBrain { id: usize, reason: String },
+    /// A creature covers the tile at `(x, y)` but `SoftBodiesInPositions` doesn't know.
+    MissingFromSbip { id: usize, x: usize, y: usize },
+    /// `SoftBodiesInPositions` has a body at `(x, y)` that isn't alive or doesn't cover that tile.
+    SbipOrphan { x: usize, y: usize },
+    /// A selected creature isn't alive.
+    DanglingSelection,
+}
+
+impl Inconsistency {
+    /// Returns the id of the creature this is about, if it's about a single creature.
+    pub fn get_creature_id(&self) -> Option<usize> {
+        use Inconsistency::*;
+
+        match self {
+            OutOfBounds { id, .. }
+            | InvalidEnergy { id, .. }
+            | InvalidBrain { id, .. }
+            | MissingFromSbip { id, .. } => Some(*id),
+            SbipOrphan { .. } | DanglingSelection => None,
+        }
+    }
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Inconsistency::*;
+
+        match self {
+            OutOfBounds { id, px, py } => write!(
+                f,
+                "creature {} is at ({}, {}) which is outside of the board",
+                id, px, py
+            ),
+            InvalidEnergy { id, energy } => {
+                write!(f, "creature {} has an energy of {}", id, energy)
+            }
+            InvalidBrain { id, reason } => {
+                write!(f, "creature {} has an invalid brain: {}", id, reason)
+            }
+            MissingFromSbip { id, x, y } => write!(
+                f,
+                "creature {} is missing from the collision grid at ({}, {})",
+                id, x, y
+            ),
+            SbipOrphan { x, y } => write!(
+                f,
+                "the collision grid has a body at ({}, {}) which isn't there",
+                x, y
+            ),
+            DanglingSelection => write!(f, "a selected creature isn't alive"),
+        }
+    }
+}
+
+/// Returns everything that's wrong with the creatures of `board`, see `Board::find_inconsistencies`.
+pub(crate) fn find_inconsistencies<B: NeuralNet>(board: &Board<B>) -> Vec<Inconsistency> {
+    let mut found = Vec::new();
+    let (width, height) = board.get_board_size();
+    let sbip = &board.soft_bodies_in_positions;
+
+    for c_rc in &board.creatures {
+        let c = c_rc.borrow();
+        let id = c.get_id();
+        let (px, py) = (c.get_px(), c.get_py());
+
+        if !(px >= 0.0 && py >= 0.0 && px < width as f64 && py < height as f64) {
+            found.push(Inconsistency::OutOfBounds { id, px, py });
+        }
+
+        if !c.get_energy().is_finite() || c.get_energy() < 0.0 {
+            found.push(Inconsistency::InvalidEnergy {
+                id,
+                energy: c.get_energy(),
+            });
+        }
+
+        if let Err(reason) = c.brain.validate() {
+            found.push(Inconsistency::InvalidBrain { id, reason });
+        }
+
+        for x in c.current_x_range() {
+            for y in c.current_y_range() {
+                if !sbip.get_soft_bodies_at(x, y).contains(c_rc) {
+                    found.push(Inconsistency::MissingFromSbip { id, x, y });
+                }
+            }
+        }
+    }
+
+    for x in 0..width {
+        for y in 0..height {
+            let orphan = sbip.get_soft_bodies_at(x, y).iter().any(|body| {
+                if !board.creatures.contains(body) {
+                    return true;
+                }
+
+                let b = body.borrow();
+                !(b.current_x_range().contains(&x) && b.current_y_range().contains(&y))
+            });
+
+            if orphan {
+                found.push(Inconsistency::SbipOrphan { x, y });
+            }
+        }
+    }
+
+    for &id in board.selected_creature.get_ids() {
+        if board.get_creature_by_id(id).is_none() {
+            found.push(Inconsistency::DanglingSelection);
+        }
+    }
+
+    found
+}
+
+/// Fixes everything `find_inconsistencies` finds and returns what was found, see `Board::repair`.
+pub(crate) fn repair<B: NeuralNet>(board: &mut Board<B>) -> Vec<Inconsistency> {
+    let found = find_inconsistencies(board);
+    if found.is_empty() {
+        return found;
+    }
+
+    let board_size = board.get_board_size();
+
+    // There is nothing sensible to give these creatures instead so they're removed.
+    let broken: Vec<usize> = found
+        .iter()
+        .filter_map(|i| match i {
+            Inconsistency::InvalidEnergy { id, .. } | Inconsistency::InvalidBrain { id, .. } => {
+                Some(*id)
+            }
+            _ => None,
+        })
+        .collect();
+    board
+        .creatures
+        .retain(|c| !broken.contains(&c.borrow().get_id()));
+    let time = board.get_time();
+    for &id in &broken {
+        board.get_phylogeny_mut().record_death(id, time);
+        board.get_species_mut().remove_creature(id);
+    }
+
+    for c in &board.creatures {
+        let mut c = c.borrow_mut();
+        let (px, py) = (c.get_px(), c.get_py());
+
+        c.set_body_x(px, board_size.0);
+        c.set_body_y(py, board_size.1);
+    }
+
+    // Rebuild the collision grid from scratch, that takes care of orphans as well.
+    let mut sbip = SoftBodiesInPositions::new_allocated(board_size);
+    for c_rc in &board.creatures {
+        c_rc.add_to_sbip(&mut sbip, board_size);
+    }
+    for egg in board.get_eggs() {
+        egg.add_to_sbip(&mut sbip);
+    }
+    board.soft_bodies_in_positions = sbip;
+
+    let dead: Vec<usize> = board
+        .selected_creature
+        .get_ids()
+        .iter()
+        .cloned()
+        .filter(|&id| board.get_creature_by_id(id).is_none())
+        .collect();
+    for id in dead {
+        board.selected_creature.unselect_if_dead(id);
+    }
+
+    found
+}
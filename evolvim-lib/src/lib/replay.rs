@@ -0,0 +1,234 @@
+//! Recording a run so it can be watched again later.
+//!
+//! Saving the whole `Board` every frame would take far too much space. A `Recorder` writes a `BoardSnapshot` once and
+//! after that only what changed every time `Recorder::record` is called: creatures that moved, were born or died and
+//! tiles that changed color. A `Replayer` reads such a file back and rebuilds the snapshots frame by frame, ready to be
+//! drawn just like a live board.
+//!
+//! NOTE: tiles are updated lazily (see `Terrain::update_all`), so the colors that are recorded are the ones the board
+//! had stored at that moment.
+
+extern crate bincode;
+
+use crate::brain::NeuralNet;
+use crate::error::EvolvimError;
+use crate::serde_structs::Version;
+use crate::snapshot::{BoardSnapshot, CreatureSnapshot};
+use crate::Board;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Tile colors that change less than this (in every channel) since they were last recorded aren't recorded again.
+pub const TILE_COLOR_TOLERANCE: f32 = 0.005;
+
+/// What comes before the frames in a replay file.
+#[derive(Serialize, Deserialize)]
+struct ReplayHeader {
+    version: Version,
+    start: BoardSnapshot,
+}
+
+/// Everything that changed between two recorded moments.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub time: f64,
+    /// Creatures that were already there but changed in any way.
+    pub changed: Vec<CreatureSnapshot>,
+    pub born: Vec<CreatureSnapshot>,
+    /// The ids of the creatures that died.
+    pub died: Vec<usize>,
+    /// The index (row by row) and new HSBA color of every tile that changed.
+    pub tiles: Vec<(usize, [f32; 4])>,
+    /// The id of the selected creature.
+    pub selected: Option<usize>,
+}
+
+impl ReplayFrame {
+    /// Returns the changes needed to go from `old` to `new`.
+    fn between(old: &BoardSnapshot, new: &BoardSnapshot) -> Self {
+        let mut frame = ReplayFrame {
+            time: new.time,
+            selected: new.selected,
+            ..ReplayFrame::default()
+        };
+
+        for c in &new.creatures {
+            match old.get_creature_by_id(c.id) {
+                Some(previous) if previous == c => {}
+                Some(_) => frame.changed.push(c.clone()),
+                None => frame.born.push(c.clone()),
+            }
+        }
+
+        for c in &old.creatures {
+            if new.get_creature_by_id(c.id).is_none() {
+                frame.died.push(c.id);
+            }
+        }
+
+        for (i, (a, b)) in old.tile_colors.iter().zip(&new.tile_colors).enumerate() {
+            if a.iter()
+                .zip(b)
+                .any(|(a, b)| (a - b).abs() > TILE_COLOR_TOLERANCE)
+            {
+                frame.tiles.push((i, *b));
+            }
+        }
+
+        frame
+    }
+
+    /// Applies these changes to `snapshot`.
+    fn apply_to(&self, snapshot: &mut BoardSnapshot) {
+        snapshot.time = self.time;
+        snapshot.selected = self.selected;
+
+        snapshot.creatures.retain(|c| !self.died.contains(&c.id));
+        for c in &self.changed {
+            if let Some(old) = snapshot.creatures.iter_mut().find(|old| old.id == c.id) {
+                *old = c.clone();
+            }
+        }
+        snapshot.creatures.extend(self.born.iter().cloned());
+
+        for &(i, color) in &self.tiles {
+            snapshot.tile_colors[i] = color;
+        }
+    }
+}
+
+/// Writes the changes of a `Board` to a replay file.
+pub struct Recorder<W: Write> {
+    writer: W,
+    /// The state as a `Replayer` will see it after the last frame, so small tile changes can't add up unnoticed.
+    last: BoardSnapshot,
+    frames: usize,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Starts recording `board` to a new file at `path`.
+    pub fn create<B: NeuralNet, P: AsRef<Path>>(
+        path: P,
+        board: &Board<B>,
+    ) -> Result<Self, EvolvimError> {
+        Recorder::new(BufWriter::new(File::create(path)?), board)
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Starts recording `board` to `writer`, the current state is written right away.
+    pub fn new<B: NeuralNet>(mut writer: W, board: &Board<B>) -> Result<Self, EvolvimError> {
+        let header = ReplayHeader {
+            version: Version::current_version(),
+            start: BoardSnapshot::new(board),
+        };
+        bincode::serialize_into(&mut writer, &header)?;
+
+        Ok(Recorder {
+            writer,
+            last: header.start,
+            frames: 0,
+        })
+    }
+
+    /// Records what changed since the previous frame, call this after every update you want to see again.
+    pub fn record<B: NeuralNet>(&mut self, board: &Board<B>) -> Result<(), EvolvimError> {
+        let frame = ReplayFrame::between(&self.last, &BoardSnapshot::new(board));
+        bincode::serialize_into(&mut self.writer, &frame)?;
+
+        frame.apply_to(&mut self.last);
+        self.frames += 1;
+
+        Ok(())
+    }
+
+    /// Returns the amount of frames recorded so far.
+    pub fn get_frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Stops recording and makes sure everything is written.
+    pub fn finish(mut self) -> Result<W, EvolvimError> {
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Reads a replay file written by a `Recorder` back, one frame at a time.
+pub struct Replayer<R: Read> {
+    reader: R,
+    current: BoardSnapshot,
+    frame: usize,
+    finished: bool,
+}
+
+impl Replayer<BufReader<File>> {
+    /// Opens the replay file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, EvolvimError> {
+        Replayer::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> Replayer<R> {
+    /// Reads the start of a replay from `reader`.
+    pub fn new(mut reader: R) -> Result<Self, EvolvimError> {
+        let header: ReplayHeader = bincode::deserialize_from(&mut reader)
+            .map_err(|e| EvolvimError::from(e).into_corrupt())?;
+
+        if !header.version.is_compatible_with_current() {
+            return Err(EvolvimError::IncompatibleVersion {
+                found: header.version.to_string(),
+                current: Version::current_version().to_string(),
+            });
+        }
+
+        Ok(Replayer {
+            reader,
+            current: header.start,
+            frame: 0,
+            finished: false,
+        })
+    }
+
+    /// Returns the state of the board at the current frame.
+    pub fn get_current(&self) -> &BoardSnapshot {
+        &self.current
+    }
+
+    /// Returns the amount of frames played so far.
+    pub fn get_frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns true once all frames have been played.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Moves on to the next frame and returns what changed, or `None` at the end of the replay.
+    pub fn next_frame(&mut self) -> Result<Option<ReplayFrame>, EvolvimError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let frame: ReplayFrame = match bincode::deserialize_from(&mut self.reader) {
+            Ok(frame) => frame,
+            Err(e) => match *e {
+                bincode::ErrorKind::Io(ref io)
+                    if io.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    self.finished = true;
+                    return Ok(None);
+                }
+                _ => return Err(EvolvimError::from(e).into_corrupt()),
+            },
+        };
+
+        frame.apply_to(&mut self.current);
+        self.frame += 1;
+
+        Ok(Some(frame))
+    }
+}
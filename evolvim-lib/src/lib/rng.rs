@@ -0,0 +1,109 @@
+//! The random number generator behind all randomness in the simulation.
+//!
+//! Everything random (terrain generation, spawning creatures, brain mutations, ...) takes its numbers from here instead
+//! of `rand::thread_rng()`. Every thread has its own generators, seeded from the OS by default. A seeded `Board` (see
+//! `Board::new_random_seeded`) swaps its own generators in while it's updating, so two boards with the same seed
+//! develop in exactly the same way, no matter what else happens on the thread.
+//!
+//! Every `Stream` has a generator of its own, so a part of the simulation that starts to use more (or less) random
+//! numbers doesn't change the numbers the other parts get. E.g. a new kind of mutation doesn't change the terrain of a
+//! seeded board. Numbers come from `Stream::Simulation` unless `with_stream` says otherwise.
+//!
+//! NOTE: Lua scripts of a `ScriptBrain` have their own random numbers, which aren't seeded.
+
+use crate::hash::StateHasher;
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::StdRng;
+use rand::{FromEntropy, Rng, SeedableRng};
+use std::cell::{Cell, RefCell};
+
+/// A part of the simulation with its own random numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stream {
+    /// Generating terrain.
+    Terrain,
+    /// Creatures spawned to keep up the creature minimum, including their brains.
+    Spawning,
+    /// Babies and their mutations.
+    Mutation,
+    /// Everything else.
+    Simulation,
+}
+
+const STREAM_AMOUNT: usize = 4;
+
+/// A generator for every `Stream`.
+#[derive(Clone, Debug)]
+pub struct Streams([StdRng; STREAM_AMOUNT]);
+
+impl Streams {
+    fn from_entropy() -> Self {
+        Streams([
+            StdRng::from_entropy(),
+            StdRng::from_entropy(),
+            StdRng::from_entropy(),
+            StdRng::from_entropy(),
+        ])
+    }
+
+    fn get_mut(&mut self, stream: Stream) -> &mut StdRng {
+        &mut self.0[stream as usize]
+    }
+
+    /// Feeds the state of every generator to `hasher` without advancing them, see `Board::state_hash`.
+    pub(crate) fn write_state(&self, hasher: &mut StateHasher) {
+        // A `StdRng` can't be looked into, but two of them in the same state give the same next number.
+        for rng in &self.0 {
+            hasher.write_u64(rng.clone().gen());
+        }
+    }
+}
+
+thread_local! {
+    static RNG: RefCell<Streams> = RefCell::new(Streams::from_entropy());
+    static STREAM: Cell<Stream> = Cell::new(Stream::Simulation);
+}
+
+/// Returns generators that always produce the same numbers for the same `seed`.
+pub fn from_seed(seed: u64) -> Streams {
+    let mut seeds = StdRng::seed_from_u64(seed);
+    let mut next = || StdRng::seed_from_u64(seeds.gen());
+
+    Streams([next(), next(), next(), next()])
+}
+
+/// Calls `f` with the generator of the current stream of this thread.
+///
+/// Don't call any other function of this module from inside `f`, the generator is already borrowed.
+pub fn with_rng<T, F: FnOnce(&mut StdRng) -> T>(f: F) -> T {
+    let stream = STREAM.with(Cell::get);
+
+    RNG.with(|rng| f(rng.borrow_mut().get_mut(stream)))
+}
+
+/// Calls `f` with all random numbers taken from `stream`.
+pub fn with_stream<T, F: FnOnce() -> T>(stream: Stream, f: F) -> T {
+    let previous = STREAM.with(|s| s.replace(stream));
+    let result = f();
+    STREAM.with(|s| s.set(previous));
+
+    result
+}
+
+/// Returns a random value, like `rand::random`.
+pub fn random<T>() -> T
+where
+    Standard: Distribution<T>,
+{
+    with_rng(|rng| rng.gen())
+}
+
+/// Returns a random value in the range `low..high`, like `Rng::gen_range`.
+pub fn gen_range(low: usize, high: usize) -> usize {
+    with_rng(|rng| rng.gen_range(low, high))
+}
+
+/// Makes `rng` the generators of this thread and returns the ones it replaced.
+pub fn replace(rng: Streams) -> Streams {
+    RNG.with(|r| std::mem::replace(&mut *r.borrow_mut(), rng))
+}
@@ -0,0 +1,85 @@
+//! Running a `Board` as fast as possible, without anyone watching.
+//!
+//! `Board::run_for` advances the simulation by a number of years with a fixed time step and hands back a `RunSummary`,
+//! nothing is done to prepare the board for drawing. This is what overnight batch experiments are built on.
+
+use crate::constants::DEFAULT_TIME_STEP;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How `Board::run_for` should run.
+#[derive(Clone, Debug)]
+pub struct RunOptions {
+    /// The amount of years that pass every update.
+    pub time_step: f64,
+    /// Stop early as soon as this becomes true, e.g. set it from a Ctrl-C handler.
+    pub abort: Option<Arc<AtomicBool>>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            time_step: DEFAULT_TIME_STEP,
+            abort: None,
+        }
+    }
+}
+
+impl RunOptions {
+    pub fn new(time_step: f64) -> Self {
+        RunOptions {
+            time_step,
+            ..Self::default()
+        }
+    }
+
+    /// Stops the run as soon as `abort` becomes true.
+    pub fn with_abort(mut self, abort: Arc<AtomicBool>) -> Self {
+        self.abort = Some(abort);
+        self
+    }
+
+    pub(crate) fn should_abort(&self) -> bool {
+        match &self.abort {
+            Some(abort) => abort.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+}
+
+/// What happened during a `Board::run_for`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunSummary {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub updates: usize,
+    /// Whether the run was stopped early through `RunOptions::abort`.
+    pub aborted: bool,
+    /// Whether the run stopped early because the board is paused, e.g. by `ExtinctionPolicy::Halt`.
+    pub paused: bool,
+
+    pub start_population: usize,
+    pub end_population: usize,
+    pub min_population: usize,
+    pub max_population: usize,
+    /// The population averaged over all updates.
+    pub mean_population: f64,
+    pub births: usize,
+    pub deaths: usize,
+    /// The energy of all creatures at the end of the run.
+    pub creature_energy: f64,
+
+    /// How long the run took in real life.
+    pub duration: Duration,
+}
+
+impl RunSummary {
+    /// Returns the amount of simulated years per second of real time.
+    pub fn get_years_per_second(&self) -> f64 {
+        let seconds =
+            self.duration.as_secs() as f64 + f64::from(self.duration.subsec_nanos()) * 1e-9;
+
+        (self.end_time - self.start_time) / seconds
+    }
+}
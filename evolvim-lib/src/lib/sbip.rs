@@ -1,12 +1,13 @@
 //! Used for collision detection.
 //!
-//! `SoftBodiesInPositions` contains references to all `SoftBody`'s.
+//! `SoftBodiesInPositions` contains references to all `SoftBody`'s and all `Egg`s.
 //! It is of critical importance that these are **always** valid!
 //! Seriously, otherwise the application will just crash.
 //!
 //! Please don't mess with this module if you don't understand it: it will save you a lot of frustration!
 
 use super::*;
+use crate::egg::HLEgg;
 use std::ops::Range;
 
 pub trait SoftBodyBucket<B> {
@@ -40,8 +41,13 @@ impl<B> SoftBodyBucket<B> for SoftBodiesAt<B> {
     }
 }
 
-/// Contains a list of every `SoftBody` in a given coordinate.
-pub struct SoftBodiesInPositions<B>(Vec<Vec<SoftBodiesAt<B>>>);
+pub type EggsAt<B> = Vec<HLEgg<B>>;
+
+/// Contains a list of every `SoftBody` and every `Egg` in a given coordinate.
+pub struct SoftBodiesInPositions<B> {
+    bodies: Vec<Vec<SoftBodiesAt<B>>>,
+    eggs: Vec<Vec<EggsAt<B>>>,
+}
 
 impl<B> SoftBodiesInPositions<B> {
     pub fn new_allocated(board_size: BoardSize) -> Self {
@@ -55,20 +61,53 @@ impl<B> SoftBodiesInPositions<B> {
             .take(board_width)
             .collect();
 
-        return SoftBodiesInPositions(allocated_rows);
+        // Eggs are rare, don't allocate anything for them yet.
+        let egg_rows = std::iter::repeat(vec![Vec::new(); board_height])
+            .take(board_width)
+            .collect();
+
+        return SoftBodiesInPositions {
+            bodies: allocated_rows,
+            eggs: egg_rows,
+        };
     }
 
     pub fn get_soft_bodies_at(&self, x: usize, y: usize) -> &SoftBodiesAt<B> {
-        return &self.0[x][y];
+        return &self.bodies[x][y];
     }
 
     pub fn add_soft_body_at(&mut self, x: usize, y: usize, body: HLSoftBody<B>) {
-        self.0[x][y].push(body);
+        self.bodies[x][y].push(body);
     }
 
     /// NOTE: only removes one instance of `body`.
     pub fn remove_soft_body_at(&mut self, x: usize, y: usize, body: HLSoftBody<B>) {
-        self.0[x][y].remove_softbody(body);
+        self.bodies[x][y].remove_softbody(body);
+    }
+
+    pub fn get_eggs_at(&self, x: usize, y: usize) -> &EggsAt<B> {
+        return &self.eggs[x][y];
+    }
+
+    pub fn add_egg_at(&mut self, x: usize, y: usize, egg: HLEgg<B>) {
+        self.eggs[x][y].push(egg);
+    }
+
+    pub fn remove_egg_at(&mut self, x: usize, y: usize, egg: &HLEgg<B>) {
+        self.eggs[x][y].retain(|e| e != egg);
+    }
+
+    /// Returns all eggs lying in the given tiles.
+    pub fn get_eggs_in(&self, x_range: Range<usize>, y_range: Range<usize>) -> EggsAt<B> {
+        let mut eggs = Vec::new();
+
+        for x in x_range {
+            for y in y_range.clone() {
+                eggs.extend(self.get_eggs_at(x, y).iter().cloned());
+            }
+        }
+
+        return eggs;
     }
 
     pub fn get_soft_bodies_in(
@@ -0,0 +1,63 @@
+//! Keeping the brains of the best creatures apart from a full save.
+//!
+//! A seed bank file only holds brains, so it's a lot smaller than a save of the whole `Board`. Write one with
+//! `Board::export_seed_bank` and start another world from it with `Board::load_seed_bank` and
+//! `Board::reseed_from_seed_bank`, e.g. to continue an experiment on new terrain or to let the champions of several
+//! worlds compete. The format goes by the file extension, just like for saves (see `SaveFormat::from_path`).
+
+use crate::error::EvolvimError;
+use crate::serde_structs::{SaveFormat, Version};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Serialize)]
+struct SeedBankRef<'a, B> {
+    version: Version,
+    brains: &'a [B],
+}
+
+#[derive(Deserialize)]
+struct SeedBankSerde<B> {
+    version: Version,
+    brains: Vec<B>,
+}
+
+/// Writes `brains` to a seed bank file at `path`.
+pub(crate) fn save<B: Serialize, P: AsRef<Path>>(
+    path: P,
+    brains: &[B],
+) -> Result<(), EvolvimError> {
+    let path = path.as_ref();
+    let mut file = BufWriter::new(File::create(path)?);
+
+    SaveFormat::from_path(path).serialize_into(
+        &mut file,
+        &SeedBankRef {
+            version: Version::current_version(),
+            brains,
+        },
+    )?;
+
+    file.flush()?;
+
+    Ok(())
+}
+
+/// Reads the brains from the seed bank file at `path`.
+pub(crate) fn load<B: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<Vec<B>, EvolvimError> {
+    let path = path.as_ref();
+    let file = BufReader::new(File::open(path)?);
+    let bank: SeedBankSerde<B> = SaveFormat::from_path(path).deserialize_from(file)?;
+
+    if !bank.version.is_compatible_with_current() {
+        return Err(EvolvimError::IncompatibleVersion {
+            found: bank.version.to_string(),
+            current: Version::current_version().to_string(),
+        });
+    }
+
+    Ok(bank.brains)
+}
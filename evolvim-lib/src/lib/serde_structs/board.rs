@@ -1,13 +1,20 @@
 extern crate serde_derive;
 
+use super::version::Version;
 use crate::board::Board;
-use crate::terrain::Terrain;
-use crate::softbody::SoftBody;
 use crate::climate::Climate;
-use super::version::Version;
+use crate::config::SimConfig;
+use crate::egg::{Egg, HLEgg};
+use crate::experiment::SplitExperiment;
+use crate::pheromone::PheromoneField;
+use crate::phylogeny::Phylogeny;
+use crate::softbody::{HLSoftBody, SoftBody};
+use crate::species::SpeciesTracker;
+use crate::terrain::Terrain;
 
-use serde_derive::{Deserialize, Serialize};
 use crate::brain::NeuralNet;
+use serde::ser::{SerializeSeq, Serializer};
+use serde_derive::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
 pub struct BoardSerde<B: NeuralNet> {
@@ -18,47 +25,120 @@ pub struct BoardSerde<B: NeuralNet> {
     board_width: usize,
     board_height: usize,
     pub terrain: Terrain,
+    config: SimConfig,
 
     // Fields relevant for the creatures.
-    creature_minimum: usize,
     // pub soft_bodies_in_positions: SoftBodiesInPositions<B>,
     pub creatures: Vec<SoftBody<B>>,
     creature_id_up_to: usize,
-    // _creature_rank_metric: usize,
+    pub phylogeny: Phylogeny,
+    pub species: SpeciesTracker,
+    pub eggs: Vec<Egg<B>>,
 
     // Fields relevant for time or history
     year: f64,
 
     // Fields relevant for temperature
     pub climate: Climate,
+    pub split_experiment: Option<SplitExperiment>,
 
+    // Fields relevant for communication
+    pub pheromones: PheromoneField,
     // Miscelanious
     // pub selected_creature: SelectedCreature<B>,
 }
 
-impl<B: NeuralNet> From<Board<B>> for BoardSerde<B> {
-    fn from(bd: Board<B>) -> BoardSerde<B> {
-        let (board_width, board_height) = bd.get_board_size();
-        let creature_minimum = bd.get_creature_minimum();
-        let creature_id_up_to = bd.get_creature_id_up_to();
-        let year = bd.get_time();
+impl<B: NeuralNet> BoardSerde<B> {
+    /// Returns the version of this crate the board was saved with.
+    pub fn get_version(&self) -> &Version {
+        &self.version
+    }
+}
+
+/// Borrows everything `BoardSerde` holds so a `Board` can be saved without giving it up.
+///
+/// NOTE: the fields have to stay in the same order as those of `BoardSerde`, it's read back as one.
+#[derive(Serialize)]
+pub struct BoardSerdeRef<'a, B: NeuralNet> {
+    version: Version,
+
+    board_width: usize,
+    board_height: usize,
+    terrain: &'a Terrain,
+    config: &'a SimConfig,
+
+    creatures: CreaturesRef<'a, B>,
+    creature_id_up_to: usize,
+    phylogeny: &'a Phylogeny,
+    species: &'a SpeciesTracker,
+    eggs: EggsRef<'a, B>,
+
+    year: f64,
+
+    climate: &'a Climate,
+    split_experiment: Option<&'a SplitExperiment>,
+
+    pheromones: &'a PheromoneField,
+}
+
+/// Serializes the creatures behind the pointers as if they were a `Vec<SoftBody<B>>`.
+struct CreaturesRef<'a, B>(&'a [HLSoftBody<B>]);
+
+impl<'a, B> serde::Serialize for CreaturesRef<'a, B>
+where
+    SoftBody<B>: serde::Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for c in self.0 {
+            seq.serialize_element(&*c.borrow())?;
+        }
+
+        seq.end()
+    }
+}
 
-        let creatures: Vec<SoftBody<B>> = bd.creatures.into_iter().map(|c| c.into_inner()).collect();
+/// Serializes the eggs behind the pointers as if they were a `Vec<Egg<B>>`.
+struct EggsRef<'a, B>(&'a [HLEgg<B>]);
+
+impl<'a, B> serde::Serialize for EggsRef<'a, B>
+where
+    Egg<B>: serde::Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for egg in self.0 {
+            seq.serialize_element(&**egg)?;
+        }
 
-        BoardSerde {
+        seq.end()
+    }
+}
+
+impl<'a, B: NeuralNet> From<&'a Board<B>> for BoardSerdeRef<'a, B> {
+    fn from(bd: &'a Board<B>) -> BoardSerdeRef<'a, B> {
+        let (board_width, board_height) = bd.get_board_size();
+
+        BoardSerdeRef {
             version: Version::current_version(),
 
             board_width,
             board_height,
-            terrain: bd.terrain,
+            terrain: &bd.terrain,
+            config: bd.get_config(),
 
-            creature_minimum,
-            creatures,
-            creature_id_up_to,
-            
-            year,
+            creatures: CreaturesRef(&bd.creatures),
+            creature_id_up_to: bd.get_creature_id_up_to(),
+            phylogeny: bd.get_phylogeny(),
+            species: bd.get_species(),
+            eggs: EggsRef(bd.get_eggs()),
 
-            climate: bd.climate,
+            year: bd.get_time(),
+
+            climate: &bd.climate,
+            split_experiment: bd.get_split_experiment(),
+
+            pheromones: &bd.pheromones,
         }
     }
 }
@@ -70,37 +150,49 @@ impl<B: NeuralNet> From<BoardSerde<B>> for Board<B> {
         use crate::softbody::HLSoftBody;
 
         if !bs.version.is_compatible_with_current() {
-            panic!("File from version {} can not be used with current version ({}).",
-                    bs.version,
-                    Version::current_version()
+            panic!(
+                "File from version {} can not be used with current version ({}).",
+                bs.version,
+                Version::current_version()
             );
         }
 
         let board_size = (bs.board_width, bs.board_height);
         let mut soft_bodies_in_positions = SoftBodiesInPositions::new_allocated(board_size);
-        let creatures: Vec<HLSoftBody<B>> = bs.creatures.into_iter()
-            .map(|c| HLSoftBody::from(c)).collect();
+        let creatures: Vec<HLSoftBody<B>> = bs
+            .creatures
+            .into_iter()
+            .map(|c| HLSoftBody::from(c))
+            .collect();
 
         for c in &creatures {
-            c.set_sbip(&mut soft_bodies_in_positions, board_size);
-            c.set_sbip(&mut soft_bodies_in_positions, board_size);
+            c.add_to_sbip(&mut soft_bodies_in_positions, board_size);
         }
 
-        Board::new(
+        let mut board = Board::new(
             bs.board_width,
             bs.board_height,
             bs.terrain,
-
-            bs.creature_minimum,
+            bs.config,
             soft_bodies_in_positions,
             creatures,
             bs.creature_id_up_to,
-
             bs.year,
-
             bs.climate,
-
+            bs.pheromones,
             SelectedCreature::default(),
-        )
+        );
+        *board.get_phylogeny_mut() = bs.phylogeny;
+        *board.get_species_mut() = bs.species;
+        for egg in bs.eggs {
+            board.add_egg(egg);
+        }
+
+        // The climate and terrain were saved with the experiment already applied, this only brings back the barrier.
+        if let Some(split) = bs.split_experiment {
+            board.set_split_experiment(split);
+        }
+
+        board
     }
-}
\ No newline at end of file
+}
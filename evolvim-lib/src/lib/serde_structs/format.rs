@@ -0,0 +1,86 @@
+extern crate bincode;
+#[cfg(feature = "compression")]
+extern crate flate2;
+extern crate ron;
+extern crate serde_json;
+
+use crate::error::EvolvimError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// The file formats a `Board` can be saved in, see `Board::save_to_with_format`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SaveFormat {
+    /// Small and fast but not readable for humans, this is the default.
+    Bincode,
+    /// Rusty Object Notation, the easiest to read and edit by hand.
+    Ron,
+    Json,
+    /// Bincode compressed with gzip, worth it for big boards with thousands of creatures.
+    #[cfg(feature = "compression")]
+    Gzip,
+}
+
+impl Default for SaveFormat {
+    fn default() -> Self {
+        SaveFormat::Bincode
+    }
+}
+
+impl SaveFormat {
+    /// Picks the format from the extension of `path`: `.ron` and `.json` are text, `.gz` is compressed (with the
+    /// `compression` feature) and anything else is bincode.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("ron") => SaveFormat::Ron,
+            Some("json") => SaveFormat::Json,
+            #[cfg(feature = "compression")]
+            Some("gz") => SaveFormat::Gzip,
+            _ => SaveFormat::Bincode,
+        }
+    }
+
+    pub(crate) fn serialize_into<W: Write, T: Serialize>(
+        self,
+        mut writer: W,
+        value: &T,
+    ) -> Result<(), EvolvimError> {
+        match self {
+            SaveFormat::Bincode => bincode::serialize_into(writer, value)?,
+            SaveFormat::Ron => {
+                let text = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?;
+                writer.write_all(text.as_bytes())?;
+            }
+            SaveFormat::Json => serde_json::to_writer_pretty(writer, value)?,
+            #[cfg(feature = "compression")]
+            SaveFormat::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+                bincode::serialize_into(&mut encoder, value)?;
+                encoder.finish()?.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn deserialize_from<R: Read, T: DeserializeOwned>(
+        self,
+        reader: R,
+    ) -> Result<T, EvolvimError> {
+        let result: Result<T, EvolvimError> = match self {
+            SaveFormat::Bincode => bincode::deserialize_from(reader).map_err(Into::into),
+            SaveFormat::Ron => ron::de::from_reader(reader).map_err(Into::into),
+            SaveFormat::Json => serde_json::from_reader(reader).map_err(Into::into),
+            #[cfg(feature = "compression")]
+            SaveFormat::Gzip => {
+                bincode::deserialize_from(flate2::read::GzDecoder::new(reader)).map_err(Into::into)
+            }
+        };
+
+        // Anything but reading itself going wrong means the file is broken.
+        result.map_err(EvolvimError::into_corrupt)
+    }
+}
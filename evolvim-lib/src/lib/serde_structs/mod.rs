@@ -1,5 +1,7 @@
 pub mod board;
+pub mod format;
 pub mod version;
 
-pub use board::BoardSerde;
-pub use version::Version;
\ No newline at end of file
+pub use board::{BoardSerde, BoardSerdeRef};
+pub use format::SaveFormat;
+pub use version::Version;
@@ -0,0 +1,103 @@
+//! Running a `Board` in real time, e.g. while it's being drawn.
+//!
+//! Bigger time steps make creatures move further every update, which makes collisions and eating unstable. So instead
+//! of changing the time step to go faster, a `Simulation` keeps it fixed and changes the amount of updates: the real
+//! time that passed since the last frame is turned into simulated years according to the speed, and those are
+//! simulated in updates of `time_step` years. Whatever doesn't make up a whole update is saved for the next frame.
+
+use crate::brain::{GenerateRandom, NeuralNet, RecombinationInfinite};
+use crate::constants::*;
+use crate::Board;
+
+/// Turns the real time between frames into updates of a fixed size.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    time_step: f64,
+    /// How fast the simulation runs, in `SIMULATION_YEARS_PER_SECOND`.
+    speed: f64,
+    max_updates: usize,
+    /// The simulated years that are due but didn't make up a whole update yet.
+    accumulator: f64,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation::new(DEFAULT_TIME_STEP)
+    }
+}
+
+impl Simulation {
+    /// Returns a simulation that runs at speed 1 with updates of `time_step` years.
+    pub fn new(time_step: f64) -> Self {
+        assert!(time_step > 0.0, "The time step has to be positive.");
+
+        Simulation {
+            time_step,
+            speed: 1.0,
+            max_updates: DEFAULT_MAX_UPDATES_PER_FRAME,
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn get_time_step(&self) -> f64 {
+        self.time_step
+    }
+
+    pub fn get_speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Changes how fast the simulation runs, 1 is `SIMULATION_YEARS_PER_SECOND` and 0 stops it.
+    pub fn set_speed(&mut self, speed: f64) {
+        assert!(speed >= 0.0, "The speed can't be negative.");
+
+        self.speed = speed;
+        if speed == 0.0 {
+            self.accumulator = 0.0;
+        }
+    }
+
+    /// Changes the most updates done in a single frame, any time beyond that is dropped.
+    pub fn set_max_updates_per_frame(&mut self, max_updates: usize) {
+        self.max_updates = max_updates;
+    }
+
+    /// Returns the amount of updates that are due now that `elapsed` seconds of real time passed.
+    ///
+    /// Use this to do the updates yourself, e.g. to do something after every update. Otherwise use `advance`.
+    pub fn take_updates(&mut self, elapsed: f64) -> usize {
+        self.accumulator += elapsed.max(0.0) * self.speed * SIMULATION_YEARS_PER_SECOND;
+
+        // Don't let rounding errors skip an update.
+        let updates = (self.accumulator / self.time_step + 1e-9).floor() as usize;
+        if updates > self.max_updates {
+            // Don't try to catch up, that would only make the next frame take longer.
+            self.accumulator = 0.0;
+            return self.max_updates;
+        }
+
+        self.accumulator = (self.accumulator - updates as f64 * self.time_step).max(0.0);
+
+        updates
+    }
+
+    /// Updates `board` as much as `elapsed` seconds of real time ask for and returns the amount of updates.
+    ///
+    /// A paused board isn't updated, the time that passes while it's paused is forgotten.
+    pub fn advance<B: NeuralNet + RecombinationInfinite + GenerateRandom + Clone>(
+        &mut self,
+        board: &mut Board<B>,
+        elapsed: f64,
+    ) -> usize {
+        let updates = self.take_updates(elapsed);
+
+        for done in 0..updates {
+            if board.is_paused() {
+                return done;
+            }
+            board.update(self.time_step);
+        }
+
+        updates
+    }
+}
@@ -0,0 +1,187 @@
+//! Read-only copies of the state of a `Board` that can be shared with other threads.
+//!
+//! A `Board` is full of `Rc<RefCell<_>>`s so it can't leave the thread it lives on. A `BoardSnapshot` only holds plain
+//! data: enough to draw the board or analyse it, while the simulation goes on. Publish snapshots through a
+//! `SharedSnapshot` to hand them to a renderer thread, a server or anything else that wants to watch.
+
+use crate::brain::NeuralNet;
+#[cfg(feature = "images")]
+use crate::error::EvolvimError;
+use crate::softbody::SoftBody;
+#[cfg(feature = "images")]
+use crate::terrain::images;
+use crate::{Board, BoardSize};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The state of a single creature at the time of the snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatureSnapshot {
+    pub id: usize,
+    pub px: f64,
+    pub py: f64,
+    pub radius: f64,
+    pub rotation: f64,
+    pub hue: f64,
+    pub energy: f64,
+    pub age: f64,
+    /// The age at which it dies of old age, see `Rock::get_lifespan`.
+    pub lifespan: f64,
+}
+
+/// A read-only copy of a living creature, see `Board::creatures_iter`.
+pub type CreatureView = CreatureSnapshot;
+
+impl CreatureSnapshot {
+    /// Copies the state of `creature` at `time`.
+    pub fn new<B>(creature: &SoftBody<B>, time: f64) -> Self {
+        CreatureSnapshot {
+            id: creature.get_id(),
+            px: creature.get_px(),
+            py: creature.get_py(),
+            radius: creature.get_radius(),
+            rotation: creature.get_rotation(),
+            hue: creature.get_mouth_hue(),
+            energy: creature.get_energy(),
+            age: creature.get_age(time),
+            lifespan: creature.get_lifespan(),
+        }
+    }
+}
+
+/// An immutable copy of the state of a `Board`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+    pub(crate) time: f64,
+    pub(crate) board_size: BoardSize,
+    /// The HSBA colors of all tiles, row by row.
+    pub(crate) tile_colors: Vec<[f32; 4]>,
+    pub(crate) creatures: Vec<CreatureSnapshot>,
+    /// The id of the selected creature.
+    pub(crate) selected: Option<usize>,
+}
+
+impl BoardSnapshot {
+    /// Copies the current state of `board`.
+    pub fn new<B: NeuralNet>(board: &Board<B>) -> Self {
+        let (width, height) = board.get_board_size();
+
+        let mut tile_colors = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                tile_colors.push(board.terrain.get_tile_at((x, y)).get_hsba_color());
+            }
+        }
+
+        let creatures = board.creatures_iter().collect();
+
+        let selected = board.get_selected_creature().map(|c| c.borrow().get_id());
+
+        BoardSnapshot {
+            time: board.get_time(),
+            board_size: (width, height),
+            tile_colors,
+            creatures,
+            selected,
+        }
+    }
+
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn get_board_size(&self) -> BoardSize {
+        self.board_size
+    }
+
+    /// Returns the HSBA color of the tile at `(x, y)`.
+    pub fn get_tile_color(&self, x: usize, y: usize) -> [f32; 4] {
+        self.tile_colors[y * self.board_size.0 + x]
+    }
+
+    pub fn get_creatures(&self) -> &[CreatureSnapshot] {
+        &self.creatures
+    }
+
+    pub fn get_creature_by_id(&self, id: usize) -> Option<&CreatureSnapshot> {
+        self.creatures.iter().find(|c| c.id == id)
+    }
+
+    /// Returns the id of the creature that was selected, if any.
+    pub fn get_selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn get_population(&self) -> usize {
+        self.creatures.len()
+    }
+
+    pub fn get_total_energy(&self) -> f64 {
+        self.creatures.iter().map(|c| c.energy).sum()
+    }
+
+    /// Draws the board with every tile as a square of `scale` by `scale` pixels and every creature as a disc.
+    #[cfg(feature = "images")]
+    pub fn render_to_image(&self, scale: u32) -> image::RgbaImage {
+        let width = self.board_size.0;
+        let mut image = images::render_tiles(self.board_size, scale, |x, y| {
+            self.tile_colors[y * width + x]
+        });
+
+        let scale = f64::from(scale.max(1));
+        for c in &self.creatures {
+            let center = (c.px * scale, c.py * scale);
+            // Small creatures would disappear between the pixels.
+            let radius = (c.radius * scale).max(1.0);
+            images::draw_circle(&mut image, center, radius, [c.hue as f32, 1.0, 1.0, 1.0]);
+        }
+
+        image
+    }
+
+    /// Saves a picture of the board to `path`, see `render_to_image`.
+    ///
+    /// The format goes by the file extension, only PNG (`.png`) is supported.
+    #[cfg(feature = "images")]
+    pub fn save_image<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        scale: u32,
+    ) -> Result<(), EvolvimError> {
+        self.render_to_image(scale).save(path)?;
+
+        Ok(())
+    }
+}
+
+/// The latest `BoardSnapshot`, shared between threads.
+///
+/// Cloning gives another handle to the same snapshot. The lock is only held to swap or copy a pointer, so the
+/// simulation is never held up by a slow reader: readers keep their own `Arc` for as long as they like.
+#[derive(Clone)]
+pub struct SharedSnapshot(Arc<Mutex<Arc<BoardSnapshot>>>);
+
+impl SharedSnapshot {
+    pub fn new(snapshot: BoardSnapshot) -> Self {
+        SharedSnapshot(Arc::new(Mutex::new(Arc::new(snapshot))))
+    }
+
+    /// Replaces the shared snapshot by a new one, readers holding the old one keep it.
+    pub fn publish(&self, snapshot: BoardSnapshot) {
+        let snapshot = Arc::new(snapshot);
+        // Nothing can panic while the lock is held so it's fine to ignore poisoning.
+        let old = std::mem::replace(
+            &mut *self.0.lock().unwrap_or_else(PoisonError::into_inner),
+            snapshot,
+        );
+        // Freeing the old snapshot can take a while, don't do it while holding the lock.
+        drop(old);
+    }
+
+    /// Returns the most recently published snapshot.
+    pub fn get(&self) -> Arc<BoardSnapshot> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
@@ -8,6 +8,9 @@ pub const MINIMUM_SURVIVABLE_SIZE: f64 = 0.06;
 pub struct Creature<B> {
     pub base: Rock,
     pub brain: B,
+    /// Given by the `Board` when this creature is added to it, unique within that `Board`.
+    id: usize,
+    parent_ids: Vec<usize>,
 }
 
 impl<B> std::ops::Deref for Creature<B> {
@@ -26,13 +29,23 @@ impl<B> std::ops::DerefMut for Creature<B> {
 
 impl<B: GenerateRandom> Creature<B> {
     pub fn new_random(board_size: BoardSize, time: f64) -> Self {
+        Self::new_random_with_brain(board_size, time, B::new_random())
+    }
+}
+
+impl<B> Creature<B> {
+    /// Creates a creature with a random body around the given brain.
+    pub fn new_random_with_brain(board_size: BoardSize, time: f64, brain: B) -> Self {
         let energy = CREATURE_MIN_ENERGY
-            + rand::random::<f64>() * (CREATURE_MAX_ENERGY - CREATURE_MIN_ENERGY);
+            + crate::rng::random::<f64>() * (CREATURE_MAX_ENERGY - CREATURE_MIN_ENERGY);
         let base = Rock::new_random(board_size, CREATURE_DENSITY, energy, time);
-        let brain = B::new_random();
-        // TODO: add id
 
-        Creature { base, brain }
+        Creature {
+            base,
+            brain,
+            id: 0,
+            parent_ids: Vec::new(),
+        }
     }
 }
 
@@ -42,27 +55,93 @@ impl<B: NeuralNet + RecombinationInfinite> Creature<B> {
     pub fn new_baby(parents: Vec<HLSoftBody<B>>, energy: f64, time: f64) -> Creature<B> {
         let brain = B::recombination_infinite_parents(&parents);
         let base = Rock::new_from_parents(&parents, energy, time);
+        let parent_ids = parents.iter().map(|p| p.borrow().get_id()).collect();
 
-        Creature { base, brain }
+        Creature {
+            base,
+            brain,
+            id: 0,
+            parent_ids,
+        }
     }
 }
 
 impl<B> Creature<B> {
     // The `Creature` version of `apply_motions`, this is different to the `Rock` version.
     pub fn apply_motions(&mut self, time_step: f64, terrain: &Terrain, board_size: BoardSize) {
-        if self.is_on_water(terrain, board_size) {
-            let energy_to_lose = time_step * SWIM_ENERGY * self.get_energy();
+        let tile = terrain.get_tile_at(self.get_random_covered_tile(board_size));
+        if tile.is_water() {
+            // Good swimmers don't feel the water as much.
+            let penalty = 1.0 - self.get_swimming();
+            let depth = tile.get_water_depth();
+
+            let mut cost = SWIM_ENERGY * (1.0 + depth);
+            if tile.is_deep_water() {
+                cost += DROWN_ENERGY;
+            }
+
+            let energy_to_lose = time_step * cost * penalty * self.get_energy();
+            self.lose_energy(energy_to_lose);
+
+            self.apply_drag(WATER_DRAG * depth * penalty);
+        } else {
+            // Walking uphill, the higher the land the more it costs to move over it.
+            let energy_to_lose = time_step
+                * CLIMB_ENERGY
+                * tile.get_elevation()
+                * self.get_total_velocity()
+                * self.get_energy();
             self.lose_energy(energy_to_lose);
         }
 
+        let old_position = self.get_position();
         self.base.apply_motions(time_step, board_size);
+        self.base.stay_off_obstacles(old_position, terrain);
     }
 
-    pub fn should_die(&self) -> bool {
-        return self.get_energy() < SAFE_SIZE;
+    /// Returns true if this creature ran out of energy or got too old at `time`.
+    pub fn should_die(&self, time: f64) -> bool {
+        return self.get_energy() < SAFE_SIZE || self.is_past_lifespan(time);
     }
 
     pub fn get_baby_energy(&self) -> f64 {
         self.base.get_energy() - SAFE_SIZE
     }
+
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    pub(crate) fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    /// Returns the ids of the parents of this creature, empty if it was spawned randomly.
+    pub fn get_parent_ids(&self) -> &[usize] {
+        &self.parent_ids
+    }
+}
+
+impl<B: NeuralNet> Creature<B> {
+    /// Returns a hash of everything this creature passes on to its babies, its body genes and its brain.
+    ///
+    /// Creatures with the same fingerprint are almost certainly genetically identical, see `Phylogeny`.
+    pub fn get_genome_fingerprint(&self) -> u64 {
+        let mut hasher = crate::hash::StateHasher::new();
+        self.base.write_genes(&mut hasher);
+        self.brain.write_genome(&mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Returns the name of this creature, made from its genome fingerprint. See `names`.
+    pub fn get_name(&self) -> String {
+        crate::names::name_from_fingerprint(self.get_genome_fingerprint())
+    }
+
+    /// Returns how different the genes of this creature and `other` are, the sum of the distances between their bodies
+    /// and their brains. See `Species`.
+    pub fn get_genetic_distance(&self, other: &Self) -> f64 {
+        self.base.get_gene_distance(&other.base) + self.brain.genome_distance(&other.brain)
+    }
 }
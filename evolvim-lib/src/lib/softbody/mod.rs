@@ -9,9 +9,9 @@ pub use self::rock::*;
 use std::cell::{Ref, RefMut};
 
 #[cfg(multithreading)]
-type ReferenceCounter = std::sync::Arc;
+pub(crate) type ReferenceCounter = std::sync::Arc;
 #[cfg(not(multithreading))]
-type ReferenceCounter<A> = std::rc::Rc<A>;
+pub(crate) type ReferenceCounter<A> = std::rc::Rc<A>;
 
 #[cfg(multithreading)]
 type MutPoint = std::sync::RwLock;
@@ -23,6 +23,24 @@ const PIECES: usize = 20;
 const AGE_FACTOR: f64 = 1.0;
 const MATURE_AGE: f64 = 0.01;
 
+/// Who takes part in a birth, part of a `SimConfig`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReproductionMode {
+    /// A creature gives birth whenever it wants to, creatures that touch it and don't refuse chip in their energy and
+    /// genes.
+    Asexual,
+    /// A creature needs a mate within `MATING_RANGE` that's old enough and whose "help birth" output is at least
+    /// `MATE_WILLINGNESS`. The most willing one becomes the other parent and their brains are crossed over.
+    Sexual,
+}
+
+impl Default for ReproductionMode {
+    fn default() -> Self {
+        ReproductionMode::Asexual
+    }
+}
+
 /// Higher-Level SoftBody
 ///
 /// This is a wrapper struct providing some useful functions.
@@ -140,6 +158,23 @@ impl<B> HLSoftBody<B> {
         }
     }
 
+    /// Adds this `HLSoftBody` to every tile it covers, use this instead of `set_sbip` for a new body.
+    ///
+    /// `set_sbip` only adds it to the tiles it wasn't in before, and a new body seems to have been at `(0, 0)`.
+    pub fn add_to_sbip(&self, sbip: &mut SoftBodiesInPositions<B>, board_size: BoardSize) {
+        let mut self_borrow = self.borrow_mut();
+
+        // Twice to make the previous position the same as the current one.
+        self_borrow.update_sbip_variables(board_size);
+        self_borrow.update_sbip_variables(board_size);
+
+        for x in self_borrow.current_x_range() {
+            for y in self_borrow.current_y_range() {
+                sbip.add_soft_body_at(x, y, self.clone());
+            }
+        }
+    }
+
     /// Completely removes this `HLSoftBody` from `sbip`.
     ///
     /// NOTE: `HLSoftBody` is added again when `set_sbip` is called.
@@ -189,6 +224,9 @@ impl<B> HLSoftBody<B> {
 
     /// This function requires a reference to a `Board`.
     /// This is usually impossible so you'll have to turn to `unsafe`.
+    ///
+    /// Returns the amount of energy that ended up as food, pieces falling into water are lost. If `carrion` is true
+    /// the food is left as carrion instead of plant food.
     pub fn return_to_earth(
         &mut self,
         time: f64,
@@ -196,29 +234,43 @@ impl<B> HLSoftBody<B> {
         terrain: &mut Terrain,
         climate: &Climate,
         sbip: &mut SoftBodiesInPositions<B>,
-    ) {
+        carrion: bool,
+    ) -> f64 {
+        let mut returned = 0.0;
+
         // To keep the borrowchecker happy.
         {
             let self_deref = self.borrow_mut();
+            let piece = self_deref.get_energy() / PIECES as f64;
 
             for _i in 0..PIECES {
                 let tile_pos = self_deref.get_random_covered_tile(board_size);
-                terrain.add_food_or_nothing_at(tile_pos, self_deref.get_energy() / PIECES as f64);
+                if terrain.get_tile_at(tile_pos).is_land() {
+                    returned += piece;
+                }
+                if carrion {
+                    terrain.add_carrion_or_nothing_at(tile_pos, piece);
+                } else {
+                    terrain.add_food_or_nothing_at(tile_pos, piece);
+                }
 
                 terrain.update_at(tile_pos, time, climate);
             }
         }
 
         self.remove_from_sbip(sbip);
+
+        returned
     }
 }
 
 impl<B: Intentions> HLSoftBody<B> {
+    /// Checks whether this creature asked to give birth (see `EnvironmentMut::give_birth`) and is able to.
     fn wants_primary_birth(&self, time: f64) -> bool {
-        let temp = self.borrow();
+        let mut temp = self.borrow_mut();
 
-        temp.get_energy() > SAFE_SIZE
-            && temp.brain.wants_birth() > 0.0
+        temp.take_birth_request()
+            && temp.get_energy() > SAFE_SIZE
             && temp.get_age(time) > MATURE_AGE
     }
 }
@@ -231,6 +283,7 @@ impl<B: NeuralNet + Intentions + RecombinationInfinite> HLSoftBody<B> {
         time: f64,
         sbip: &mut SoftBodiesInPositions<B>,
         board_size: BoardSize,
+        mode: ReproductionMode,
     ) -> Option<HLSoftBody<B>> {
         if self.wants_primary_birth(time) {
             let self_px = self.borrow().get_px();
@@ -242,21 +295,47 @@ impl<B: NeuralNet + Intentions + RecombinationInfinite> HLSoftBody<B> {
             // Remove self
             colliders.remove_softbody(self.clone());
 
-            let mut parents: Vec<HLSoftBody<B>> = colliders
-                .into_iter()
-                .filter(|rc_soft| {
-                    let c = rc_soft.borrow();
-                    let dist = distance(self_px, self_py, c.get_px(), c.get_py());
-                    let combined_radius = self_radius * FIGHT_RANGE + c.get_radius();
+            let mut parents: Vec<HLSoftBody<B>> = match mode {
+                ReproductionMode::Asexual => colliders
+                    .into_iter()
+                    .filter(|rc_soft| {
+                        let c = rc_soft.borrow();
+                        let dist = distance(self_px, self_py, c.get_px(), c.get_py());
+                        let combined_radius = self_radius * FIGHT_RANGE + c.get_radius();
 
-                    c.brain.wants_help_birth() > -1.0 // must be a willing creature
+                        c.brain.wants_help_birth() > -1.0 // must be a willing creature
                             && dist < combined_radius // must be close enough
 
-                    // TODO: find out if this addition to the Processing code works
-                    // && c.get_age(time) >= MATURE_AGE // creature must be old enough
-                    // && c.base.get_energy() > SAFE_SIZE
-                })
-                .collect();
+                        // TODO: find out if this addition to the Processing code works
+                        // && c.get_age(time) >= MATURE_AGE // creature must be old enough
+                        // && c.base.get_energy() > SAFE_SIZE
+                    })
+                    .collect(),
+                ReproductionMode::Sexual => {
+                    let mate = colliders
+                        .into_iter()
+                        .filter(|rc_soft| {
+                            let c = rc_soft.borrow();
+                            let dist = distance(self_px, self_py, c.get_px(), c.get_py());
+                            let combined_radius = self_radius * MATING_RANGE + c.get_radius();
+
+                            c.brain.wants_help_birth() >= MATE_WILLINGNESS
+                                && dist < combined_radius
+                                && c.get_age(time) > MATURE_AGE
+                        })
+                        .max_by(|a, b| {
+                            let a = a.borrow().brain.wants_help_birth();
+                            let b = b.borrow().brain.wants_help_birth();
+                            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                        });
+
+                    match mate {
+                        Some(mate) => vec![mate],
+                        // Nobody wants to, no baby this time.
+                        None => return None,
+                    }
+                }
+            };
 
             parents.push(self.clone());
 
@@ -272,13 +351,17 @@ impl<B: NeuralNet + Intentions + RecombinationInfinite> HLSoftBody<B> {
                     let mut c = c.borrow_mut();
 
                     let energy_to_lose = energy * (c.get_baby_energy() / available_energy);
-                    c.lose_energy(energy_to_lose);
+                    // Babies haven't eaten anything yet so they start out as herbivores.
+                    c.give_to_offspring(
+                        energy_to_lose,
+                        crate::energy_flow::Compartment::Herbivores,
+                    );
+                    c.add_offspring();
                 });
 
                 let sb = HLSoftBody::from(Creature::new_baby(parents, energy, time));
 
-                sb.set_sbip(sbip, board_size);
-                sb.set_sbip(sbip, board_size);
+                sb.add_to_sbip(sbip, board_size);
 
                 // Hooray! Return the little baby!
                 Some(sb)
@@ -296,14 +379,23 @@ impl<B: NeuralNet + Intentions + RecombinationInfinite> HLSoftBody<B> {
 pub type SoftBody<B = Brain> = Creature<B>;
 
 // Here are all the functions only applicable to `Creature`s.
-impl<B> SoftBody<B> {
+impl<B: NeuralNet> SoftBody<B> {
     /// Performs the energy requirement to keep living.
-    pub fn metabolize(&mut self, time_step: f64, time: f64) {
+    ///
+    /// This includes the cost of running the brain, which grows with its complexity, of a long lifespan and of a big
+    /// body. Old creatures lose more energy as they decline, see `Rock::get_senescence`.
+    pub fn metabolize(&mut self, time_step: f64, time: f64, config: &SimConfig) {
         // TODO: fix ugly code.
         let age = AGE_FACTOR * (time - self.get_birth_time());
+        let brain_cost =
+            self.brain.complexity() as f64 * config.brain_metabolism_energy * time_step;
         let creature = self;
-        let energy_to_lose = creature.get_energy() * METABOLISM_ENERGY * age * time_step;
-        creature.lose_energy(energy_to_lose);
+        let decline = SENESCENCE_ENERGY * creature.get_senescence(time)
+            + LIFESPAN_ENERGY * creature.get_lifespan();
+        let energy_to_lose =
+            creature.get_energy() * (config.metabolism_energy * age + decline) * time_step;
+        let body_cost = BODY_SIZE_ENERGY * creature.get_body_size() * time_step;
+        creature.lose_energy(energy_to_lose + brain_cost + body_cost);
 
         // Creature should die if it doesn't have enough energy, this is done by `Board`.
     }
@@ -1,12 +1,11 @@
-extern crate rand;
-
-use self::rand::Rng;
-
 use super::HLSoftBody;
 use crate::board::{BoardCoordinate, BoardPreciseCoordinate, BoardSize};
 use crate::climate::Climate;
 use crate::constants::*;
+use crate::energy_flow::{Compartment, EnergyLedger};
+use crate::rng;
 use crate::sbip::{SoftBodiesAt, SoftBodiesInPositions};
+use crate::terrain::resource::ResourceKind;
 use crate::terrain::Terrain;
 use std::f64::consts::PI;
 use std::ops::Range;
@@ -41,23 +40,45 @@ pub struct Rock {
     // Stats or info
     prev_energy: f64,
     birth_time: f64,
+    last_bitten_time: Option<f64>,
+    offspring: usize,
+    distance_traveled: f64,
+    energy_from_plants: f64,
+    energy_from_prey: f64,
+    #[serde(skip)]
+    energy_ledger: EnergyLedger,
+    #[serde(skip)]
+    birth_requested: bool,
     // Miscellanious
     mouth_hue: f64,
+    /// Between 0 and 1, how much of the cost of being in the water this body doesn't pay, see
+    /// `Creature::apply_motions`. It's 0 unless it's set with `set_swimming`.
+    swimming: f64,
+    /// The kind of food this body digests best, see `get_food_efficiency`.
+    mouth_specialization: ResourceKind,
+    /// How many years this body lives at most, see `get_senescence`.
+    lifespan: f64,
+    /// Between 0 for a plant eater and 1 for a meat eater, see `get_plant_efficiency` and `get_meat_efficiency`.
+    diet: f64,
+    /// How big this body can grow, see `get_energy_capacity`.
+    body_size: f64,
 }
 
 impl Rock {
     pub fn new_random(board_size: BoardSize, density: f64, energy: f64, time: f64) -> Self {
         let (board_width, board_height) = board_size;
 
-        let mut thread_rng = rand::thread_rng();
-        let px = thread_rng.gen::<f64>() * (board_width - 1) as f64;
-        let py = thread_rng.gen::<f64>() * (board_height - 1) as f64;
-        let mouth_hue = thread_rng.gen::<f64>();
+        let px = rng::random::<f64>() * (board_width - 1) as f64;
+        let py = rng::random::<f64>() * (board_height - 1) as f64;
+        let mouth_hue = rng::random::<f64>();
+        let lifespan = DEFAULT_LIFESPAN * (0.5 + rng::random::<f64>());
+        let diet = DEFAULT_DIET * 2.0 * rng::random::<f64>();
+        let body_size = (DEFAULT_BODY_SIZE * (0.5 + rng::random::<f64>())).max(MIN_BODY_SIZE);
 
         Self {
             px,
             py,
-            rotation: rand::random::<f64>() * 2.0 * PI,
+            rotation: rng::random::<f64>() * 2.0 * PI,
 
             vx: 0.0,
             vy: 0.0,
@@ -77,8 +98,20 @@ impl Rock {
 
             prev_energy: energy,
             birth_time: time,
+            last_bitten_time: None,
+            offspring: 0,
+            distance_traveled: 0.0,
+            energy_from_plants: 0.0,
+            energy_from_prey: 0.0,
+            energy_ledger: EnergyLedger::default(),
+            birth_requested: false,
 
             mouth_hue,
+            swimming: 0.0,
+            mouth_specialization: ResourceKind::Plant,
+            lifespan,
+            diet,
+            body_size,
         }
     }
 
@@ -101,8 +134,35 @@ impl Rock {
             acc + parent.borrow().mouth_hue / parent_amount as f64
         });
 
+        let swimming = parents.iter().fold(0.0, |acc, parent| {
+            acc + parent.borrow().swimming / parent_amount as f64
+        });
+
         let density = parents[0].borrow().density;
 
+        let lifespan = parents.iter().fold(0.0, |acc, parent| {
+            acc + parent.borrow().lifespan / parent_amount as f64
+        }) * (1.0 + LIFESPAN_MUTATION * (rng::random::<f64>() * 2.0 - 1.0));
+
+        let diet = (parents.iter().fold(0.0, |acc, parent| {
+            acc + parent.borrow().diet / parent_amount as f64
+        }) + DIET_MUTATION * (rng::random::<f64>() * 2.0 - 1.0))
+            .min(1.0)
+            .max(0.0);
+
+        let body_size = (parents.iter().fold(0.0, |acc, parent| {
+            acc + parent.borrow().body_size / parent_amount as f64
+        }) * (1.0 + BODY_SIZE_MUTATION * (rng::random::<f64>() * 2.0 - 1.0)))
+            .max(MIN_BODY_SIZE);
+
+        // The mouth is like the one of the first parent, but once in a while it's made for other food.
+        let mouth_specialization = if rng::random::<f64>() < MOUTH_SPECIALIZATION_MUTATION_CHANCE {
+            let kinds = ResourceKind::get_all();
+            kinds[rng::random::<usize>() % kinds.len()]
+        } else {
+            parents[0].borrow().mouth_specialization
+        };
+
         Rock {
             px,
             py,
@@ -126,8 +186,20 @@ impl Rock {
 
             prev_energy: energy,
             birth_time: time,
+            last_bitten_time: None,
+            offspring: 0,
+            distance_traveled: 0.0,
+            energy_from_plants: 0.0,
+            energy_from_prey: 0.0,
+            energy_ledger: EnergyLedger::default(),
+            birth_requested: false,
 
             mouth_hue,
+            swimming,
+            mouth_specialization,
+            lifespan,
+            diet,
+            body_size,
         }
     }
 
@@ -157,7 +229,13 @@ impl Rock {
         climate: &Climate,
         tile: &mut crate::terrain::tile::Tile,
     ) {
-        let amount = attempted_amount
+        // The appetite gene changes how much is eaten, not how much is given back.
+        let appetite = if attempted_amount > 0.0 {
+            self.get_appetite()
+        } else {
+            1.0
+        };
+        let amount = attempted_amount * appetite
             / (1.0 + self.get_total_velocity() * EAT_WHILE_MOVING_INEFFICIENCY_MULTIPLIER);
         if amount < 0.0 {
             // Vomit
@@ -178,15 +256,73 @@ impl Rock {
             if multiplier < 0.0 {
                 // Poison
                 self.lose_energy(food_to_eat * -multiplier);
+                self.energy_ledger
+                    .flows
+                    .add(Compartment::Tiles, Compartment::Decay, food_to_eat);
             } else {
                 // Healthy food
-                self.add_energy(food_to_eat * multiplier);
+                let gained =
+                    food_to_eat * multiplier * self.get_food_efficiency(ResourceKind::Plant);
+                self.add_energy(gained);
+
+                let compartment = self.get_compartment();
+                self.energy_ledger
+                    .flows
+                    .add(Compartment::Tiles, compartment, gained);
+                self.energy_ledger.flows.add(
+                    Compartment::Tiles,
+                    Compartment::Decay,
+                    food_to_eat - gained,
+                );
+                self.energy_ledger.gained += gained;
+                self.energy_from_plants += gained;
+            }
+
+            // Toxic food hurts unless the mouth is adapted to it.
+            let toxin_damage = food_to_eat * tile.get_toxin_damage(self.get_mouth_hue());
+            self.lose_energy(toxin_damage);
+
+            for &kind in &ResourceKind::get_all()[1..] {
+                self.eat_resource(kind, amount * time_step, tile);
             }
 
             self.lose_energy(attempted_amount * EAT_ENERGY * time_step);
         }
     }
 
+    /// Eats the resource of `kind` on `tile`, a bigger `amount` eats more of it.
+    fn eat_resource(
+        &mut self,
+        kind: ResourceKind,
+        amount: f64,
+        tile: &mut crate::terrain::tile::Tile,
+    ) {
+        let level = tile.get_resource_level(kind);
+        if level <= 0.0 {
+            return;
+        }
+
+        let eaten = level * (1.0 - (1.0 - EAT_SPEED).powf(amount));
+        tile.remove_resource(kind, eaten);
+
+        let gained = eaten * self.get_food_efficiency(kind);
+        self.add_energy(gained);
+
+        let compartment = self.get_compartment();
+        self.energy_ledger
+            .flows
+            .add(Compartment::Tiles, compartment, gained);
+        self.energy_ledger
+            .flows
+            .add(Compartment::Tiles, Compartment::Decay, eaten - gained);
+        self.energy_ledger.gained += gained;
+        if kind == ResourceKind::Carrion {
+            self.energy_from_prey += gained;
+        } else {
+            self.energy_from_plants += gained;
+        }
+    }
+
     pub fn fight<B>(
         &mut self,
         amount: f64,
@@ -222,11 +358,103 @@ impl Rock {
         }
     }
 
+    /// Bites the creatures in range, taking their energy.
+    ///
+    /// The damage done scales with the size of the attacker compared to its victim.
+    /// Returns the total amount of energy gained.
+    pub fn bite<B>(
+        &mut self,
+        amount: f64,
+        time: f64,
+        time_step: f64,
+        sbip: &SoftBodiesInPositions<B>,
+        self_pointer: HLSoftBody<B>,
+    ) -> f64 {
+        use super::MATURE_AGE;
+        use crate::sbip::SoftBodyBucket;
+
+        let mut energy_gained = 0.0;
+
+        if amount > 0.0 && self.get_age(time) >= MATURE_AGE {
+            self.lose_energy(amount * time_step * BITE_ENERGY);
+
+            let self_x = self.get_px();
+            let self_y = self.get_py();
+            let self_radius = self.get_radius();
+
+            let compartment = self.get_compartment();
+            let mut colliders = self.get_colliders(sbip);
+
+            // Remove self
+            colliders.remove_softbody(self_pointer);
+
+            for collider in colliders {
+                let mut col = collider.borrow_mut();
+                let distance = distance(self_x, self_y, col.get_px(), col.get_py());
+                let combined_radius = self_radius * FIGHT_RANGE + col.get_radius();
+
+                if distance < combined_radius {
+                    let size_multiplier =
+                        (self_radius / col.get_radius()).min(BITE_MAX_SIZE_MULTIPLIER);
+                    // A full belly doesn't take big bites.
+                    let damage =
+                        amount * self.get_appetite() * BITE_DAMAGE * size_multiplier * time_step;
+
+                    let energy_taken = col.take_bite(damage, time);
+                    let gained = energy_taken * BITE_EFFICIENCY * self.get_meat_efficiency();
+                    energy_gained += gained;
+
+                    let victim = col.get_compartment();
+                    self.energy_ledger.flows.add(victim, compartment, gained);
+                    self.energy_ledger
+                        .flows
+                        .add(victim, Compartment::Decay, energy_taken - gained);
+                }
+            }
+
+            self.add_energy(energy_gained);
+            self.energy_ledger.gained += energy_gained;
+            self.energy_from_prey += energy_gained;
+        }
+
+        energy_gained
+    }
+
+    /// Eats an egg with `energy` in it, returns the energy gained. Eggs are meat, see `Egg`.
+    pub fn eat_egg(&mut self, energy: f64) -> f64 {
+        // Babies start out as herbivores, so that's where the energy of an egg is.
+        let gained = energy * EGG_EFFICIENCY * self.get_meat_efficiency();
+        let compartment = self.get_compartment();
+        self.energy_ledger
+            .flows
+            .add(Compartment::Herbivores, compartment, gained);
+        self.energy_ledger
+            .flows
+            .add(Compartment::Herbivores, Compartment::Decay, energy - gained);
+
+        self.add_energy(gained);
+        self.energy_ledger.gained += gained;
+        self.energy_from_prey += gained;
+
+        gained
+    }
+
+    /// Removes the energy lost to a bite and remembers when it happened, returns the energy actually lost.
+    pub fn take_bite(&mut self, damage: f64, time: f64) -> f64 {
+        let energy_lost = damage.min(self.energy).max(0.0);
+        self.lose_energy(energy_lost);
+        self.energy_ledger.lost += energy_lost;
+        self.last_bitten_time = Some(time);
+
+        energy_lost
+    }
+
     /// Accelerate
     ///
     /// Costs energy.
     pub fn accelerate(&mut self, amount: f64, time_step: f64) {
-        let multiplier = amount * time_step / self.get_mass();
+        // A big body is slow to get going, even when it's still thin.
+        let multiplier = amount * time_step / (self.get_mass() * self.body_size);
         self.vx += self.rotation.cos() * multiplier;
         self.vy += self.rotation.sin() * multiplier;
 
@@ -255,10 +483,12 @@ impl Rock {
     /// NOTE: Includes rotation unlike the Processing code.
     /// NOTE: Does not call `set_sbip`.
     pub fn apply_motions(&mut self, time_step: f64, board_size: BoardSize) {
+        let (old_px, old_py) = (self.px, self.py);
         let new_px = self.px + self.vx * time_step;
         let new_py = self.py + self.vy * time_step;
         self.set_body_x(new_px, board_size.0);
         self.set_body_y(new_py, board_size.1);
+        self.distance_traveled += distance(old_px, old_py, self.px, self.py);
         self.rotation += self.vr * time_step;
 
         self.vx *= 0f64.max(1.0 - FRICTION / self.get_mass());
@@ -266,6 +496,57 @@ impl Rock {
         self.vr *= 0f64.max(1.0 - FRICTION / self.get_mass());
     }
 
+    /// Slows this body down by the fraction `drag` of its speed, e.g. because it's in the water.
+    pub fn apply_drag(&mut self, drag: f64) {
+        let remaining = (1.0 - drag).max(0.0);
+        self.vx *= remaining;
+        self.vy *= remaining;
+    }
+
+    /// Moves this body back if its center went into an obstacle tile since it was at `old_position`.
+    ///
+    /// It keeps the part of its movement along the obstacle, so it slides along walls instead of sticking to them. A
+    /// body that already was on an obstacle, e.g. because one was placed under it, can move freely to get off it.
+    pub fn stay_off_obstacles(&mut self, old_position: BoardPreciseCoordinate, terrain: &Terrain) {
+        let (old_px, old_py) = old_position.unpack();
+        let is_blocked = |x: f64, y: f64| {
+            let x = check_center_x(x.floor() as usize, terrain.get_width());
+            let y = check_center_y(y.floor() as usize, terrain.get_height());
+
+            terrain.get_tile_at((x, y)).is_obstacle()
+        };
+
+        if !is_blocked(self.px, self.py) || is_blocked(old_px, old_py) {
+            return;
+        }
+
+        if !is_blocked(self.px, old_py) {
+            self.py = old_py;
+            self.vy = 0.0;
+        } else if !is_blocked(old_px, self.py) {
+            self.px = old_px;
+            self.vx = 0.0;
+        } else {
+            self.px = old_px;
+            self.py = old_py;
+            self.vx = 0.0;
+            self.vy = 0.0;
+        }
+    }
+
+    /// Pushes this body back to the side of a vertical wall at `wall_x` it was on, `was_left` tells which side that is.
+    pub fn stay_beside_wall(&mut self, wall_x: f64, was_left: bool) {
+        let radius = self.get_radius();
+
+        if was_left && self.px + radius > wall_x {
+            self.px = wall_x - radius;
+            self.vx = self.vx.min(0.0);
+        } else if !was_left && self.px - radius < wall_x {
+            self.px = wall_x + radius;
+            self.vx = self.vx.max(0.0);
+        }
+    }
+
     pub fn moved_between_tiles(&self) -> bool {
         return self.prev_sbip_max_x != self.sbip_max_x
             || self.prev_sbip_max_y != self.sbip_max_y
@@ -289,8 +570,8 @@ impl Rock {
         let mut choice_x = 0.0;
         let mut choice_y = 0.0;
         while distance(self.px, self.py, choice_x, choice_y) > radius {
-            choice_x = rand::random::<f64>() * 2.0 * radius - radius + self.px;
-            choice_y = rand::random::<f64>() * 2.0 * radius - radius + self.py;
+            choice_x = rng::random::<f64>() * 2.0 * radius - radius + self.px;
+            choice_y = rng::random::<f64>() * 2.0 * radius - radius + self.py;
         }
 
         let choice_x = check_center_x(choice_x.floor() as usize, board_size.0);
@@ -395,6 +676,26 @@ impl Rock {
     pub fn set_mouth_hue(&mut self, value: f64) {
         self.mouth_hue = value.min(1.0).max(0.0);
     }
+
+    pub fn set_swimming(&mut self, value: f64) {
+        self.swimming = value.min(1.0).max(0.0);
+    }
+
+    pub fn set_mouth_specialization(&mut self, kind: ResourceKind) {
+        self.mouth_specialization = kind;
+    }
+
+    pub fn set_lifespan(&mut self, lifespan: f64) {
+        self.lifespan = lifespan.max(0.0);
+    }
+
+    pub fn set_diet(&mut self, diet: f64) {
+        self.diet = diet.min(1.0).max(0.0);
+    }
+
+    pub fn set_body_size(&mut self, body_size: f64) {
+        self.body_size = body_size.max(MIN_BODY_SIZE);
+    }
 }
 
 // Here are all the functions to simply get a property.
@@ -423,11 +724,229 @@ impl Rock {
         return self.mouth_hue;
     }
 
+    pub fn get_swimming(&self) -> f64 {
+        return self.swimming;
+    }
+
+    pub fn get_mouth_specialization(&self) -> ResourceKind {
+        return self.mouth_specialization;
+    }
+
+    /// Returns the most years this body can live, it dies of old age when it gets older than this.
+    pub fn get_lifespan(&self) -> f64 {
+        self.lifespan
+    }
+
+    /// Returns how far this body has declined at `time`, from 0 until `SENESCENCE_ONSET` of its lifespan to 1 at the
+    /// end of it. A body that declines loses extra energy, see `SoftBody::metabolize`.
+    pub fn get_senescence(&self, time: f64) -> f64 {
+        if self.lifespan <= 0.0 {
+            return 1.0;
+        }
+
+        let life = self.get_age(time) / self.lifespan;
+        ((life - SENESCENCE_ONSET) / (1.0 - SENESCENCE_ONSET))
+            .max(0.0)
+            .min(1.0)
+    }
+
+    /// Returns true if this body is older than its lifespan at `time`.
+    pub fn is_past_lifespan(&self, time: f64) -> bool {
+        self.get_age(time) >= self.lifespan
+    }
+
+    /// Returns the fraction of the energy in food of `kind` this body gets out of it. It's the efficiency of its diet
+    /// for plants and fruit or for carrion, halved by `UNSPECIALIZED_MOUTH_EFFICIENCY` unless its mouth is specialized
+    /// in that food.
+    pub fn get_food_efficiency(&self, kind: ResourceKind) -> f64 {
+        let diet_efficiency = match kind {
+            ResourceKind::Carrion => self.get_meat_efficiency(),
+            _ => self.get_plant_efficiency(),
+        };
+
+        if kind == self.mouth_specialization {
+            diet_efficiency
+        } else {
+            diet_efficiency * UNSPECIALIZED_MOUTH_EFFICIENCY
+        }
+    }
+
+    /// Returns how big this body can grow, 1 for a body of the default size.
+    pub fn get_body_size(&self) -> f64 {
+        self.body_size
+    }
+
+    /// Returns the most energy this body can store, it eats less and less as it gets close to it. See
+    /// `get_appetite`.
+    pub fn get_energy_capacity(&self) -> f64 {
+        BODY_SIZE_ENERGY_CAPACITY * self.body_size
+    }
+
+    /// Returns how much of the food it tries to eat this body actually eats, from 1 when it's starving to 0 when it
+    /// stores as much energy as it can.
+    pub fn get_appetite(&self) -> f64 {
+        (1.0 - (self.energy / self.get_energy_capacity()).max(0.0).powi(2)).max(0.0)
+    }
+
+    /// Returns where this body is between a plant eater (0) and a meat eater (1).
+    pub fn get_diet(&self) -> f64 {
+        self.diet
+    }
+
+    /// Returns the fraction of the energy in plants and fruit this body's diet lets it digest, 1 for a pure plant
+    /// eater down to `DIET_MIN_EFFICIENCY` for a pure meat eater.
+    pub fn get_plant_efficiency(&self) -> f64 {
+        1.0 - (1.0 - DIET_MIN_EFFICIENCY) * self.diet
+    }
+
+    /// Returns the fraction of the energy in meat this body's diet lets it digest, both from carrion and from biting
+    /// other creatures. The opposite of `get_plant_efficiency`.
+    pub fn get_meat_efficiency(&self) -> f64 {
+        1.0 - (1.0 - DIET_MIN_EFFICIENCY) * (1.0 - self.diet)
+    }
+
     /// Returns the time when this creature was born.
     pub fn get_birth_time(&self) -> f64 {
         return self.birth_time;
     }
 
+    pub(crate) fn set_birth_time(&mut self, time: f64) {
+        self.birth_time = time;
+    }
+
+    /// Returns true if this body was bitten at the given time, used to find out who died by predation.
+    pub fn was_bitten_at(&self, time: f64) -> bool {
+        self.last_bitten_time == Some(time)
+    }
+
+    /// Feeds everything that influences the simulation to `hasher`, see `Board::state_hash`.
+    ///
+    /// The bookkeeping for `SoftBodiesInPositions` is left out since it's rebuilt when loading.
+    pub fn write_state(&self, hasher: &mut crate::hash::StateHasher) {
+        for v in &[
+            self.px,
+            self.py,
+            self.rotation,
+            self.vx,
+            self.vy,
+            self.vr,
+            self.energy,
+            self.density,
+            self.prev_energy,
+            self.birth_time,
+            self.last_bitten_time.unwrap_or(std::f64::NAN),
+            self.mouth_hue,
+            self.swimming,
+            self.lifespan,
+            self.diet,
+            self.body_size,
+        ] {
+            hasher.write_f64(*v);
+        }
+
+        hasher.write_f64(self.energy_from_plants);
+        hasher.write_f64(self.energy_from_prey);
+        hasher.write_usize(self.offspring);
+        hasher.write_usize(self.mouth_specialization as usize);
+        hasher.write_f64(self.distance_traveled);
+    }
+
+    /// Feeds the genes of this body to `hasher`, see `Creature::get_genome_fingerprint`.
+    ///
+    /// The mouth hue is left out, brains change it all the time.
+    pub fn write_genes(&self, hasher: &mut crate::hash::StateHasher) {
+        for v in &[self.swimming, self.lifespan, self.diet, self.body_size] {
+            hasher.write_f64(*v);
+        }
+        hasher.write_usize(self.mouth_specialization as usize);
+    }
+
+    /// Returns how different the genes of this body and `other` are, every gene adds at most about 1.
+    pub fn get_gene_distance(&self, other: &Rock) -> f64 {
+        let mouth = if self.mouth_specialization == other.mouth_specialization {
+            0.0
+        } else {
+            1.0
+        };
+
+        (self.swimming - other.swimming).abs()
+            + (self.lifespan - other.lifespan).abs() / DEFAULT_LIFESPAN
+            + (self.diet - other.diet).abs()
+            + (self.body_size - other.body_size).abs() / DEFAULT_BODY_SIZE
+            + mouth
+    }
+
+    /// Returns the `Compartment` this body belongs to in the energy flows.
+    ///
+    /// Bodies that got more energy from biting others than from eating food count as predators.
+    pub fn get_compartment(&self) -> Compartment {
+        if self.energy_from_prey > self.energy_from_plants {
+            Compartment::Predators
+        } else {
+            Compartment::Herbivores
+        }
+    }
+
+    /// Records that `energy` was given to a baby in `baby_compartment`.
+    pub fn give_to_offspring(&mut self, energy: f64, baby_compartment: Compartment) {
+        self.lose_energy(energy);
+        let compartment = self.get_compartment();
+        self.energy_ledger
+            .flows
+            .add(compartment, baby_compartment, energy);
+        self.energy_ledger.lost += energy;
+    }
+
+    /// Returns the energy flows of this body during the current update and starts a new ledger.
+    ///
+    /// Whatever change in energy since `record_energy` can't be explained by the ledger is counted as lost to `Decay`,
+    /// this includes metabolism, moving around, swimming, fighting and poison. Call this once per update.
+    pub fn take_energy_flows(&mut self) -> crate::energy_flow::EnergyFlows {
+        let ledger = std::mem::replace(&mut self.energy_ledger, EnergyLedger::default());
+        let mut flows = ledger.flows;
+
+        let unexplained = self.prev_energy - self.energy + ledger.gained - ledger.lost;
+        flows.add(self.get_compartment(), Compartment::Decay, unexplained);
+
+        flows
+    }
+
+    /// Returns the energy this body got from eating food during the current update.
+    pub fn get_energy_eaten(&self) -> f64 {
+        self.energy_ledger
+            .flows
+            .get(Compartment::Tiles, Compartment::Herbivores)
+            + self
+                .energy_ledger
+                .flows
+                .get(Compartment::Tiles, Compartment::Predators)
+    }
+
+    /// Asks to give birth at the end of this update, see `HLSoftBody::try_reproduce`.
+    pub fn request_birth(&mut self) {
+        self.birth_requested = true;
+    }
+
+    /// Returns whether a birth was requested since the last call and forgets about the request.
+    pub fn take_birth_request(&mut self) -> bool {
+        std::mem::replace(&mut self.birth_requested, false)
+    }
+
+    /// Counts one more child for this body, called for every parent of a baby.
+    pub fn add_offspring(&mut self) {
+        self.offspring += 1;
+    }
+
+    /// Returns the amount of children this body helped bring into the world.
+    pub fn get_offspring_count(&self) -> usize {
+        self.offspring
+    }
+
+    /// Returns the distance (in tiles) this body moved since it was born.
+    pub fn get_distance_traveled(&self) -> f64 {
+        self.distance_traveled
+    }
+
     /// Returns the age of this creature.
     ///
     /// More concretely: this function is equivalent to `time - self.get_birth_time()`.
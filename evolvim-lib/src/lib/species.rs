@@ -0,0 +1,208 @@
+//! Dividing the living population into species by how different their genes are.
+//!
+//! Every species has a representative, a creature that belongs to it. Every now and then (see `SpeciesConfig`) the
+//! `SpeciesTracker` of a `Board` puts every creature in the first species whose representative is close enough to it,
+//! see `Creature::get_genetic_distance`, trying the species it was in before first. Creatures that are too different
+//! from all of them found a new species. Babies belong to the species of their first parent until then.
+//!
+//! Species keep their id and color for as long as they have members, an id is never used again after a species died
+//! out. The amount of creatures of every species is part of `PopulationSample` and `BoardStats`.
+
+use crate::brain::NeuralNet;
+use crate::constants::*;
+use crate::softbody::HLSoftBody;
+use std::collections::BTreeMap;
+
+/// Spreads the hues of species that are next to each other far apart.
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618_033_988_749_895;
+
+/// How different creatures can be and still belong to the same species, part of a `SimConfig`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpeciesConfig {
+    /// The largest genetic distance to the representative of a species for its members.
+    pub threshold: f64,
+    /// The amount of updates between two times the population is divided into species, it's cheap enough for every
+    /// update on small boards.
+    pub interval: usize,
+}
+
+impl Default for SpeciesConfig {
+    fn default() -> Self {
+        SpeciesConfig {
+            threshold: DEFAULT_SPECIES_THRESHOLD,
+            interval: DEFAULT_SPECIES_INTERVAL,
+        }
+    }
+}
+
+/// A group of creatures with about the same genes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Species {
+    pub id: usize,
+    /// The id of the creature the others are compared to.
+    pub representative: usize,
+    /// The time the species was found.
+    pub founded: f64,
+    /// The amount of living creatures that belong to it.
+    pub size: usize,
+}
+
+impl Species {
+    /// Returns the HSBA color of this species, see `get_species_color`.
+    pub fn get_color(&self) -> [f32; 4] {
+        get_species_color(self.id)
+    }
+}
+
+/// Returns the HSBA color of the species with `id`, species found after each other get very different hues.
+pub fn get_species_color(id: usize) -> [f32; 4] {
+    let hue = (id as f64 * GOLDEN_RATIO_CONJUGATE) % 1.0;
+
+    [hue as f32, 0.8, 0.9, 1.0]
+}
+
+/// The species of the living creatures of a `Board`, see the module documentation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesTracker {
+    /// The species with at least one member, in order of their id.
+    species: Vec<Species>,
+    /// The species of every creature that's been divided into one, by creature id.
+    members: BTreeMap<usize, usize>,
+    next_id: usize,
+    ticks: usize,
+}
+
+impl SpeciesTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Divides `creatures` into species if it's time for it according to `config`, `Board::update` calls this.
+    ///
+    /// Returns true if they were divided.
+    pub fn tick<B: NeuralNet>(
+        &mut self,
+        creatures: &[HLSoftBody<B>],
+        time: f64,
+        config: &SpeciesConfig,
+    ) -> bool {
+        let due = self.ticks % config.interval.max(1) == 0;
+        self.ticks += 1;
+        if due {
+            self.divide(creatures, time, config.threshold);
+        }
+
+        due
+    }
+
+    /// Puts every creature in `creatures` in a species right away, creatures that aren't in it are forgotten.
+    pub fn divide<B: NeuralNet>(&mut self, creatures: &[HLSoftBody<B>], time: f64, threshold: f64) {
+        let previous = std::mem::replace(&mut self.members, BTreeMap::new());
+
+        // The old representative stays if it's alive, otherwise the oldest member takes over.
+        let mut representatives: Vec<(Species, HLSoftBody<B>)> = Vec::new();
+        for mut species in std::mem::replace(&mut self.species, Vec::new()) {
+            let representative = creatures
+                .iter()
+                .find(|c| c.borrow().get_id() == species.representative)
+                .or_else(|| {
+                    creatures
+                        .iter()
+                        .find(|c| previous.get(&c.borrow().get_id()) == Some(&species.id))
+                });
+
+            if let Some(representative) = representative {
+                species.representative = representative.borrow().get_id();
+                species.size = 0;
+                representatives.push((species, representative.clone()));
+            }
+        }
+
+        for c in creatures {
+            let creature = c.borrow();
+            let id = creature.get_id();
+            let close_enough = |(_, r): &(Species, HLSoftBody<B>)| {
+                creature.get_genetic_distance(&r.borrow()) <= threshold
+            };
+
+            // Trying the old species first keeps creatures from hopping between species that overlap.
+            let old = previous
+                .get(&id)
+                .and_then(|&old| representatives.iter().position(|(s, _)| s.id == old))
+                .filter(|&index| close_enough(&representatives[index]));
+            let index = match old.or_else(|| representatives.iter().position(&close_enough)) {
+                Some(index) => index,
+                None => {
+                    let species = Species {
+                        id: self.next_id,
+                        representative: id,
+                        founded: time,
+                        size: 0,
+                    };
+                    self.next_id += 1;
+                    representatives.push((species, c.clone()));
+                    representatives.len() - 1
+                }
+            };
+
+            let species = &mut representatives[index].0;
+            species.size += 1;
+            self.members.insert(id, species.id);
+        }
+
+        self.species = representatives
+            .into_iter()
+            .map(|(species, _)| species)
+            .filter(|species| species.size > 0)
+            .collect();
+    }
+
+    /// Puts the baby with id `baby` in the species of `parent`, if it has one.
+    pub(crate) fn add_baby(&mut self, baby: usize, parent: usize) {
+        if let Some(&species) = self.members.get(&parent) {
+            self.members.insert(baby, species);
+            if let Some(s) = self.find_mut(species) {
+                s.size += 1;
+            }
+        }
+    }
+
+    /// Takes the creature with `id` out of its species, the species dies out if it was the last member.
+    pub(crate) fn remove_creature(&mut self, id: usize) {
+        if let Some(species) = self.members.remove(&id) {
+            if let Some(s) = self.find_mut(species) {
+                s.size -= 1;
+            }
+            self.species.retain(|s| s.size > 0);
+        }
+    }
+
+    /// Returns the species with at least one member, in order of their id.
+    pub fn get_species(&self) -> &[Species] {
+        &self.species
+    }
+
+    pub fn get_species_by_id(&self, id: usize) -> Option<&Species> {
+        self.species.iter().find(|s| s.id == id)
+    }
+
+    /// Returns the id of the species of the creature with `id`, `None` if it wasn't put in one yet.
+    pub fn get_species_of(&self, id: usize) -> Option<usize> {
+        self.members.get(&id).cloned()
+    }
+
+    /// Returns the amount of species that ever existed.
+    pub fn get_species_founded(&self) -> usize {
+        self.next_id
+    }
+
+    /// Returns the size of every species by their id.
+    pub fn get_sizes(&self) -> BTreeMap<usize, usize> {
+        self.species.iter().map(|s| (s.id, s.size)).collect()
+    }
+
+    fn find_mut(&mut self, id: usize) -> Option<&mut Species> {
+        self.species.iter_mut().find(|s| s.id == id)
+    }
+}
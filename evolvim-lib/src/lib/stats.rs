@@ -0,0 +1,84 @@
+//! A summary of the state of a `Board` in one call.
+//!
+//! `Board::stats` goes over the creatures once and only borrows each of them briefly, so it's cheap enough to call
+//! every frame, e.g. for a status bar or a log.
+
+use crate::brain::NeuralNet;
+use crate::Board;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BoardStats {
+    pub time: f64,
+    pub population: usize,
+    /// The amount of eggs waiting to hatch, see `Board::get_eggs`.
+    pub eggs: usize,
+    pub mean_energy: f64,
+    pub max_energy: f64,
+    pub mean_age: f64,
+    /// The mean of the lifespans of all creatures, see `Rock::get_lifespan`.
+    pub mean_lifespan: f64,
+    /// The mean of the diets of all creatures, 0 if they all eat plants and 1 if they all eat meat. See
+    /// `Rock::get_diet`.
+    pub mean_diet: f64,
+    /// The mean of the body sizes of all creatures, see `Rock::get_body_size`.
+    pub mean_body_size: f64,
+    /// The amount of species the creatures are divided into, see `Board::get_species`.
+    pub species: usize,
+    /// The id of the species with the most members, `None` if there are no species.
+    pub largest_species: Option<usize>,
+    /// The id of the oldest creature, `None` if there are no creatures.
+    pub oldest_creature: Option<usize>,
+    /// The food lying on and stored in all tiles, see `Board::get_total_food`.
+    pub total_food: f64,
+    pub temperature: f64,
+}
+
+impl BoardStats {
+    /// Returns the statistics of `board` right now, see `Board::stats`.
+    pub(crate) fn new<B: NeuralNet>(board: &Board<B>) -> Self {
+        let time = board.get_time();
+        let mut stats = BoardStats {
+            time,
+            population: board.creatures.len(),
+            eggs: board.get_eggs().len(),
+            total_food: board.get_total_food(),
+            temperature: board.climate.get_temperature(),
+            species: board.get_species().get_species().len(),
+            largest_species: board
+                .get_species()
+                .get_species()
+                .iter()
+                .max_by_key(|s| s.size)
+                .map(|s| s.id),
+            ..BoardStats::default()
+        };
+        let mut oldest_age = std::f64::NEG_INFINITY;
+
+        for c in &board.creatures {
+            let c = c.borrow();
+            let (energy, age) = (c.get_energy(), c.get_age(time));
+
+            stats.mean_energy += energy;
+            stats.max_energy = stats.max_energy.max(energy);
+            stats.mean_age += age;
+            stats.mean_lifespan += c.get_lifespan();
+            stats.mean_diet += c.get_diet();
+            stats.mean_body_size += c.get_body_size();
+            if age > oldest_age {
+                oldest_age = age;
+                stats.oldest_creature = Some(c.get_id());
+            }
+        }
+
+        if stats.population > 0 {
+            let n = stats.population as f64;
+            stats.mean_energy /= n;
+            stats.mean_age /= n;
+            stats.mean_lifespan /= n;
+            stats.mean_diet /= n;
+            stats.mean_body_size /= n;
+        }
+
+        stats
+    }
+}
@@ -0,0 +1,77 @@
+//! The kinds of land a tile can be.
+//!
+//! Every land tile gets a `Biome` when the terrain is made, decided by its fertility and how far it is from the middle
+//! of the board: the rows near the top and bottom edge are the cold poles. The biome changes how fast food grows and
+//! what the bare ground looks like, and brains can sense it (see `Environment::get_biome`).
+
+use crate::constants::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Biome {
+    /// Dry land where little grows.
+    Desert,
+    Grassland,
+    /// Fertile land where food grows fast.
+    Forest,
+    /// The wettest land, next to the water.
+    Swamp,
+    /// The cold land near the poles.
+    Tundra,
+}
+
+impl Default for Biome {
+    fn default() -> Self {
+        Biome::Grassland
+    }
+}
+
+impl Biome {
+    /// Returns the biome of land with the given `fertility`, `latitude` goes from 0 in the middle of the board to 1 at
+    /// the top and bottom edge.
+    pub fn classify(fertility: f64, latitude: f64) -> Self {
+        if latitude > BIOME_TUNDRA_LATITUDE {
+            Biome::Tundra
+        } else if fertility < BIOME_DESERT_FERTILITY {
+            Biome::Desert
+        } else if fertility > BIOME_SWAMP_FERTILITY {
+            Biome::Swamp
+        } else if fertility > BIOME_FOREST_FERTILITY {
+            Biome::Forest
+        } else {
+            Biome::Grassland
+        }
+    }
+
+    /// Returns how fast food grows here compared to grassland.
+    pub fn get_growth_multiplier(self) -> f64 {
+        match self {
+            Biome::Desert => 0.5,
+            Biome::Grassland => 1.0,
+            Biome::Forest => 1.25,
+            Biome::Swamp => 0.9,
+            Biome::Tundra => 0.6,
+        }
+    }
+
+    /// Returns the color of the bare ground as hsb, see `Tile::get_hsba_color`.
+    pub fn get_ground_color(self) -> [f32; 3] {
+        match self {
+            Biome::Desert => COLOR_DESERT,
+            Biome::Grassland => COLOR_BARREN,
+            Biome::Forest => COLOR_FOREST,
+            Biome::Swamp => COLOR_SWAMP,
+            Biome::Tundra => COLOR_TUNDRA,
+        }
+    }
+
+    /// Returns a different number between 0 and 1 for every biome, for brains to sense. Water senses as 0.
+    pub fn get_sensor_value(self) -> f64 {
+        match self {
+            Biome::Desert => 0.2,
+            Biome::Grassland => 0.4,
+            Biome::Forest => 0.6,
+            Biome::Swamp => 0.8,
+            Biome::Tundra => 1.0,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! The tiles of a `Terrain` are stored in square chunks of `TERRAIN_CHUNK_SIZE` by `TERRAIN_CHUNK_SIZE` tiles.
+//!
+//! On a big board updating every tile at once takes long, so `Terrain::update_all_lazily` only remembers the time and
+//! every chunk catches up the first time it's used afterwards (see `Terrain::catch_up_at`). A chunk also remembers if
+//! any of its tiles changed, so a renderer that caches the terrain only has to redraw the dirty chunks. Renderers that
+//! work tile by tile can ask for the changed tiles themselves with `Terrain::take_dirty_tiles`.
+
+use super::tile::Tile;
+use crate::board::{BoardCoordinate, BoardSize};
+use crate::climate::Climate;
+use crate::constants::TERRAIN_CHUNK_SIZE;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Chunk {
+    /// The position of the top left tile of this chunk on the board.
+    origin: BoardCoordinate,
+    size: BoardSize,
+    /// The tiles column by column, just like the board.
+    tiles: Vec<Tile>,
+    /// How many of the pending updates of the terrain this chunk already did.
+    caught_up: usize,
+    dirty: bool,
+    /// Which tiles changed while the chunk is dirty, empty if all of them did.
+    #[serde(skip)]
+    dirty_tiles: Vec<bool>,
+}
+
+impl Chunk {
+    /// Creates the chunk with `origin` from `columns`, which are the columns of the whole board.
+    ///
+    /// The tiles of the chunk are taken out of `columns` and replaced by water.
+    pub(crate) fn from_columns(origin: BoardCoordinate, columns: &mut [Vec<Tile>]) -> Self {
+        let (x0, y0) = origin;
+        let width = (columns.len() - x0).min(TERRAIN_CHUNK_SIZE);
+        let height = (columns[0].len() - y0).min(TERRAIN_CHUNK_SIZE);
+
+        let mut tiles = Vec::with_capacity(width * height);
+        for column in &mut columns[x0..x0 + width] {
+            for tile in &mut column[y0..y0 + height] {
+                tiles.push(std::mem::replace(tile, Tile::Water(0.0)));
+            }
+        }
+
+        Chunk {
+            origin,
+            size: (width, height),
+            tiles,
+            caught_up: 0,
+            dirty: true,
+            dirty_tiles: Vec::new(),
+        }
+    }
+
+    /// Moves the tiles of this chunk back into `columns`, the opposite of `from_columns`.
+    pub(crate) fn into_columns(self, columns: &mut [Vec<Tile>]) {
+        let (x0, y0) = self.origin;
+        let height = self.size.1;
+
+        for (i, tile) in self.tiles.into_iter().enumerate() {
+            columns[x0 + i / height][y0 + i % height] = tile;
+        }
+    }
+
+    pub fn get_origin(&self) -> BoardCoordinate {
+        self.origin
+    }
+
+    pub fn get_size(&self) -> BoardSize {
+        self.size
+    }
+
+    /// Returns true if a tile of this chunk might have changed since `Terrain::clear_dirty_flags` was last called.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns all tiles of this chunk together with their position on the board.
+    pub fn iter(&self) -> impl Iterator<Item = (BoardCoordinate, &Tile)> {
+        let (x0, y0) = self.origin;
+        let height = self.size.1;
+
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(move |(i, tile)| ((x0 + i / height, y0 + i % height), tile))
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (BoardCoordinate, &mut Tile)> {
+        let (x0, y0) = self.origin;
+        let height = self.size.1;
+        self.mark_all_dirty();
+
+        self.tiles
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, tile)| ((x0 + i / height, y0 + i % height), tile))
+    }
+
+    pub(crate) fn get_tile(&self, pos: BoardCoordinate) -> &Tile {
+        &self.tiles[self.get_index(pos)]
+    }
+
+    pub(crate) fn get_tile_mut(&mut self, pos: BoardCoordinate) -> &mut Tile {
+        let index = self.get_index(pos);
+        self.dirty = true;
+        if let Some(dirty) = self.dirty_tiles.get_mut(index) {
+            *dirty = true;
+        }
+
+        &mut self.tiles[index]
+    }
+
+    pub(crate) fn update_all(&mut self, time: f64, climate: &Climate) {
+        self.mark_all_dirty();
+        for tile in &mut self.tiles {
+            tile.update(time, climate);
+        }
+    }
+
+    /// Does the updates in `pending` this chunk didn't do yet.
+    pub(crate) fn catch_up(&mut self, pending: &[f64], climate: &Climate) {
+        if self.caught_up < pending.len() {
+            for &time in &pending[self.caught_up..] {
+                self.update_all(time, climate);
+            }
+            self.caught_up = pending.len();
+        }
+    }
+
+    pub(crate) fn get_caught_up(&self) -> usize {
+        self.caught_up
+    }
+
+    pub(crate) fn set_caught_up(&mut self, caught_up: usize) {
+        self.caught_up = caught_up;
+    }
+
+    pub(crate) fn clear_dirty_flag(&mut self) {
+        self.dirty = false;
+        self.dirty_tiles = vec![false; self.tiles.len()];
+    }
+
+    /// Returns the positions of the tiles that changed since the dirty flag was last cleared and clears it.
+    pub(crate) fn take_dirty_tiles(&mut self) -> Vec<BoardCoordinate> {
+        let dirty: Vec<BoardCoordinate> = if !self.dirty {
+            Vec::new()
+        } else if self.dirty_tiles.is_empty() {
+            self.iter().map(|(pos, _)| pos).collect()
+        } else {
+            self.iter()
+                .zip(&self.dirty_tiles)
+                .filter(|(_, &dirty)| dirty)
+                .map(|((pos, _), _)| pos)
+                .collect()
+        };
+        self.clear_dirty_flag();
+
+        dirty
+    }
+
+    pub(crate) fn get_tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.dirty = true;
+        self.dirty_tiles.clear();
+    }
+
+    /// Returns the index in `tiles` of the tile at `pos` on the board.
+    fn get_index(&self, pos: BoardCoordinate) -> usize {
+        (pos.0 - self.origin.0) * self.size.1 + (pos.1 - self.origin.1)
+    }
+}
@@ -0,0 +1,277 @@
+//! Tools for changing the terrain by hand, with undo and redo.
+//!
+//! Every change goes through `EditHistory` so it can be taken back later. A script that doesn't need undo can use
+//! `Terrain::raise_fertility` and `Terrain::flood` instead, e.g. to turn part of the world into a desert mid-run.
+//! Changes that should only last a while, like the ones a `Disaster` makes, go through `Terrain::scar`.
+
+use super::tile::Tile;
+use super::{get_latitude, Biome, Terrain};
+use crate::board::{BoardCoordinate, BoardSize};
+use crate::constants::TERRAIN_UNDO_CAPACITY;
+use std::collections::VecDeque;
+
+/// A circular brush used to edit many tiles at once.
+#[derive(Clone, Debug)]
+pub struct Brush {
+    /// The radius of the brush in tiles, a radius of 0 only covers a single tile.
+    pub radius: f64,
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Brush { radius: 2.0 }
+    }
+}
+
+impl Brush {
+    /// Returns all tiles this brush covers when centered on `center`.
+    ///
+    /// Use this for previews as well as for painting, that way the preview always matches the result.
+    pub fn get_footprint(
+        &self,
+        center: BoardCoordinate,
+        board_size: BoardSize,
+    ) -> Vec<BoardCoordinate> {
+        let reach = self.radius.max(0.0).floor() as usize;
+        let (cx, cy) = center;
+        let min_x = cx.saturating_sub(reach);
+        let min_y = cy.saturating_sub(reach);
+        let max_x = (cx + reach).min(board_size.0 - 1);
+        let max_y = (cy + reach).min(board_size.1 - 1);
+
+        let mut footprint = Vec::new();
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                let dx = x as f64 - cx as f64;
+                let dy = y as f64 - cy as f64;
+
+                if (dx * dx + dy * dy).sqrt() <= self.radius {
+                    footprint.push((x, y));
+                }
+            }
+        }
+
+        footprint
+    }
+}
+
+impl Terrain {
+    /// Adds `amount` to the fertility of every land tile within `radius` tiles of `pos`, a negative `amount` makes it
+    /// less fertile.
+    ///
+    /// The fertility stays between 0 and 1 and the biomes change along with it.
+    pub fn raise_fertility(&mut self, pos: BoardCoordinate, radius: f64, amount: f64) {
+        let height = self.get_height() as f64;
+
+        for pos in self.get_footprint(pos, radius) {
+            let tile = self.get_tile_at_mut(pos);
+            let fertility = tile.get_fertility() + amount;
+            tile.set_fertility(fertility);
+
+            let biome = Biome::classify(tile.get_fertility(), get_latitude(pos.1, height));
+            tile.set_biome(biome);
+        }
+    }
+
+    /// Turns every tile within `radius` tiles of `pos` into water, which is deepest at `pos` and shallow at the edge.
+    pub fn flood(&mut self, pos: BoardCoordinate, radius: f64) {
+        let (cx, cy) = (pos.0 as f64, pos.1 as f64);
+
+        for tile_pos in self.get_footprint(pos, radius) {
+            let distance =
+                ((tile_pos.0 as f64 - cx).powi(2) + (tile_pos.1 as f64 - cy).powi(2)).sqrt();
+            let depth = if radius > 0.0 {
+                1.0 - distance / radius
+            } else {
+                1.0
+            };
+            self.set_tile(tile_pos, Tile::new_water(depth));
+        }
+    }
+
+    /// Sets all given tiles until `until`, after that `heal_scars` brings back the tiles that were there before.
+    ///
+    /// A tile that is still scarred from before comes back along with the newest scar.
+    pub fn scar(&mut self, changes: Vec<(BoardCoordinate, Tile)>, until: f64) {
+        if changes.is_empty() {
+            return;
+        }
+
+        let mut tiles = Vec::with_capacity(changes.len());
+        for (pos, tile) in changes {
+            let old = self.set_tile(pos, tile);
+            let original = self.scars.iter_mut().find_map(|s| s.take(pos));
+            tiles.push((pos, original.unwrap_or(old)));
+        }
+
+        self.scars.retain(|s| !s.tiles.is_empty());
+        self.scars.push(Scar { until, tiles });
+    }
+
+    /// Brings back the tiles of every scar that lasted until `time`, returns true if any tile came back.
+    pub fn heal_scars(&mut self, time: f64) -> bool {
+        let (healed, scars): (Vec<Scar>, Vec<Scar>) =
+            std::mem::replace(&mut self.scars, Vec::new())
+                .into_iter()
+                .partition(|s| s.until <= time);
+        self.scars = scars;
+
+        let any = !healed.is_empty();
+        for scar in healed {
+            for (pos, tile) in scar.tiles {
+                self.set_tile(pos, tile);
+            }
+        }
+
+        any
+    }
+
+    /// Returns the positions of all tiles that are scarred right now, see `scar`.
+    pub fn get_scarred_tiles(&self) -> Vec<BoardCoordinate> {
+        self.scars
+            .iter()
+            .flat_map(|s| s.tiles.iter().map(|&(pos, _)| pos))
+            .collect()
+    }
+
+    fn get_footprint(&self, center: BoardCoordinate, radius: f64) -> Vec<BoardCoordinate> {
+        let board_size = (self.get_width(), self.get_height());
+
+        Brush { radius }.get_footprint(center, board_size)
+    }
+}
+
+/// A single change to the terrain, it remembers the tiles it overwrote.
+struct TerrainEdit {
+    tiles: Vec<(BoardCoordinate, Tile)>,
+}
+
+impl TerrainEdit {
+    /// Puts all stored tiles into `terrain`, returns the edit that reverses this.
+    fn swap_into(self, terrain: &mut Terrain) -> TerrainEdit {
+        let tiles = self
+            .tiles
+            .into_iter()
+            .rev()
+            .map(|(pos, tile)| (pos, terrain.set_tile(pos, tile)))
+            .collect();
+
+        TerrainEdit { tiles }
+    }
+}
+
+/// Tiles that were changed for a while, it remembers the tiles it overwrote until they come back at `until`.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Scar {
+    until: f64,
+    tiles: Vec<(BoardCoordinate, Tile)>,
+}
+
+impl Scar {
+    /// Takes the tile this scar overwrote at `pos` out of it, so it won't bring it back.
+    fn take(&mut self, pos: BoardCoordinate) -> Option<Tile> {
+        let i = self.tiles.iter().position(|&(p, _)| p == pos)?;
+
+        Some(self.tiles.swap_remove(i).1)
+    }
+}
+
+/// Keeps track of changes to the terrain so they can be undone and redone.
+pub struct EditHistory {
+    undo: VecDeque<TerrainEdit>,
+    redo: Vec<TerrainEdit>,
+    capacity: usize,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        EditHistory::with_capacity(TERRAIN_UNDO_CAPACITY)
+    }
+}
+
+impl EditHistory {
+    /// Creates an empty history which remembers at most `capacity` edits.
+    pub fn with_capacity(capacity: usize) -> Self {
+        EditHistory {
+            undo: VecDeque::with_capacity(capacity),
+            redo: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Sets all given tiles as a single edit which can be undone.
+    pub fn apply(&mut self, terrain: &mut Terrain, changes: Vec<(BoardCoordinate, Tile)>) {
+        if changes.is_empty() {
+            return;
+        }
+
+        let reverse = TerrainEdit { tiles: changes }.swap_into(terrain);
+        self.push_undo(reverse);
+        self.redo.clear();
+    }
+
+    /// Replaces every tile covered by `brush` with the result of `paint`, as a single edit.
+    pub fn paint<F>(
+        &mut self,
+        terrain: &mut Terrain,
+        brush: &Brush,
+        center: BoardCoordinate,
+        paint: F,
+    ) where
+        F: Fn(&Tile) -> Tile,
+    {
+        let board_size = (terrain.get_width(), terrain.get_height());
+        let changes = brush
+            .get_footprint(center, board_size)
+            .into_iter()
+            .map(|pos| (pos, paint(terrain.get_tile_at(pos))))
+            .collect();
+
+        self.apply(terrain, changes);
+    }
+
+    /// Takes back the last edit, returns false if there was nothing to undo.
+    pub fn undo(&mut self, terrain: &mut Terrain) -> bool {
+        match self.undo.pop_back() {
+            Some(edit) => {
+                let reverse = edit.swap_into(terrain);
+                self.redo.push(reverse);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Performs the last undone edit again, returns false if there was nothing to redo.
+    pub fn redo(&mut self, terrain: &mut Terrain) -> bool {
+        match self.redo.pop() {
+            Some(edit) => {
+                let reverse = edit.swap_into(terrain);
+                self.push_undo(reverse);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn push_undo(&mut self, edit: TerrainEdit) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.undo.len() == self.capacity {
+            // Forget the oldest edit.
+            self.undo.pop_front();
+        }
+
+        self.undo.push_back(edit);
+    }
+}
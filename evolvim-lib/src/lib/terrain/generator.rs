@@ -0,0 +1,290 @@
+//! Different ways to generate the land and water of a new `Terrain`.
+//!
+//! A generator decides the fertility of every tile, anything above 1 becomes water (see `Tile::new`). The food types
+//! and the fruiting tiles come from perlin noise for every generator, see `Terrain::from_fertility`. A `Board` uses the
+//! generator chosen by `SimConfig::terrain_generator`, write your own by implementing `TerrainGenerator`.
+
+use super::noise::{Fbm, MultiFractal, NoiseFn, Point2};
+use super::{get_noise, seeded, Terrain};
+use crate::constants::*;
+use crate::rng::{self, Stream};
+use crate::BoardSize;
+
+/// The parameters of the noise `Terrain::generate_perlin_with` makes the land and water out of.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoiseParams {
+    /// The seed of the noise, `None` takes a random one from `Stream::Terrain`.
+    pub seed: Option<u32>,
+    /// The amount of layers of noise, every next layer has twice as much detail.
+    pub octaves: usize,
+    /// How much weaker every next layer is than the one before it, between 0 and 1.
+    pub persistence: f64,
+    /// Tiles with a fertility above this become water, higher gives more land.
+    pub water_threshold: f64,
+}
+
+impl Default for NoiseParams {
+    fn default() -> Self {
+        NoiseParams {
+            seed: None,
+            octaves: DEFAULT_NOISE_OCTAVES,
+            persistence: DEFAULT_NOISE_PERSISTENCE,
+            water_threshold: DEFAULT_WATER_THRESHOLD,
+        }
+    }
+}
+
+pub trait TerrainGenerator {
+    /// Returns new terrain of `board_size`, a smaller `step_size` gives bigger features.
+    ///
+    /// Random numbers should be taken from `Stream::Terrain`.
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain;
+}
+
+/// The built-in generators, to choose one in a `SimConfig`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerrainAlgorithm {
+    /// See `Terrain::generate_perlin`.
+    Perlin,
+    /// See `FbmGenerator`.
+    Fbm,
+    /// See `DiamondSquareGenerator`.
+    DiamondSquare,
+    /// See `IslandGenerator`.
+    Island,
+    /// See `ArchipelagoGenerator`.
+    Archipelago,
+}
+
+impl Default for TerrainAlgorithm {
+    fn default() -> Self {
+        TerrainAlgorithm::Perlin
+    }
+}
+
+impl TerrainGenerator for TerrainAlgorithm {
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain {
+        match self {
+            TerrainAlgorithm::Perlin => Terrain::generate_perlin(board_size, step_size),
+            TerrainAlgorithm::Fbm => FbmGenerator::default().generate(board_size, step_size),
+            TerrainAlgorithm::DiamondSquare => {
+                DiamondSquareGenerator::default().generate(board_size, step_size)
+            }
+            TerrainAlgorithm::Island => IslandGenerator.generate(board_size, step_size),
+            TerrainAlgorithm::Archipelago => {
+                ArchipelagoGenerator::default().generate(board_size, step_size)
+            }
+        }
+    }
+}
+
+/// The same as `TerrainAlgorithm::Perlin` but with the given noise parameters.
+#[derive(Clone, Debug, Default)]
+pub struct PerlinGenerator {
+    pub params: NoiseParams,
+}
+
+impl TerrainGenerator for PerlinGenerator {
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain {
+        Terrain::generate_perlin_with(board_size, step_size, &self.params)
+    }
+}
+
+/// Fractal brownian motion: layers of perlin noise that get smaller and smaller, giving detailed coasts.
+#[derive(Clone, Debug)]
+pub struct FbmGenerator {
+    pub octaves: usize,
+}
+
+impl Default for FbmGenerator {
+    fn default() -> Self {
+        FbmGenerator {
+            octaves: DEFAULT_FBM_OCTAVES,
+        }
+    }
+}
+
+impl TerrainGenerator for FbmGenerator {
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain {
+        let fbm = seeded(Fbm::new().set_octaves(self.octaves));
+
+        Terrain::from_fertility(board_size, step_size, |x, y| {
+            get_fbm_fertility(&fbm, x, y, step_size)
+        })
+    }
+}
+
+/// The diamond-square algorithm, which makes rough and mountainous looking terrain.
+///
+/// It doesn't use the step size, the continents get smaller with a higher `roughness`.
+#[derive(Clone, Debug)]
+pub struct DiamondSquareGenerator {
+    /// How much smaller every next layer of detail is, between 0 and 1.
+    pub roughness: f64,
+}
+
+impl Default for DiamondSquareGenerator {
+    fn default() -> Self {
+        DiamondSquareGenerator {
+            roughness: DEFAULT_DIAMOND_SQUARE_ROUGHNESS,
+        }
+    }
+}
+
+impl TerrainGenerator for DiamondSquareGenerator {
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain {
+        let heights = rng::with_stream(Stream::Terrain, || {
+            diamond_square(board_size, self.roughness)
+        });
+
+        Terrain::from_fertility(board_size, step_size, |x, y| heights[x][y] * 4.0 - 1.5)
+    }
+}
+
+/// A single island in the middle of the board, surrounded by water.
+#[derive(Clone, Debug)]
+pub struct IslandGenerator;
+
+impl TerrainGenerator for IslandGenerator {
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain {
+        let (width, height) = (board_size.0 as f64, board_size.1 as f64);
+
+        generate_masked(board_size, step_size, |x, y| {
+            let dx = (x as f64 + 0.5) / width * 2.0 - 1.0;
+            let dy = (y as f64 + 0.5) / height * 2.0 - 1.0;
+
+            (dx * dx + dy * dy).sqrt()
+        })
+    }
+}
+
+/// A few islands at random places, creatures can only get from one to another by swimming.
+#[derive(Clone, Debug)]
+pub struct ArchipelagoGenerator {
+    pub islands: usize,
+}
+
+impl Default for ArchipelagoGenerator {
+    fn default() -> Self {
+        ArchipelagoGenerator {
+            islands: DEFAULT_ARCHIPELAGO_ISLANDS,
+        }
+    }
+}
+
+impl TerrainGenerator for ArchipelagoGenerator {
+    fn generate(&self, board_size: BoardSize, step_size: f64) -> Terrain {
+        let (width, height) = (board_size.0 as f64, board_size.1 as f64);
+        let radius = width.min(height) / (self.islands as f64).sqrt() / 2.0;
+        let centers: Vec<(f64, f64)> = rng::with_stream(Stream::Terrain, || {
+            (0..self.islands)
+                .map(|_| (rng::random::<f64>() * width, rng::random::<f64>() * height))
+                .collect()
+        });
+
+        generate_masked(board_size, step_size, |x, y| {
+            let (x, y) = (x as f64 + 0.5, y as f64 + 0.5);
+
+            centers
+                .iter()
+                .map(|&(cx, cy)| ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() / radius)
+                .fold(std::f64::INFINITY, f64::min)
+        })
+    }
+}
+
+fn get_fbm_fertility<N: NoiseFn<Point2<f64>>>(ng: N, x: usize, y: usize, step_size: f64) -> f64 {
+    get_noise(ng, x as f64 * step_size * 0.5, y as f64 * step_size * 0.5) * 4.0 - 1.5
+}
+
+/// Returns fBm terrain that turns into water where `get_distance` goes over 1.
+///
+/// `get_distance` returns how far a position is from the land, 0 is the middle of an island.
+fn generate_masked<F: FnMut(usize, usize) -> f64>(
+    board_size: BoardSize,
+    step_size: f64,
+    mut get_distance: F,
+) -> Terrain {
+    let fbm = seeded(Fbm::new().set_octaves(DEFAULT_FBM_OCTAVES));
+
+    Terrain::from_fertility(board_size, step_size, |x, y| {
+        let distance = get_distance(x, y);
+
+        get_fbm_fertility(&fbm, x, y, step_size) - 0.5 + 3.0 * distance * distance
+    })
+}
+
+/// Returns a height map of `board_size` made with the diamond-square algorithm, going from 0 to 1.
+fn diamond_square(board_size: BoardSize, roughness: f64) -> Vec<Vec<f64>> {
+    let (width, height) = board_size;
+    // The algorithm only works on squares of a power of two (plus one) tiles.
+    let size = width.max(height).max(1).next_power_of_two();
+    let offset = || rng::random::<f64>() - 0.5;
+
+    let mut heights = vec![vec![0.0; size + 1]; size + 1];
+    for &(x, y) in &[(0, 0), (0, size), (size, 0), (size, size)] {
+        heights[x][y] = offset();
+    }
+
+    let mut step = size;
+    let mut scale = 1.0;
+    while step > 1 {
+        let half = step / 2;
+
+        // The square step: the middle of every square.
+        for x in (half..size).step_by(step) {
+            for y in (half..size).step_by(step) {
+                let mean = (heights[x - half][y - half]
+                    + heights[x + half][y - half]
+                    + heights[x - half][y + half]
+                    + heights[x + half][y + half])
+                    / 4.0;
+                heights[x][y] = mean + offset() * scale;
+            }
+        }
+
+        // The diamond step: the middle of every edge, the edges of the map have one neighbour less.
+        for x in (0..=size).step_by(half) {
+            for y in ((x + half) % step..=size).step_by(step) {
+                let mut neighbours = Vec::with_capacity(4);
+                if x >= half {
+                    neighbours.push(heights[x - half][y]);
+                }
+                if x + half <= size {
+                    neighbours.push(heights[x + half][y]);
+                }
+                if y >= half {
+                    neighbours.push(heights[x][y - half]);
+                }
+                if y + half <= size {
+                    neighbours.push(heights[x][y + half]);
+                }
+
+                let mean = neighbours.iter().sum::<f64>() / neighbours.len() as f64;
+                heights[x][y] = mean + offset() * scale;
+            }
+        }
+
+        step = half;
+        scale *= roughness;
+    }
+
+    heights.truncate(width);
+    for column in &mut heights {
+        column.truncate(height);
+    }
+
+    let (min, max) = heights.iter().flatten().fold(
+        (std::f64::INFINITY, std::f64::NEG_INFINITY),
+        |(min, max), &h| (min.min(h), max.max(h)),
+    );
+    if max > min {
+        for h in heights.iter_mut().flatten() {
+            *h = (*h - min) / (max - min);
+        }
+    }
+
+    heights
+}
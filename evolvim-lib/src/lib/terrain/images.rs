@@ -0,0 +1,149 @@
+//! Turning terrain into images and back, e.g. to design a map in any paint program.
+//!
+//! Only available with the `images` feature. When reading an image every pixel becomes a single tile, so an image of 100
+//! by 100 pixels makes a board of 100 by 100 tiles. Use `Board::set_terrain` to play on it.
+//!
+//! Rendering uses the same colors as the graphics of `evolvim-tools` but doesn't need a window, so a server can save a
+//! picture of the board every now and then (see `BoardSnapshot::save_image`).
+
+extern crate image;
+
+use self::image::{Rgba, RgbaImage};
+use super::tile::Tile;
+use super::Terrain;
+use crate::color::hsba_to_rgba;
+use crate::error::EvolvimError;
+use crate::BoardSize;
+use std::path::Path;
+
+/// What the colors of an image mean, see `Terrain::from_image`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageMapping {
+    /// A grayscale height map: pixels darker than `sea_level` (between 0 and 1) are water, the darker the deeper.
+    ///
+    /// Low land is more fertile than high land, just like the coasts of generated terrain, and all of it grows food of
+    /// `food_type`. The brightness above the sea level becomes the elevation of the tile.
+    HeightMap { sea_level: f64, food_type: f64 },
+    /// A painted map: pixels with more blue than green are water, pure blue is the deepest. On land green is the
+    /// fertility and red the food type.
+    Painted,
+}
+
+impl ImageMapping {
+    fn get_tile(self, [red, green, blue]: [f64; 3]) -> Tile {
+        match self {
+            ImageMapping::HeightMap {
+                sea_level,
+                food_type,
+            } => {
+                let height = 0.299 * red + 0.587 * green + 0.114 * blue;
+
+                if height < sea_level {
+                    Tile::new_water((sea_level - height) / sea_level)
+                } else {
+                    let elevation = (height - sea_level) / (1.0 - sea_level);
+                    let mut tile = Tile::new(1.0 - elevation, food_type);
+                    tile.set_elevation(elevation);
+
+                    tile
+                }
+            }
+            ImageMapping::Painted => {
+                if blue > green {
+                    Tile::new_water(1.0 - green / blue)
+                } else {
+                    Tile::new(green, red)
+                }
+            }
+        }
+    }
+}
+
+impl Terrain {
+    /// Reads terrain from the image at `path`, every pixel becomes a tile according to `mapping`.
+    pub fn from_image<P: AsRef<Path>>(
+        path: P,
+        mapping: ImageMapping,
+    ) -> Result<Self, EvolvimError> {
+        if let ImageMapping::HeightMap { sea_level, .. } = mapping {
+            if !(sea_level >= 0.0 && sea_level < 1.0) {
+                return Err(EvolvimError::InvalidConfig(format!(
+                    "the sea level should be at least 0 and less than 1 but is {}",
+                    sea_level
+                )));
+            }
+        }
+
+        let image = image::open(path)?.to_rgb();
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return Err(EvolvimError::InvalidConfig(
+                "an empty image has no room for anything".to_string(),
+            ));
+        }
+
+        let tiles = (0..width)
+            .map(|x| {
+                (0..height)
+                    .map(|y| {
+                        let pixel = image.get_pixel(x, y).data;
+                        let channel = |i: usize| f64::from(pixel[i]) / 255.0;
+
+                        mapping.get_tile([channel(0), channel(1), channel(2)])
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut terrain = Terrain::from_columns(tiles);
+        terrain.assign_biomes();
+
+        Ok(terrain)
+    }
+
+    /// Draws every tile as a square of `scale` by `scale` pixels.
+    pub fn render_to_image(&self, scale: u32) -> RgbaImage {
+        let board_size = (self.get_width(), self.get_height());
+
+        render_tiles(board_size, scale, |x, y| {
+            self.get_tile_at((x, y)).get_hsba_color()
+        })
+    }
+}
+
+/// Returns an image of `board_size` tiles of `scale` by `scale` pixels, `get_color` gives the HSBA color of a tile.
+pub(crate) fn render_tiles<F: Fn(usize, usize) -> [f32; 4]>(
+    board_size: BoardSize,
+    scale: u32,
+    get_color: F,
+) -> RgbaImage {
+    let scale = scale.max(1);
+    let (width, height) = (board_size.0 as u32 * scale, board_size.1 as u32 * scale);
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        to_pixel(get_color((x / scale) as usize, (y / scale) as usize))
+    })
+}
+
+/// Draws a filled circle on `image`, the center and radius are in pixels.
+pub(crate) fn draw_circle(image: &mut RgbaImage, center: (f64, f64), radius: f64, hsba: [f32; 4]) {
+    let pixel = to_pixel(hsba);
+    let (cx, cy) = center;
+    let clamp = |v: f64, max: u32| v.max(0.0).min(f64::from(max)) as u32;
+
+    for x in clamp(cx - radius, image.width())..clamp(cx + radius + 1.0, image.width()) {
+        for y in clamp(cy - radius, image.height())..clamp(cy + radius + 1.0, image.height()) {
+            let (dx, dy) = (f64::from(x) + 0.5 - cx, f64::from(y) + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                image.put_pixel(x, y, pixel);
+            }
+        }
+    }
+}
+
+fn to_pixel(hsba: [f32; 4]) -> Rgba<u8> {
+    let rgba = hsba_to_rgba(hsba);
+    let channel = |i: usize| (rgba[i].max(0.0).min(1.0) * 255.0).round() as u8;
+
+    Rgba([channel(0), channel(1), channel(2), channel(3)])
+}
@@ -10,50 +10,230 @@
 extern crate noise;
 extern crate rand;
 
+pub mod biome;
+pub mod chunk;
+pub mod edit;
+pub mod generator;
+#[cfg(feature = "images")]
+pub mod images;
+pub mod palette;
+pub mod query;
+pub mod resource;
+mod save;
 pub mod tile;
 
+pub use self::biome::Biome;
+pub use self::generator::{NoiseParams, TerrainAlgorithm, TerrainGenerator};
+#[cfg(feature = "images")]
+pub use self::images::ImageMapping;
+pub use self::palette::SeasonalPalette;
+
+use self::chunk::Chunk;
+use self::edit::Scar;
 use self::noise::{NoiseFn, Point2, Seedable};
+use self::resource::Resource;
 use self::tile::Tile;
 use super::*;
+use crate::constants::{
+    FRUITING_MIN_FERTILITY, FRUITING_PERIOD, TERRAIN_CHUNK_SIZE, TOXIC_NOISE_THRESHOLD,
+};
+use crate::rng::{self, Stream};
+use std::ops::Range;
+
+/// What the tiles a `Terrain` gets when it grows are made of, see `Board::resize`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TerrainFill {
+    /// Land and water generated from perlin noise, just like a new board.
+    Perlin,
+    Water,
+    /// Land without any fertility.
+    Barren,
+}
 
 /// Contains the terrain of the world.
 ///
-/// TODO: possibly speed up with `nalgebra`.
+/// The tiles are stored in chunks, see `chunk`.
 #[derive(Serialize, Deserialize)]
 pub struct Terrain {
-    tiles: Vec<Vec<Tile>>,
+    width: usize,
+    height: usize,
+    /// The chunks column by column, just like the tiles.
+    chunks: Vec<Chunk>,
+    /// The times at which every tile still has to be updated, see `update_all_lazily`.
+    pending_updates: Vec<f64>,
+    /// The noise this terrain was generated with, including the seed. Only for `generate_perlin_with`.
+    noise_params: Option<NoiseParams>,
+    /// Tiles that were changed for a while, see `scar`.
+    scars: Vec<Scar>,
 }
 
 impl Terrain {
+    /// Creates terrain out of the columns of tiles of a board, every column should be equally high.
+    ///
+    /// Every tile gets the latitude of its row, see `Tile::set_latitude`.
+    fn from_columns(mut columns: Vec<Vec<Tile>>) -> Self {
+        let width = columns.len();
+        let height = columns.first().map_or(0, Vec::len);
+
+        for column in &mut columns {
+            for (y, tile) in column.iter_mut().enumerate() {
+                tile.set_latitude(get_latitude(y, height as f64));
+            }
+        }
+
+        let mut chunks = Vec::new();
+        for x in (0..width).step_by(TERRAIN_CHUNK_SIZE) {
+            for y in (0..height).step_by(TERRAIN_CHUNK_SIZE) {
+                chunks.push(Chunk::from_columns((x, y), &mut columns));
+            }
+        }
+
+        Terrain {
+            width,
+            height,
+            chunks,
+            pending_updates: Vec::new(),
+            noise_params: None,
+            scars: Vec::new(),
+        }
+    }
+
+    /// Takes all tiles out of the chunks again, the opposite of `from_columns`.
+    fn into_columns(self) -> Vec<Vec<Tile>> {
+        let mut columns = vec![vec![Tile::Water(0.0); self.height]; self.width];
+        for chunk in self.chunks {
+            chunk.into_columns(&mut columns);
+        }
+
+        columns
+    }
+
     #[cfg(multithreading)]
     pub fn update_all(&mut self, time: f64, climate: &Climate) {
         extern crate rayon;
 
-        self.tiles.par_iter_mut().flatten().for_each(|t| {
-            t.update(time, climate);
+        let pending = &self.pending_updates;
+        self.chunks.par_iter_mut().for_each(|c| {
+            c.catch_up(pending, climate);
+            c.update_all(time, climate);
         })
     }
     #[cfg(not(multithreading))]
     pub fn update_all(&mut self, time: f64, climate: &Climate) {
-        self.tiles.iter_mut().flatten().for_each(|t| {
-            t.update(time, climate);
+        let pending = &self.pending_updates;
+        self.chunks.iter_mut().for_each(|c| {
+            c.catch_up(pending, climate);
+            c.update_all(time, climate);
         })
     }
 
+    /// Makes every tile update to `time` as soon as its chunk is used, instead of updating all of them right away.
+    ///
+    /// Chunks catch up in `update_at`, `update_all_at`, `update_all` and `catch_up_at`.
+    pub fn update_all_lazily(&mut self, time: f64) {
+        // Forget the updates all chunks already did.
+        let done = self
+            .chunks
+            .iter()
+            .map(Chunk::get_caught_up)
+            .min()
+            .unwrap_or(0);
+        if done > 0 {
+            self.pending_updates.drain(..done);
+            for chunk in &mut self.chunks {
+                let caught_up = chunk.get_caught_up();
+                chunk.set_caught_up(caught_up - done);
+            }
+        }
+
+        self.pending_updates.push(time);
+    }
+
+    /// Lets the chunks covering the given ranges catch up with `update_all_lazily`.
+    pub fn catch_up_at(&mut self, x_range: Range<usize>, y_range: Range<usize>, climate: &Climate) {
+        for i in self.get_chunk_indices(x_range, y_range) {
+            self.chunks[i].catch_up(&self.pending_updates, climate);
+        }
+    }
+
     pub fn update_all_at(
         &mut self,
         time: f64,
         climate: &Climate,
-        x_range: std::ops::Range<usize>,
-        y_range: std::ops::Range<usize>,
+        x_range: Range<usize>,
+        y_range: Range<usize>,
     ) {
+        self.catch_up_at(x_range.clone(), y_range.clone(), climate);
+
         for x in x_range {
             for y in y_range.clone() {
-                self.tiles[x][y].update(time, climate);
+                self.get_tile_at_mut((x, y)).update(time, climate);
             }
         }
     }
 
+    /// Returns all chunks, column by column.
+    pub fn get_chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    /// Returns the chunks that cover the given ranges, e.g. the ones that are visible.
+    pub fn get_chunks_in<'a>(
+        &'a self,
+        x_range: Range<usize>,
+        y_range: Range<usize>,
+    ) -> impl Iterator<Item = &'a Chunk> {
+        self.get_chunk_indices(x_range, y_range)
+            .map(move |i| &self.chunks[i])
+    }
+
+    /// Marks all chunks as drawn, see `Chunk::is_dirty`.
+    pub fn clear_dirty_flags(&mut self) {
+        for chunk in &mut self.chunks {
+            chunk.clear_dirty_flag();
+        }
+    }
+
+    /// Returns the positions of all tiles that might have changed since the last call to this function or to
+    /// `clear_dirty_flags`, and marks everything as drawn.
+    ///
+    /// This way a renderer or anything streaming the board somewhere only has to send the tiles that changed. A tile
+    /// counts as changed as soon as it's borrowed mutably, and a chunk that's updated changes all of its tiles.
+    pub fn take_dirty_tiles(&mut self) -> Vec<BoardCoordinate> {
+        self.chunks
+            .iter_mut()
+            .flat_map(Chunk::take_dirty_tiles)
+            .collect()
+    }
+
+    /// Returns the indices in `chunks` of the chunks that cover the given ranges.
+    fn get_chunk_indices(
+        &self,
+        x_range: Range<usize>,
+        y_range: Range<usize>,
+    ) -> impl Iterator<Item = usize> {
+        let to_chunks = |range: Range<usize>, max: usize| {
+            let end = range.end.min(max);
+            if range.start < end {
+                range.start / TERRAIN_CHUNK_SIZE..(end - 1) / TERRAIN_CHUNK_SIZE + 1
+            } else {
+                0..0
+            }
+        };
+        let chunks_high = (self.height + TERRAIN_CHUNK_SIZE - 1) / TERRAIN_CHUNK_SIZE;
+        let y_chunks = to_chunks(y_range, self.height);
+
+        to_chunks(x_range, self.width)
+            .flat_map(move |cx| y_chunks.clone().map(move |cy| cx * chunks_high + cy))
+    }
+
+    /// Returns the index in `chunks` of the chunk containing `pos`.
+    fn get_chunk_index(&self, pos: BoardCoordinate) -> usize {
+        let chunks_high = (self.height + TERRAIN_CHUNK_SIZE - 1) / TERRAIN_CHUNK_SIZE;
+
+        (pos.0 / TERRAIN_CHUNK_SIZE) * chunks_high + pos.1 / TERRAIN_CHUNK_SIZE
+    }
+
     /// Gets a mutable reference to that tile, this function should be used as little as possible.
     pub fn get_tile_at_mut(&mut self, pos: BoardCoordinate) -> &mut Tile {
         let (x, y) = pos;
@@ -69,7 +249,8 @@ impl Terrain {
             y
         );
 
-        return &mut self.tiles[x][y];
+        let i = self.get_chunk_index(pos);
+        return self.chunks[i].get_tile_mut(pos);
     }
 
     /// Gets a reference to that tile, this function should be used as little as possible.
@@ -89,34 +270,173 @@ impl Terrain {
             y
         );
 
-        return &self.tiles[x][y];
+        return self.chunks[self.get_chunk_index(pos)].get_tile(pos);
+    }
+
+    /// Replaces the `Tile` at the given position and returns the old one.
+    ///
+    /// The new tile gets the latitude of `pos`, see `Tile::set_latitude`.
+    pub fn set_tile(&mut self, pos: BoardCoordinate, mut tile: Tile) -> Tile {
+        tile.set_latitude(get_latitude(pos.1, self.get_height() as f64));
+        std::mem::replace(self.get_tile_at_mut(pos), tile)
+    }
+
+    /// Returns the temperature of the tile at `pos` right now, which is colder the further it is from the middle of
+    /// the board and the higher it is. Tiles in a climate region get the temperature of their region, see
+    /// `Climate::get_local_temperature`.
+    pub fn temperature_at(&self, pos: BoardCoordinate, climate: &Climate) -> f64 {
+        let latitude = get_latitude(pos.1, self.get_height() as f64);
+        let tile = self.get_tile_at(pos);
+        climate.get_local_temperature(tile.get_region(), tile.get_elevation(), latitude)
     }
 
     pub fn update_at(&mut self, pos: BoardCoordinate, time: f64, climate: &Climate) {
-        let (x, y) = pos;
-        self.tiles[x][y].update(time, climate);
+        let i = self.get_chunk_index(pos);
+        let chunk = &mut self.chunks[i];
+        chunk.catch_up(&self.pending_updates, climate);
+        chunk.get_tile_mut(pos).update(time, climate);
     }
 
     pub fn generate_perlin(board_size: BoardSize, step_size: f64) -> Self {
-        let noise_generator = noise::Perlin::new();
+        Terrain::generate_perlin_with(board_size, step_size, &NoiseParams::default())
+    }
+
+    /// Does the same as `generate_perlin` but with the given noise parameters, the same parameters with the same seed
+    /// always give the same terrain.
+    ///
+    /// The parameters are remembered along with the seed that was used, see `get_noise_params`.
+    pub fn generate_perlin_with(
+        board_size: BoardSize,
+        step_size: f64,
+        params: &NoiseParams,
+    ) -> Self {
+        let params = NoiseParams {
+            seed: Some(params.seed.unwrap_or_else(random_seed)),
+            ..params.clone()
+        };
+        let noise_generator = noise::Perlin::new().set_seed(params.seed.unwrap());
+
+        let mut terrain =
+            Terrain::generate_terrain_with_noise(noise_generator, board_size, step_size, &params);
+        terrain.noise_params = Some(params);
+
+        terrain
+    }
+
+    /// Returns the noise parameters this terrain was generated with, with the seed that was used. Returns `None` if
+    /// it wasn't made by `generate_perlin_with`.
+    pub fn get_noise_params(&self) -> Option<&NoiseParams> {
+        self.noise_params.as_ref()
+    }
+
+    /// Returns terrain where every tile gets the fertility `fertility` returns for its position, see `TerrainGenerator`.
+    ///
+    /// Tiles with a fertility above 1 become water. The food types and the fruiting tiles come from perlin noise with
+    /// `step_size`, just like for `Terrain::generate_perlin`.
+    pub fn from_fertility<F: FnMut(usize, usize) -> f64>(
+        board_size: BoardSize,
+        step_size: f64,
+        fertility: F,
+    ) -> Self {
+        let noise_generator = seeded(noise::Perlin::new());
+
+        Terrain::generate_with_fertility(&noise_generator, board_size, step_size, fertility)
+    }
+
+    /// Changes the size of this terrain to `board_size`, new tiles are added on the right and at the bottom.
+    ///
+    /// Tiles that no longer fit are dropped. The new tiles are made according to `fill`, `step_size` is the step size
+    /// of the noise used for `TerrainFill::Perlin`. Scarred tiles come back first, see `scar`.
+    pub fn resize(&mut self, board_size: BoardSize, fill: TerrainFill, step_size: f64) {
+        self.heal_scars(std::f64::INFINITY);
+        let (width, height) = board_size;
+        let (old_width, old_height) = (self.get_width(), self.get_height());
+        let mut generated = match fill {
+            TerrainFill::Perlin => {
+                Some(Terrain::generate_perlin(board_size, step_size).into_columns())
+            }
+            _ => None,
+        };
+
+        let mut new_tile = |x: usize, y: usize| match generated {
+            Some(ref mut columns) => std::mem::replace(&mut columns[x][y], Tile::Water(0.0)),
+            None if fill == TerrainFill::Water => Tile::new_water(1.0),
+            None => Tile::new(0.0, 0.0),
+        };
+
+        // Chunks keep their place, so the ones that stay know which updates they already did.
+        let caught_up: Vec<(BoardCoordinate, usize)> = self
+            .chunks
+            .iter()
+            .map(|c| (c.get_origin(), c.get_caught_up()))
+            .collect();
+        let pending_updates = std::mem::replace(&mut self.pending_updates, Vec::new());
+        let noise_params = self.noise_params.take();
+        let mut tiles = std::mem::replace(self, Terrain::from_columns(Vec::new())).into_columns();
+
+        tiles.truncate(width);
+        for (x, column) in tiles.iter_mut().enumerate() {
+            column.truncate(height);
+            column.extend((old_height..height).map(|y| new_tile(x, y)));
+        }
+        for x in old_width..width {
+            tiles.push((0..height).map(|y| new_tile(x, y)).collect());
+        }
 
-        // Seed the noise generator.
-        let noise_generator = noise_generator.set_seed(rand::random());
+        *self = Terrain::from_columns(tiles);
+        for chunk in &mut self.chunks {
+            let done = caught_up
+                .iter()
+                .find(|(origin, _)| *origin == chunk.get_origin())
+                .map_or(pending_updates.len(), |&(_, done)| done);
+            chunk.set_caught_up(done);
+        }
+        self.pending_updates = pending_updates;
+        self.noise_params = noise_params;
 
-        return Terrain::generate_terrain_with_noise(noise_generator, board_size, step_size);
+        // The middle of the board moved.
+        self.assign_biomes();
     }
 
     /// Tries to add `food` to the food level of that `Tile`.
     ///
     /// Does nothing for water tiles.
     pub fn add_food_or_nothing_at(&mut self, pos: BoardCoordinate, food: f64) {
-        self.tiles[pos.0][pos.1].add_food_or_nothing(food);
+        self.get_tile_at_mut(pos).add_food_or_nothing(food);
+    }
+
+    /// Leaves `amount` carrion at `pos`, see `Resource::carrion`. Does nothing if the tile isn't land.
+    pub fn add_carrion_or_nothing_at(&mut self, pos: BoardCoordinate, amount: f64) {
+        self.get_tile_at_mut(pos)
+            .add_resource(Resource::carrion(amount));
     }
 
     fn generate_terrain_with_noise<N: NoiseFn<Point2<f64>>>(
         ng: N,
         board_size: BoardSize,
         step_size: f64,
+        params: &NoiseParams,
+    ) -> Self {
+        let board_height = board_size.1;
+        let layered = |x: f64, y: f64| get_octave_noise(&ng, x, y, params);
+
+        Terrain::generate_with_fertility(&ng, board_size, step_size, |x, y| {
+            let big_force = (y as f64 / board_height as f64).sqrt();
+
+            // TODO: understand these formulas.
+            layered(x as f64 * step_size * 3.0, y as f64 * step_size * 3.0) * (1.0 - big_force) * 4.0
+                + layered(x as f64 * step_size * 0.5, y as f64 * step_size * 0.5) * big_force * 4.0
+                - 1.5
+                // Moving the threshold to 1 is the same as moving the fertility the other way.
+                + (1.0 - params.water_threshold)
+        })
+    }
+
+    fn generate_with_fertility<N: NoiseFn<Point2<f64>>, F: FnMut(usize, usize) -> f64>(
+        ng: &N,
+        board_size: BoardSize,
+        step_size: f64,
+        mut get_fertility: F,
     ) -> Self {
         let (board_width, board_height) = board_size;
 
@@ -125,43 +445,128 @@ impl Terrain {
         for x in 0..board_width {
             tiles.push(Vec::with_capacity(board_height));
             for y in 0..board_height {
-                let big_force = (y as f64 / board_height as f64).sqrt();
-
-                // TODO: understand these formulas.
-                let fertility =
-                    get_noise(&ng, x as f64 * step_size * 3.0, y as f64 * step_size * 3.0)
-                        * (1.0 - big_force)
-                        * 4.0
-                        + get_noise(&ng, x as f64 * step_size * 0.5, y as f64 * step_size * 0.5)
-                            * big_force
-                            * 4.0
-                        - 1.5;
+                let fertility = get_fertility(x, y);
 
                 let mut climate_type = get_noise(
-                    &ng,
+                    ng,
                     x as f64 * step_size * 0.2 + 10000.0,
                     y as f64 * step_size * 0.2 + 10000.0,
                 ) * 1.63
                     - 0.4;
 
                 climate_type = climate_type.max(0.0).min(0.8);
-                tiles[x].push(Tile::new(fertility, climate_type));
+
+                let mut tile = if fertility >= FRUITING_MIN_FERTILITY && fertility <= 1.0 {
+                    // Neighbouring tiles fruit at about the same time, making waves of food.
+                    let phase = get_noise(
+                        ng,
+                        x as f64 * step_size * 0.1 + 20000.0,
+                        y as f64 * step_size * 0.1 + 20000.0,
+                    ) * FRUITING_PERIOD;
+
+                    Tile::new_fruiting(fertility, climate_type, phase)
+                } else {
+                    Tile::new(fertility, climate_type)
+                };
+
+                // The coasts are low, mountains rise where the land is far from the water.
+                let hills = get_noise(
+                    ng,
+                    x as f64 * step_size * 0.5 + 30000.0,
+                    y as f64 * step_size * 0.5 + 30000.0,
+                );
+                tile.set_elevation(hills * 2.0 * (1.0 - fertility));
+
+                // Patches of toxic food, each with its own toxin.
+                let toxin = get_noise(
+                    ng,
+                    x as f64 * step_size * 0.3 + 40000.0,
+                    y as f64 * step_size * 0.3 + 40000.0,
+                );
+                if toxin > TOXIC_NOISE_THRESHOLD {
+                    let toxin_hue = get_noise(
+                        ng,
+                        x as f64 * step_size * 0.05 + 50000.0,
+                        y as f64 * step_size * 0.05 + 50000.0,
+                    );
+                    // The toxin gets strong quickly so the patches have sharp edges.
+                    let toxicity = ((toxin - TOXIC_NOISE_THRESHOLD) * 20.0).min(1.5);
+                    tile.set_toxin(toxicity, toxin_hue);
+                }
+
+                tiles[x].push(tile);
             }
         }
 
         // Return the generated terrain.
-        Terrain { tiles }
+        let mut terrain = Terrain::from_columns(tiles);
+        terrain.assign_biomes();
+
+        terrain
+    }
+
+    /// Gives every land tile the biome of its fertility and position, see `Biome::classify`.
+    pub(crate) fn assign_biomes(&mut self) {
+        let height = self.get_height() as f64;
+
+        for chunk in &mut self.chunks {
+            for ((_, y), tile) in chunk.iter_mut() {
+                let latitude = get_latitude(y, height);
+                tile.set_biome(Biome::classify(tile.get_fertility(), latitude));
+            }
+        }
+    }
+
+    /// Returns the sum of the food lying on and stored in all tiles, including the resources besides plants.
+    pub fn get_total_food(&self) -> f64 {
+        self.chunks
+            .iter()
+            .flat_map(Chunk::get_tiles)
+            .map(|t| t.get_food_level() + t.get_stored_food() + t.get_resource_food())
+            .sum()
     }
 
     pub fn get_width(&self) -> usize {
-        return self.tiles.len();
+        return self.width;
     }
 
     pub fn get_height(&self) -> usize {
-        return self.tiles[0].len();
+        return self.height;
     }
 }
 
 fn get_noise<N: NoiseFn<Point2<f64>>>(ng: N, x: f64, y: f64) -> f64 {
     (ng.get([x, y]) + 1.0) / 2.0
 }
+
+/// Returns how far row `y` is from the middle of a board `height` tiles high, see `Biome::classify`.
+fn get_latitude(y: usize, height: f64) -> f64 {
+    ((y as f64 + 0.5) / height * 2.0 - 1.0).abs()
+}
+
+/// Adds up `params.octaves` layers of noise, every layer with twice the detail and `params.persistence` times the
+/// strength of the one before it. Just like `get_noise` it goes from 0 to 1.
+fn get_octave_noise<N: NoiseFn<Point2<f64>>>(ng: N, x: f64, y: f64, params: &NoiseParams) -> f64 {
+    let mut total = 0.0;
+    let mut strength = 1.0;
+    let mut max = 0.0;
+
+    for octave in 0..params.octaves.max(1) {
+        let detail = (1 << octave) as f64;
+        total += get_noise(&ng, x * detail, y * detail) * strength;
+        max += strength;
+        strength *= params.persistence;
+    }
+
+    total / max
+}
+
+/// Returns a random seed from `Stream::Terrain`.
+fn random_seed() -> u32 {
+    rng::with_stream(Stream::Terrain, rng::random)
+}
+
+/// Seeds `noise` from `Stream::Terrain`.
+fn seeded<N: Seedable>(noise: N) -> N {
+    noise.set_seed(random_seed())
+}
@@ -0,0 +1,64 @@
+//! The colors the terrain gets in every season, so observers can tell winter from summer at a glance.
+//!
+//! The season only changes how the terrain is drawn, brains still see the colors of `Tile::get_hsba_color`.
+
+use crate::constants::{AUTUMN_TINT, COLOR_AUTUMN, COLOR_FROST, FROST_TINT};
+
+/// A color as hsb and how much of it is mixed into the color of a tile, between 0 and 1.
+pub type Tint = ([f32; 3], f32);
+
+/// The tints of winter, spring, summer and autumn, see `Tile::get_seasonal_hsba_color`.
+///
+/// Every tint is the strongest in the middle of its season and fades into the tint of the next season.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalPalette {
+    pub winter: Tint,
+    pub spring: Tint,
+    pub summer: Tint,
+    pub autumn: Tint,
+}
+
+impl Default for SeasonalPalette {
+    /// Frozen winters and brown autumns, spring and summer keep the normal colors.
+    fn default() -> Self {
+        SeasonalPalette {
+            winter: (COLOR_FROST, FROST_TINT),
+            spring: (COLOR_FROST, 0.0),
+            summer: (COLOR_FROST, 0.0),
+            autumn: (COLOR_AUTUMN, AUTUMN_TINT),
+        }
+    }
+}
+
+impl SeasonalPalette {
+    /// Returns a palette that leaves the colors the same all year round.
+    pub fn none() -> Self {
+        SeasonalPalette {
+            winter: (COLOR_FROST, 0.0),
+            spring: (COLOR_FROST, 0.0),
+            summer: (COLOR_FROST, 0.0),
+            autumn: (COLOR_AUTUMN, 0.0),
+        }
+    }
+
+    /// Returns the tints of the two seasons `year` is between, with their strength scaled by how close it is to the
+    /// middle of each season.
+    pub fn get_tints(&self, year: f64) -> [Tint; 2] {
+        let tints = [self.winter, self.spring, self.summer, self.autumn];
+
+        // Winter is centered on 0.125, so the year is shifted to make 0 the middle of winter.
+        let position = ((year % 1.0 + 1.0) % 1.0) * 4.0 - 0.5;
+        let floor = position.floor();
+        let fraction = (position - floor) as f32;
+        let before = (floor as isize).rem_euclid(4) as usize;
+        let after = (before + 1) % 4;
+
+        let (before_color, before_strength) = tints[before];
+        let (after_color, after_strength) = tints[after];
+
+        [
+            (before_color, before_strength * (1.0 - fraction)),
+            (after_color, after_strength * fraction),
+        ]
+    }
+}
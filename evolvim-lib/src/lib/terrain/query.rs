@@ -0,0 +1,88 @@
+//! Questions about the tiles around a position, for sensors and for placing creatures.
+
+use super::edit::Brush;
+use super::Terrain;
+use crate::board::BoardCoordinate;
+
+impl Terrain {
+    /// Returns the land tile closest to `pos`, which is `pos` itself if it's land. Returns `None` if there's no land.
+    pub fn nearest_land(&self, pos: BoardCoordinate) -> Option<BoardCoordinate> {
+        let (width, height) = (self.get_width() as isize, self.get_height() as isize);
+        let (px, py) = (pos.0 as isize, pos.1 as isize);
+        let mut best: Option<(isize, BoardCoordinate)> = None;
+
+        // Search in growing square rings until no tile in the next ring can be closer than the best one.
+        for ring in 0..width.max(height) {
+            if let Some((distance, _)) = best {
+                if ring * ring > distance {
+                    break;
+                }
+            }
+
+            let mut check = |x: isize, y: isize| {
+                if x < 0 || y < 0 || x >= width || y >= height {
+                    return;
+                }
+
+                let candidate = (x as usize, y as usize);
+                let distance = (x - px).pow(2) + (y - py).pow(2);
+                let closer = best.map_or(true, |(d, _)| distance < d);
+                if closer && self.get_tile_at(candidate).is_land() {
+                    best = Some((distance, candidate));
+                }
+            };
+
+            for x in px - ring..=px + ring {
+                check(x, py - ring);
+                if ring > 0 {
+                    check(x, py + ring);
+                }
+            }
+            for y in py - ring + 1..py + ring {
+                check(px - ring, y);
+                check(px + ring, y);
+            }
+        }
+
+        best.map(|(_, pos)| pos)
+    }
+
+    /// Returns the direction in which the fertility goes up the fastest at `pos`, as the change in fertility per tile
+    /// in the x and y direction.
+    ///
+    /// Tiles that aren't land count as a fertility of 0, so on land next to the water the gradient points inland.
+    pub fn fertility_gradient(&self, pos: BoardCoordinate) -> (f64, f64) {
+        let (x, y) = pos;
+        let fertility = |x: usize, y: usize| self.get_tile_at((x, y)).get_fertility();
+
+        let left = x.saturating_sub(1);
+        let right = (x + 1).min(self.get_width() - 1);
+        let top = y.saturating_sub(1);
+        let bottom = (y + 1).min(self.get_height() - 1);
+
+        let dx = if right > left {
+            (fertility(right, y) - fertility(left, y)) / (right - left) as f64
+        } else {
+            0.0
+        };
+        let dy = if bottom > top {
+            (fertility(x, bottom) - fertility(x, top)) / (bottom - top) as f64
+        } else {
+            0.0
+        };
+
+        (dx, dy)
+    }
+
+    /// Returns the food lying on all tiles within `radius` tiles of `pos`, the same tiles a `Brush` of that radius
+    /// covers.
+    pub fn total_food_within(&self, pos: BoardCoordinate, radius: f64) -> f64 {
+        let board_size = (self.get_width(), self.get_height());
+
+        Brush { radius }
+            .get_footprint(pos, board_size)
+            .into_iter()
+            .map(|pos| self.get_tile_at(pos).get_food_level())
+            .sum()
+    }
+}
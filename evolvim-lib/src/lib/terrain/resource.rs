@@ -0,0 +1,91 @@
+//! Food besides the plants of a tile, like fruit on the trees or the carrion dead creatures leave behind.
+//!
+//! Every land tile grows plants (see `Tile::get_food_level`) and can hold a few other resources, each with its own
+//! `GrowthRule`. Creatures get the most out of the kind of food their mouth is specialized in and their diet suits,
+//! see `Rock::get_food_efficiency`.
+
+use crate::constants::{CARRION_ROT_RATE, FOOD_GROWTH_RATE, MAX_GROWTH_LEVEL};
+
+/// The kinds of food a tile can hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResourceKind {
+    /// The food every land tile grows, see `Tile::get_food_level`.
+    Plant,
+    Fruit,
+    /// What's left of dead creatures.
+    Carrion,
+}
+
+impl Default for ResourceKind {
+    fn default() -> Self {
+        ResourceKind::Plant
+    }
+}
+
+impl ResourceKind {
+    pub const fn get_all() -> [Self; 3] {
+        [
+            ResourceKind::Plant,
+            ResourceKind::Fruit,
+            ResourceKind::Carrion,
+        ]
+    }
+}
+
+/// How the amount of a resource on a tile changes over time.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GrowthRule {
+    /// Grows and withers with the climate just like plants, `rate` times as fast.
+    Climate { rate: f64 },
+    /// Grows `rate` every year whatever the weather, until `MAX_GROWTH_LEVEL`.
+    Steady { rate: f64 },
+    /// Doesn't grow, the fraction `rate` of it rots away every year.
+    Rot { rate: f64 },
+}
+
+/// An amount of food of one kind on a tile, see `Tile::add_resource`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Resource {
+    pub kind: ResourceKind,
+    pub level: f64,
+    pub rule: GrowthRule,
+}
+
+impl Resource {
+    pub fn new(kind: ResourceKind, level: f64, rule: GrowthRule) -> Self {
+        Resource { kind, level, rule }
+    }
+
+    /// Returns `level` carrion that rots at `CARRION_ROT_RATE`.
+    pub fn carrion(level: f64) -> Self {
+        Resource::new(
+            ResourceKind::Carrion,
+            level,
+            GrowthRule::Rot {
+                rate: CARRION_ROT_RATE,
+            },
+        )
+    }
+
+    /// Grows this resource over `time_step` years in which the climate gave `growth`, see
+    /// `Climate::get_growth_over_time_range`.
+    pub(crate) fn grow(&mut self, growth: f64, time_step: f64) {
+        match self.rule {
+            GrowthRule::Climate { rate } => {
+                if growth <= 0.0 {
+                    self.level *= (growth * rate * FOOD_GROWTH_RATE).exp();
+                } else if self.level < MAX_GROWTH_LEVEL {
+                    let dist_to_max = MAX_GROWTH_LEVEL - self.level;
+                    self.level =
+                        MAX_GROWTH_LEVEL - dist_to_max * (-growth * rate * FOOD_GROWTH_RATE).exp();
+                }
+            }
+            GrowthRule::Steady { rate } => {
+                if self.level < MAX_GROWTH_LEVEL {
+                    self.level = (self.level + rate * time_step).min(MAX_GROWTH_LEVEL);
+                }
+            }
+            GrowthRule::Rot { rate } => self.level *= (-rate * time_step).exp(),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Saving a `Terrain` on its own, apart from a full save of the `Board`.
+//!
+//! A terrain file holds only the tiles, so a carefully designed map can be reused for many experiment runs with fresh
+//! creatures each time: load it with `Terrain::load_from` and hand it to `Board::set_terrain`. The format goes by the
+//! file extension, just like for saves (see `SaveFormat::from_path`).
+
+use super::Terrain;
+use crate::error::EvolvimError;
+use crate::serde_structs::{SaveFormat, Version};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Serialize)]
+struct TerrainRef<'a> {
+    version: Version,
+    terrain: &'a Terrain,
+}
+
+#[derive(Deserialize)]
+struct TerrainSerde {
+    version: Version,
+    terrain: Terrain,
+}
+
+impl Terrain {
+    /// Writes this terrain to a file at `path`.
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<(), EvolvimError> {
+        let path = path.as_ref();
+        let mut file = BufWriter::new(File::create(path)?);
+
+        SaveFormat::from_path(path).serialize_into(
+            &mut file,
+            &TerrainRef {
+                version: Version::current_version(),
+                terrain: self,
+            },
+        )?;
+
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Reads a terrain written by `save_to`.
+    ///
+    /// The tiles keep their food, but their clocks start at year 0 again, just like freshly generated terrain. That
+    /// way the food on the map grows normally on a new board.
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Terrain, EvolvimError> {
+        let path = path.as_ref();
+        let file = BufReader::new(File::open(path)?);
+        let saved: TerrainSerde = SaveFormat::from_path(path).deserialize_from(file)?;
+
+        if !saved.version.is_compatible_with_current() {
+            return Err(EvolvimError::IncompatibleVersion {
+                found: saved.version.to_string(),
+                current: Version::current_version().to_string(),
+            });
+        }
+
+        let mut terrain = saved.terrain;
+        terrain.restart_clock();
+
+        Ok(terrain)
+    }
+
+    /// Sets the time of every tile back to 0 and forgets the pending updates.
+    fn restart_clock(&mut self) {
+        self.pending_updates.clear();
+        for chunk in &mut self.chunks {
+            chunk.set_caught_up(0);
+            for (_, tile) in chunk.iter_mut() {
+                tile.restart_clock();
+            }
+        }
+    }
+}
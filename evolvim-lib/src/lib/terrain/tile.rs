@@ -1,17 +1,22 @@
+use super::biome::Biome;
 use super::constants::*;
+use super::resource::{Resource, ResourceKind};
 use super::*;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum Tile {
-    Water,
+    /// Water with a depth between 0 at the shore and 1 in the deepest seas, see `Tile::get_water_depth`.
+    Water(f64),
     Land(LandTile),
+    /// Bare rock that creatures can't enter, to build mazes or to keep populations apart.
+    Obstacle,
 }
 
 impl Tile {
     pub fn new(fertility: f64, food_type: f64) -> Self {
         if fertility > 1.0 {
-            // Tile is water
-            Tile::Water
+            // Tile is water, the further the fertility goes over 1 the deeper it is.
+            Tile::new_water((fertility - 1.0) * WATER_DEPTH_SCALE)
         } else {
             // Tile is land
             let t = LandTile::new(fertility.max(0.0), food_type);
@@ -20,93 +25,312 @@ impl Tile {
         }
     }
 
+    /// Creates a water tile, `depth` is clamped between 0 and 1.
+    pub fn new_water(depth: f64) -> Self {
+        Tile::Water(depth.max(0.0).min(1.0))
+    }
+
+    /// Creates a land tile that stores its food and releases it all at once every `FRUITING_PERIOD` years.
+    ///
+    /// `phase` is the time within a period at which the food is released.
+    pub fn new_fruiting(fertility: f64, food_type: f64, phase: f64) -> Self {
+        let mut t = LandTile::new(fertility.max(0.0).min(1.0), food_type);
+        t.fruiting_phase = Some(phase);
+
+        Tile::Land(t)
+    }
+
     pub fn is_water(&self) -> bool {
         match self {
-            Tile::Water => true,
-            Tile::Land(_) => false,
+            Tile::Water(_) => true,
+            Tile::Land(_) | Tile::Obstacle => false,
+        }
+    }
+
+    /// Returns the depth of the water, between 0 at the shore and 1 in the deepest seas. Returns 0 if this isn't water.
+    pub fn get_water_depth(&self) -> f64 {
+        match self {
+            Tile::Water(depth) => *depth,
+            Tile::Land(_) | Tile::Obstacle => 0.0,
+        }
+    }
+
+    /// Changes the depth of the water, clamped between 0 and 1. Does nothing for tiles that aren't water.
+    pub fn set_water_depth(&mut self, depth: f64) {
+        if let Tile::Water(d) = self {
+            *d = depth.max(0.0).min(1.0);
+        }
+    }
+
+    /// Returns true if this `Tile` is water deep enough to drown creatures that can't swim, see `DEEP_WATER_DEPTH`.
+    pub fn is_deep_water(&self) -> bool {
+        self.get_water_depth() > DEEP_WATER_DEPTH
+    }
+
+    /// Returns true if creatures can't enter this `Tile`.
+    pub fn is_obstacle(&self) -> bool {
+        match self {
+            Tile::Obstacle => true,
+            Tile::Water(_) | Tile::Land(_) => false,
+        }
+    }
+
+    /// Returns true if this `Tile` is land, the only kind of tile that grows food.
+    pub fn is_land(&self) -> bool {
+        match self {
+            Tile::Land(_) => true,
+            Tile::Water(_) | Tile::Obstacle => false,
         }
     }
 
-    /// Get the `food_level` of this `Tile`, returns 0 if it is water.
+    /// Get the `food_level` of this `Tile`, returns 0 if it isn't land.
     pub fn get_food_level(&self) -> f64 {
         match self {
-            Tile::Water => 0.0,
+            Tile::Water(_) | Tile::Obstacle => 0.0,
             Tile::Land(t) => t.food_level,
         }
     }
 
-    /// Get the `fertility` of this `Tile`, returns 0 if it is water.
+    /// Returns the climate region of this `Tile`, water and obstacles are always in region 0.
+    pub fn get_region(&self) -> usize {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0,
+            Tile::Land(t) => t.region,
+        }
+    }
+
+    /// Makes this `Tile` grow food according to the climate of the given region, see `Climate::set_regions`.
+    ///
+    /// Does nothing for tiles that aren't land.
+    pub fn set_region(&mut self, region: usize) {
+        if let Tile::Land(t) = self {
+            t.region = region;
+        }
+    }
+
+    /// Returns the biome of this `Tile`, `None` if it isn't land.
+    pub fn get_biome(&self) -> Option<Biome> {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => None,
+            Tile::Land(t) => Some(t.biome),
+        }
+    }
+
+    /// Changes the biome of this `Tile`, does nothing for tiles that aren't land.
+    pub fn set_biome(&mut self, biome: Biome) {
+        if let Tile::Land(t) = self {
+            t.biome = biome;
+        }
+    }
+
+    /// Returns the height of this `Tile` between 0 and 1, anything but land is at 0.
+    ///
+    /// Higher tiles are colder and cost more energy to walk over.
+    pub fn get_elevation(&self) -> f64 {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0.0,
+            Tile::Land(t) => t.elevation,
+        }
+    }
+
+    /// Changes the elevation of this `Tile`, clamped between 0 and 1. Does nothing for tiles that aren't land.
+    pub fn set_elevation(&mut self, elevation: f64) {
+        if let Tile::Land(t) = self {
+            t.elevation = elevation.max(0.0).min(1.0);
+        }
+    }
+
+    /// Returns how far this `Tile` is from the equator, from 0 in the middle of the board to 1 at the top and bottom.
+    /// Anything but land is at 0.
+    ///
+    /// The terrain sets it when a tile is placed, see `Terrain::temperature_at`.
+    pub fn get_latitude(&self) -> f64 {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0.0,
+            Tile::Land(t) => t.latitude,
+        }
+    }
+
+    /// Changes the latitude of this `Tile`, clamped between 0 and 1. Does nothing for tiles that aren't land.
+    pub fn set_latitude(&mut self, latitude: f64) {
+        if let Tile::Land(t) = self {
+            t.latitude = latitude.max(0.0).min(1.0);
+        }
+    }
+
+    /// Get the `fertility` of this `Tile`, returns 0 if it isn't land.
     pub fn get_fertility(&self) -> f64 {
         match self {
-            Tile::Water => 0.0,
+            Tile::Water(_) | Tile::Obstacle => 0.0,
             Tile::Land(t) => t.fertility,
         }
     }
 
-    /// Get the `food_type` of this `Tile`, returns 0 if it is water.
+    /// Changes the fertility of this `Tile`, clamped between 0 and 1. Does nothing for tiles that aren't land.
+    ///
+    /// This doesn't change the biome, see `Terrain::raise_fertility` for that.
+    pub fn set_fertility(&mut self, fertility: f64) {
+        if let Tile::Land(t) = self {
+            t.fertility = fertility.max(0.0).min(1.0);
+        }
+    }
+
+    /// Returns true if this `Tile` releases its food in bursts.
+    pub fn is_fruiting(&self) -> bool {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => false,
+            Tile::Land(t) => t.fruiting_phase.is_some(),
+        }
+    }
+
+    /// Get the food a fruiting `Tile` has grown but not yet released, returns 0 for other tiles.
+    pub fn get_stored_food(&self) -> f64 {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0.0,
+            Tile::Land(t) => t.stored_food,
+        }
+    }
+
+    /// Returns the food besides the plants on this `Tile`, empty if it isn't land.
+    pub fn get_resources(&self) -> &[Resource] {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => &[],
+            Tile::Land(t) => &t.resources,
+        }
+    }
+
+    /// Returns the amount of food of the given kind on this `Tile`, for plants this is the food level.
+    pub fn get_resource_level(&self, kind: ResourceKind) -> f64 {
+        if kind == ResourceKind::Plant {
+            return self.get_food_level();
+        }
+
+        self.get_resources()
+            .iter()
+            .filter(|r| r.kind == kind)
+            .map(|r| r.level)
+            .sum()
+    }
+
+    /// Returns the sum of all resources besides the plants on this `Tile`.
+    pub fn get_resource_food(&self) -> f64 {
+        self.get_resources().iter().map(|r| r.level).sum()
+    }
+
+    /// Puts `resource` on this `Tile`, does nothing if it isn't land.
+    ///
+    /// A tile holds at most one resource of every kind: if it already has one of this kind, the level is added to it
+    /// and it keeps growing by its own rule. Plants are added to the food level.
+    pub fn add_resource(&mut self, resource: Resource) {
+        if let Tile::Land(t) = self {
+            if resource.kind == ResourceKind::Plant {
+                t.add_food(resource.level);
+            } else if let Some(r) = t.resources.iter_mut().find(|r| r.kind == resource.kind) {
+                r.level += resource.level.max(0.0);
+            } else {
+                t.resources.push(resource);
+            }
+        }
+    }
+
+    /// Removes the given amount of food of `kind` from this `Tile`, the level can't get negative.
+    ///
+    /// This does nothing for tiles that aren't land.
+    pub fn remove_resource(&mut self, kind: ResourceKind, amount: f64) {
+        if let Tile::Land(t) = self {
+            if kind == ResourceKind::Plant {
+                t.remove_food(amount);
+            } else {
+                for r in t.resources.iter_mut().filter(|r| r.kind == kind) {
+                    r.level = (r.level - amount).max(0.0);
+                }
+            }
+        }
+    }
+
+    /// Get the `food_type` of this `Tile`, returns 0 if it isn't land.
     pub fn get_food_type(&self) -> f64 {
         match self {
-            Tile::Water => 0.0,
+            Tile::Water(_) | Tile::Obstacle => 0.0,
             Tile::Land(t) => t.food_type,
         }
     }
 
+    /// Returns the color of this `Tile`, higher land is shaded darker and toxic land is tinted purple.
     pub fn get_hsba_color(&self) -> [f32; 4] {
         match self {
-            Tile::Water => COLOR_WATER,
+            Tile::Water(depth) => {
+                // Shallow water is light, the deepest water is as dark as `COLOR_WATER`.
+                let c = inter_color(COLOR_SHALLOW_WATER, COLOR_DEEP_WATER, *depth as f32);
+                [c[0], c[1], c[2], COLOR_WATER[3]]
+            }
+            Tile::Obstacle => COLOR_OBSTACLE,
             Tile::Land(t) => {
-                let food_color = [t.food_type as f32, 1.0, 1.0];
-
-                if t.food_level < MAX_GROWTH_LEVEL {
-                    if t.food_level > 0.0 {
-                        let c = inter_color(COLOR_BARREN, COLOR_FERTILE, t.fertility as f32);
-                        return inter_color_fixed_hue(
-                            c,
-                            food_color,
-                            (t.food_level / MAX_GROWTH_LEVEL) as f32,
-                            t.food_type as f32,
-                        );
-                    } else {
-                        return [COLOR_BARREN[0], COLOR_BARREN[1], COLOR_BARREN[2], 1.0];
-                    }
-                } else {
-                    return inter_color_fixed_hue(
-                        food_color,
-                        COLOR_BLACK,
-                        1.0 - (MAX_GROWTH_LEVEL / t.food_level) as f32,
-                        t.food_type as f32,
-                    );
+                let mut color = t.get_hsba_color();
+                if t.toxicity > 0.0 {
+                    let tint = TOXIC_TINT * t.toxicity.min(1.0) as f32;
+                    let c = inter_color([color[0], color[1], color[2]], COLOR_TOXIC, tint);
+                    color = [c[0], c[1], c[2], color[3]];
                 }
+                color[2] *= 1.0 - ELEVATION_SHADING * t.elevation as f32;
+
+                color
             }
         }
     }
 
+    /// Returns the color of this `Tile` at the time of the year of `year`, tinted according to `palette`.
+    ///
+    /// Obstacles keep their color all year round.
+    pub fn get_seasonal_hsba_color(&self, year: f64, palette: &SeasonalPalette) -> [f32; 4] {
+        let mut color = self.get_hsba_color();
+        if self.is_obstacle() {
+            return color;
+        }
+
+        for &(tint_color, strength) in &palette.get_tints(year) {
+            if strength > 0.0 {
+                let c = inter_color([color[0], color[1], color[2]], tint_color, strength);
+                color = [c[0], c[1], c[2], color[3]];
+            }
+        }
+
+        color
+    }
+
     /// Update this tile
     pub fn update(&mut self, time: f64, climate: &Climate) {
         match self {
-            Tile::Water => {}
+            Tile::Water(_) | Tile::Obstacle => {}
             Tile::Land(t) => t.update(time, climate),
         }
     }
 
+    /// Makes this tile act as if it was last updated in year 0, see `Terrain::load_from`.
+    pub(crate) fn restart_clock(&mut self) {
+        if let Tile::Land(t) = self {
+            t.last_update_time = 0.0;
+        }
+    }
+
     /// Adds the given value to the food level if it's possible.
     ///
-    /// This does nothing for water tiles.
+    /// This does nothing for tiles that aren't land.
     pub fn add_food_or_nothing(&mut self, food_to_add: f64) {
         match self {
-            Tile::Water => {}
+            Tile::Water(_) | Tile::Obstacle => {}
             Tile::Land(t) => t.add_food(food_to_add),
         }
     }
 
     /// Removes the given value from the food level.
     ///
-    /// This panics for water tiles since you should never try gaining food from them.
+    /// This panics for water and obstacle tiles since you should never try gaining food from them.
     pub fn remove_food(&mut self, food_to_remove: f64) {
         match self {
-            Tile::Water => {
+            Tile::Water(_) | Tile::Obstacle => {
                 if food_to_remove > 0.0 {
-                    panic!("You called `remove_food` on a water tile, water tiles don't have any food and should not be eaten.")
+                    panic!("You called `remove_food` on a tile that isn't land, only land tiles have food that can be eaten.")
                 }
             }
             Tile::Land(t) => t.remove_food(food_to_remove),
@@ -116,31 +340,105 @@ impl Tile {
     pub fn get_food_multiplier(&self, hue: f64) -> Option<f64> {
         match self {
             // Tile::Water => panic!("You called `get_food_multiplier` on a water tile, water tiles don't have any food and should not be eaten."),
-            Tile::Water => None,
+            Tile::Water(_) | Tile::Obstacle => None,
             Tile::Land(t) => Some(t.get_food_multiplier(hue)),
         }
     }
+
+    /// Returns how poisonous the food of this `Tile` is, 0 for normal food and for tiles that aren't land.
+    pub fn get_toxicity(&self) -> f64 {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0.0,
+            Tile::Land(t) => t.toxicity,
+        }
+    }
+
+    /// Returns the mouth hue that is adapted to the toxin of this `Tile`, 0 for tiles that aren't land.
+    pub fn get_toxin_hue(&self) -> f64 {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0.0,
+            Tile::Land(t) => t.toxin_hue,
+        }
+    }
+
+    /// Makes the food of this `Tile` poisonous to creatures whose mouth hue isn't close to `toxin_hue`, a `toxicity`
+    /// of 0 makes it normal food again. Does nothing for tiles that aren't land.
+    pub fn set_toxin(&mut self, toxicity: f64, toxin_hue: f64) {
+        if let Tile::Land(t) = self {
+            t.toxicity = toxicity.max(0.0);
+            t.toxin_hue = toxin_hue;
+        }
+    }
+
+    /// Returns the energy a creature with `hue` as mouth hue loses for every unit of food it eats here.
+    ///
+    /// Creatures with a mouth hue within `TOXIN_TOLERANCE` of the toxin hue are (partly) adapted and lose less.
+    pub fn get_toxin_damage(&self, hue: f64) -> f64 {
+        match self {
+            Tile::Water(_) | Tile::Obstacle => 0.0,
+            Tile::Land(t) => {
+                let adaptation = (1.0 - (t.toxin_hue - hue).abs() / TOXIN_TOLERANCE).max(0.0);
+
+                t.toxicity * (1.0 - adaptation)
+            }
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LandTile {
     fertility: f64,
     food_level: f64,
     food_type: f64,
 
+    /// When this is set the tile grows food into `stored_food` and releases it every `FRUITING_PERIOD` years, at this
+    /// offset within the period.
+    fruiting_phase: Option<f64>,
+    stored_food: f64,
+
+    /// The climate region this tile is in.
+    region: usize,
+    biome: Biome,
+    /// Between 0 at sea level and 1 on the highest mountains.
+    elevation: f64,
+    /// Between 0 at the equator and 1 at the poles, the further from the equator the colder it is.
+    latitude: f64,
+
+    /// How poisonous the food of this tile is, 0 for normal food. See `Tile::get_toxin_damage`.
+    toxicity: f64,
+    /// The mouth hue of the creatures that are adapted to the toxin.
+    toxin_hue: f64,
+
+    /// The food besides the plants, see `Tile::add_resource`.
+    resources: Vec<Resource>,
+
     last_update_time: f64,
 }
 
 impl LandTile {
     /// Creates a new tile with the given `fertility` and `food_type`.
     ///
-    /// Begins with `food_level` set to `fertility` and `last_update_time` set to `0`.
+    /// Begins with `food_level` set to `fertility` and `last_update_time` set to `0`. The biome is the one of that
+    /// fertility in the middle of the board, the terrain corrects it for the actual position.
     pub fn new(fertility: f64, food_type: f64) -> Self {
         LandTile {
             fertility,
             food_level: fertility,
             food_type,
 
+            fruiting_phase: None,
+            stored_food: 0.0,
+
+            region: 0,
+            biome: Biome::classify(fertility, 0.0),
+            elevation: 0.0,
+            latitude: 0.0,
+
+            toxicity: 0.0,
+            toxin_hue: 0.0,
+
+            resources: Vec::new(),
+
             last_update_time: 0.0,
         }
     }
@@ -151,24 +449,81 @@ impl LandTile {
     fn update(&mut self, time: f64, climate: &Climate) {
         // TODO: clean up this mess!
         if time - self.last_update_time > 0.00001 {
-            let growth_change = climate.get_growth_over_time_range(time, self.last_update_time);
+            let growth_change = climate.get_local_growth_over_time_range(
+                self.region,
+                self.elevation,
+                self.latitude,
+                time,
+                self.last_update_time,
+            );
 
             if growth_change <= 0.0 {
                 let food_to_remove =
                     self.food_level - self.food_level * (growth_change * FOOD_GROWTH_RATE).exp();
                 self.remove_food(food_to_remove);
-            } else if self.food_level < MAX_GROWTH_LEVEL {
-                let new_dist_to_max = (MAX_GROWTH_LEVEL - self.food_level)
-                    * (-growth_change * self.fertility * FOOD_GROWTH_RATE).exp();
+            } else if self.food_level + self.stored_food < MAX_GROWTH_LEVEL {
+                let grown = self.food_level + self.stored_food;
+                let new_dist_to_max = (MAX_GROWTH_LEVEL - grown)
+                    * (-growth_change
+                        * self.fertility
+                        * self.biome.get_growth_multiplier()
+                        * FOOD_GROWTH_RATE)
+                        .exp();
 
-                let food_to_add = MAX_GROWTH_LEVEL - new_dist_to_max - self.food_level;
-                self.add_food(food_to_add);
+                let food_to_add = MAX_GROWTH_LEVEL - new_dist_to_max - grown;
+                if self.fruiting_phase.is_some() {
+                    self.stored_food += food_to_add;
+                } else {
+                    self.add_food(food_to_add);
+                }
+            }
+
+            let time_step = time - self.last_update_time;
+            for r in &mut self.resources {
+                r.grow(growth_change, time_step);
+            }
+
+            if let Some(phase) = self.fruiting_phase {
+                // Release the stored food if a fruiting moment passed since the last update.
+                let period = |t: f64| ((t - phase) / FRUITING_PERIOD).floor();
+                if period(time) > period(self.last_update_time) {
+                    let stored = self.stored_food;
+                    self.add_food(stored);
+                    self.stored_food = 0.0;
+                }
             }
 
             self.last_update_time = time;
         }
     }
 
+    fn get_hsba_color(&self) -> [f32; 4] {
+        let food_color = [self.food_type as f32, 1.0, 1.0];
+
+        if self.food_level < MAX_GROWTH_LEVEL {
+            if self.food_level > 0.0 {
+                let ground = self.biome.get_ground_color();
+                let c = inter_color(ground, COLOR_FERTILE, self.fertility as f32);
+                return inter_color_fixed_hue(
+                    c,
+                    food_color,
+                    (self.food_level / MAX_GROWTH_LEVEL) as f32,
+                    self.food_type as f32,
+                );
+            } else {
+                let ground = self.biome.get_ground_color();
+                return [ground[0], ground[1], ground[2], 1.0];
+            }
+        } else {
+            return inter_color_fixed_hue(
+                food_color,
+                COLOR_BLACK,
+                1.0 - (MAX_GROWTH_LEVEL / self.food_level) as f32,
+                self.food_type as f32,
+            );
+        }
+    }
+
     pub fn get_food_multiplier(&self, hue: f64) -> f64 {
         return 1.0 - (self.food_type - hue).abs() / FOOD_SENSITIVITY;
     }
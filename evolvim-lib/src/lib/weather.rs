@@ -0,0 +1,115 @@
+//! Rain, droughts and storms that make food grow faster or slower in a climate region for a while.
+//!
+//! A `Climate` starts new weather at random while a board runs, how often and how bad is chosen by the
+//! `WeatherConfig` of its `SimConfig`. The random numbers come from the board, so a seeded board always gets the same
+//! weather. Subscribe to `Board::on_weather` to show new weather as it starts, or look at what's going on right now
+//! with `Climate::get_active_weather`.
+
+use crate::constants::*;
+use crate::rng;
+
+/// The kinds of weather, see `get_growth_change`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeatherKind {
+    Rain,
+    Drought,
+    Storm,
+}
+
+impl WeatherKind {
+    pub const fn get_all() -> [Self; 3] {
+        [WeatherKind::Rain, WeatherKind::Drought, WeatherKind::Storm]
+    }
+
+    /// Returns the growth this weather adds every year at a severity of 1, negative if it makes the food wither.
+    pub fn get_growth_change(self) -> f64 {
+        match self {
+            WeatherKind::Rain => WEATHER_RAIN_GROWTH,
+            WeatherKind::Drought => WEATHER_DROUGHT_GROWTH,
+            WeatherKind::Storm => WEATHER_STORM_GROWTH,
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            WeatherKind::Rain => "rain",
+            WeatherKind::Drought => "drought",
+            WeatherKind::Storm => "storm",
+        }
+    }
+}
+
+/// Weather in one climate region from `start` until `end`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WeatherEvent {
+    pub kind: WeatherKind,
+    /// The climate region it's in, see `Tile::get_region`.
+    pub region: usize,
+    pub start: f64,
+    pub end: f64,
+    /// How strong it is, this multiplies `WeatherKind::get_growth_change`.
+    pub severity: f64,
+}
+
+impl WeatherEvent {
+    /// Returns true if this weather is going on at `time`.
+    pub fn is_active(&self, time: f64) -> bool {
+        time >= self.start && time < self.end
+    }
+
+    /// Returns the growth this weather adds to its region from `last_updated` until `time`, see
+    /// `Climate::get_region_growth_over_time_range`.
+    pub fn get_growth_over_time_range(&self, time: f64, last_updated: f64) -> f64 {
+        let overlap = (time.min(self.end) - last_updated.max(self.start)).max(0.0);
+
+        self.kind.get_growth_change() * self.severity * overlap
+    }
+}
+
+/// How often weather happens and how bad it is, part of a `SimConfig`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeatherConfig {
+    /// How many times a year new weather starts on average, 0 turns the weather off.
+    pub frequency: f64,
+    /// Makes all weather stronger or weaker, every event gets between half and one and a half times this.
+    pub severity: f64,
+    /// How many years weather lasts on average.
+    pub duration: f64,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        WeatherConfig {
+            frequency: DEFAULT_WEATHER_FREQUENCY,
+            severity: DEFAULT_WEATHER_SEVERITY,
+            duration: DEFAULT_WEATHER_DURATION,
+        }
+    }
+}
+
+impl WeatherConfig {
+    /// Returns new weather starting at `time` in one of `regions` climate regions if it's time for it, the chance
+    /// goes up with `time_step`.
+    ///
+    /// No random numbers are taken without weather, so turning it off doesn't change anything else.
+    pub(crate) fn roll(&self, time: f64, time_step: f64, regions: usize) -> Option<WeatherEvent> {
+        if self.frequency <= 0.0 || rng::random::<f64>() >= self.frequency * time_step {
+            return None;
+        }
+
+        let kinds = WeatherKind::get_all();
+        let kind = kinds[rng::gen_range(0, kinds.len())];
+        let region = rng::gen_range(0, regions.max(1));
+        let duration = self.duration * (0.5 + rng::random::<f64>());
+        let severity = self.severity * (0.5 + rng::random::<f64>());
+
+        Some(WeatherEvent {
+            kind,
+            region,
+            start: time,
+            end: time + duration,
+            severity,
+        })
+    }
+}
@@ -1,6 +1,7 @@
 extern crate lib_evolvim;
 
 // use lib_evolvim::graphics::*;
+use lib_evolvim::terrain::tile::Tile;
 use lib_evolvim::*;
 
 #[test]
@@ -14,3 +15,1220 @@ fn test_board_update() {
 fn test_board_default_intialise() {
     let _board = Board::<Brain>::default();
 }
+
+#[test]
+fn test_board_greedy_brain() {
+    use std::f64::consts::PI;
+
+    let board_size = (20, 20);
+    let mut board = Board::<GreedyBrain>::new_random_seeded(board_size, 0.1, 5, -0.5, 1.0, 12);
+    // The terrain takes its noise seed from the random generators of this thread, seed those as well.
+    let previous = rng::replace(rng::from_seed(12));
+    board.terrain = Terrain::from_fertility(board_size, 0.1, |_, _| 0.5);
+    rng::replace(previous);
+    let creature = board
+        .creatures
+        .iter()
+        .find(|c| {
+            let c = c.borrow();
+            (3.0..17.0).contains(&c.get_px()) && (3.0..17.0).contains(&c.get_py())
+        })
+        .unwrap()
+        .clone();
+
+    // There's only food right next to the creature, on the tile it's looking at.
+    let (x, y, rotation) = {
+        let c = creature.borrow();
+        (c.get_px(), c.get_py(), c.get_rotation())
+    };
+    let step = PI / 4.0;
+    let angle = (rotation / step).round() * step;
+    let food = (
+        (x + angle.cos() * 1.5).floor() as usize,
+        (y + angle.sin() * 1.5).floor() as usize,
+    );
+    for tx in 0..board_size.0 {
+        for ty in 0..board_size.1 {
+            let tile = board.terrain.get_tile_at_mut((tx, ty));
+            let level = tile.get_food_level();
+            tile.remove_food(level);
+        }
+    }
+    let hue = creature.borrow().get_mouth_hue();
+    *board.terrain.get_tile_at_mut(food) = Tile::new(0.5, hue);
+    board.terrain.get_tile_at_mut(food).add_food_or_nothing(3.0);
+    let distance_to_food = |c: &HLSoftBody<GreedyBrain>| {
+        let c = c.borrow();
+        distance(
+            c.get_px(),
+            c.get_py(),
+            food.0 as f64 + 0.5,
+            food.1 as f64 + 0.5,
+        )
+    };
+    let start = distance_to_food(&creature);
+
+    for _ in 0..40 {
+        board.update(0.001);
+    }
+    // It steers towards the food.
+    assert!(distance_to_food(&creature) < start - 0.3);
+
+    // With food under it, it stays and eats.
+    let here = creature.borrow().get_position().into();
+    let hue = creature.borrow().get_mouth_hue();
+    *board.terrain.get_tile_at_mut(here) = Tile::new(0.5, hue);
+    board.terrain.get_tile_at_mut(here).add_food_or_nothing(3.0);
+    let food_before = board.terrain.get_tile_at(here).get_food_level();
+    let energy_before = creature.borrow().get_energy();
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+    assert!(board.terrain.get_tile_at(here).get_food_level() < food_before);
+    assert!(creature.borrow().get_energy() > energy_before);
+}
+
+#[test]
+fn test_board_mixed_brains() {
+    let mut board = Board::<AnyBrain>::new_random_with_brain_factory(
+        (20, 20),
+        0.1,
+        40,
+        -0.5,
+        1.0,
+        Box::new(AnyBrain::new_random_mixed),
+    );
+
+    for _ in 0..10 {
+        board.update(0.001);
+    }
+
+    let kinds: Vec<BrainKind> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().brain.get_kind())
+        .collect();
+    assert!(kinds.contains(&BrainKind::FeedForward));
+    assert!(kinds.contains(&BrainKind::Greedy));
+}
+
+#[test]
+fn test_board_validate() {
+    let mut board = Board::<Brain>::default();
+
+    assert_eq!(board.validate(), Vec::<String>::new());
+
+    // A brain made for other eyes doesn't fit the board.
+    let one_ray = Vision {
+        ray_amount: 1,
+        ..Vision::default()
+    };
+    board.add_to_seed_bank(Brain::new_random_with_vision(&one_ray));
+    assert_eq!(
+        board.validate(),
+        vec!["a brain has inputs for 1 rays but the vision casts 3".to_string()]
+    );
+
+    // Greedy brains never mate, every one of them has the same problem but it's only reported once.
+    let config = SimConfig {
+        reproduction: ReproductionMode::Sexual,
+        ..SimConfig::default()
+    };
+    let greedy = Board::<GreedyBrain>::new_random_with_config((20, 20), config).unwrap();
+    assert_eq!(greedy.validate().len(), 1);
+    assert!(greedy.validate()[0].contains("mate"));
+    assert_eq!(
+        Board::<GreedyBrain>::default().validate(),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn test_board_state_hash() {
+    let mut board = Board::<Brain>::default();
+    board.update(0.001);
+
+    let hash = board.state_hash();
+    assert_eq!(hash, board.state_hash());
+
+    // Selecting a creature doesn't change the simulation.
+    board.select_oldest();
+    assert_eq!(hash, board.state_hash());
+
+    board.update(0.001);
+    assert_ne!(hash, board.state_hash());
+
+    // The random numbers of a seeded board are part of its state.
+    board.set_seed(1);
+    let hash = board.state_hash();
+    assert_eq!(hash, board.state_hash());
+    board.set_seed(2);
+    assert_ne!(hash, board.state_hash());
+}
+
+#[test]
+fn test_board_energy_flows() {
+    use lib_evolvim::energy_flow::{Compartment, EnergyFlowLog};
+
+    fn creature_energy(board: &Board<Brain>) -> f64 {
+        board
+            .creatures
+            .iter()
+            .map(|c| c.borrow().get_energy())
+            .sum()
+    }
+
+    let mut board = Board::<Brain>::default();
+    let mut log = EnergyFlowLog::new(2);
+
+    for _ in 0..10 {
+        let before = creature_energy(&board);
+        board.update(0.001);
+        log.tick(&board).unwrap();
+
+        // Everything that flowed into the creatures should be accounted for in their energy.
+        let flows = board.get_energy_flows();
+        let net = flows.get_net_flow(Compartment::Herbivores)
+            + flows.get_net_flow(Compartment::Predators);
+        assert!((creature_energy(&board) - before - net).abs() < 1e-6);
+    }
+
+    assert_eq!(log.get_flows().count(), 5);
+
+    let total = log.get_total();
+    assert!(total.get(Compartment::Herbivores, Compartment::Decay) > 0.0);
+
+    let mut csv = Vec::new();
+    total.write_matrix_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("from,sun,immigration,tiles,herbivores,predators,decay\n"));
+    assert_eq!(csv.lines().count(), 7);
+}
+
+#[test]
+fn test_board_seeded() {
+    let new_board = |seed| Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, seed);
+    let mut a = new_board(7);
+    let mut b = new_board(7);
+    let c = new_board(8);
+
+    assert_eq!(a.state_hash(), b.state_hash());
+    assert_ne!(a.state_hash(), c.state_hash());
+
+    for _ in 0..50 {
+        a.update(0.001);
+        // Random numbers used in between shouldn't change anything.
+        let _: f64 = lib_evolvim::rng::random();
+        b.update(0.001);
+    }
+
+    assert_eq!(a.state_hash(), b.state_hash());
+}
+
+#[test]
+fn test_board_seeded_with_config() {
+    let config = SimConfig {
+        terrain_generator: TerrainAlgorithm::Archipelago,
+        day_length: 0.01,
+        creature_minimum: 30,
+        ..SimConfig::default()
+    };
+    let new_board = |seed| {
+        Board::<Brain>::new_random_with_config_seeded((30, 30), config.clone(), seed).unwrap()
+    };
+    let mut a = new_board(7);
+    let mut b = new_board(7);
+
+    // Everything is generated with the config, not only the settings `new_random_seeded` takes.
+    assert_eq!(a.get_config(), &config);
+    assert_eq!(a.climate.get_day_length(), 0.01);
+    let default = Board::<Brain>::new_random_seeded((30, 30), 0.1, 30, -0.5, 1.0, 7);
+    let water = |board: &Board<Brain>| -> Vec<bool> {
+        (0..30 * 30)
+            .map(|i| board.terrain.get_tile_at((i % 30, i / 30)).is_water())
+            .collect()
+    };
+    assert_ne!(water(&a), water(&default));
+
+    assert_eq!(a.state_hash(), b.state_hash());
+    for _ in 0..20 {
+        a.update(0.001);
+        b.update(0.001);
+    }
+    assert_eq!(a.state_hash(), b.state_hash());
+
+    let invalid = SimConfig {
+        day_length: -1.0,
+        ..SimConfig::default()
+    };
+    assert!(Board::<Brain>::new_random_with_config_seeded((30, 30), invalid, 7).is_err());
+}
+
+#[test]
+fn test_board_history() {
+    use lib_evolvim::board_history::{BoardHistory, SPECIES_AMOUNT};
+
+    let mut board = Board::<Brain>::default();
+    board.set_history(BoardHistory::new(
+        2,
+        RetentionPolicy::KeepCount { entries: 3 },
+    ));
+
+    for _ in 0..10 {
+        board.update(0.001);
+    }
+
+    let history = board.get_history();
+    assert_eq!(history.len(), 3);
+
+    let (time, last) = history.get_last().unwrap();
+    assert!((time - board.get_time()).abs() < 1e-9);
+    assert_eq!(last.population, board.get_population_size());
+    assert_eq!(last.species.len(), SPECIES_AMOUNT);
+    assert_eq!(last.species.iter().sum::<usize>(), last.population);
+    assert!(last.median_energy > 0.0);
+
+    assert_eq!(history.get_range(0.0075, 0.0085).count(), 1);
+
+    let mut csv = Vec::new();
+    history.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("time,population,mean_energy,median_energy,mean_age,births,deaths,"));
+    assert_eq!(csv.lines().count(), 4);
+}
+
+#[test]
+fn test_board_hooks() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 3);
+    let deaths = Rc::new(RefCell::new(0));
+    let eaten = Rc::new(RefCell::new(0.0));
+    let seasons = Rc::new(RefCell::new(Vec::new()));
+
+    let d = deaths.clone();
+    board.on_death(move |_, _, _| *d.borrow_mut() += 1);
+    let e = eaten.clone();
+    board.on_eat(move |_, _, energy| *e.borrow_mut() += energy);
+    let s = seasons.clone();
+    board.on_season_change(move |_, season| s.borrow_mut().push(season.to_string()));
+
+    for _ in 0..300 {
+        board.update(0.001);
+    }
+
+    assert_eq!(
+        *deaths.borrow(),
+        board.get_deaths_by_predation()
+            + board.get_deaths_by_starvation()
+            + board.get_deaths_by_old_age()
+    );
+    assert!(*eaten.borrow() > 0.0);
+    assert_eq!(*seasons.borrow(), vec!["Spring".to_string()]);
+
+    board.clear_hooks();
+    board.update(0.001);
+}
+
+#[test]
+fn test_board_repair() {
+    use lib_evolvim::repair::Inconsistency;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 5);
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+    assert_eq!(board.find_inconsistencies(), Vec::new());
+
+    // A creature that died without anyone cleaning up after it.
+    let dead = board.creatures.remove(0);
+    board.selected_creature.select(dead.borrow().get_id());
+    let population = board.get_population_size();
+
+    let found = board.find_inconsistencies();
+    assert!(found.contains(&Inconsistency::DanglingSelection));
+    assert!(found.iter().any(|i| match i {
+        Inconsistency::SbipOrphan { .. } => true,
+        _ => false,
+    }));
+    assert!(!board.validate().is_empty());
+
+    assert_eq!(board.repair(), found);
+    assert_eq!(board.find_inconsistencies(), Vec::new());
+    assert!(board.selected_creature.is_empty());
+    assert_eq!(board.get_population_size(), population);
+
+    board.update(0.001);
+}
+
+#[test]
+fn test_board_run_for() {
+    use lib_evolvim::run::RunOptions;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 11);
+    let summary = board.run_for(0.05, &RunOptions::default());
+
+    assert_eq!(summary.updates, 50);
+    assert!(!summary.aborted);
+    assert!((summary.end_time - 0.05).abs() < 1e-9);
+    assert_eq!(summary.end_population, board.get_population_size());
+    assert!(summary.min_population <= summary.max_population);
+    assert!(summary.mean_population >= summary.min_population as f64);
+    assert_eq!(summary.births, board.get_births());
+
+    // Running headless is the same as updating by hand.
+    let mut other = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 11);
+    for _ in 0..50 {
+        other.update(0.001);
+    }
+    assert_eq!(board.state_hash(), other.state_hash());
+
+    let abort = Arc::new(AtomicBool::new(true));
+    let summary = board.run_for(1.0, &RunOptions::default().with_abort(abort));
+    assert!(summary.aborted);
+    assert_eq!(summary.updates, 0);
+}
+
+#[test]
+fn test_board_save_and_continue() {
+    let path = std::env::temp_dir().join("evolvim_test_save_and_continue.bin");
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 13);
+    board.update(0.001);
+    let creature = board.creatures[0].borrow().get_id();
+    board.selected_creature.select(creature);
+
+    board.save_to(&path).unwrap();
+    let mut loaded = Board::<Brain>::load_from(&path).unwrap();
+    // The random number generators aren't saved.
+    assert_ne!(loaded.state_hash(), board.state_hash());
+    loaded.set_seed(14);
+    board.set_seed(14);
+    assert_eq!(loaded.state_hash(), board.state_hash());
+
+    // The board is still ours after saving.
+    board.update(0.001);
+    board.save_to(&path).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_board_save_formats() {
+    use lib_evolvim::serde_structs::SaveFormat;
+
+    let mut board = Board::<Brain>::new_random_seeded((20, 20), 0.1, 20, -0.5, 1.0, 21);
+    board.update(0.001);
+
+    for &name in &[
+        "evolvim_test_save_format.ron",
+        "evolvim_test_save_format.json",
+    ] {
+        let path = std::env::temp_dir().join(name);
+        let format = SaveFormat::from_path(&path);
+        assert_ne!(format, SaveFormat::Bincode);
+
+        board.save_to_with_format(&path, format).unwrap();
+        let mut loaded = Board::<Brain>::load_from_with_format(&path, format).unwrap();
+        loaded.set_seed(21);
+        board.set_seed(21);
+        assert_eq!(loaded.state_hash(), board.state_hash());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+#[cfg(feature = "compression")]
+fn test_board_save_compressed() {
+    use lib_evolvim::serde_structs::SaveFormat;
+
+    let bin = std::env::temp_dir().join("evolvim_test_save_compressed.bin");
+    let gz = std::env::temp_dir().join("evolvim_test_save_compressed.bin.gz");
+    assert_eq!(SaveFormat::from_path(&gz), SaveFormat::Gzip);
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 22);
+    board.update(0.001);
+
+    board.save_to(&bin).unwrap();
+    board.save_to_with_format(&gz, SaveFormat::Gzip).unwrap();
+    let mut loaded = Board::<Brain>::load_from_with_format(&gz, SaveFormat::Gzip).unwrap();
+    loaded.set_seed(22);
+    board.set_seed(22);
+    assert_eq!(loaded.state_hash(), board.state_hash());
+
+    let size = |p: &std::path::Path| std::fs::metadata(p).unwrap().len();
+    assert!(size(&gz) < size(&bin));
+
+    std::fs::remove_file(&bin).unwrap();
+    std::fs::remove_file(&gz).unwrap();
+}
+
+#[test]
+fn test_board_creature_queries() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 60, -0.5, 1.0, 23);
+    board.update(0.001);
+
+    let (id, px, py) = {
+        let c = board.creatures[0].borrow();
+        (c.get_id(), c.get_px(), c.get_py())
+    };
+    let found = board.get_creature_by_id(id).unwrap();
+    assert_eq!(found.borrow().get_id(), id);
+    assert!(board.get_creature_by_id(usize::max_value()).is_none());
+
+    let radius = 5.0;
+    let within = board.get_creatures_within((px, py), radius);
+    assert_eq!(within[0].borrow().get_id(), id);
+    let expected = board.find_creatures(|c| (c.get_px() - px).hypot(c.get_py() - py) <= radius);
+    assert_eq!(within.len(), expected.len());
+    for c in &expected {
+        assert!(within.contains(c));
+    }
+
+    let energetic = board.find_creatures(|c| c.get_energy() > 1.0);
+    assert!(energetic.iter().all(|c| c.borrow().get_energy() > 1.0));
+    assert_eq!(board.find_creatures(|_| true).len(), board.creatures.len());
+}
+
+#[test]
+fn test_board_select_multiple() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 24);
+    board.update(0.001);
+    let ids: Vec<usize> = board.creatures[..3]
+        .iter()
+        .map(|c| c.borrow().get_id())
+        .collect();
+
+    board.selected_creature.select(ids[0]);
+    board.selected_creature.add(ids[1]);
+    board.selected_creature.toggle(ids[2]);
+    assert_eq!(board.selected_creature.get_ids(), &ids[..]);
+    assert_eq!(board.selected_creature.get_primary(), Some(ids[2]));
+    assert_eq!(board.get_selected_creatures().len(), 3);
+
+    board.selected_creature.toggle(ids[2]);
+    assert!(!board.selected_creature.is_selected(ids[2]));
+    assert_eq!(
+        board.get_selected_creature().unwrap().borrow().get_id(),
+        ids[1]
+    );
+
+    // Creatures that die are removed from the selection.
+    board
+        .get_creature_by_id(ids[1])
+        .unwrap()
+        .borrow_mut()
+        .lose_energy(1000.0);
+    board.update(0.001);
+    assert_eq!(board.selected_creature.get_ids(), &ids[..1]);
+}
+
+#[test]
+fn test_board_load_errors() {
+    use lib_evolvim::serde_structs::SaveFormat;
+
+    let path = std::env::temp_dir().join("evolvim_test_load_errors.json");
+    let _ = std::fs::remove_file(&path);
+
+    match Board::<Brain>::load_from(&path) {
+        Err(EvolvimError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+        _ => panic!("loading a missing file should fail with an io error"),
+    }
+
+    std::fs::write(&path, "{\"version\": 3}").unwrap();
+    match Board::<Brain>::load_from_with_format(&path, SaveFormat::Json) {
+        Err(EvolvimError::CorruptSave(_)) => {}
+        _ => panic!("loading a broken file should fail with CorruptSave"),
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_board_spawn_policy() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 20, -0.5, 1.0, 25);
+    assert_eq!(board.get_spawn_policy(), SpawnPolicy::Random);
+
+    let seed = Brain::new_random();
+    let threshold = seed.get_birth_threshold();
+    board.add_to_seed_bank(seed);
+    board.set_spawn_policy(SpawnPolicy::FromSeedBank);
+
+    for c in &board.creatures {
+        let mut c = c.borrow_mut();
+        let energy = c.get_energy();
+        c.lose_energy(energy);
+    }
+    board.update(0.001);
+
+    assert_eq!(board.creatures.len(), 20);
+    for c in &board.creatures {
+        assert_eq!(c.borrow().brain.get_birth_threshold(), threshold);
+    }
+
+    board.clear_seed_bank();
+    assert!(board.get_seed_bank().is_empty());
+    for &policy in &[SpawnPolicy::CloneRandomSurvivor, SpawnPolicy::MutatedClone] {
+        board.set_spawn_policy(policy);
+        for c in &board.creatures[..10] {
+            let mut c = c.borrow_mut();
+            let energy = c.get_energy();
+            c.lose_energy(energy);
+        }
+        board.update(0.001);
+        assert!(board.creatures.len() >= 20);
+    }
+}
+
+#[test]
+fn test_board_config() {
+    let config = SimConfig {
+        creature_minimum: 25,
+        metabolism_energy: 0.01,
+        ..SimConfig::default()
+    };
+    let mut board = Board::<Brain>::new_random_with_config((30, 30), config.clone()).unwrap();
+    assert_eq!(board.get_config(), &config);
+    assert_eq!(board.get_population_size(), 25);
+
+    let invalid = SimConfig {
+        object_timesteps_per_year: 0.0,
+        ..SimConfig::default()
+    };
+    match Board::<Brain>::new_random_with_config((30, 30), invalid) {
+        Err(EvolvimError::InvalidConfig(_)) => {}
+        _ => panic!("an invalid config should be refused"),
+    }
+
+    // The creature minimum can be changed while running, the temperatures can't.
+    let mut changed = board.get_config().clone();
+    changed.creature_minimum = 35;
+    board.set_config(changed.clone()).unwrap();
+    board.update(0.001);
+    assert!(board.get_population_size() >= 35);
+
+    changed.max_temp += 1.0;
+    assert!(board.set_config(changed).is_err());
+    assert_eq!(board.get_creature_minimum(), 35);
+
+    let path = std::env::temp_dir().join("evolvim_test_config.bin");
+    board.save_to(&path).unwrap();
+    let loaded = Board::<Brain>::load_from(&path).unwrap();
+    assert_eq!(loaded.get_config(), board.get_config());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_board_extinction() {
+    use lib_evolvim::events::ExtinctionCause;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn starve(board: &mut Board<Brain>) {
+        for c in &board.creatures {
+            let mut c = c.borrow_mut();
+            let energy = c.get_energy();
+            c.lose_energy(energy);
+        }
+        board.update(0.001);
+    }
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 20, -0.5, 1.0, 26);
+    let extinctions = Rc::new(RefCell::new(Vec::new()));
+    let log = extinctions.clone();
+    board.on_extinction(move |_, cause| log.borrow_mut().push(cause));
+
+    board.update(0.001);
+    assert!(extinctions.borrow().is_empty());
+
+    // The creature minimum is kept up as usual.
+    starve(&mut board);
+    assert_eq!(*extinctions.borrow(), vec![ExtinctionCause::NoCreatures]);
+    assert_eq!(board.get_population_size(), 20);
+
+    let seed = Brain::new_random();
+    let threshold = seed.get_birth_threshold();
+    board.add_to_seed_bank(seed);
+    board.set_extinction_policy(ExtinctionPolicy::ReseedFromSeedBank);
+    starve(&mut board);
+    for c in &board.creatures {
+        assert_eq!(c.borrow().brain.get_birth_threshold(), threshold);
+    }
+
+    let food = board.terrain.get_total_food();
+    board.set_extinction_policy(ExtinctionPolicy::RestartTerrain);
+    starve(&mut board);
+    assert_ne!(food, board.terrain.get_total_food());
+    assert_eq!(board.get_population_size(), 20);
+
+    board.set_extinction_policy(ExtinctionPolicy::Halt);
+    starve(&mut board);
+    assert_eq!(extinctions.borrow().len(), 4);
+    assert!(board.is_paused());
+    let time = board.get_time();
+    board.update(0.001);
+    assert_eq!(board.get_time(), time);
+
+    board.resume();
+    board.update(0.001);
+    assert!(board.get_time() > time);
+}
+
+#[test]
+fn test_board_pause() {
+    use lib_evolvim::run::RunOptions;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 20, -0.5, 1.0, 27);
+    let mut other = Board::<Brain>::new_random_seeded((30, 30), 0.1, 20, -0.5, 1.0, 27);
+
+    board.pause();
+    assert!(board.is_paused());
+    board.update(0.001);
+    assert_eq!(board.get_time(), 0.0);
+    let summary = board.run_for(1.0, &RunOptions::default());
+    assert!(summary.paused);
+    assert_eq!(summary.updates, 0);
+
+    // Stepping works while paused and is the same as updating.
+    board.step_once(0.001);
+    other.update(0.001);
+    assert!(board.is_paused());
+    assert_eq!(board.state_hash(), other.state_hash());
+
+    board.resume();
+    board.update(0.001);
+    other.update(0.001);
+    assert_eq!(board.state_hash(), other.state_hash());
+}
+
+#[test]
+fn test_board_stats() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 28);
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+
+    let stats = board.stats();
+    let time = board.get_time();
+    let energies: Vec<f64> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().get_energy())
+        .collect();
+    let oldest = board
+        .get_creatures_sorted_by(CreatureMetric::Age)
+        .first()
+        .map(|c| c.borrow().get_age(time));
+
+    assert_eq!(stats.time, time);
+    assert_eq!(stats.population, board.get_population_size());
+    assert!(
+        (stats.mean_energy - energies.iter().sum::<f64>() / energies.len() as f64).abs() < 1e-9
+    );
+    assert_eq!(
+        stats.max_energy,
+        energies.iter().cloned().fold(0.0, f64::max)
+    );
+    let oldest_creature = board
+        .get_creature_by_id(stats.oldest_creature.unwrap())
+        .unwrap();
+    assert_eq!(Some(oldest_creature.borrow().get_age(time)), oldest);
+    assert!(stats.mean_age > 0.0 && stats.mean_age <= oldest.unwrap());
+    assert!((stats.total_food - board.terrain.get_total_food()).abs() < 1e-6);
+    assert_eq!(stats.temperature, board.climate.get_temperature());
+}
+
+#[test]
+fn test_board_spawned_creatures_in_sbip() {
+    // On a small board some creatures are bound to cover the corner at (0, 0).
+    for seed in 0..10 {
+        let mut board = Board::<Brain>::new_random_seeded((8, 8), 0.1, 30, -0.5, 1.0, seed);
+        assert_eq!(board.find_inconsistencies(), vec![]);
+
+        board.update(0.001);
+        assert_eq!(board.find_inconsistencies(), vec![]);
+    }
+}
+
+#[test]
+fn test_board_leaderboard() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 29);
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+    let time = board.get_time();
+
+    for &metric in &[
+        CreatureMetric::Age,
+        CreatureMetric::Energy,
+        CreatureMetric::Offspring,
+        CreatureMetric::Distance,
+    ] {
+        let leaderboard = board.get_leaderboard(metric, 5);
+        let values: Vec<f64> = leaderboard
+            .iter()
+            .map(|c| metric.get_value(&c.borrow(), time))
+            .collect();
+
+        assert_eq!(leaderboard.len(), 5);
+        assert!(values.windows(2).all(|w| w[0] >= w[1]));
+        assert!(board
+            .creatures
+            .iter()
+            .all(|c| metric.get_value(&c.borrow(), time) <= values[0]));
+
+        board.select_best(metric);
+        assert_eq!(
+            board.selected_creature.get_primary(),
+            Some(leaderboard[0].borrow().get_id())
+        );
+    }
+
+    // Creatures moved around.
+    assert!(board
+        .get_leaderboard(CreatureMetric::Distance, 1)
+        .iter()
+        .all(|c| c.borrow().get_distance_traveled() > 0.0));
+    assert_eq!(
+        board.get_leaderboard(CreatureMetric::Age, 1000).len(),
+        board.get_population_size()
+    );
+}
+
+#[test]
+fn test_board_remove_dead_creatures() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 33);
+    board.update(0.001);
+
+    let ids: Vec<usize> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().get_id())
+        .collect();
+    let killed: Vec<usize> = ids.iter().cloned().step_by(3).collect();
+    for c in &board.creatures {
+        if killed.contains(&c.borrow().get_id()) {
+            c.borrow_mut().lose_energy(1000.0);
+        }
+    }
+    board.selected_creature.select(killed[1]);
+    board.update(0.001);
+
+    let after: Vec<usize> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().get_id())
+        .collect();
+    assert!(killed.iter().all(|id| !after.contains(id)));
+    // The survivors keep their order, spawned creatures come after them.
+    let survivors: Vec<usize> = after
+        .iter()
+        .cloned()
+        .filter(|id| ids.contains(id))
+        .collect();
+    let expected: Vec<usize> = ids
+        .iter()
+        .cloned()
+        .filter(|id| survivors.contains(id))
+        .collect();
+    assert_eq!(survivors, expected);
+    assert_eq!(&after[..survivors.len()], &survivors[..]);
+
+    assert!(board.selected_creature.is_empty());
+    assert_eq!(board.find_inconsistencies(), vec![]);
+}
+
+#[test]
+fn test_creatures_die_of_old_age() {
+    use lib_evolvim::constants::DEFAULT_LIFESPAN;
+    use lib_evolvim::events::DeathCause;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 8);
+    for c in &board.creatures {
+        let lifespan = c.borrow().get_lifespan();
+        assert!(lifespan >= 0.5 * DEFAULT_LIFESPAN && lifespan <= 1.5 * DEFAULT_LIFESPAN);
+    }
+
+    // Creatures only start to decline near the end of their lifespan.
+    let old = board.creatures[0].clone();
+    let (id, birth) = {
+        let mut c = old.borrow_mut();
+        c.set_lifespan(0.02);
+        c.add_energy(10.0);
+        (c.get_id(), c.get_birth_time())
+    };
+    assert_eq!(old.borrow().get_senescence(birth + 0.01), 0.0);
+    assert!((old.borrow().get_senescence(birth + 0.0175) - 0.5).abs() < 1e-9);
+    assert_eq!(old.borrow().get_senescence(birth + 0.03), 1.0);
+
+    let causes = Rc::new(RefCell::new(Vec::new()));
+    let hook_causes = causes.clone();
+    board.on_death(move |_, c, cause| hook_causes.borrow_mut().push((c.get_id(), cause)));
+    for _ in 0..25 {
+        board.update(0.001);
+    }
+
+    // It had plenty of energy left, but it got too old.
+    assert!(board.get_creature_by_id(id).is_none());
+    assert!(causes.borrow().contains(&(id, DeathCause::OldAge)));
+    assert!(board.get_deaths_by_old_age() >= 1);
+    assert_eq!(
+        board.get_deaths(),
+        board.get_deaths_by_predation()
+            + board.get_deaths_by_starvation()
+            + board.get_deaths_by_old_age()
+    );
+}
+
+#[test]
+fn test_body_size() {
+    let board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 8);
+    let config = board.get_config().clone();
+    let (mut small, mut big) = (
+        board.creatures[0].borrow_mut(),
+        board.creatures[1].borrow_mut(),
+    );
+    let energy = small.get_energy().max(big.get_energy());
+    for c in &mut [&mut small, &mut big] {
+        let missing = energy - c.get_energy();
+        c.add_energy(missing);
+    }
+    small.set_body_size(0.0);
+    big.set_body_size(2.0);
+    assert!(small.get_body_size() > 0.0);
+    assert!(big.get_energy_capacity() > small.get_energy_capacity());
+
+    // A big body takes longer to get going and costs more to keep up.
+    small.accelerate(1.0, 0.1);
+    big.accelerate(1.0, 0.1);
+    assert!(big.get_total_velocity() < small.get_total_velocity());
+
+    let (small_energy, big_energy) = (small.get_energy(), big.get_energy());
+    small.metabolize(0.1, 1.0, &config);
+    big.metabolize(0.1, 1.0, &config);
+    assert!(big_energy - big.get_energy() > small_energy - small.get_energy());
+
+    // A body that's full doesn't eat anymore.
+    let capacity = small.get_energy_capacity();
+    small.add_energy(capacity);
+    assert_eq!(small.get_appetite(), 0.0);
+    assert!(big.get_appetite() > 0.0);
+    let mut tile = Tile::new(0.5, 0.3);
+    tile.update(1.0, &board.climate);
+    let food = tile.get_food_level();
+    small.eat(1.0, 0.1, 0.0, &board.climate, &mut tile);
+    assert_eq!(tile.get_food_level(), food);
+}
+
+#[test]
+fn test_sexual_reproduction() {
+    use lib_evolvim::constants::MATE_WILLINGNESS;
+    use lib_evolvim::run::RunOptions;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 8);
+    let set_mode = |board: &mut Board<Brain>, reproduction| {
+        let config = SimConfig {
+            reproduction,
+            ..board.get_config().clone()
+        };
+        board.set_config(config).unwrap();
+    };
+    set_mode(&mut board, ReproductionMode::Sexual);
+    // Everyone has to be old enough.
+    board.run_for(0.02, &RunOptions::default());
+
+    let willingness = |c: &HLSoftBody<Brain>| c.borrow().brain.wants_help_birth();
+    let mate = board
+        .creatures
+        .iter()
+        .max_by(|a, b| willingness(a).partial_cmp(&willingness(b)).unwrap())
+        .unwrap()
+        .clone();
+    assert!(willingness(&mate) >= MATE_WILLINGNESS);
+    let lonely = board
+        .creatures
+        .iter()
+        .find(|c| {
+            let position = (c.borrow().get_px(), c.borrow().get_py());
+            board.get_creatures_within(position, 3.0).len() == 1
+        })
+        .unwrap()
+        .clone();
+    let request_birth = |board: &mut Board<Brain>| {
+        {
+            let mut c = lonely.borrow_mut();
+            c.add_energy(10.0);
+            c.request_birth();
+        }
+        board.update(0.001);
+    };
+
+    // Without a mate there's no baby.
+    for _ in 0..5 {
+        request_birth(&mut board);
+    }
+    assert_eq!(lonely.borrow().get_offspring_count(), 0);
+
+    // Unless the creature can do it on its own.
+    set_mode(&mut board, ReproductionMode::Asexual);
+    request_birth(&mut board);
+    assert_eq!(lonely.borrow().get_offspring_count(), 1);
+    assert_eq!(
+        board.creatures.last().unwrap().borrow().get_parent_ids(),
+        &[lonely.borrow().get_id()]
+    );
+
+    // Next to a willing mate the baby gets two parents.
+    set_mode(&mut board, ReproductionMode::Sexual);
+    let board_size = board.get_board_size();
+    let mut updates = 0;
+    while lonely.borrow().get_offspring_count() == 1 && updates < 100 {
+        {
+            let (px, py) = (mate.borrow().get_px(), mate.borrow().get_py());
+            let mut c = lonely.borrow_mut();
+            c.set_body_x(px + 0.01, board_size.0);
+            c.set_body_y(py, board_size.1);
+        }
+        lonely.set_sbip(&mut board.soft_bodies_in_positions, board_size);
+        request_birth(&mut board);
+        updates += 1;
+    }
+    assert_eq!(lonely.borrow().get_offspring_count(), 2);
+    assert_eq!(
+        board.creatures.last().unwrap().borrow().get_parent_ids(),
+        &[mate.borrow().get_id(), lonely.borrow().get_id()]
+    );
+}
+
+#[test]
+fn test_eggs() {
+    use lib_evolvim::constants::EGG_EFFICIENCY;
+    use lib_evolvim::run::RunOptions;
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 8);
+    let config = SimConfig {
+        egg_incubation: 0.01,
+        ..board.get_config().clone()
+    };
+    board.set_config(config).unwrap();
+    board.run_for(0.02, &RunOptions::default());
+
+    // Lay two eggs.
+    let parent = board.creatures[0].clone();
+    let births = board.get_births();
+    while board.get_eggs().len() < 2 {
+        {
+            let mut c = parent.borrow_mut();
+            c.add_energy(10.0);
+            c.request_birth();
+        }
+        board.update(0.001);
+    }
+    assert_eq!(board.get_births(), births);
+    assert_eq!(board.stats().eggs, 2);
+    let parent_id = parent.borrow().get_id();
+    let egg = &board.get_eggs()[0];
+    assert_eq!(egg.get_parent_ids().last(), Some(&parent_id));
+    assert!((egg.get_hatch_time() - egg.get_laid_time() - 0.01).abs() < 1e-9);
+    assert!(!egg.is_ready_to_hatch(board.get_time()));
+    // Eggs lie on their tile like the creatures do.
+    let (x, y) = egg.get_tile();
+    assert!(board
+        .soft_bodies_in_positions
+        .get_eggs_at(x, y)
+        .contains(egg));
+
+    // Eggs are saved.
+    let path = std::env::temp_dir().join("evolvim_test_eggs.bin");
+    board.save_to(&path).unwrap();
+    let mut loaded = Board::<Brain>::load_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.get_eggs().len(), 2);
+    assert_eq!(
+        loaded.soft_bodies_in_positions.get_eggs_at(x, y).len(),
+        board.soft_bodies_in_positions.get_eggs_at(x, y).len()
+    );
+    loaded.set_seed(8);
+    board.set_seed(8);
+    assert_eq!(loaded.state_hash(), board.state_hash());
+
+    // Eating an egg gives its energy to the eater.
+    let eater = board.creatures[1].clone();
+    let energy = eater.borrow().get_energy();
+    let gained = eater.borrow_mut().eat_egg(1.0);
+    assert!(gained > 0.0 && gained <= EGG_EFFICIENCY);
+    assert!((eater.borrow().get_energy() - energy - gained).abs() < 1e-9);
+
+    // The first egg hatches, the baby's life starts then.
+    let hatch_time = board.get_eggs()[0].get_hatch_time();
+    while board.get_births() == births {
+        board.update(0.001);
+    }
+    let baby = board.creatures.last().unwrap().borrow();
+    assert!(baby.get_birth_time() >= hatch_time);
+    assert!(baby.get_parent_ids().contains(&parent_id));
+    assert_eq!(board.get_name_of(baby.get_id()), Some(baby.get_name()));
+    // Other creatures may have laid eggs by now, but the hatched one is gone.
+    assert!(board
+        .get_eggs()
+        .iter()
+        .all(|egg| egg.get_hatch_time() != hatch_time));
+    assert!(board
+        .soft_bodies_in_positions
+        .get_eggs_at(x, y)
+        .iter()
+        .all(|egg| egg.get_hatch_time() != hatch_time));
+}
+
+#[test]
+fn test_board_resize() {
+    let mut board = Board::<Brain>::new_random_seeded((20, 20), 0.1, 30, -0.5, 1.0, 34);
+    board.update(0.001);
+    let fertility = |board: &Board<Brain>, pos| board.terrain.get_tile_at(pos).get_fertility();
+    let old_fertility: Vec<f64> = (0..20).map(|i| fertility(&board, (i, 19 - i))).collect();
+
+    board.resize((30, 25), TerrainFill::Perlin).unwrap();
+    assert_eq!(board.get_board_size(), (30, 25));
+    assert_eq!(
+        (0..20)
+            .map(|i| fertility(&board, (i, 19 - i)))
+            .collect::<Vec<f64>>(),
+        old_fertility
+    );
+    assert_eq!(board.validate(), Vec::<String>::new());
+    assert_eq!(board.find_inconsistencies(), vec![]);
+    for _ in 0..10 {
+        board.update(0.001);
+    }
+
+    board.resize((10, 12), TerrainFill::Water).unwrap();
+    assert_eq!(board.get_board_size(), (10, 12));
+    for c in &board.creatures {
+        let c = c.borrow();
+        assert!(c.get_px() <= 10.0 && c.get_py() <= 12.0);
+    }
+    assert_eq!(board.validate(), Vec::<String>::new());
+    assert_eq!(board.find_inconsistencies(), vec![]);
+
+    board.resize((15, 12), TerrainFill::Water).unwrap();
+    assert!((10..15).all(|x| board.terrain.get_tile_at((x, 5)).is_water()));
+    board.update(0.001);
+
+    assert!(board.resize((0, 12), TerrainFill::Barren).is_err());
+    assert_eq!(board.get_board_size(), (15, 12));
+}
+
+#[test]
+fn test_board_rng_streams() {
+    use lib_evolvim::rng::{self, Stream};
+
+    // Spawning more creatures doesn't change the terrain that is generated later on.
+    let mut a = Board::<Brain>::new_random_seeded((20, 20), 0.1, 10, -0.5, 1.0, 35);
+    let mut b = Board::<Brain>::new_random_seeded((20, 20), 0.1, 60, -0.5, 1.0, 35);
+    for _ in 0..5 {
+        a.update(0.001);
+        b.update(0.001);
+    }
+    a.resize((30, 30), TerrainFill::Perlin).unwrap();
+    b.resize((30, 30), TerrainFill::Perlin).unwrap();
+    for x in 0..30 {
+        for y in 20..30 {
+            assert_eq!(
+                a.terrain.get_tile_at((x, y)).get_fertility(),
+                b.terrain.get_tile_at((x, y)).get_fertility()
+            );
+        }
+    }
+
+    let previous = rng::replace(rng::from_seed(36));
+    let first: u64 = rng::with_stream(Stream::Terrain, rng::random);
+    rng::replace(rng::from_seed(36));
+    for _ in 0..10 {
+        let _: u64 = rng::with_stream(Stream::Mutation, rng::random);
+        let _: u64 = rng::random();
+    }
+    assert_eq!(first, rng::with_stream(Stream::Terrain, rng::random::<u64>));
+    rng::replace(previous);
+}
+
+#[test]
+fn test_board_prepare_for_drawing_at() {
+    let mut board = Board::<Brain>::default();
+    // Not a whole year, the temperature turns around then which updates all tiles.
+    for _ in 0..120 {
+        board.update(0.01);
+    }
+
+    let (width, height) = board.get_board_size();
+    // Chunks no creature visited since the temperature turned around still have to catch up with that.
+    board
+        .terrain
+        .catch_up_at(0..width, 0..height, &board.climate);
+    let positions: Vec<BoardCoordinate> = (0..width)
+        .flat_map(|x| (0..height).map(move |y| (x, y)))
+        .collect();
+    let mut expected: Vec<Tile> = positions
+        .iter()
+        .map(|&pos| board.terrain.get_tile_at(pos).clone())
+        .collect();
+    for (tile, &(x, _)) in expected.iter_mut().zip(&positions) {
+        if x < 10 {
+            tile.update(board.get_time(), &board.climate);
+        }
+    }
+
+    // Only the first 10 columns are updated, ranges past the edge of the board are cut off.
+    board.prepare_for_drawing_at(0..10, 0..10_000);
+    for (tile, &pos) in expected.iter().zip(&positions) {
+        assert_eq!(
+            board.terrain.get_tile_at(pos).get_food_level(),
+            tile.get_food_level()
+        );
+    }
+}
+
+#[test]
+fn test_obstacles_block_creatures() {
+    let mut board = Board::<Brain>::default();
+    let (width, height) = board.get_board_size();
+    let wall = (width / 2)..(width / 2 + 2);
+    for x in wall.clone() {
+        for y in 0..height {
+            board.terrain.set_tile((x, y), Tile::Obstacle);
+        }
+    }
+
+    // Put every creature next to the wall and push it towards it.
+    for c in &board.creatures {
+        let mut c = c.borrow_mut();
+        c.set_body_x(wall.start as f64 - 0.5, width);
+        c.add_vx(2.0);
+    }
+    let ids: Vec<usize> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().get_id())
+        .collect();
+
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+
+    for c in &board.creatures {
+        let c = c.borrow();
+        if ids.contains(&c.get_id()) {
+            assert!(c.get_px() < wall.start as f64);
+        }
+    }
+    assert!(board.terrain.get_tile_at((wall.start, 0)).is_obstacle());
+    assert_eq!(
+        board.terrain.get_tile_at((wall.start, 0)).get_food_level(),
+        0.0
+    );
+}
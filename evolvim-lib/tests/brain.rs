@@ -10,3 +10,162 @@ fn test_brain_evolve() {
 
     let _new_brain = Brain::recombination_infinite_parents(&vec![c_1, c_2]);
 }
+
+#[test]
+fn test_sense_nearby() {
+    let mut board = Board::<Brain>::default();
+    board.update(0.001);
+
+    let this = board.creatures[0].clone();
+    let c = this.borrow();
+    let env = Environment::new(
+        &board.terrain,
+        &c.base,
+        board.get_time(),
+        &board.climate,
+        &board.soft_bodies_in_positions,
+        &board.pheromones,
+        &board.get_config().vision,
+        this.clone(),
+    );
+    let nearby = env.sense_nearby();
+
+    let expected = board.creatures[1..]
+        .iter()
+        .filter(|o| {
+            let o = o.borrow();
+            distance(c.get_px(), c.get_py(), o.get_px(), o.get_py())
+                <= constants::NEARBY_SENSE_RADIUS
+        })
+        .count();
+    assert_eq!(nearby.count, expected);
+    assert!(nearby.direction.abs() <= std::f64::consts::PI);
+    assert!(nearby.heading.abs() <= std::f64::consts::PI);
+}
+
+#[test]
+fn test_give_birth_threshold() {
+    let mut board = Board::<Brain>::default();
+    let board_size = board.get_board_size();
+    let time = board.get_time();
+
+    let this = board.creatures[0].clone();
+    let c = &mut this.borrow_mut();
+    let mut env = EnvironmentMut::new(
+        &mut board.terrain,
+        &mut c.base,
+        board_size,
+        time,
+        &board.climate,
+        &board.soft_bodies_in_positions,
+        &mut board.pheromones,
+        this.clone(),
+    );
+
+    env.give_birth(0.5, 1.0);
+    assert!(!env.this_body.take_birth_request());
+
+    env.give_birth(1.5, 1.0);
+    assert!(env.this_body.take_birth_request());
+    // The request is forgotten once it has been taken.
+    assert!(!env.this_body.take_birth_request());
+}
+
+#[test]
+fn test_day_night_cycle() {
+    let mut climate = Climate::new(0.0, 1.0);
+    assert_eq!(climate.get_light_level(0.3), 1.0);
+    let without_days = climate.get_growth_over_time_range(0.2, 0.1);
+
+    climate.set_day_length(0.01);
+    assert!(climate.get_light_level(0.0) < 1e-9);
+    assert!((climate.get_light_level(0.005) - 1.0).abs() < 1e-9);
+
+    // Days are warmer than nights, but over whole days food grows just as much.
+    let day = climate.get_growth_over_time_range(0.0075, 0.0025);
+    let night = climate.get_growth_over_time_range(0.0125, 0.0075);
+    assert!(day > night);
+    let with_days = climate.get_growth_over_time_range(0.2, 0.1);
+    assert!((with_days - without_days).abs() < 1e-9);
+
+    climate.update(0.005);
+    let noon = climate.get_temperature();
+    climate.update(0.01);
+    assert!(noon > climate.get_temperature());
+
+    // The brains see whether it's day or night.
+    let config = SimConfig {
+        day_length: 0.01,
+        ..SimConfig::default()
+    };
+    let mut board = Board::<Brain>::new_random_with_config((50, 50), config).unwrap();
+    board.update(0.005);
+
+    let this = board.creatures[0].clone();
+    let c = this.borrow();
+    let env = Environment::new(
+        &board.terrain,
+        &c.base,
+        board.get_time(),
+        &board.climate,
+        &board.soft_bodies_in_positions,
+        &board.pheromones,
+        &board.get_config().vision,
+        this.clone(),
+    );
+    assert!(env.sense_light() > 0.99);
+
+    let config = SimConfig {
+        day_length: -1.0,
+        ..SimConfig::default()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_configured_vision() {
+    let vision = Vision {
+        ray_amount: 5,
+        field_of_view: std::f64::consts::PI,
+        range: 8.0,
+    };
+    let config = SimConfig {
+        vision: vision.clone(),
+        ..SimConfig::default()
+    };
+    let mut board = Board::<Brain>::new_random_with_config((30, 30), config.clone()).unwrap();
+    board.update(0.001);
+
+    // Both brain types get an input for every ray.
+    for c in &board.creatures {
+        let brain = &c.borrow().brain;
+        assert_eq!(brain.get_ray_input_amount(), vision.input_count());
+        assert_eq!(brain.validate(), Ok(()));
+    }
+    let nodes = neat::Genome::new_fully_linked(&vision)
+        .get_node_genome()
+        .len();
+    let default_nodes = neat::Genome::new_fully_linked(&Vision::default())
+        .get_node_genome()
+        .len();
+    assert_eq!(
+        nodes - default_nodes,
+        vision.input_count() - Vision::default().input_count()
+    );
+
+    let readings = board.get_sensor_readings(&board.creatures[0]);
+    assert_eq!(readings.rays.len(), 5);
+    assert!(readings.rays.iter().all(|r| r.range == 8.0));
+
+    // The brains are made for these eyes, they can't change while the board runs.
+    assert!(board.set_config(SimConfig::default()).is_err());
+
+    let blind = SimConfig {
+        vision: Vision {
+            ray_amount: 0,
+            ..Vision::default()
+        },
+        ..SimConfig::default()
+    };
+    assert!(blind.validate().is_err());
+}
@@ -0,0 +1,60 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::checkpoint::{self, Checkpoints};
+use lib_evolvim::*;
+
+#[test]
+fn test_checkpoint_rotation() {
+    let dir = std::env::temp_dir().join("evolvim_test_checkpoint_rotation");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 17);
+    let mut checkpoints = Checkpoints::new(&dir, 0.005, 2);
+
+    assert_eq!(checkpoints.tick(&board).unwrap(), None);
+    let mut saved = 0;
+    for _ in 0..20 {
+        board.update(0.001);
+        if checkpoints.tick(&board).unwrap().is_some() {
+            saved += 1;
+        }
+    }
+    assert_eq!(saved, 4);
+    assert_eq!(checkpoint::list(&dir).unwrap().len(), 2);
+
+    let mut restored = Board::<Brain>::restore_latest(&dir).unwrap();
+    restored.set_seed(17);
+    board.set_seed(17);
+    assert_eq!(restored.state_hash(), board.state_hash());
+
+    // A broken newest checkpoint falls back to the one before it.
+    let newest = checkpoint::list(&dir).unwrap().pop().unwrap();
+    std::fs::write(&newest, b"garbage").unwrap();
+    let restored = Board::<Brain>::restore_latest(&dir).unwrap();
+    assert!(restored.get_time() < board.get_time());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(Board::<Brain>::restore_latest(&dir).is_err());
+}
+
+#[test]
+fn test_checkpoint_failed_save_keeps_old() {
+    let dir = std::env::temp_dir().join("evolvim_test_checkpoint_failed_save");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 18);
+    let mut checkpoints = Checkpoints::new(&dir, 0.005, 1);
+    let first = checkpoints.save(&board).unwrap();
+
+    // The temporary file of the next checkpoint can't be created if there's a directory in the way.
+    board.update(0.001);
+    let blocked = dir.join(format!(
+        "checkpoint-{:012}.tmp",
+        (board.get_time() * 1000.0).round() as u64
+    ));
+    std::fs::create_dir(&blocked).unwrap();
+    assert!(checkpoints.save(&board).is_err());
+    assert_eq!(checkpoint::list(&dir).unwrap(), vec![first]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,118 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::climate::{ClimateDrift, ClimateOscillation, ClimateSample};
+use lib_evolvim::history::RetentionPolicy;
+use lib_evolvim::*;
+
+/// Adds up `get_offset` in small steps, to check the exact integral against.
+fn integrate(drift: &ClimateDrift, from: f64, to: f64) -> f64 {
+    let steps = 10000;
+    let step = (to - from) / steps as f64;
+
+    (0..steps)
+        .map(|i| drift.get_offset(from + (i as f64 + 0.5) * step) * step)
+        .sum()
+}
+
+#[test]
+fn test_climate_drift() {
+    let warming = ClimateDrift {
+        trend: 0.01,
+        ..ClimateDrift::default()
+    };
+    let ice_ages = ClimateDrift {
+        cycle_amplitude: 0.4,
+        cycle_period: 50.0,
+        ..ClimateDrift::default()
+    };
+
+    for drift in &[warming.clone(), ice_ages.clone()] {
+        let exact = drift.get_growth_over_time_range(70.0, 10.0);
+        assert!((exact - integrate(drift, 10.0, 70.0)).abs() < 1e-3);
+    }
+    assert!(ice_ages.get_offset(12.5) > 0.0);
+    assert!(ice_ages.get_offset(37.5) < 0.0);
+
+    // After a hundred years of warming it's a whole degree warmer, and the food grows faster.
+    let mut warm = Climate::new(-0.5, 0.7);
+    warm.set_drift(warming);
+    let normal = Climate::new(-0.5, 0.7);
+    warm.update(100.0);
+    assert!((warm.get_temperature() - warm.get_growth_rate(100.0) - 1.0).abs() < 1e-9);
+    assert!(
+        warm.get_growth_over_time_range(101.0, 100.0)
+            > normal.get_growth_over_time_range(101.0, 100.0)
+    );
+
+    // Boards get the drift of their config.
+    let config = SimConfig {
+        drift: ice_ages.clone(),
+        ..SimConfig::default()
+    };
+    let board = Board::<Brain>::new_random_with_config((20, 20), config).unwrap();
+    assert_eq!(board.climate.get_drift(), &ice_ages);
+
+    let config = SimConfig {
+        drift: ClimateDrift {
+            cycle_period: 0.0,
+            ..ice_ages
+        },
+        ..SimConfig::default()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_climate_oscillation() {
+    let oscillation = ClimateOscillation {
+        amplitude: 0.3,
+        period: 4.0,
+    };
+    let mut climate = Climate::new(-0.5, 0.7);
+    climate.set_oscillation(oscillation.clone());
+    let normal = Climate::new(-0.5, 0.7);
+
+    // The first half of every period has good years, the second half bad ones.
+    for &(time, good) in &[(1.25, true), (3.25, false), (5.25, true)] {
+        let change = climate.get_growth_rate(time) - normal.get_growth_rate(time);
+        assert_eq!(change > 0.0, good);
+        assert!((change - oscillation.get_offset(time)).abs() < 1e-9);
+    }
+
+    // Over a whole period the good and bad years even out.
+    let growth = climate.get_growth_over_time_range(4.0, 0.0);
+    assert!((growth - normal.get_growth_over_time_range(4.0, 0.0)).abs() < 1e-9);
+    assert!(
+        climate.get_growth_over_time_range(2.0, 0.0) > normal.get_growth_over_time_range(2.0, 0.0)
+    );
+
+    // Boards get the oscillation of their config.
+    let config = SimConfig {
+        oscillation: oscillation.clone(),
+        ..SimConfig::default()
+    };
+    let board = Board::<Brain>::new_random_with_config((20, 20), config).unwrap();
+    assert_eq!(board.climate.get_oscillation(), &oscillation);
+}
+
+#[test]
+fn test_climate_history() {
+    let mut climate = Climate::new(-0.5, 1.0);
+    climate.set_history_resolution(0.1);
+
+    for step in 1..=100 {
+        climate.update(step as f64 * 0.01);
+    }
+
+    // A sample every tenth of a year, the first one right away.
+    let samples: Vec<&(f64, ClimateSample)> = climate.history().iter().collect();
+    assert_eq!(samples.len(), 10);
+    assert!(samples.windows(2).all(|w| w[1].0 - w[0].0 >= 0.1 - 1e-9));
+    let &(time, last) = climate.history().last().unwrap();
+    assert_eq!(last.growth_rate, climate.get_growth_rate(time));
+
+    climate.set_history_policy(RetentionPolicy::KeepCount { entries: 3 });
+    climate.update(2.0);
+    assert_eq!(climate.history().len(), 3);
+    assert_eq!(climate.history().last().unwrap().0, 2.0);
+}
@@ -0,0 +1,77 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::*;
+
+fn write_temporary(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, contents).unwrap();
+
+    path
+}
+
+#[test]
+fn test_config_from_path() {
+    let toml = write_temporary(
+        "evolvim_test_config.toml",
+        "creature_minimum = 100\nmax_temp = 1.5\n",
+    );
+    let yaml = write_temporary(
+        "evolvim_test_config.yaml",
+        "creature_minimum: 100\nmax_temp: 1.5\n",
+    );
+
+    let expected = SimConfig {
+        creature_minimum: 100,
+        max_temp: 1.5,
+        ..SimConfig::default()
+    };
+    assert_eq!(SimConfig::from_path(&toml).unwrap(), expected);
+    assert_eq!(SimConfig::from_path(&yaml).unwrap(), expected);
+
+    std::fs::remove_file(&toml).unwrap();
+    std::fs::remove_file(&yaml).unwrap();
+}
+
+#[test]
+fn test_config_from_path_errors() {
+    let invalid = |name, contents| {
+        let path = write_temporary(name, contents);
+        let result = SimConfig::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        match result {
+            Err(EvolvimError::InvalidConfig(_)) => {}
+            other => panic!("{} should be refused but got {:?}", contents, other),
+        }
+    };
+
+    // A typo shouldn't be silently ignored.
+    invalid("evolvim_test_typo.toml", "creature_minimun = 100\n");
+    invalid(
+        "evolvim_test_wrong_type.toml",
+        "creature_minimum = \"lots\"\n",
+    );
+    invalid("evolvim_test_cold.yml", "min_temp: 2.0\nmax_temp: 1.0\n");
+    invalid("evolvim_test_extension.ini", "creature_minimum = 100\n");
+
+    match SimConfig::from_path("this/does/not/exist.toml") {
+        Err(EvolvimError::Io(_)) => {}
+        other => panic!("a missing file should be an io error but got {:?}", other),
+    }
+}
+
+#[test]
+fn test_config_terrain_generator() {
+    let path = write_temporary(
+        "evolvim_test_terrain_generator.toml",
+        "terrain_generator = \"diamond_square\"\n[noise]\nseed = 7\noctaves = 3\n",
+    );
+
+    let config = SimConfig::from_path(&path).unwrap();
+    assert_eq!(config.terrain_generator, TerrainAlgorithm::DiamondSquare);
+    assert_eq!(config.noise.seed, Some(7));
+    assert_eq!(config.noise.octaves, 3);
+    assert_eq!(config.noise.persistence, NoiseParams::default().persistence);
+
+    std::fs::remove_file(&path).unwrap();
+}
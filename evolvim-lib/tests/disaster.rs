@@ -0,0 +1,94 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::disaster::{Disaster, DisasterConfig, DisasterKind};
+use lib_evolvim::run::RunOptions;
+use lib_evolvim::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn disaster(kind: DisasterKind, center: (usize, usize), end: f64) -> Disaster {
+    Disaster {
+        kind,
+        center,
+        radius: 3.0,
+        start: 0.0,
+        end,
+    }
+}
+
+#[test]
+fn test_disaster_scars_and_heals() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 5);
+    let center = board.terrain.nearest_land((15, 15)).unwrap();
+    let land: Vec<(usize, usize)> = (12..19)
+        .flat_map(|x| (12..19).map(move |y| (x, y)))
+        .filter(|&pos| board.terrain.get_tile_at(pos).is_land())
+        .collect();
+
+    // A flood turns the land into water and washes away the food.
+    board.terrain.add_food_or_nothing_at(center, 1.0);
+    board.strike(&disaster(DisasterKind::Flood, center, 1.0));
+    assert!(board.terrain.get_tile_at(center).is_water());
+    assert!(!board.terrain.get_tile_at(center).is_deep_water());
+
+    // A later meteor on the same spot keeps the tiles both hit until it's over.
+    board.strike(&disaster(DisasterKind::Meteor, center, 2.0));
+    assert!(board.terrain.get_tile_at(center).is_deep_water());
+    assert!(!board.terrain.heal_scars(0.5));
+    board.terrain.heal_scars(1.0);
+    assert!(board.terrain.get_tile_at(center).is_water());
+
+    assert!(board.terrain.heal_scars(2.0));
+    assert!(board.terrain.get_scarred_tiles().is_empty());
+    for &pos in &land {
+        assert!(board.terrain.get_tile_at(pos).is_land());
+    }
+    assert_eq!(board.terrain.get_tile_at(center).get_food_level(), 0.0);
+}
+
+#[test]
+fn test_disaster_hurts_creatures() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 5);
+    let (px, py) = {
+        let c = board.creatures[0].borrow();
+        (c.get_px(), c.get_py())
+    };
+    let center = (px as usize, py as usize);
+    let energy = board.creatures[0].borrow().get_energy();
+
+    board.strike(&disaster(DisasterKind::Wildfire, center, 1.0));
+    let burnt = board.creatures[0].borrow().get_energy();
+    assert!(burnt < energy && burnt > 0.0);
+
+    board.strike(&disaster(DisasterKind::Meteor, center, 1.0));
+    assert!(board.creatures[0].borrow().get_energy() <= 0.0);
+}
+
+#[test]
+fn test_disasters_are_seeded() {
+    let disasters = DisasterConfig {
+        frequency: 20.0,
+        ..DisasterConfig::default()
+    };
+
+    let run = |seed: u64| {
+        let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, seed);
+        let config = SimConfig {
+            disasters: disasters.clone(),
+            ..board.get_config().clone()
+        };
+        board.set_config(config).unwrap();
+        let struck = Rc::new(RefCell::new(Vec::new()));
+        let hook_struck = struck.clone();
+        board.on_disaster(move |_, disaster| hook_struck.borrow_mut().push(disaster.clone()));
+        board.run_for(0.2, &RunOptions::default());
+
+        let struck = struck.borrow().clone();
+        struck
+    };
+
+    let disasters = run(3);
+    assert!(!disasters.is_empty());
+    assert!(disasters.iter().all(|d| d.end > d.start && d.radius > 0.0));
+    assert_eq!(run(3), disasters);
+}
@@ -0,0 +1,73 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::climate::RegionalClimate;
+use lib_evolvim::experiment::*;
+use lib_evolvim::*;
+use std::collections::HashMap;
+
+#[test]
+fn test_split_wall() {
+    let mut board = Board::<Brain>::default();
+    board.set_split_experiment(SplitExperiment::default());
+    let width = board.get_board_width();
+
+    let sides: HashMap<usize, Half> = board
+        .creatures
+        .iter()
+        .map(|c| {
+            let c = c.borrow();
+            (c.get_id(), SplitExperiment::get_half(c.get_px(), width))
+        })
+        .collect();
+
+    for _ in 0..50 {
+        board.update(0.001);
+    }
+
+    for c in &board.creatures {
+        let c = c.borrow();
+        if let Some(side) = sides.get(&c.get_id()) {
+            assert_eq!(*side, SplitExperiment::get_half(c.get_px(), width));
+        }
+    }
+}
+
+#[test]
+fn test_split_climates_and_statistics() {
+    let mut board = Board::<Brain>::default();
+    let split = SplitExperiment::new(
+        RegionalClimate::new(0.5, 1.0, 2.0),
+        // Nothing grows in half B.
+        RegionalClimate::new(0.5, 1.0, 0.0),
+        Barrier::Water(4),
+    );
+    board.set_split_experiment(split);
+
+    let width = board.get_board_width();
+    let middle = width / 2;
+    for y in 0..board.get_board_height() {
+        assert!(board.terrain.get_tile_at((middle, y)).is_water());
+    }
+
+    // Food grows on the left but not on the right.
+    for (x, should_grow) in [(1, true), (width - 2, false)].iter() {
+        let tile = (0..board.get_board_height())
+            .map(|y| board.terrain.get_tile_at((*x, y)).clone())
+            .find(|t| !t.is_water() && !t.is_fruiting() && t.get_fertility() > 0.1);
+
+        if let Some(mut tile) = tile {
+            let food = tile.get_food_level();
+            tile.update(0.25, &board.climate);
+            assert_eq!(tile.get_food_level() > food, *should_grow);
+        }
+    }
+
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+    let stats = board.get_half_statistics().unwrap();
+    assert_eq!(
+        stats[0].population + stats[1].population,
+        board.creatures.len()
+    );
+}
@@ -0,0 +1,42 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::brain::export::*;
+use lib_evolvim::*;
+
+#[test]
+fn test_npy_header() {
+    let array = NamedArray::new("a", vec![2, 3], ArrayData::F64(vec![0.0; 6]));
+    let npy = array.to_npy();
+
+    assert_eq!(&npy[..8], b"\x93NUMPY\x01\x00");
+    let header_length = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+    // The data starts aligned and contains all values.
+    assert_eq!((10 + header_length) % 64, 0);
+    assert_eq!(npy.len(), 10 + header_length + 6 * 8);
+
+    let header = std::str::from_utf8(&npy[10..10 + header_length]).unwrap();
+    assert!(header.contains("'shape': (2, 3)"));
+    assert!(header.ends_with('\n'));
+}
+
+#[test]
+fn test_crc32() {
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+}
+
+#[test]
+fn test_export_brain_npz() {
+    let brain = Brain::new_random();
+    let arrays = brain.get_arrays();
+    assert_eq!(arrays[0].shape, vec![24, 10]);
+    assert_eq!(arrays[1].shape, vec![11, 10]);
+
+    let mut npz = Vec::new();
+    write_npz(&arrays, &mut npz).unwrap();
+
+    // Starts with a local file header and ends with the end of central directory record.
+    assert_eq!(&npz[..4], b"PK\x03\x04");
+    let end = npz.len() - 22;
+    assert_eq!(&npz[end..end + 4], b"PK\x05\x06");
+    assert_eq!(u16::from_le_bytes([npz[end + 10], npz[end + 11]]), 3);
+}
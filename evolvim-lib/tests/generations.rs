@@ -0,0 +1,50 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::generations::*;
+use lib_evolvim::*;
+
+fn new_generations() -> Generations<Brain> {
+    let options = GenerationOptions {
+        epoch: 0.02,
+        ..GenerationOptions::default()
+    };
+
+    Generations::new(Box::new(energy_fitness), options)
+}
+
+#[test]
+fn test_generations_run() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 30, -0.5, 1.0, 31);
+    let mut generations = new_generations();
+    let first_id = board.get_creature_id_up_to();
+
+    for generation in 0..3 {
+        let summary = generations.run_generation(&mut board);
+
+        assert_eq!(summary.generation, generation);
+        assert!(summary.ranked > 0);
+        assert!(summary.best_fitness >= summary.mean_fitness);
+        assert_eq!(board.get_population_size(), board.get_creature_minimum());
+    }
+    assert_eq!(generations.get_generation(), 3);
+
+    // Everyone is replaced by new creatures every generation.
+    for c in &board.creatures {
+        assert!(c.borrow().get_id() >= first_id);
+    }
+}
+
+#[test]
+fn test_generations_seeded() {
+    let mut a = Board::<Brain>::new_random_seeded((30, 30), 0.1, 30, -0.5, 1.0, 32);
+    let mut b = Board::<Brain>::new_random_seeded((30, 30), 0.1, 30, -0.5, 1.0, 32);
+
+    let mut generations_a = new_generations();
+    let mut generations_b = new_generations();
+    for _ in 0..2 {
+        generations_a.run_generation(&mut a);
+        generations_b.run_generation(&mut b);
+    }
+
+    assert_eq!(a.state_hash(), b.state_hash());
+}
@@ -0,0 +1,119 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::{History, RetentionPolicy};
+
+#[test]
+fn test_keep_last() {
+    let mut history = History::new(RetentionPolicy::KeepLast { years: 1.0 });
+
+    for i in 0..20 {
+        history.push(i as f64 * 0.25, i).unwrap();
+    }
+
+    // Only the entries from 3.75 up to 4.75 are left.
+    assert_eq!(history.len(), 5);
+    assert_eq!(history.iter().next().unwrap().1, 15);
+    assert_eq!(history.last().unwrap().1, 19);
+}
+
+#[test]
+fn test_keep_count() {
+    let mut history = History::new(RetentionPolicy::KeepCount { entries: 3 });
+
+    for i in 0..10 {
+        history.push(i as f64, i).unwrap();
+    }
+
+    assert_eq!(history.len(), 3);
+    let range: Vec<i32> = history.range(7.5, 9.0).map(|(_, v)| *v).collect();
+    assert_eq!(range, vec![8, 9]);
+}
+
+#[test]
+fn test_downsample() {
+    let mut history = History::new(RetentionPolicy::Downsample {
+        recent: 1.0,
+        interval: 0.5,
+    });
+
+    for i in 0..=30 {
+        history.push(i as f64 * 0.1, i).unwrap();
+    }
+
+    let times: Vec<f64> = history.iter().map(|(t, _)| *t).collect();
+    for pair in times.windows(2) {
+        assert!(pair[0] < pair[1]);
+    }
+
+    // Everything older than a year is at least half a year apart.
+    let old: Vec<f64> = times.iter().cloned().filter(|t| *t < 2.0 - 1e-9).collect();
+    for pair in old.windows(2) {
+        assert!(pair[1] - pair[0] >= 0.5 - 1e-9);
+    }
+    assert!(history.len() < 31);
+}
+
+#[test]
+fn test_spill_to_disk() {
+    let path = std::env::temp_dir().join("evolvim_test_spill_to_disk.bin");
+    let _ = std::fs::remove_file(&path);
+
+    let mut history = History::new(RetentionPolicy::SpillToDisk {
+        years: 1.0,
+        path: path.clone(),
+    });
+
+    for i in 0..20 {
+        history.push(i as f64 * 0.1, i).unwrap();
+    }
+
+    let spilled: Vec<(f64, i32)> = History::read_spilled(&path).unwrap();
+    assert_eq!(spilled.len(), history.get_spilled_count());
+    assert_eq!(spilled.len() + history.len(), 20);
+
+    // Nothing got lost or reordered.
+    let all: Vec<i32> = spilled
+        .iter()
+        .chain(history.iter())
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq!(all, (0..20).collect::<Vec<i32>>());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_spill_to_disk_failed_write() {
+    // A directory can't be appended to, so nothing can be spilled there.
+    let dir = std::env::temp_dir();
+    let path = std::env::temp_dir().join("evolvim_test_spill_failed_write.bin");
+    let _ = std::fs::remove_file(&path);
+
+    let mut history = History::new(RetentionPolicy::SpillToDisk {
+        years: 1.0,
+        path: dir,
+    });
+
+    for i in 0..10 {
+        history.push(i as f64 * 0.1, i).unwrap();
+    }
+    assert!(history.push(2.0, 10).is_err());
+    assert_eq!(history.len(), 11);
+    assert_eq!(history.get_spilled_count(), 0);
+
+    // The entries are still there to be spilled once writing works again.
+    history.set_policy(RetentionPolicy::SpillToDisk {
+        years: 1.0,
+        path: path.clone(),
+    });
+    history.enforce(2.0).unwrap();
+    let spilled: Vec<(f64, i32)> = History::read_spilled(&path).unwrap();
+    let all: Vec<i32> = spilled
+        .iter()
+        .chain(history.iter())
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq!(all, (0..11).collect::<Vec<i32>>());
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "images")]
+
+extern crate image;
+extern crate lib_evolvim;
+
+use lib_evolvim::snapshot::BoardSnapshot;
+use lib_evolvim::*;
+
+#[test]
+fn test_terrain_from_image() {
+    // Water on the left, land that gets brighter (less fertile) to the right.
+    let path = std::env::temp_dir().join("evolvim_test_terrain.png");
+    image::RgbImage::from_fn(8, 4, |x, _| {
+        let gray = (x * 32) as u8;
+        image::Rgb([gray, gray, gray])
+    })
+    .save(&path)
+    .unwrap();
+
+    let mapping = ImageMapping::HeightMap {
+        sea_level: 0.25,
+        food_type: 0.3,
+    };
+    let terrain = Terrain::from_image(&path, mapping).unwrap();
+    assert_eq!((terrain.get_width(), terrain.get_height()), (8, 4));
+    assert!(terrain.get_tile_at((1, 2)).is_water());
+    assert!(!terrain.get_tile_at((2, 2)).is_water());
+    assert!(
+        terrain.get_tile_at((3, 0)).get_fertility() > terrain.get_tile_at((7, 0)).get_fertility()
+    );
+    assert_eq!(terrain.get_tile_at((5, 1)).get_food_type(), 0.3);
+
+    let mut board = Board::<Brain>::default();
+    board.set_terrain(terrain).unwrap();
+    assert_eq!(board.get_board_size(), (8, 4));
+    for c in &board.creatures {
+        let c = c.borrow();
+        assert!(c.get_px() <= 8.0 && c.get_py() <= 4.0);
+    }
+
+    let invalid = ImageMapping::HeightMap {
+        sea_level: 1.0,
+        food_type: 0.3,
+    };
+    match Terrain::from_image(&path, invalid) {
+        Err(EvolvimError::InvalidConfig(_)) => {}
+        _ => panic!("a sea level of 1 should be refused"),
+    }
+    std::fs::remove_file(&path).unwrap();
+
+    match Terrain::from_image("this/does/not/exist.png", mapping) {
+        Err(EvolvimError::Io(_)) => {}
+        other => panic!(
+            "a missing image should be an io error but got {:?}",
+            other.err()
+        ),
+    }
+}
+
+#[test]
+fn test_render_to_image() {
+    let mut board = Board::<Brain>::default();
+    board.update(0.001);
+    let (width, height) = board.get_board_size();
+
+    let terrain = board.terrain.render_to_image(3);
+    assert_eq!(terrain.dimensions(), (width as u32 * 3, height as u32 * 3));
+    let water = terrain.get_pixel(0, 0).data;
+    let expected =
+        lib_evolvim::color::hsba_to_rgba(board.terrain.get_tile_at((0, 0)).get_hsba_color());
+    assert_eq!(water[0], (expected[0] * 255.0).round() as u8);
+    // Every pixel of a tile has the same color.
+    assert_eq!(terrain.get_pixel(2, 2), terrain.get_pixel(0, 0));
+
+    let snapshot = BoardSnapshot::new(&board);
+    let path = std::env::temp_dir().join("evolvim_test_snapshot.png");
+    snapshot.save_image(&path, 4).unwrap();
+    let image = image::open(&path).unwrap().to_rgba();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(image.dimensions(), (width as u32 * 4, height as u32 * 4));
+
+    // Creatures are drawn on top of the terrain.
+    let c = &snapshot.get_creatures()[0];
+    let center = ((c.px * 4.0) as u32, (c.py * 4.0) as u32);
+    let color = lib_evolvim::color::hsba_to_rgba([c.hue as f32, 1.0, 1.0, 1.0]);
+    assert_eq!(
+        image.get_pixel(center.0, center.1).data[1],
+        (color[1] * 255.0).round() as u8
+    );
+}
@@ -0,0 +1,51 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::lineage::LineageTracker;
+use lib_evolvim::*;
+
+#[test]
+fn test_creature_ids_are_unique() {
+    let mut board = Board::<Brain>::default();
+    for _ in 0..10 {
+        board.update(0.001);
+    }
+
+    let mut ids: Vec<usize> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().get_id())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), board.get_population_size());
+}
+
+#[test]
+fn test_lineage_succession() {
+    // Seeded, otherwise the founder is sometimes killed before the test starves it.
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 36);
+    let founder = board.creatures[0].borrow().get_id();
+
+    let mut tracker = LineageTracker::new(&mut board, founder);
+    assert_eq!(tracker.get_tracked(), Some(founder));
+    assert!(tracker.is_member(founder));
+    assert!(!board.selected_creature.is_empty());
+
+    board.update(0.001);
+    assert_eq!(tracker.update(&mut board), None);
+
+    // Starve the founder, it has no children yet so the lineage dies out.
+    board
+        .get_creature_by_id(founder)
+        .unwrap()
+        .borrow_mut()
+        .lose_energy(1000.0);
+    board.update(0.001);
+
+    let event = tracker.update(&mut board).unwrap();
+    assert_eq!(event.deceased, founder);
+    assert_eq!(event.successor, None);
+    assert_eq!(tracker.get_member_count(), 0);
+    assert_eq!(tracker.get_events().len(), 1);
+    assert!(board.selected_creature.is_empty());
+}
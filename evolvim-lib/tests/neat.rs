@@ -1,10 +1,11 @@
 extern crate lib_evolvim;
 
 use lib_evolvim::neat;
+use lib_evolvim::Vision;
 
 #[test]
 fn test_construct_random() {
-    let gen1 = neat::Genome::new_fully_linked();
+    let gen1 = neat::Genome::new_fully_linked(&Vision::default());
 
     println!("Initial random network:");
     gen1.log_nodes();
@@ -13,8 +14,8 @@ fn test_construct_random() {
 
 #[test]
 fn test_recombination() {
-    let mut gen1 = neat::Genome::new_fully_linked();
-    let mut gen2 = neat::Genome::new_fully_linked();
+    let mut gen1 = neat::Genome::new_fully_linked(&Vision::default());
+    let mut gen2 = neat::Genome::new_fully_linked(&Vision::default());
 
     for _i in 0..5 {
         gen1.mutate();
@@ -37,7 +38,7 @@ fn test_recombination() {
 
 #[test]
 fn test_mutation() {
-    let mut gen = neat::Genome::new_fully_linked();
+    let mut gen = neat::Genome::new_fully_linked(&Vision::default());
 
     println!("Before mutation:");
     gen.log_nodes();
@@ -54,8 +55,8 @@ fn test_mutation() {
 
 #[test]
 fn test_genetical_distance() {
-    let mut gen1 = neat::Genome::new_fully_linked();
-    let mut gen2 = neat::Genome::new_fully_linked();
+    let mut gen1 = neat::Genome::new_fully_linked(&Vision::default());
+    let mut gen2 = neat::Genome::new_fully_linked(&Vision::default());
 
     for _i in 0..5 {
         gen1.mutate();
@@ -71,14 +72,85 @@ fn test_genetical_distance() {
 
 #[test]
 fn test_generate_phenotype() {
-    let gen = neat::Genome::new_fully_linked();
+    let gen = neat::Genome::new_fully_linked(&Vision::default());
     let _phen: neat::NeuralNet = (&gen).into();
 }
 
 #[test]
 fn test_run_phenotype() {
-    let gen = neat::Genome::new_fully_linked();
+    let gen = neat::Genome::new_fully_linked(&Vision::default());
     let mut phen: neat::NeuralNet = (&gen).into();
 
     phen.run_calculations();
 }
+
+#[test]
+fn test_to_svg() {
+    let mut gen = neat::Genome::new_fully_linked(&Vision::default());
+
+    for _i in 0..10 {
+        gen.mutate();
+    }
+
+    let svg = gen.to_svg();
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<circle").count(), gen.get_node_genome().len());
+}
+
+#[test]
+fn test_allele_tracking() {
+    let gen1 = neat::Genome::new_fully_linked(&Vision::default());
+    let mut gen2 = gen1.clone();
+    for _i in 0..10 {
+        gen2.mutate();
+    }
+
+    let mut tracker = neat::AlleleTracker::new();
+    tracker.record(0.0, vec![&gen1, &gen2]).unwrap();
+    tracker.record(1.0, vec![&gen1]).unwrap();
+
+    // The first connection gene can only get disabled, never removed
+    let first = neat::Allele::Connection(1);
+    let trajectory = tracker.get_trajectory(first);
+    assert_eq!(trajectory.len(), 2);
+    assert_eq!(trajectory[1], (1.0, 1.0));
+    assert!(tracker.get_fixed_alleles().contains(&first));
+
+    for allele in gen2.get_alleles() {
+        if !gen1.get_alleles().contains(&allele) {
+            assert!(tracker.get_lost_alleles().contains(&allele));
+        }
+    }
+
+    let mut csv = Vec::new();
+    tracker.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("time,allele,count,frequency\n"));
+    assert_eq!(csv.lines().count(), 1 + 2 * tracker.get_alleles().len());
+}
+
+#[test]
+fn test_condition_genes() {
+    let mut gen = neat::Genome::new_fully_linked(&Vision::default());
+    gen.mutate_add_condition();
+    gen.mutate_extend_condition();
+    gen.mutate_tweak_condition();
+
+    let condition = &gen.get_condition_genome()[0];
+    assert!(!condition.connections.is_empty());
+    assert_eq!(
+        condition.is_expressed(condition.threshold + 1.0),
+        condition.when_above
+    );
+    assert_eq!(
+        condition.is_expressed(condition.threshold - 1.0),
+        !condition.when_above
+    );
+
+    // Condition genes survive recombination
+    let baby = neat::Genome::new_from_2(&gen, &neat::Genome::new_fully_linked(&Vision::default()));
+    assert_eq!(baby.get_condition_genome().len(), 1);
+
+    let mut phen: neat::NeuralNet = (&baby).into();
+    phen.run_calculations();
+}
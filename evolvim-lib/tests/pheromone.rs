@@ -0,0 +1,15 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::*;
+
+#[test]
+fn test_pheromone_diffuses_and_decays() {
+    let mut field = PheromoneField::new((10, 10));
+    field.deposit((5, 5), 1.0);
+
+    field.update(0.001);
+
+    assert!(field.get_concentration_at((5, 5)) < 1.0);
+    assert!(field.get_concentration_at((4, 5)) > 0.0);
+    assert!(field.get_total() < 1.0);
+}
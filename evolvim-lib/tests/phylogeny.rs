@@ -0,0 +1,120 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::run::RunOptions;
+use lib_evolvim::*;
+
+/// Makes the newest creature have a baby as soon as it's old enough, `generations` times over.
+fn breed(board: &mut Board<Brain>, generations: usize) {
+    for _ in 0..generations {
+        let parent = board.creatures.last().unwrap().clone();
+        let births = board.get_births();
+        while board.get_births() == births {
+            {
+                let mut c = parent.borrow_mut();
+                c.add_energy(10.0);
+                c.request_birth();
+            }
+            board.update(0.001);
+        }
+    }
+}
+
+#[test]
+fn test_phylogeny_records_everyone() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 6);
+    breed(&mut board, 3);
+    board.run_for(0.05, &RunOptions::default());
+
+    let phylogeny = board.get_phylogeny();
+    assert_eq!(phylogeny.len(), board.get_creature_id_up_to());
+
+    // Exactly the creatures on the board are alive.
+    let living = phylogeny
+        .get_records()
+        .iter()
+        .filter(|r| r.is_alive())
+        .count();
+    assert_eq!(living, board.get_population_size());
+    for c in &board.creatures {
+        let c = c.borrow();
+        let record = phylogeny.get(c.get_id()).unwrap();
+        assert!(record.is_alive());
+        assert_eq!(record.parents, c.get_parent_ids());
+        assert_eq!(record.genome_fingerprint, c.get_genome_fingerprint());
+    }
+
+    // Every ancestry goes back to the founder, one generation at a time.
+    let baby = phylogeny
+        .get_records()
+        .iter()
+        .max_by_key(|r| r.generation)
+        .unwrap();
+    assert!(baby.generation >= 3);
+    let ancestry = phylogeny.get_ancestry(baby.id);
+    assert_eq!(ancestry.len(), baby.generation + 1);
+    assert_eq!(*ancestry.last().unwrap(), baby.founder);
+    assert!(phylogeny.get(baby.founder).unwrap().parents.is_empty());
+    assert!(phylogeny.get_descendants(baby.founder).contains(&baby.id));
+    assert!(phylogeny.get_children(baby.parents[0]).contains(&baby.id));
+    assert_eq!(
+        phylogeny.get_common_ancestor(baby.id, baby.founder),
+        Some(baby.founder)
+    );
+
+    // Every creature belongs to exactly one lineage.
+    let lineages = phylogeny.get_lineages();
+    assert_eq!(
+        lineages.iter().map(|l| l.members).sum::<usize>(),
+        phylogeny.len()
+    );
+    assert_eq!(lineages.iter().map(|l| l.living).sum::<usize>(), living);
+    let stats = phylogeny.get_lineage_stats(baby.founder).unwrap();
+    assert!(stats.members > 1 && stats.generations >= baby.generation);
+    assert!(lineages
+        .iter()
+        .all(|l| (l.living == 0) == l.extinct_at.is_some()));
+}
+
+#[test]
+fn test_phylogeny_is_saved() {
+    let path = std::env::temp_dir().join("evolvim_test_phylogeny.bin");
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 6);
+    board.run_for(0.2, &RunOptions::default());
+    board.save_to(&path).unwrap();
+    let loaded = Board::<Brain>::load_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!board.get_phylogeny().is_empty());
+    assert_eq!(loaded.get_phylogeny(), board.get_phylogeny());
+}
+
+#[test]
+fn test_names() {
+    use lib_evolvim::names::name_from_fingerprint;
+
+    // Names only depend on the fingerprint and can be pronounced.
+    assert_eq!(name_from_fingerprint(48213), name_from_fingerprint(48213));
+    for fingerprint in 0..100 {
+        let name = name_from_fingerprint(fingerprint);
+        assert!(name.len() >= 4, "{} is too short", name);
+        assert!(name.chars().next().unwrap().is_uppercase());
+        assert!(name.chars().skip(1).all(|c| c.is_ascii_lowercase()));
+        assert!(name.chars().any(|c| "aeiou".contains(c)));
+    }
+
+    // Almost every genome gets a name of its own.
+    let names: std::collections::HashSet<String> = (0..1000).map(name_from_fingerprint).collect();
+    assert!(names.len() > 990);
+
+    // The dead keep their names.
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 6);
+    let c = board.creatures[0].clone();
+    let (id, name) = (c.borrow().get_id(), c.borrow().get_name());
+    assert_eq!(board.get_name_of(id), Some(name.clone()));
+    c.borrow_mut().lose_energy(1000.0);
+    board.update(0.001);
+    assert!(board.get_creature_by_id(id).is_none());
+    assert_eq!(board.get_phylogeny().get(id).unwrap().get_name(), name);
+    assert_eq!(board.get_name_of(board.get_creature_id_up_to()), None);
+}
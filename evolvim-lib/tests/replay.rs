@@ -0,0 +1,44 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::replay::*;
+use lib_evolvim::snapshot::BoardSnapshot;
+use lib_evolvim::*;
+
+#[test]
+fn test_record_and_replay() {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 31);
+    let mut recorder = Recorder::new(Vec::new(), &board).unwrap();
+    let mut expected = Vec::new();
+
+    for _ in 0..30 {
+        board.update(0.001);
+        board.terrain.update_all(board.get_time(), &board.climate);
+
+        recorder.record(&board).unwrap();
+        expected.push(BoardSnapshot::new(&board));
+    }
+    assert_eq!(recorder.get_frames(), 30);
+    let bytes = recorder.finish().unwrap();
+
+    let mut replayer = Replayer::new(&bytes[..]).unwrap();
+    for snapshot in &expected {
+        assert!(replayer.next_frame().unwrap().is_some());
+        let current = replayer.get_current();
+
+        assert_eq!(current.get_time(), snapshot.get_time());
+        assert_eq!(current.get_population(), snapshot.get_population());
+        for c in snapshot.get_creatures() {
+            assert_eq!(current.get_creature_by_id(c.id), Some(c));
+        }
+
+        let (x, y) = (7, 11);
+        let (a, b) = (current.get_tile_color(x, y), snapshot.get_tile_color(x, y));
+        for i in 0..4 {
+            assert!((a[i] - b[i]).abs() <= TILE_COLOR_TOLERANCE);
+        }
+    }
+
+    assert_eq!(replayer.next_frame().unwrap(), None);
+    assert!(replayer.is_finished());
+    assert_eq!(replayer.get_frame(), 30);
+}
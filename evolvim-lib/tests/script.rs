@@ -0,0 +1,25 @@
+#![cfg(feature = "scripting")]
+
+extern crate lib_evolvim;
+
+use lib_evolvim::{Board, ScriptBrain};
+
+#[test]
+fn test_script_must_define_think() {
+    assert!(ScriptBrain::new("function think(input) return {} end").is_ok());
+    assert!(ScriptBrain::new("x = 1").is_err());
+    assert!(ScriptBrain::new("this is not lua").is_err());
+}
+
+#[test]
+fn test_default_script_runs() {
+    let mut board: Board<ScriptBrain> = Board::default();
+
+    for _ in 0..10 {
+        board.update(0.001);
+    }
+
+    for c in &board.creatures {
+        assert_eq!(c.borrow().brain.get_error(), None);
+    }
+}
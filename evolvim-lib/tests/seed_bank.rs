@@ -0,0 +1,60 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::brain::ExportWeights;
+use lib_evolvim::*;
+
+#[test]
+fn test_seed_bank_roundtrip() {
+    let dir = std::env::temp_dir().join("evolvim_test_seed_bank_roundtrip");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("champions.bin");
+
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 30);
+    for _ in 0..20 {
+        board.update(0.001);
+    }
+    assert_eq!(
+        board
+            .export_seed_bank(&path, CreatureMetric::Energy, 5)
+            .unwrap(),
+        5
+    );
+
+    let champions: Vec<_> = board
+        .get_leaderboard(CreatureMetric::Energy, 5)
+        .iter()
+        .map(|c| c.borrow().brain.get_arrays())
+        .collect();
+
+    let mut other = Board::<Brain>::new_random_seeded((20, 20), 0.1, 12, -0.5, 1.0, 31);
+    assert_eq!(other.load_seed_bank(&path).unwrap(), 5);
+    let loaded: Vec<_> = other
+        .get_seed_bank()
+        .iter()
+        .map(|b| b.get_arrays())
+        .collect();
+    assert_eq!(loaded, champions);
+
+    // Every new creature gets the brain of one of the champions.
+    other.reseed_from_seed_bank();
+    assert_eq!(other.get_population_size(), 12);
+    assert!(other
+        .creatures
+        .iter()
+        .all(|c| champions.contains(&c.borrow().brain.get_arrays())));
+
+    // The seed bank of a board can be saved as well, in any save format.
+    let path = dir.join("bank.json");
+    other.save_seed_bank(&path).unwrap();
+    let mut empty = Board::<Brain>::new_random_seeded((20, 20), 0.1, 12, -0.5, 1.0, 32);
+    assert_eq!(empty.load_seed_bank(&path).unwrap(), 5);
+
+    std::fs::write(&path, b"garbage").unwrap();
+    match empty.load_seed_bank(&path) {
+        Err(EvolvimError::CorruptSave(_)) => {}
+        _ => panic!("A broken seed bank should be reported as corrupt."),
+    }
+    assert_eq!(empty.get_seed_bank().len(), 5);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,46 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::constants::*;
+use lib_evolvim::*;
+
+#[test]
+fn test_simulation_take_updates() {
+    let mut simulation = Simulation::new(0.001);
+    // A second at speed 1 is `SIMULATION_YEARS_PER_SECOND` years.
+    let per_second = (SIMULATION_YEARS_PER_SECOND / 0.001).round() as usize;
+
+    // Frames that are too short for an update add up.
+    let frames = 20;
+    let updates: usize = (0..frames)
+        .map(|_| simulation.take_updates(1.0 / frames as f64))
+        .sum();
+    assert_eq!(updates, per_second);
+
+    simulation.set_speed(4.0);
+    assert_eq!(simulation.take_updates(1.0), 4 * per_second);
+
+    simulation.set_speed(0.0);
+    assert_eq!(simulation.take_updates(1.0), 0);
+
+    // A very slow frame doesn't have to be caught up with.
+    simulation.set_speed(1.0);
+    simulation.set_max_updates_per_frame(5);
+    assert_eq!(simulation.take_updates(100.0), 5);
+    assert_eq!(simulation.take_updates(0.0), 0);
+}
+
+#[test]
+fn test_simulation_advance() {
+    let mut board = Board::<Brain>::new_random_seeded((20, 20), 0.1, 20, -0.5, 1.0, 37);
+    let mut simulation = Simulation::new(0.001);
+    let start = board.get_time();
+
+    let updates = simulation.advance(&mut board, 0.5);
+    assert!(updates > 0);
+    assert!((board.get_time() - start - updates as f64 * 0.001).abs() < 1e-9);
+
+    board.pause();
+    let time = board.get_time();
+    assert_eq!(simulation.advance(&mut board, 0.5), 0);
+    assert_eq!(board.get_time(), time);
+}
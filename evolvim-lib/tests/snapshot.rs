@@ -0,0 +1,79 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::snapshot::*;
+use lib_evolvim::*;
+
+#[test]
+fn test_snapshot_copies_board() {
+    let mut board = Board::<Brain>::default();
+    board.update(0.001);
+
+    let snapshot = BoardSnapshot::new(&board);
+
+    assert_eq!(snapshot.get_time(), board.get_time());
+    assert_eq!(snapshot.get_population(), board.creatures.len());
+    assert_eq!(
+        snapshot.get_tile_color(3, 5),
+        board.terrain.get_tile_at((3, 5)).get_hsba_color()
+    );
+
+    let creature = board.creatures[0].borrow();
+    let copy = snapshot.get_creature_by_id(creature.get_id()).unwrap();
+    assert_eq!(copy.px, creature.get_px());
+    assert_eq!(copy.energy, creature.get_energy());
+}
+
+#[test]
+fn test_shared_snapshot_across_threads() {
+    let mut board = Board::<Brain>::default();
+    let shared = SharedSnapshot::new(BoardSnapshot::new(&board));
+
+    let reader = shared.clone();
+    let observer = std::thread::spawn(move || {
+        let first = reader.get();
+        let mut last_time = first.get_time();
+        for _ in 0..100 {
+            let snapshot = reader.get();
+            // Snapshots are published in order.
+            assert!(snapshot.get_time() >= last_time);
+            last_time = snapshot.get_time();
+        }
+
+        first.get_population()
+    });
+
+    for _ in 0..10 {
+        board.update(0.001);
+        shared.publish(BoardSnapshot::new(&board));
+    }
+
+    assert!(observer.join().unwrap() > 0);
+    assert_eq!(shared.get().get_time(), board.get_time());
+}
+
+#[test]
+fn test_board_creatures_iter() {
+    let mut board = Board::<Brain>::default();
+    board.update(0.001);
+
+    let views: Vec<CreatureView> = board.creatures_iter().collect();
+    assert_eq!(views.len(), board.creatures.len());
+    for (view, creature) in views.iter().zip(&board.creatures) {
+        let creature = creature.borrow();
+        assert_eq!(view.id, creature.get_id());
+        assert_eq!(view.radius, creature.get_radius());
+    }
+
+    // Nothing stays borrowed, so creatures can be changed while going over them.
+    let mut seen = 0;
+    board.for_each_creature(|view| {
+        let creature = board.get_creature_by_id(view.id).unwrap();
+        creature.borrow_mut().add_energy(1.0);
+        seen += 1;
+    });
+    assert_eq!(seen, views.len());
+    assert_eq!(
+        board.creatures_iter().next().unwrap().energy,
+        views[0].energy + 1.0
+    );
+}
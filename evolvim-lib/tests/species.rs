@@ -0,0 +1,121 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::species::SpeciesConfig;
+use lib_evolvim::*;
+
+fn new_board(species: SpeciesConfig) -> Board<Brain> {
+    let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, 9);
+    let config = SimConfig {
+        species,
+        ..board.get_config().clone()
+    };
+    board.set_config(config).unwrap();
+
+    board
+}
+
+#[test]
+fn test_species_division() {
+    let mut board = new_board(SpeciesConfig {
+        interval: 1,
+        ..SpeciesConfig::default()
+    });
+    board.update(0.001);
+
+    // Everyone belongs to a species close enough to its representative.
+    let threshold = board.get_config().species.threshold;
+    let tracker = board.get_species();
+    let sizes = tracker.get_sizes();
+    assert_eq!(sizes.values().sum::<usize>(), board.get_population_size());
+    for c in &board.creatures {
+        let c = c.borrow();
+        let species = tracker.get_species_of(c.get_id()).unwrap();
+        let representative = tracker.get_species_by_id(species).unwrap().representative;
+        let representative = board.get_creature_by_id(representative).unwrap();
+        assert!(c.get_genetic_distance(&representative.borrow()) <= threshold);
+    }
+    assert_eq!(board.stats().species, tracker.get_species().len());
+
+    // A species keeps its id as long as it lives.
+    let before: Vec<(usize, Option<usize>)> = board
+        .creatures
+        .iter()
+        .map(|c| c.borrow().get_id())
+        .map(|id| (id, board.get_species().get_species_of(id)))
+        .collect();
+    board.update(0.001);
+    for (id, species) in before {
+        if board.get_creature_by_id(id).is_some() {
+            assert_eq!(board.get_species().get_species_of(id), species);
+        }
+    }
+
+    // A baby is as good as a clone of its parent, so it's in the same species.
+    let parent = board.creatures[0].clone();
+    let parent_id = parent.borrow().get_id();
+    let births = board.get_births();
+    while board.get_births() == births {
+        {
+            let mut c = parent.borrow_mut();
+            c.add_energy(10.0);
+            c.request_birth();
+        }
+        board.update(0.001);
+    }
+    let baby = board.creatures.last().unwrap().borrow().get_id();
+    let species = board.get_species().get_species_of(parent_id);
+    assert!(species.is_some());
+    assert_eq!(board.get_species().get_species_of(baby), species);
+}
+
+#[test]
+fn test_species_threshold() {
+    // Without a threshold every creature is a species of its own.
+    let mut board = new_board(SpeciesConfig {
+        threshold: 0.0,
+        interval: 1,
+    });
+    board.update(0.001);
+    let population = board.get_population_size();
+    assert_eq!(board.get_species().get_species().len(), population);
+    assert!(board.get_species().get_species_founded() >= population);
+
+    // With a huge threshold they're all the same species.
+    let mut board = new_board(SpeciesConfig {
+        threshold: 1e9,
+        interval: 1,
+    });
+    board.update(0.001);
+    let species = board.get_species().get_species();
+    assert_eq!(species.len(), 1);
+    assert_eq!(species[0].size, board.get_population_size());
+    assert_ne!(
+        species[0].get_color(),
+        lib_evolvim::species::get_species_color(1)
+    );
+}
+
+#[test]
+fn test_species_history_and_saves() {
+    use lib_evolvim::board_history::BoardHistory;
+
+    let mut board = new_board(SpeciesConfig::default());
+    board.set_history(BoardHistory::new(
+        1,
+        RetentionPolicy::KeepCount { entries: 5 },
+    ));
+    board.update(0.001);
+
+    let (_, sample) = board.get_history().get_last().unwrap();
+    assert_eq!(sample.genetic_species, board.get_species().get_sizes());
+    assert_eq!(
+        sample.genetic_species.values().sum::<usize>(),
+        sample.population
+    );
+
+    let path = std::env::temp_dir().join("evolvim_test_species.bin");
+    board.save_to(&path).unwrap();
+    let loaded = Board::<Brain>::load_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.get_species(), board.get_species());
+}
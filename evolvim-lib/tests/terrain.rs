@@ -0,0 +1,633 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::climate::ClimateZones;
+use lib_evolvim::constants::DIET_MIN_EFFICIENCY;
+use lib_evolvim::terrain::edit::{Brush, EditHistory};
+use lib_evolvim::terrain::palette::SeasonalPalette;
+use lib_evolvim::terrain::resource::{GrowthRule, Resource, ResourceKind};
+use lib_evolvim::terrain::tile::Tile;
+use lib_evolvim::*;
+
+fn get_water_map(terrain: &Terrain) -> Vec<bool> {
+    let mut map = Vec::new();
+    for x in 0..terrain.get_width() {
+        for y in 0..terrain.get_height() {
+            map.push(terrain.get_tile_at((x, y)).is_water());
+        }
+    }
+
+    map
+}
+
+#[test]
+fn test_edit_undo_redo() {
+    let mut terrain = Terrain::generate_perlin((20, 20), 0.1);
+    let mut history = EditHistory::default();
+    let brush = Brush { radius: 3.0 };
+    let before = get_water_map(&terrain);
+
+    history.paint(&mut terrain, &brush, (10, 10), |_| Tile::new_water(1.0));
+    for pos in brush.get_footprint((10, 10), (20, 20)) {
+        assert!(terrain.get_tile_at(pos).is_water());
+    }
+
+    assert!(history.undo(&mut terrain));
+    assert_eq!(get_water_map(&terrain), before);
+    assert!(!history.undo(&mut terrain));
+
+    assert!(history.redo(&mut terrain));
+    assert!(terrain.get_tile_at((10, 10)).is_water());
+
+    // Without room for any edits they're still made, but can't be undone.
+    let mut history = EditHistory::with_capacity(0);
+    history.paint(&mut terrain, &brush, (5, 5), |_| Tile::Obstacle);
+    assert!(terrain.get_tile_at((5, 5)).is_obstacle());
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn test_fruiting_tile_releases_food_in_bursts() {
+    let climate = Climate::new(1.0, 1.0);
+    let mut tile = Tile::new_fruiting(1.0, 0.0, 0.1);
+    let start = tile.get_food_level();
+
+    for i in 1..10 {
+        tile.update(i as f64 * 0.01, &climate);
+    }
+
+    // Food is growing, but it's kept until the tile fruits.
+    assert_eq!(tile.get_food_level(), start);
+    assert!(tile.get_stored_food() > 0.0);
+
+    tile.update(0.11, &climate);
+    assert!(tile.get_food_level() > start);
+    assert_eq!(tile.get_stored_food(), 0.0);
+}
+
+#[test]
+fn test_terrain_generators() {
+    let algorithms = [
+        TerrainAlgorithm::Perlin,
+        TerrainAlgorithm::Fbm,
+        TerrainAlgorithm::DiamondSquare,
+        TerrainAlgorithm::Island,
+        TerrainAlgorithm::Archipelago,
+    ];
+
+    for algorithm in &algorithms {
+        let terrain = algorithm.generate((100, 80), 0.1);
+        assert_eq!((terrain.get_width(), terrain.get_height()), (100, 80));
+
+        let water = get_water_map(&terrain);
+        assert!(water.iter().any(|&w| w), "{:?} made no water", algorithm);
+        assert!(water.iter().any(|&w| !w), "{:?} made no land", algorithm);
+    }
+
+    // An island is surrounded by water.
+    let island = TerrainAlgorithm::Island.generate((100, 80), 0.1);
+    for &corner in &[(0, 0), (99, 0), (0, 79), (99, 79)] {
+        assert!(island.get_tile_at(corner).is_water());
+    }
+
+    let config = SimConfig {
+        terrain_generator: TerrainAlgorithm::Archipelago,
+        ..SimConfig::default()
+    };
+    let board = Board::<Brain>::new_random_with_config((30, 30), config.clone()).unwrap();
+    assert_eq!(
+        board.get_config().terrain_generator,
+        config.terrain_generator
+    );
+}
+
+#[test]
+fn test_biomes() {
+    assert_eq!(Biome::classify(0.5, 0.9), Biome::Tundra);
+    assert_eq!(Biome::classify(0.05, 0.0), Biome::Desert);
+    assert_eq!(Biome::classify(0.4, 0.0), Biome::Grassland);
+    assert_eq!(Biome::classify(0.7, 0.0), Biome::Forest);
+    assert_eq!(Biome::classify(0.95, 0.0), Biome::Swamp);
+
+    // Every land tile has a biome and the top and bottom rows are tundra.
+    let terrain = Terrain::generate_perlin((40, 40), 0.1);
+    for x in 0..40 {
+        for y in 0..40 {
+            let tile = terrain.get_tile_at((x, y));
+            assert_eq!(tile.get_biome().is_none(), tile.is_water());
+            if (y == 0 || y == 39) && !tile.is_water() {
+                assert_eq!(tile.get_biome(), Some(Biome::Tundra));
+            }
+        }
+    }
+
+    // Food grows faster in a forest than on grassland.
+    let climate = Climate::new(0.5, 1.0);
+    let mut grassland = Tile::new(0.5, 0.3);
+    let mut forest = grassland.clone();
+    forest.set_biome(Biome::Forest);
+    assert_eq!(grassland.get_biome(), Some(Biome::Grassland));
+    grassland.update(0.2, &climate);
+    forest.update(0.2, &climate);
+    assert!(forest.get_food_level() > grassland.get_food_level());
+    assert_ne!(
+        forest.get_hsba_color(),
+        Tile::new(0.0, 0.3).get_hsba_color()
+    );
+}
+
+#[test]
+fn test_elevation() {
+    // Generated land has an elevation, water stays at sea level.
+    let terrain = Terrain::generate_perlin((40, 40), 0.1);
+    let mut highest: f64 = 0.0;
+    for x in 0..40 {
+        for y in 0..40 {
+            let tile = terrain.get_tile_at((x, y));
+            let elevation = tile.get_elevation();
+            assert!(elevation >= 0.0 && elevation <= 1.0);
+            if tile.is_water() {
+                assert_eq!(elevation, 0.0);
+            }
+            highest = highest.max(elevation);
+        }
+    }
+    assert!(highest > 0.0);
+
+    // Food grows slower on a mountain, which is drawn darker.
+    let climate = Climate::new(0.5, 1.0);
+    let mut low = Tile::new(0.5, 0.3);
+    let mut high = low.clone();
+    high.set_elevation(1.0);
+    assert_eq!(high.get_elevation(), 1.0);
+    low.update(0.2, &climate);
+    high.update(0.2, &climate);
+    assert!(high.get_food_level() < low.get_food_level());
+    assert!(high.get_hsba_color()[2] < low.get_hsba_color()[2]);
+    assert!(climate.get_temperature_at_elevation(1.0) < climate.get_temperature_at_elevation(0.0));
+
+    let mut water = Tile::new_water(1.0);
+    water.set_elevation(0.5);
+    assert_eq!(water.get_elevation(), 0.0);
+}
+
+#[test]
+fn test_raise_fertility_and_flood() {
+    let mut terrain = Terrain::generate_perlin((20, 20), 0.1);
+    for x in 0..20 {
+        for y in 0..20 {
+            terrain.set_tile((x, y), Tile::new(0.5, 0.3));
+        }
+    }
+
+    // Turn the middle into a desert, the edges stay as they were.
+    terrain.raise_fertility((10, 10), 2.0, -0.45);
+    let middle = terrain.get_tile_at((10, 10));
+    assert!((middle.get_fertility() - 0.05).abs() < 1e-9);
+    assert_eq!(middle.get_biome(), Some(Biome::Desert));
+    assert_eq!(terrain.get_tile_at((0, 10)).get_fertility(), 0.5);
+
+    // The fertility can't go over 1.
+    terrain.raise_fertility((10, 10), 0.0, 3.0);
+    assert_eq!(terrain.get_tile_at((10, 10)).get_fertility(), 1.0);
+
+    terrain.flood((0, 0), 1.0);
+    assert!(terrain.get_tile_at((0, 0)).is_water());
+    assert!(terrain.get_tile_at((1, 0)).is_water());
+    assert!(!terrain.get_tile_at((1, 1)).is_water());
+}
+
+#[test]
+fn test_toxic_tiles() {
+    let mut tile = Tile::new(0.5, 0.3);
+    assert_eq!(tile.get_toxin_damage(0.3), 0.0);
+    tile.set_toxin(1.0, 0.6);
+    assert_eq!(tile.get_toxicity(), 1.0);
+    assert_eq!(tile.get_toxin_damage(0.6), 0.0);
+    assert_eq!(tile.get_toxin_damage(0.3), 1.0);
+    assert!(tile.get_toxin_damage(0.55) < tile.get_toxin_damage(0.5));
+    assert_ne!(tile.get_hsba_color(), Tile::new(0.5, 0.3).get_hsba_color());
+
+    // An adapted creature gets more out of eating here than one that isn't. Both mouths are equally far from the food
+    // type, so only the toxin makes a difference.
+    let mut tile = Tile::new(0.5, 0.45);
+    tile.set_toxin(1.0, 0.6);
+    let board = Board::<Brain>::default();
+    let energy_change = |mouth_hue: f64| {
+        let mut creature = board.creatures[0].borrow_mut();
+        creature.set_mouth_hue(mouth_hue);
+        let before = creature.get_energy();
+        creature.eat(1.0, 0.1, 0.0, &board.climate, &mut tile.clone());
+
+        creature.get_energy() - before
+    };
+    let unadapted = energy_change(0.3);
+    let adapted = energy_change(0.6);
+    assert!(unadapted < 0.0);
+    assert!(adapted > 0.0);
+}
+
+#[test]
+fn test_tile_resources() {
+    let climate = Climate::new(-0.5, 0.7);
+    let mut tile = Tile::new(0.5, 0.3);
+    tile.remove_resource(ResourceKind::Plant, 10.0);
+    assert_eq!(tile.get_resource_level(ResourceKind::Plant), 0.0);
+    assert!(tile.get_resources().is_empty());
+
+    tile.add_resource(Resource::new(
+        ResourceKind::Fruit,
+        0.0,
+        GrowthRule::Steady { rate: 1.0 },
+    ));
+    tile.add_resource(Resource::carrion(1.0));
+    tile.add_resource(Resource::carrion(1.0));
+    assert_eq!(tile.get_resources().len(), 2);
+    assert_eq!(tile.get_resource_level(ResourceKind::Carrion), 2.0);
+    assert_eq!(tile.get_resource_food(), 2.0);
+
+    // Fruit grows steadily while carrion rots away.
+    tile.update(1.0, &climate);
+    assert!((tile.get_resource_level(ResourceKind::Fruit) - 1.0).abs() < 1e-9);
+    assert!(tile.get_resource_level(ResourceKind::Carrion) < 2.0);
+
+    tile.remove_resource(ResourceKind::Fruit, 5.0);
+    assert_eq!(tile.get_resource_level(ResourceKind::Fruit), 0.0);
+
+    // Water can't hold any resources.
+    let mut water = Tile::new_water(1.0);
+    water.add_resource(Resource::carrion(1.0));
+    assert_eq!(water.get_resource_food(), 0.0);
+
+    // A mouth made for carrion gets more out of it than one made for plants.
+    let mut tile = Tile::new(0.5, 0.3);
+    tile.remove_resource(ResourceKind::Plant, 10.0);
+    tile.add_resource(Resource::carrion(1.0));
+    let board = Board::<Brain>::default();
+    let energy_change = |kind: ResourceKind| {
+        let mut creature = board.creatures[0].borrow_mut();
+        creature.set_mouth_specialization(kind);
+        let before = creature.get_energy();
+        creature.eat(1.0, 0.1, 0.0, &board.climate, &mut tile.clone());
+
+        creature.get_energy() - before
+    };
+    assert!(energy_change(ResourceKind::Carrion) > energy_change(ResourceKind::Plant));
+}
+
+#[test]
+fn test_diet() {
+    let board = Board::<Brain>::default();
+    let mut plants = Tile::new(0.5, 0.3);
+    plants.update(1.0, &board.climate);
+    let mut carrion = Tile::new(0.5, 0.3);
+    carrion.remove_resource(ResourceKind::Plant, 10.0);
+    carrion.add_resource(Resource::carrion(1.0));
+
+    let energy_change = |diet: f64, tile: &Tile| {
+        let mut creature = board.creatures[0].borrow_mut();
+        creature.set_mouth_hue(tile.get_food_type());
+        creature.set_diet(diet);
+        let before = creature.get_energy();
+        creature.eat(1.0, 0.1, 0.0, &board.climate, &mut tile.clone());
+
+        creature.get_energy() - before
+    };
+
+    // Plant eaters get more out of plants and meat eaters more out of carrion.
+    assert!(energy_change(0.0, &plants) > energy_change(1.0, &plants));
+    assert!(energy_change(1.0, &carrion) > energy_change(0.0, &carrion));
+
+    let mut creature = board.creatures[0].borrow_mut();
+    creature.set_diet(2.0);
+    assert_eq!(creature.get_diet(), 1.0);
+    assert_eq!(creature.get_meat_efficiency(), 1.0);
+    assert_eq!(creature.get_plant_efficiency(), DIET_MIN_EFFICIENCY);
+}
+
+#[test]
+fn test_temperature_map() {
+    let mut climate = Climate::new(0.2, 0.2);
+    climate.set_latitude_cooling(0.5);
+    climate.update(0.0);
+
+    let mut terrain = Terrain::generate_perlin((20, 40), 0.1);
+    for y in 0..40 {
+        terrain.set_tile((0, y), Tile::new(0.5, 0.3));
+    }
+    terrain.get_tile_at_mut((0, 20)).set_elevation(1.0);
+
+    // The poles are colder than the equator and mountains are colder than the lowlands.
+    let equator = terrain.temperature_at((0, 19), &climate);
+    let pole = terrain.temperature_at((0, 0), &climate);
+    assert!(pole < equator);
+    assert!((equator - pole - 0.45).abs() < 0.05);
+    assert!(terrain.temperature_at((0, 20), &climate) < equator);
+    let latitude = |y: usize| terrain.get_tile_at((0, y)).get_latitude();
+    assert!((latitude(39) - latitude(0)).abs() < 1e-9);
+
+    // So less food grows near the poles.
+    terrain.update_all(1.0, &climate);
+    let food_at = |y: usize| terrain.get_tile_at((0, y)).get_food_level();
+    assert!(food_at(0) < food_at(19));
+
+    // Without cooling the whole board is just as warm.
+    climate.set_latitude_cooling(0.0);
+    assert_eq!(
+        terrain.temperature_at((0, 0), &climate),
+        terrain.temperature_at((0, 19), &climate)
+    );
+}
+
+#[test]
+fn test_climate_zones() {
+    let zones = ClimateZones::Latitude;
+    let regions = zones.get_regions(-0.5, 0.7);
+    assert_eq!(regions.len(), 4);
+    assert_eq!(zones.get_region(0, 40), 1);
+    assert_eq!(zones.get_region(39, 40), 4);
+    assert!(ClimateZones::Uniform.get_regions(-0.5, 0.7).is_empty());
+
+    // The winters near the poles are colder, the seasons of the two halves are half a year apart.
+    let mut climate = Climate::new(-0.5, 0.7);
+    climate.set_regions(regions);
+    climate.update(0.5);
+    assert!(climate.get_region_temperature(2) > climate.get_region_temperature(3));
+    assert_eq!(climate.get_region_temperature(2), climate.get_temperature());
+    climate.update(0.0);
+    assert!(climate.get_region_temperature(2) < climate.get_region_temperature(3));
+    assert!(climate.get_region_temperature(1) < climate.get_region_temperature(2));
+
+    let config = SimConfig {
+        climate_zones: ClimateZones::Latitude,
+        ..SimConfig::default()
+    };
+    // Water is always in region 0, so only the land is checked.
+    let land_regions = |board: &Board<Brain>, y: usize| -> Vec<usize> {
+        (0..20)
+            .map(|x| board.terrain.get_tile_at((x, y)))
+            .filter(|t| t.is_land())
+            .map(|t| t.get_region())
+            .collect()
+    };
+    let board = Board::<Brain>::new_random_with_config((20, 40), config).unwrap();
+    assert_eq!(board.climate.get_regions().len(), 4);
+    assert!(land_regions(&board, 0).iter().all(|&r| r == 1));
+    assert!(land_regions(&board, 39).iter().all(|&r| r == 4));
+
+    // The default keeps the whole board in one climate.
+    let board = Board::<Brain>::new_random_with_config((20, 40), SimConfig::default()).unwrap();
+    assert!(board.climate.get_regions().is_empty());
+    assert!(land_regions(&board, 39).iter().all(|&r| r == 0));
+}
+
+#[test]
+fn test_terrain_chunks() {
+    let climate = Climate::new(-0.5, 0.7);
+    let new_terrain = || {
+        let mut terrain = Terrain::generate_perlin((100, 70), 0.1);
+        for x in 0..100 {
+            for y in 0..70 {
+                terrain.set_tile((x, y), Tile::new(0.5, 0.3));
+            }
+        }
+        terrain.clear_dirty_flags();
+
+        terrain
+    };
+
+    let mut eager = new_terrain();
+    let mut lazy = new_terrain();
+    assert_eq!(lazy.get_chunks().len(), 12);
+    assert_eq!(lazy.get_chunks_in(0..40, 0..10).count(), 2);
+    let tiles: usize = lazy.get_chunks().iter().map(|c| c.iter().count()).sum();
+    assert_eq!(tiles, 100 * 70);
+
+    eager.update_all(0.5, &climate);
+    lazy.update_all_lazily(0.5);
+    assert!(lazy.get_chunks().iter().all(|c| !c.is_dirty()));
+    assert_ne!(
+        eager.get_tile_at((99, 69)).get_food_level(),
+        lazy.get_tile_at((99, 69)).get_food_level()
+    );
+
+    // Only the chunk that's used catches up, after that it's the same as updating everything right away.
+    eager.update_at((1, 1), 0.9, &climate);
+    lazy.update_at((1, 1), 0.9, &climate);
+    assert_eq!(
+        eager.get_tile_at((1, 1)).get_food_level(),
+        lazy.get_tile_at((1, 1)).get_food_level()
+    );
+    let dirty: Vec<_> = lazy.get_chunks().iter().filter(|c| c.is_dirty()).collect();
+    assert_eq!(dirty.len(), 1);
+    assert_eq!(dirty[0].get_origin(), (0, 0));
+
+    eager.update_all(1.2, &climate);
+    lazy.update_all(1.2, &climate);
+    assert_eq!(eager.get_total_food(), lazy.get_total_food());
+}
+
+#[test]
+fn test_terrain_queries() {
+    let mut terrain = Terrain::generate_perlin((20, 20), 0.1);
+    for x in 0..20 {
+        for y in 0..20 {
+            let tile = if x < 10 {
+                Tile::new_water(1.0)
+            } else {
+                Tile::new((x - 10) as f64 / 10.0, 0.3)
+            };
+            terrain.set_tile((x, y), tile);
+        }
+    }
+
+    assert_eq!(terrain.nearest_land((15, 3)), Some((15, 3)));
+    assert_eq!(terrain.nearest_land((2, 7)), Some((10, 7)));
+
+    // The land gets more fertile to the right.
+    let (dx, dy) = terrain.fertility_gradient((15, 5));
+    assert!((dx - 0.1).abs() < 1e-9);
+    assert_eq!(dy, 0.0);
+
+    let food = terrain.total_food_within((15, 5), 1.0);
+    let expected: f64 = [(15, 5), (14, 5), (16, 5), (15, 4), (15, 6)]
+        .iter()
+        .map(|&pos| terrain.get_tile_at(pos).get_food_level())
+        .sum();
+    assert!((food - expected).abs() < 1e-9);
+    assert_eq!(terrain.total_food_within((2, 2), 3.0), 0.0);
+
+    let mut flooded = Terrain::generate_perlin((5, 5), 0.1);
+    flooded.flood((2, 2), 10.0);
+    assert_eq!(flooded.nearest_land((2, 2)), None);
+
+    // Creatures spawned to keep up the minimum start on land.
+    let config = SimConfig {
+        spawn_on_land: true,
+        ..SimConfig::default()
+    };
+    let board = Board::<Brain>::new_random_with_config((50, 50), config).unwrap();
+    for c in &board.creatures {
+        let pos = c.borrow().get_position().into();
+        assert!(board.terrain.get_tile_at(pos).is_land());
+    }
+}
+
+#[test]
+fn test_terrain_save_and_load() {
+    let climate = Climate::new(1.0, 1.0);
+    let mut terrain = Terrain::generate_perlin((40, 40), 0.1);
+    terrain.update_all(10.0, &climate);
+
+    let land = (0..40)
+        .flat_map(|x| (0..40).map(move |y| (x, y)))
+        .find(|&pos| terrain.get_tile_at(pos).is_land())
+        .unwrap();
+    terrain.get_tile_at_mut(land).remove_food(0.5);
+    let food = terrain.get_tile_at(land).get_food_level();
+
+    for name in &["evolvim_test_terrain.bin", "evolvim_test_terrain.ron"] {
+        let path = std::env::temp_dir().join(name);
+        terrain.save_to(&path).unwrap();
+        let mut loaded = Terrain::load_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(get_water_map(&loaded), get_water_map(&terrain));
+        assert_eq!(loaded.get_tile_at(land).get_food_level(), food);
+        assert_eq!(loaded.get_noise_params(), terrain.get_noise_params());
+
+        // The clock starts over, so the food grows back on a new board.
+        loaded.update_all(0.5, &climate);
+        assert!(loaded.get_tile_at(land).get_food_level() > food);
+    }
+
+    assert!(Terrain::load_from(std::env::temp_dir().join("evolvim_no_such_terrain.bin")).is_err());
+}
+
+#[test]
+fn test_take_dirty_tiles() {
+    let climate = Climate::new(0.5, 1.0);
+    let mut terrain = Terrain::generate_perlin((40, 30), 0.1);
+    assert_eq!(terrain.take_dirty_tiles().len(), 40 * 30);
+    assert!(terrain.take_dirty_tiles().is_empty());
+
+    // Only the tiles that were touched are reported.
+    terrain.add_food_or_nothing_at((3, 5), 1.0);
+    terrain.update_at((20, 17), 0.5, &climate);
+    let mut dirty = terrain.take_dirty_tiles();
+    dirty.sort();
+    assert_eq!(dirty, vec![(3, 5), (20, 17)]);
+    assert!(terrain.get_chunks().iter().all(|c| !c.is_dirty()));
+
+    // Updating everything changes everything.
+    terrain.update_all(1.0, &climate);
+    assert_eq!(terrain.take_dirty_tiles().len(), 40 * 30);
+}
+
+#[test]
+fn test_noise_params() {
+    // The seed that was picked is remembered, so the same terrain can be made again.
+    let terrain = Terrain::generate_perlin((40, 40), 0.1);
+    let params = terrain.get_noise_params().unwrap().clone();
+    assert!(params.seed.is_some());
+    let again = Terrain::generate_perlin_with((40, 40), 0.1, &params);
+    assert_eq!(get_water_map(&again), get_water_map(&terrain));
+    assert_eq!(again.get_noise_params(), Some(&params));
+
+    // More octaves give a different coast, a higher water threshold gives more land.
+    let rough = NoiseParams {
+        octaves: 4,
+        ..params.clone()
+    };
+    let rough = Terrain::generate_perlin_with((40, 40), 0.1, &rough);
+    assert_ne!(get_water_map(&rough), get_water_map(&terrain));
+
+    let count_water = |t: &Terrain| get_water_map(t).into_iter().filter(|&w| w).count();
+    let dry = NoiseParams {
+        water_threshold: 1.5,
+        ..params.clone()
+    };
+    let dry = Terrain::generate_perlin_with((40, 40), 0.1, &dry);
+    assert!(count_water(&dry) < count_water(&terrain));
+
+    // Boards use the noise of their config.
+    let config = SimConfig {
+        noise: NoiseParams {
+            seed: Some(1234),
+            ..NoiseParams::default()
+        },
+        ..SimConfig::default()
+    };
+    let a = Board::<Brain>::new_random_with_config((30, 30), config.clone()).unwrap();
+    let b = Board::<Brain>::new_random_with_config((30, 30), config).unwrap();
+    assert_eq!(get_water_map(&a.terrain), get_water_map(&b.terrain));
+    assert_eq!(a.terrain.get_noise_params().unwrap().seed, Some(1234));
+}
+
+#[test]
+fn test_water_depth() {
+    assert!((Tile::new(1.5, 0.0).get_water_depth() - 0.5).abs() < 1e-9);
+    assert_eq!(Tile::new_water(3.0).get_water_depth(), 1.0);
+    assert_eq!(Tile::new(0.5, 0.0).get_water_depth(), 0.0);
+
+    let mut water = Tile::new_water(0.1);
+    assert!(!water.is_deep_water());
+    let shallow_color = water.get_hsba_color();
+    water.set_water_depth(0.9);
+    assert!(water.is_deep_water());
+    assert!(water.get_hsba_color()[2] < shallow_color[2]);
+
+    // Deep water drains energy a lot faster than shallow water, unless the creature can swim.
+    let board_size = (10, 10);
+    let deep = Terrain::from_fertility(board_size, 0.1, |_, _| 2.0);
+    let shallow = Terrain::from_fertility(board_size, 0.1, |_, _| 1.1);
+    let energy_left = |terrain: &Terrain, swimming: f64| {
+        let mut creature = Creature::<Brain>::new_random(board_size, 0.0);
+        // Give all of them the same mass, the friction depends on it. New creatures have at least 1.2 energy.
+        let extra_energy = creature.get_energy() - 1.2;
+        creature.lose_energy(extra_energy);
+        creature.set_swimming(swimming);
+        creature.add_vx(1.0);
+        let start = creature.get_energy();
+        for _ in 0..10 {
+            creature.apply_motions(0.1, terrain, board_size);
+        }
+
+        (creature.get_energy() / start, creature.get_total_velocity())
+    };
+
+    let (in_deep, speed_in_deep) = energy_left(&deep, 0.0);
+    let (in_shallow, speed_in_shallow) = energy_left(&shallow, 0.0);
+    let (swimmer, _) = energy_left(&deep, 1.0);
+    assert!(in_deep < in_shallow);
+    assert!(in_shallow < 1.0);
+    assert_eq!(swimmer, 1.0);
+    assert!(speed_in_deep < speed_in_shallow);
+}
+
+#[test]
+fn test_seasonal_palette() {
+    let palette = SeasonalPalette::default();
+    let tile = Tile::new(0.5, 0.3);
+    let normal = tile.get_hsba_color();
+
+    // The middle of summer looks normal, winter and autumn don't.
+    assert_eq!(tile.get_seasonal_hsba_color(0.625, &palette), normal);
+    let winter = tile.get_seasonal_hsba_color(0.125, &palette);
+    let autumn = tile.get_seasonal_hsba_color(0.875, &palette);
+    assert_ne!(winter, normal);
+    assert_ne!(autumn, normal);
+    assert_ne!(winter, autumn);
+    assert_eq!(tile.get_seasonal_hsba_color(1.125, &palette), winter);
+
+    // Halfway between winter and spring the frost is half as strong.
+    let [(_, before), (_, after)] = palette.get_tints(0.25);
+    assert!((before - palette.winter.1 / 2.0).abs() < 1e-6);
+    assert_eq!(after, 0.0);
+
+    let none = SeasonalPalette::none();
+    assert_eq!(tile.get_seasonal_hsba_color(0.125, &none), normal);
+    assert_eq!(
+        Tile::Obstacle.get_seasonal_hsba_color(0.125, &palette),
+        Tile::Obstacle.get_hsba_color()
+    );
+}
@@ -0,0 +1,79 @@
+extern crate lib_evolvim;
+
+use lib_evolvim::constants::WEATHER_DROUGHT_GROWTH;
+use lib_evolvim::run::RunOptions;
+use lib_evolvim::terrain::tile::Tile;
+use lib_evolvim::weather::{WeatherConfig, WeatherEvent, WeatherKind};
+use lib_evolvim::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn drought(region: usize, start: f64, end: f64) -> WeatherEvent {
+    WeatherEvent {
+        kind: WeatherKind::Drought,
+        region,
+        start,
+        end,
+        severity: 2.0,
+    }
+}
+
+#[test]
+fn test_weather_changes_growth() {
+    let mut climate = Climate::new(0.5, 0.5);
+    let before = climate.get_region_growth_over_time_range(0, 1.0, 0.0);
+
+    // Only the part of the drought between the two times counts, and only in its own region.
+    climate.add_weather(drought(0, 0.5, 2.0));
+    let during = climate.get_region_growth_over_time_range(0, 1.0, 0.0);
+    assert!((before - during - 0.5 * 2.0 * -WEATHER_DROUGHT_GROWTH).abs() < 1e-9);
+    assert_eq!(
+        climate.get_region_growth_over_time_range(1, 1.0, 0.0),
+        before
+    );
+    assert_eq!(climate.get_active_weather(0.7).count(), 1);
+    assert_eq!(climate.get_active_weather(0.2).count(), 0);
+    assert!(climate.has_weather_changed(0.6, 0.4));
+    assert!(!climate.has_weather_changed(0.9, 0.6));
+
+    // Food withers during the drought.
+    let mut dry = Tile::new(0.5, 0.3);
+    dry.update(1.0, &climate);
+    let mut wet = Tile::new(0.5, 0.3);
+    wet.update(1.0, &Climate::new(0.5, 0.5));
+    assert!(dry.get_food_level() < wet.get_food_level());
+
+    // Old weather is forgotten, without a frequency nothing new starts.
+    let config = WeatherConfig::default();
+    assert!(climate.update_weather(10.0, 0.001, &config).is_none());
+    assert!(climate.get_weather().is_empty());
+}
+
+#[test]
+fn test_weather_is_seeded() {
+    let weather = WeatherConfig {
+        frequency: 50.0,
+        ..WeatherConfig::default()
+    };
+
+    let run = |seed: u64| {
+        let mut board = Board::<Brain>::new_random_seeded((30, 30), 0.1, 40, -0.5, 1.0, seed);
+        let config = SimConfig {
+            weather: weather.clone(),
+            ..board.get_config().clone()
+        };
+        board.set_config(config).unwrap();
+        let started = Rc::new(RefCell::new(Vec::new()));
+        let hook_started = started.clone();
+        board.on_weather(move |_, event| hook_started.borrow_mut().push(event.clone()));
+        board.run_for(0.2, &RunOptions::default());
+
+        let started = started.borrow().clone();
+        started
+    };
+
+    let weather = run(3);
+    assert!(!weather.is_empty());
+    assert!(weather.iter().all(|w| w.end > w.start && w.severity > 0.0));
+    assert_eq!(run(3), weather);
+}
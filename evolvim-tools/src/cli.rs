@@ -3,12 +3,22 @@ extern crate ctrlc;
 extern crate lib_evolvim;
 
 use clap::{App, Arg};
-use lib_evolvim::Board;
+use lib_evolvim::board_history::BoardHistory;
+use lib_evolvim::checkpoint::Checkpoints;
+use lib_evolvim::climate::RegionalClimate;
+use lib_evolvim::constants::*;
+use lib_evolvim::experiment::{Barrier, SplitExperiment};
+use lib_evolvim::run::RunOptions;
+use lib_evolvim::serde_structs::SaveFormat;
+use lib_evolvim::{Board, CreatureMetric, RetentionPolicy, SimConfig, SpawnPolicy};
 use std::sync::atomic::Ordering;
 
 // type BrainType = lib_evolvim::neat::NeatBrain;
 type BrainType = lib_evolvim::Brain;
 
+/// The amount of creatures `--export-seed-bank` writes the brain of.
+const SEED_BANK_SIZE: usize = 20;
+
 fn main() {
     let abort_reader = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
     let abort_writer = abort_reader.clone();
@@ -52,26 +62,186 @@ fn main() {
                 .takes_value(true)
                 .help("Amount of years to simulate"),
         )
+        .arg(
+            Arg::with_name("checkpoints")
+                .long("checkpoints")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Save a checkpoint to DIR every few years, only the last 3 are kept"),
+        )
+        .arg(
+            Arg::with_name("checkpoint-every")
+                .long("checkpoint-every")
+                .value_name("YEARS")
+                .takes_value(true)
+                .requires("checkpoints")
+                .help("The amount of years between two checkpoints [default: 10]"),
+        )
+        .arg(
+            Arg::with_name("resume")
+                .long("resume")
+                .takes_value(false)
+                .requires("checkpoints")
+                .conflicts_with("input")
+                .help("Start with the latest checkpoint in the checkpoint directory"),
+        )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .takes_value(true)
+                .conflicts_with_all(&["input", "resume"])
+                .help("Generate the board with the settings in FILE, a TOML or YAML file"),
+        )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .value_name("SEED")
+                .takes_value(true)
+                .help("Only use random numbers generated from SEED, runs with the same seed give the same result"),
+        )
+        .arg(
+            Arg::with_name("seed-bank")
+                .long("seed-bank")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Replace all creatures by creatures with brains from the seed bank in FILE, spawned creatures get one too"),
+        )
+        .arg(
+            Arg::with_name("export-seed-bank")
+                .long("export-seed-bank")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Write the brains of the 20 oldest creatures to FILE when done, see --seed-bank"),
+        )
+        .arg(
+            Arg::with_name("split")
+                .long("split")
+                .value_name("BARRIER")
+                .takes_value(true)
+                .possible_values(&["wall", "water", "permeable"])
+                .help(
+                    "Split the board into two halves with their own climate, separated by BARRIER",
+                ),
+        )
+        .arg(
+            Arg::with_name("split-growth")
+                .long("split-growth")
+                .value_name("MULTIPLIER")
+                .takes_value(true)
+                .requires("split")
+                .help(
+                    "How fast food grows in the right half compared to the left one [default: 1]",
+                ),
+        )
+        .arg(
+            Arg::with_name("history")
+                .long("history")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Write the population over time to FILE as CSV when done"),
+        )
         .arg(
             Arg::with_name("info")
                 .long("info")
                 .takes_value(false)
                 .help("Output a summary of this world"),
         )
+        .arg(
+            Arg::with_name("repair")
+                .long("repair")
+                .takes_value(false)
+                .help("Fix corrupt creatures and collision data of the input file before simulating"),
+        )
+        .arg(
+            Arg::with_name("validate")
+                .long("validate")
+                .takes_value(false)
+                .help("Check the arguments and the input file for errors without simulating"),
+        )
         .get_matches();
 
+    if matches.is_present("validate") {
+        let problems = validate(&matches);
+
+        if problems.is_empty() {
+            println!("Everything looks fine.");
+            return;
+        }
+
+        for problem in &problems {
+            eprintln!("error: {}", problem);
+        }
+        std::process::exit(1);
+    }
+
     let output_file = if matches.is_present("save") {
         matches.value_of("input")
     } else {
         matches.value_of("output")
     };
 
+    let seed = match get_seed(&matches) {
+        Ok(seed) => seed,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
     let mut board: Board<BrainType> = if let Some(name) = matches.value_of("input") {
-        Board::<BrainType>::load_from(name).unwrap()
+        let mut board =
+            Board::<BrainType>::load_from_with_format(name, SaveFormat::from_path(name)).unwrap();
+        if let Some(seed) = seed {
+            board.set_seed(seed);
+        }
+
+        if matches.is_present("repair") {
+            for fixed in board.repair() {
+                println!("Repaired: {}", fixed);
+            }
+        }
+
+        board
+    } else if matches.is_present("resume") {
+        let mut board = Board::restore_latest(matches.value_of("checkpoints").unwrap()).unwrap();
+        if let Some(seed) = seed {
+            board.set_seed(seed);
+        }
+
+        board
     } else {
-        Board::default()
+        let config = matches
+            .value_of("config")
+            .map_or_else(SimConfig::default, |name| {
+                SimConfig::from_path(name).unwrap()
+            });
+        match seed {
+            Some(seed) => Board::new_random_with_config_seeded(DEFAULT_BOARD_SIZE, config, seed),
+            None => Board::new_random_with_config(DEFAULT_BOARD_SIZE, config),
+        }
+        .unwrap()
     };
 
+    match get_split_experiment(&matches) {
+        Some(Ok(split)) => board.set_split_experiment(split),
+        Some(Err(e)) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+        None => {}
+    }
+
+    if let Some(name) = matches.value_of("seed-bank") {
+        board.load_seed_bank(name).unwrap();
+        board.reseed_from_seed_bank();
+        board.set_spawn_policy(SpawnPolicy::FromSeedBank);
+    }
+
+    if matches.is_present("history") {
+        // Ten samples a year
+        board.set_history(BoardHistory::new(100, RetentionPolicy::KeepAll));
+    }
+
     if let Some(years) = matches.value_of("iterations") {
         let mut years: usize = years.parse().unwrap();
 
@@ -79,16 +249,27 @@ fn main() {
             years = std::usize::MAX;
         }
 
+        let options = RunOptions::default().with_abort(abort_reader.clone());
+        let mut checkpoints = matches.value_of("checkpoints").map(|dir| {
+            let every = matches
+                .value_of("checkpoint-every")
+                .map_or(10.0, |y| y.parse().unwrap());
+            Checkpoints::new(dir, every, 3)
+        });
+        if let Some(checkpoints) = &mut checkpoints {
+            checkpoints.tick(&board).unwrap();
+        }
+
         for _j in 0..years {
-            if abort_reader.load(Ordering::SeqCst) {
+            println!("Simulating year {}...", board.get_time() as usize);
+            print!("\x1B[1A");
+            if board.run_for(1.0, &options).aborted {
                 // Ctrl-C was pressed, stop the simulation
                 break;
             }
 
-            println!("Simulating year {}...", board.get_time() as usize);
-            print!("\x1B[1A");
-            for _i in 0..1000 {
-                board.update(0.001);
+            if let Some(checkpoints) = &mut checkpoints {
+                checkpoints.tick(&board).unwrap();
             }
         }
     }
@@ -97,11 +278,209 @@ fn main() {
     print!("\x1B[2K");
 
     if matches.is_present("info") {
-        println!("Year: {}", board.get_time() as usize);
-        println!("Population: {}", board.creatures.len());
+        let stats = board.stats();
+        println!("Year: {}", stats.time as usize);
+        println!("Population: {}", stats.population);
+        if board.get_config().egg_incubation > 0.0 {
+            println!(
+                "Eggs: {} waiting to hatch, {} eaten",
+                stats.eggs,
+                board.get_eggs_eaten()
+            );
+        }
+        println!(
+            "Energy: {:.3} on average, {:.3} at most",
+            stats.mean_energy, stats.max_energy
+        );
+        println!(
+            "Age: {:.3} on average, lifespan {:.3} on average",
+            stats.mean_age, stats.mean_lifespan
+        );
+        println!("Deaths of old age: {}", board.get_deaths_by_old_age());
+        println!(
+            "Diet: {:.3} on average (0 eats plants, 1 eats meat)",
+            stats.mean_diet
+        );
+        println!("Body size: {:.3} on average", stats.mean_body_size);
+        if let Some(largest) = stats.largest_species {
+            println!("Species: {}, the largest is {}", stats.species, largest);
+        }
+        if let Some(oldest) = board.get_leaderboard(CreatureMetric::Age, 1).first() {
+            let oldest = oldest.borrow();
+            println!(
+                "Oldest: {} ({}), age {:.3}",
+                oldest.get_name(),
+                oldest.get_id(),
+                oldest.get_age(board.get_time())
+            );
+        }
+        println!("Food: {:.2}", stats.total_food);
+
+        if let Some([a, b]) = board.get_half_statistics() {
+            for (name, s) in [("A", a), ("B", b)].iter() {
+                println!(
+                    "Half {}: population {}, creature energy {:.2}, food {:.2}, mean age {:.3}, mean mouth hue {:.3}",
+                    name, s.population, s.creature_energy, s.tile_food, s.mean_age, s.mean_mouth_hue
+                );
+            }
+        }
+    }
+
+    if let Some(name) = matches.value_of("history") {
+        let file = std::fs::File::create(name).unwrap();
+        board
+            .get_history()
+            .write_csv(std::io::BufWriter::new(file))
+            .unwrap();
+    }
+
+    if let Some(name) = matches.value_of("export-seed-bank") {
+        board
+            .export_seed_bank(name, CreatureMetric::Age, SEED_BANK_SIZE)
+            .unwrap();
     }
 
     if let Some(name) = output_file {
-        board.save_to(name).unwrap();
+        board
+            .save_to_with_format(name, SaveFormat::from_path(name))
+            .unwrap();
+    }
+}
+
+/// Returns the split experiment asked for on the command line, if any.
+fn get_seed(matches: &clap::ArgMatches) -> Result<Option<u64>, String> {
+    match matches.value_of("seed") {
+        Some(seed) => seed.parse::<u64>().map(Some).map_err(|e| {
+            format!(
+                "--seed should be a whole number of at least 0 but got \"{}\": {}",
+                seed, e
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
+fn get_split_experiment(matches: &clap::ArgMatches) -> Option<Result<SplitExperiment, String>> {
+    let barrier = match matches.value_of("split")? {
+        "wall" => Barrier::Wall,
+        "water" => Barrier::Water(4),
+        _ => Barrier::Permeable,
+    };
+
+    let growth = match matches.value_of("split-growth") {
+        Some(g) => match g.parse::<f64>() {
+            Ok(g) if g >= 0.0 => g,
+            _ => {
+                return Some(Err(format!(
+                    "--split-growth should be a number of at least 0 but got \"{}\"",
+                    g
+                )))
+            }
+        },
+        None => 1.0,
+    };
+
+    Some(Ok(SplitExperiment::new(
+        RegionalClimate::new(DEFAULT_MIN_TEMP, DEFAULT_MAX_TEMP, 1.0),
+        RegionalClimate::new(DEFAULT_MIN_TEMP, DEFAULT_MAX_TEMP, growth),
+        barrier,
+    )))
+}
+
+/// Does everything needed to start a run except for the simulation itself and returns every problem it ran into.
+fn validate(matches: &clap::ArgMatches) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if let Some(years) = matches.value_of("iterations") {
+        if let Err(e) = years.parse::<usize>() {
+            problems.push(format!(
+                "--updates should be a whole amount of years but got \"{}\": {}",
+                years, e
+            ));
+        }
     }
+
+    if let Some(every) = matches.value_of("checkpoint-every") {
+        match every.parse::<f64>() {
+            Ok(y) if y > 0.0 => {}
+            _ => problems.push(format!(
+                "--checkpoint-every should be a positive amount of years but got \"{}\"",
+                every
+            )),
+        }
+    }
+
+    if let Err(e) = get_seed(matches) {
+        problems.push(e);
+    }
+
+    if let Some(Err(e)) = get_split_experiment(matches) {
+        problems.push(e);
+    }
+
+    let output_file = if matches.is_present("save") {
+        matches.value_of("input")
+    } else {
+        matches.value_of("output")
+    };
+    if let Some(name) = output_file {
+        let dir = std::path::Path::new(name)
+            .parent()
+            .filter(|d| !d.as_os_str().is_empty())
+            .unwrap_or_else(|| std::path::Path::new("."));
+
+        if !dir.is_dir() {
+            problems.push(format!(
+                "can't save to {} because the directory {} doesn't exist",
+                name,
+                dir.display()
+            ));
+        }
+    }
+
+    // Without the board there's nothing left to check.
+    let mut board = if let Some(name) = matches.value_of("input") {
+        match Board::<BrainType>::load_from_with_format(name, SaveFormat::from_path(name)) {
+            Ok(board) => board,
+            Err(e) => {
+                problems.push(format!("could not load the input file {}: {}", name, e));
+                return problems;
+            }
+        }
+    } else if matches.is_present("resume") {
+        let dir = matches.value_of("checkpoints").unwrap();
+        match Board::<BrainType>::restore_latest(dir) {
+            Ok(board) => board,
+            Err(e) => {
+                problems.push(format!("could not resume from {}: {}", dir, e));
+                return problems;
+            }
+        }
+    } else if let Some(name) = matches.value_of("config") {
+        match SimConfig::from_path(name)
+            .and_then(|config| Board::new_random_with_config(DEFAULT_BOARD_SIZE, config))
+        {
+            Ok(board) => board,
+            Err(e) => {
+                problems.push(format!("could not load the config file {}: {}", name, e));
+                return problems;
+            }
+        }
+    } else {
+        Board::default()
+    };
+
+    // The seed bank goes into the board so its brains are checked against the config as well.
+    if let Some(name) = matches.value_of("seed-bank") {
+        match board.load_seed_bank(name) {
+            Ok(0) => problems.push(format!("the seed bank {} is empty", name)),
+            Ok(_) => {}
+            Err(e) => problems.push(format!("could not load the seed bank {}: {}", name, e)),
+        }
+    }
+
+    // Also reports brains that lack a sense or an output the config needs.
+    problems.extend(board.validate());
+
+    problems
 }
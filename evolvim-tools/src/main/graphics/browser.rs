@@ -0,0 +1,155 @@
+use super::*;
+
+/// The height of a single creature in the browser, in pixels.
+const ROW_HEIGHT: f64 = 40.0;
+/// The width of the browser, in pixels.
+const PANEL_WIDTH: f64 = 180.0;
+
+/// A scrollable list of all living creatures on the right side of the window, sorted by a metric.
+///
+/// Clicking a creature selects it, which also makes the camera follow it.
+pub struct PopulationBrowser {
+    pub open: bool,
+    pub metric: CreatureMetric,
+    /// The amount of rows scrolled past.
+    scroll: usize,
+}
+
+impl Default for PopulationBrowser {
+    fn default() -> Self {
+        PopulationBrowser {
+            open: false,
+            metric: CreatureMetric::Age,
+            scroll: 0,
+        }
+    }
+}
+
+impl PopulationBrowser {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn next_metric(&mut self) {
+        self.metric = self.metric.next();
+        self.scroll = 0;
+    }
+
+    /// Scrolls `rows` rows down (or up if negative), never past the end of the population.
+    pub fn scroll(&mut self, rows: isize, population: usize) {
+        let scroll = (self.scroll as isize + rows).max(0) as usize;
+
+        self.scroll = scroll.min(population.saturating_sub(1));
+    }
+
+    /// Returns true if the given point in the window lies on the browser.
+    pub fn contains(&self, mouse: (f64, f64), window_size: (f64, f64)) -> bool {
+        self.open && mouse.0 >= window_size.0 - PANEL_WIDTH && mouse.0 < window_size.0
+    }
+
+    /// Returns the creature drawn at the given point in the window, if any.
+    pub fn get_creature_at<B: NeuralNet>(
+        &self,
+        board: &Board<B>,
+        mouse: (f64, f64),
+        window_size: (f64, f64),
+    ) -> Option<HLSoftBody<B>> {
+        if !self.contains(mouse, window_size) || mouse.1 < ROW_HEIGHT {
+            // The first row is the header.
+            return Option::None;
+        }
+
+        let row = ((mouse.1 - ROW_HEIGHT) / ROW_HEIGHT).floor() as usize + self.scroll;
+
+        board
+            .get_creatures_sorted_by(self.metric)
+            .into_iter()
+            .nth(row)
+    }
+
+    pub fn draw<B, C, G>(
+        &self,
+        board: &Board<B>,
+        window_size: (f64, f64),
+        context: Context,
+        graphics: &mut G,
+        glyphs: &mut C,
+    ) where
+        B: NeuralNet,
+        C: CharacterCache,
+        C::Error: Debug,
+        G: Graphics<Texture = C::Texture>,
+    {
+        let context = context.trans(window_size.0 - PANEL_WIDTH, 0.0);
+        let text = Text::new(14);
+        let time = board.get_time();
+
+        rectangle(
+            [1.0, 1.0, 1.0, 0.8],
+            [0.0, 0.0, PANEL_WIDTH, window_size.1],
+            context.transform,
+            graphics,
+        );
+
+        text.draw(
+            &format!(
+                "{} creatures by {:?}",
+                board.get_population_size(),
+                self.metric
+            ),
+            glyphs,
+            &context.draw_state,
+            context.transform.trans(10.0, ROW_HEIGHT * 0.6),
+            graphics,
+        )
+        .expect("Your font doesn't seem to be working... Could not draw text.");
+
+        let visible_rows = (window_size.1 / ROW_HEIGHT) as usize;
+        let creatures = board.get_leaderboard(self.metric, self.scroll + visible_rows);
+
+        for (i, c_rc) in creatures
+            .iter()
+            .skip(self.scroll)
+            .take(visible_rows.saturating_sub(1))
+            .enumerate()
+        {
+            let c = c_rc.borrow();
+            let top = ROW_HEIGHT * (i + 1) as f64;
+
+            if board.selected_creature.is_selected(c.get_id()) {
+                rectangle(
+                    [1.0, 1.0, 0.0, 0.5],
+                    [0.0, top, PANEL_WIDTH, ROW_HEIGHT],
+                    context.transform,
+                    graphics,
+                );
+            }
+
+            // The portrait: the body in its mouth color, scaled so everything fits in a row.
+            let radius = (c.get_radius() * 25.0).min(ROW_HEIGHT / 2.0 - 2.0);
+            let center = (ROW_HEIGHT / 2.0, top + ROW_HEIGHT / 2.0);
+            ellipse(
+                from_hsba([c.get_mouth_hue() as f32, 1.0, 1.0, 1.0]),
+                [
+                    center.0 - radius,
+                    center.1 - radius,
+                    radius * 2.0,
+                    radius * 2.0,
+                ],
+                context.transform,
+                graphics,
+            );
+
+            text.draw(
+                &format!("{:.2} {}", self.metric.get_value(&c, time), c.get_name()),
+                glyphs,
+                &context.draw_state,
+                context
+                    .transform
+                    .trans(ROW_HEIGHT + 5.0, top + ROW_HEIGHT * 0.6),
+                graphics,
+            )
+            .expect("Your font doesn't seem to be working... Could not draw text.");
+        }
+    }
+}
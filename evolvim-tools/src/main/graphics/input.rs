@@ -0,0 +1,170 @@
+//! Input abstractions so the viewer isn't limited to a mouse and keyboard.
+//!
+//! Touch and gamepad events are turned into `Gesture`s, `View::apply_gesture` then handles them no matter where they
+//! came from.
+
+use std::collections::HashMap;
+
+/// How far (in pixels) a finger may move before a touch stops counting as a tap.
+const TAP_MAX_MOVEMENT: f64 = 10.0;
+/// Stick positions closer to the center than this are ignored, worn sticks rarely return to exactly 0.
+const GAMEPAD_DEAD_ZONE: f64 = 0.15;
+/// How fast the left stick pans the view, in pixels per second at full tilt.
+const GAMEPAD_PAN_SPEED: f64 = 600.0;
+/// How fast the right stick zooms, the view zooms by a factor of e every second at full tilt.
+const GAMEPAD_ZOOM_SPEED: f64 = 1.0;
+
+/// Something the user wants to do with the view, independent of the device used to do it.
+#[derive(Debug, PartialEq)]
+pub enum Gesture {
+    /// Moves the board along by this many pixels, like dragging it.
+    Pan(f64, f64),
+    /// Zooms in (`factor` > 1) or out around the given window position.
+    Zoom { factor: f64, center: (f64, f64) },
+    /// Clicks on the given window position.
+    Select(f64, f64),
+    /// Stops following the selected creature.
+    Deselect,
+}
+
+/// The phase of a single touch, mirrors piston's `Touch`.
+pub enum TouchPhase {
+    Start,
+    Move,
+    End,
+    Cancel,
+}
+
+/// Keeps track of the fingers on the screen and recognizes taps, drags and pinches.
+#[derive(Default)]
+pub struct TouchTracker {
+    touches: HashMap<i64, (f64, f64)>,
+    /// Where the current touch started if it can still become a tap.
+    tap_start: Option<(f64, f64)>,
+}
+
+impl TouchTracker {
+    /// Handles a touch event, `pos` should be in pixels.
+    pub fn on_touch(&mut self, id: i64, pos: (f64, f64), phase: TouchPhase) -> Option<Gesture> {
+        match phase {
+            TouchPhase::Start => {
+                self.touches.insert(id, pos);
+                // A tap can only be done with a single finger.
+                self.tap_start = if self.touches.len() == 1 {
+                    Some(pos)
+                } else {
+                    None
+                };
+
+                None
+            }
+            TouchPhase::Move => self.on_move(id, pos),
+            TouchPhase::End => {
+                self.touches.remove(&id);
+
+                match self.tap_start.take() {
+                    Some(_) if self.touches.is_empty() => Some(Gesture::Select(pos.0, pos.1)),
+                    _ => None,
+                }
+            }
+            TouchPhase::Cancel => {
+                self.touches.remove(&id);
+                self.tap_start = None;
+
+                None
+            }
+        }
+    }
+
+    fn on_move(&mut self, id: i64, pos: (f64, f64)) -> Option<Gesture> {
+        let old = self.touches.insert(id, pos)?;
+
+        if let Some(start) = self.tap_start {
+            if distance(start, pos) > TAP_MAX_MOVEMENT {
+                self.tap_start = None;
+            }
+        }
+
+        match self.touches.len() {
+            1 => Some(Gesture::Pan(pos.0 - old.0, pos.1 - old.1)),
+            2 => {
+                let other = self
+                    .touches
+                    .iter()
+                    .find(|(other_id, _)| **other_id != id)
+                    .map(|(_, p)| *p)?;
+
+                let old_distance = distance(old, other);
+                if old_distance == 0.0 {
+                    return None;
+                }
+
+                Some(Gesture::Zoom {
+                    factor: distance(pos, other) / old_distance,
+                    center: ((pos.0 + other.0) / 2.0, (pos.1 + other.1) / 2.0),
+                })
+            }
+            // Three or more fingers don't do anything (yet).
+            _ => None,
+        }
+    }
+}
+
+/// The state of a gamepad, the left stick pans, the right stick zooms.
+///
+/// Axes and buttons are numbered like SDL2 does it.
+#[derive(Default)]
+pub struct Gamepad {
+    axes: [f64; 4],
+}
+
+impl Gamepad {
+    pub fn on_axis(&mut self, axis: u8, position: f64) {
+        if let Some(a) = self.axes.get_mut(axis as usize) {
+            *a = if position.abs() < GAMEPAD_DEAD_ZONE {
+                0.0
+            } else {
+                position
+            };
+        }
+    }
+
+    /// Handles a button press, `window_size` is used to find the center of the window.
+    ///
+    /// A selects whatever is in the center and B deselects.
+    pub fn on_button(&self, button: u8, window_size: (f64, f64)) -> Option<Gesture> {
+        match button {
+            0 => Some(Gesture::Select(window_size.0 / 2.0, window_size.1 / 2.0)),
+            1 => Some(Gesture::Deselect),
+            _ => None,
+        }
+    }
+
+    /// Returns what the sticks do during `dt` seconds.
+    pub fn get_gestures(&self, dt: f64, window_size: (f64, f64)) -> Vec<Gesture> {
+        let [left_x, left_y, _right_x, right_y] = self.axes;
+        let mut gestures = Vec::new();
+
+        if left_x != 0.0 || left_y != 0.0 {
+            // Pushing the stick right moves the camera right, so the board moves left.
+            gestures.push(Gesture::Pan(
+                -left_x * GAMEPAD_PAN_SPEED * dt,
+                -left_y * GAMEPAD_PAN_SPEED * dt,
+            ));
+        }
+
+        if right_y != 0.0 {
+            // Pushing the stick up zooms in.
+            gestures.push(Gesture::Zoom {
+                factor: (-right_y * GAMEPAD_ZOOM_SPEED * dt).exp(),
+                center: (window_size.0 / 2.0, window_size.1 / 2.0),
+            });
+        }
+
+        gestures
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
@@ -7,19 +7,28 @@
 extern crate graphics;
 extern crate lib_evolvim;
 
+pub mod browser;
+pub mod input;
 pub mod ui;
 pub mod view;
+pub use self::browser::PopulationBrowser;
+pub use self::input::{Gamepad, Gesture, TouchPhase, TouchTracker};
 pub use self::ui::{Dragging, MouseCoordinate};
-pub use self::view::View;
+pub use self::view::{TerraformTool, View};
 
 use self::graphics::character::CharacterCache;
 use self::graphics::text::Text;
 use self::graphics::types::Color;
-use self::graphics::{ellipse, rectangle};
+use self::graphics::{ellipse, line, rectangle};
 use self::graphics::{Context, Graphics, Transformed};
 use std::fmt::Debug;
 
+use lib_evolvim::brain::sensors::SensorReadings;
+use lib_evolvim::brain::vision::HitType;
 use lib_evolvim::constants::*;
+use lib_evolvim::egg::Egg;
+use lib_evolvim::snapshot::BoardSnapshot;
+use lib_evolvim::species::get_species_color;
 use lib_evolvim::*;
 
 // pub trait Drawable {
@@ -29,40 +38,8 @@ use lib_evolvim::*;
 /// Converts hsba (Hue, Saturation, Brightness, Alpha) into rgba (Red, Green, Blue, Alpha)
 ///
 /// All input values should range from 0 to 1. All output values will range from 0 to 1.
-///
-/// Formulae from [here](https://en.wikipedia.org/wiki/HSL_and_HSV#From_HSV)
 pub fn from_hsba(hsba: [f32; 4]) -> Color {
-    let [hue, sat, bri, alpha] = hsba;
-
-    assert!(hue <= 1.0, "Hue can't be larger than 1.");
-    assert!(sat <= 1.0);
-    assert!(bri <= 1.0);
-    assert!(alpha <= 1.0);
-
-    // Chroma
-    let c = bri * sat;
-    // H' = hue * 360 / 60 = hue * 6
-    let mut h = hue * 6.0;
-    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
-
-    if h == 0.0 {
-        h = 1.0;
-    }
-
-    let (r, g, b): (f32, f32, f32) = match h.ceil() as usize {
-        1 => (c, x, 0.0),
-        2 => (x, c, 0.0),
-        3 => (0.0, c, x),
-        4 => (0.0, x, c),
-        5 => (x, 0.0, c),
-        6 => (c, 0.0, x),
-        // Value should not be larger than 6 --> hue should not be larger than 1
-        _ => unreachable!(),
-    };
-
-    let m = bri - c;
-
-    return [r + m, g + m, b + m, alpha];
+    lib_evolvim::color::hsba_to_rgba(hsba)
 }
 
 pub fn draw_lines<G, C>(
@@ -122,13 +99,17 @@ pub fn draw_terrain<C, G>(
 
     let mut shape = rectangle::Rectangle::new([1., 1., 1., 1.]);
 
-    for x in view.get_x_range() {
-        for y in view.get_y_range() {
-            let tile = terrain.get_tile_at((x, y));
+    let year = view.board.get_time();
+    let (x_range, y_range) = (view.get_x_range(), view.get_y_range());
+    for chunk in terrain.get_chunks_in(x_range.clone(), y_range.clone()) {
+        for ((x, y), tile) in chunk.iter() {
+            if !x_range.contains(&x) || !y_range.contains(&y) {
+                continue;
+            }
 
             let rect = [x as f64 * size, y as f64 * size, size, size];
 
-            shape = shape.color(from_hsba(tile.get_hsba_color()));
+            shape = shape.color(from_hsba(tile.get_seasonal_hsba_color(year, &view.palette)));
 
             shape.draw(rect, &context.draw_state, transform, graphics);
         }
@@ -160,6 +141,28 @@ pub fn draw_terrain<C, G>(
     }
 }
 
+/// Draws a transparent overlay over the given tiles, used to show what a terraforming brush will change.
+pub fn draw_brush_preview<G: Graphics>(
+    footprint: &[BoardCoordinate],
+    color: Color,
+    context: Context,
+    graphics: &mut G,
+    view: &View,
+) {
+    let size = view.get_tile_size();
+    let transform = context
+        .transform
+        .trans(-view.get_precise_x() * size, -view.get_precise_y() * size);
+
+    let shape = rectangle::Rectangle::new(color);
+
+    for (x, y) in footprint {
+        let rect = [*x as f64 * size, *y as f64 * size, size, size];
+
+        shape.draw(rect, &context.draw_state, transform, graphics);
+    }
+}
+
 pub fn draw_creature<B: lib_evolvim::brain::NeuralNet, G: Graphics>(
     creature: &Creature<B>,
     context: Context,
@@ -172,7 +175,11 @@ pub fn draw_creature<B: lib_evolvim::brain::NeuralNet, G: Graphics>(
         .trans(-view.get_precise_x() * size, -view.get_precise_y() * size);
 
     let radius = creature.get_radius();
-    let color = from_hsba([creature.get_mouth_hue() as f32, 1.0, 1.0, 1.0]);
+    let species = view.board.get_species().get_species_of(creature.get_id());
+    let color = match species {
+        Some(species) if view.show_species => from_hsba(get_species_color(species)),
+        _ => from_hsba([creature.get_mouth_hue() as f32, 1.0, 1.0, 1.0]),
+    };
 
     let rect = [
         // This gives the upper-left corner of the circle so subtract the radius.
@@ -187,6 +194,137 @@ pub fn draw_creature<B: lib_evolvim::brain::NeuralNet, G: Graphics>(
     ellipse.draw(rect, &context.draw_state, transform, graphics);
 }
 
+/// Draws an egg as an off-white oval about the size of the baby inside, with a dot in the baby's mouth hue.
+pub fn draw_egg<B, G: Graphics>(egg: &Egg<B>, context: Context, graphics: &mut G, view: &View) {
+    let size = view.get_tile_size();
+    let transform = context
+        .transform
+        .trans(-view.get_precise_x() * size, -view.get_precise_y() * size);
+
+    let baby = egg.get_baby();
+    let radius = baby.get_radius();
+    let (px, py) = (baby.get_px(), baby.get_py());
+
+    let shell = [
+        (px - radius * 0.8) * size,
+        (py - radius) * size,
+        radius * 1.6 * size,
+        radius * 2.0 * size,
+    ];
+    ellipse::Ellipse::new([1.0, 0.97, 0.85, 1.0])
+        .border(ellipse::Border {
+            color: [0.4, 0.35, 0.3, 1.0],
+            radius: 0.5,
+        })
+        .draw(shell, &context.draw_state, transform, graphics);
+
+    let dot = [
+        (px - radius * 0.3) * size,
+        (py - radius * 0.3) * size,
+        radius * 0.6 * size,
+        radius * 0.6 * size,
+    ];
+    ellipse::Ellipse::new(from_hsba([baby.get_mouth_hue() as f32, 1.0, 1.0, 1.0])).draw(
+        dot,
+        &context.draw_state,
+        transform,
+        graphics,
+    );
+}
+
+/// Draws the tiles and (if `with_creatures`) the creatures of a `BoardSnapshot`, used to watch replays.
+pub fn draw_snapshot<G: Graphics>(
+    snapshot: &BoardSnapshot,
+    with_creatures: bool,
+    context: Context,
+    graphics: &mut G,
+    view: &View,
+) {
+    let size = view.get_tile_size();
+    let transform = context
+        .transform
+        .trans(-view.get_precise_x() * size, -view.get_precise_y() * size);
+
+    let mut shape = rectangle::Rectangle::new([1., 1., 1., 1.]);
+
+    for x in view.get_x_range() {
+        for y in view.get_y_range() {
+            let rect = [x as f64 * size, y as f64 * size, size, size];
+
+            shape = shape.color(from_hsba(snapshot.get_tile_color(x, y)));
+
+            shape.draw(rect, &context.draw_state, transform, graphics);
+        }
+    }
+
+    if !with_creatures {
+        return;
+    }
+
+    for c in snapshot.get_creatures() {
+        let rect = [
+            (c.px - c.radius) * size,
+            (c.py - c.radius) * size,
+            c.radius * 2.0 * size,
+            c.radius * 2.0 * size,
+        ];
+
+        ellipse::Ellipse::new(from_hsba([c.hue as f32, 1.0, 1.0, 1.0])).draw(
+            rect,
+            &context.draw_state,
+            transform,
+            graphics,
+        );
+    }
+}
+
+/// Draws a ring around a creature to show it belongs to the lineage being followed.
+pub fn draw_lineage_highlight<B, G: Graphics>(
+    creature: &Creature<B>,
+    context: Context,
+    graphics: &mut G,
+    view: &View,
+) {
+    draw_ring(creature, [1.0, 0.84, 0.0, 1.0], context, graphics, view);
+}
+
+/// Draws a ring around a creature to show it's selected.
+pub fn draw_selection_highlight<B, G: Graphics>(
+    creature: &Creature<B>,
+    context: Context,
+    graphics: &mut G,
+    view: &View,
+) {
+    draw_ring(creature, [0.0, 0.0, 0.0, 1.0], context, graphics, view);
+}
+
+fn draw_ring<B, G: Graphics>(
+    creature: &Creature<B>,
+    color: Color,
+    context: Context,
+    graphics: &mut G,
+    view: &View,
+) {
+    let size = view.get_tile_size();
+    let transform = context
+        .transform
+        .trans(-view.get_precise_x() * size, -view.get_precise_y() * size);
+
+    // Leave some room between the creature and the ring.
+    let radius = creature.get_radius() * 1.5;
+
+    let rect = [
+        (creature.get_px() - radius) * size,
+        (creature.get_py() - radius) * size,
+        radius * 2.0 * size,
+        radius * 2.0 * size,
+    ];
+
+    let ring = ellipse::Ellipse::new_border(color, 2.0);
+
+    ring.draw(rect, &context.draw_state, transform, graphics);
+}
+
 pub fn draw_details_creature<B, C, G>(
     creature: &Creature<B>,
     context: Context,
@@ -202,15 +340,25 @@ pub fn draw_details_creature<B, C, G>(
     let text = Text::new(18);
     let mut text_to_draw = Vec::new();
 
-    text_to_draw.push(format!("Energy: {:.3}", creature.get_energy()));
+    text_to_draw.push(format!(
+        "{} (id {})",
+        creature.get_name(),
+        creature.get_id()
+    ));
+    text_to_draw.push(format!(
+        "Energy: {:.3} of {:.3}",
+        creature.get_energy(),
+        creature.get_energy_capacity()
+    ));
     let time_step = 0.001;
     text_to_draw.push(format!(
         "Energy D: {:.3}",
         creature.get_energy_change(time_step)
     ));
     text_to_draw.push(format!(
-        "Age: {:.3}",
-        creature.get_age(view.board.get_time())
+        "Age: {:.3} of {:.3}",
+        creature.get_age(view.board.get_time()),
+        creature.get_lifespan()
     ));
     text_to_draw.push(format!(
         "Pos: ({:.1}, {:.1})",
@@ -218,6 +366,10 @@ pub fn draw_details_creature<B, C, G>(
         creature.get_py()
     ));
     text_to_draw.push(format!("Speed: {:.3}", creature.get_total_velocity()));
+    text_to_draw.push(format!("Diet: {:.3}", creature.get_diet()));
+    if let Some(species) = view.board.get_species().get_species_of(creature.get_id()) {
+        text_to_draw.push(format!("Species: {}", species));
+    }
 
     draw_lines(
         text_to_draw,
@@ -252,6 +404,52 @@ pub fn draw_details_creature<B, C, G>(
     ellipse.draw(rect, &context.draw_state, transform, graphics);
 }
 
+/// Draws what the sensors of a creature perceive: its vision rays in the world and a panel with all readings.
+pub fn draw_sensor_view<C, G>(
+    readings: &SensorReadings,
+    context: Context,
+    graphics: &mut G,
+    glyphs: &mut C,
+    view: &View,
+) where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let size = view.get_tile_size();
+    let transform = context
+        .transform
+        .trans(-view.get_precise_x() * size, -view.get_precise_y() * size);
+
+    let (x, y) = readings.origin;
+    for ray in &readings.rays {
+        let color = match ray.hit.hit_type {
+            HitType::Nothing => [0.5, 0.5, 0.5, 0.6],
+            HitType::Creature => from_hsba([ray.hit.hue as f32, 1.0, 1.0, 1.0]),
+            HitType::Water => [0.0, 0.0, 1.0, 1.0],
+            HitType::Obstacle => [0.5, 0.5, 0.5, 1.0],
+        };
+        let (end_x, end_y) = ray.get_end_point(readings.origin);
+
+        line::Line::new(color, 1.0).draw(
+            [x * size, y * size, end_x * size, end_y * size],
+            &context.draw_state,
+            transform,
+            graphics,
+        );
+    }
+
+    draw_lines(
+        readings.to_lines(),
+        20.0,
+        300.0,
+        context.trans(0.0, 440.0),
+        Text::new(18),
+        glyphs,
+        graphics,
+    );
+}
+
 pub trait DrawableBrain {
     fn draw_brain<C, G>(&self, context: Context, graphics: &mut G, glyphs: &mut C)
     where
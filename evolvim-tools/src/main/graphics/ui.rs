@@ -20,6 +20,11 @@ impl MouseCoordinate {
         return MouseCoordinate(x, y);
     }
 
+    /// Returns the position of the mouse in the window, in pixels.
+    pub fn get_window_position(&self) -> (f64, f64) {
+        (self.0, self.1)
+    }
+
     /// Converts this into a coordinate of the board.
     ///
     /// # Arguments
@@ -1,7 +1,21 @@
 use super::*;
 use crate::BrainType;
+use lib_evolvim::lineage::{LineageTracker, SuccessionEvent};
+use lib_evolvim::replay::Replayer;
+use lib_evolvim::terrain::edit::{Brush, EditHistory};
+use lib_evolvim::terrain::palette::SeasonalPalette;
+use lib_evolvim::terrain::tile::Tile;
+use std::fs::File;
+use std::io::BufReader;
 use std::ops::Range;
 
+/// How much one step of the mouse wheel zooms in.
+const SCROLL_ZOOM_FACTOR: f64 = 1.1;
+/// The smallest a tile can be drawn, in pixels.
+const MIN_TILE_WIDTH: f64 = 10.0;
+/// The biggest a tile can be drawn, in pixels.
+const MAX_TILE_WIDTH: f64 = 300.0;
+
 /// The view part of MVC (Model-View-Controller), currently takes on jobs for the controller too.
 ///
 /// TODO: Provide adequate error handling when the mouse leaves the window.
@@ -19,6 +33,8 @@ pub struct View {
 
     _base_tile_width: f64,
     tile_width: f64,
+    /// The size of the area the board is drawn on in pixels, stays the same when zooming.
+    window_size: (f64, f64),
 
     pub board: Board<BrainType>,
 
@@ -26,12 +42,36 @@ pub struct View {
 
     drag: Dragging,
     mode: DisplayMode,
+
+    pub terraform: Option<TerraformTool>,
+    pub brush: Brush,
+    pub edit_history: EditHistory,
+
+    /// The colors the terrain gets in every season, `SeasonalPalette::none` keeps them the same all year round.
+    pub palette: SeasonalPalette,
+
+    /// Shows what the selected creature perceives.
+    pub show_sensors: bool,
+    /// Colors creatures by their species instead of by the hue of their mouth.
+    pub show_species: bool,
+    /// Clicking a creature adds it to the selection instead of replacing the selection, e.g. while shift is held.
+    pub multi_select: bool,
+
+    pub browser: PopulationBrowser,
+
+    /// Follows the descendants of a creature the user pinned.
+    pub lineage: Option<LineageTracker>,
+
+    /// A recorded run that's shown instead of `board`.
+    pub replay: Option<Replayer<BufReader<File>>>,
 }
 
 impl Default for View {
     fn default() -> Self {
         let board = Board::default();
         let base_tile_width = 100.0;
+        let tiles_on_width = 10;
+        let tiles_on_height = 9;
 
         View {
             precise_x: 0.0,
@@ -40,11 +80,15 @@ impl Default for View {
             max_x: board.get_board_width(),
             max_y: board.get_board_height(),
 
-            tiles_on_height: 9,
-            tiles_on_width: 10,
+            tiles_on_height,
+            tiles_on_width,
 
             _base_tile_width: base_tile_width,
             tile_width: base_tile_width,
+            window_size: (
+                tiles_on_width as f64 * base_tile_width,
+                tiles_on_height as f64 * base_tile_width,
+            ),
 
             board,
 
@@ -52,6 +96,22 @@ impl Default for View {
 
             drag: Dragging::None,
             mode: DisplayMode::default(),
+
+            terraform: Option::None,
+            brush: Brush::default(),
+            edit_history: EditHistory::default(),
+
+            palette: SeasonalPalette::default(),
+
+            show_sensors: false,
+            show_species: false,
+            multi_select: false,
+
+            browser: PopulationBrowser::default(),
+
+            lineage: Option::None,
+
+            replay: Option::None,
         }
     }
 }
@@ -62,6 +122,23 @@ impl View {
 
         self.drag = None;
 
+        let mouse = self.mouse.get_window_position();
+        if self.browser.contains(mouse, self.get_window_size()) {
+            if let Some(c) =
+                self.browser
+                    .get_creature_at(&self.board, mouse, self.get_window_size())
+            {
+                let id = c.borrow().get_id();
+                self.select(id);
+            }
+            return;
+        }
+
+        if self.terraform.is_some() {
+            self.paint_terrain();
+            return;
+        }
+
         if let Some(exact_pos) = self.mouse.into_board_precise_coordinate(
             self.get_precise_x(),
             self.get_precise_y(),
@@ -70,6 +147,7 @@ impl View {
         ) {
             let (x, y) = BoardCoordinate::from(exact_pos.clone());
             let soft_bodies = self.board.soft_bodies_in_positions.get_soft_bodies_at(x, y);
+            let mut clicked = Option::None;
 
             for c_ref in soft_bodies {
                 let c = c_ref.borrow();
@@ -81,10 +159,23 @@ impl View {
                 let dist = lib_evolvim::softbody::distance(exact_pos.0, exact_pos.1, px, py);
 
                 if dist < radius {
-                    self.board.selected_creature.select(c_ref.clone());
+                    clicked = Some(c.get_id());
                     break;
                 }
             }
+
+            if let Some(id) = clicked {
+                self.select(id);
+            }
+        }
+    }
+
+    /// Selects the creature with the given id, or adds it to (or removes it from) the selection with `multi_select`.
+    fn select(&mut self, id: usize) {
+        if self.multi_select {
+            self.board.selected_creature.toggle(id);
+        } else {
+            self.board.selected_creature.select(id);
         }
     }
 
@@ -110,6 +201,183 @@ impl View {
         self.mouse = MouseCoordinate::new(x, y);
     }
 
+    /// Scrolls the population browser if the mouse is on it, zooms otherwise.
+    pub fn on_mouse_scroll(&mut self, _x: f64, y: f64) {
+        let mouse = self.mouse.get_window_position();
+
+        if self.browser.contains(mouse, self.get_window_size()) {
+            let population = self.board.get_population_size();
+            self.browser.scroll(-y.signum() as isize, population);
+        } else {
+            self.zoom_at(SCROLL_ZOOM_FACTOR.powf(y), mouse);
+        }
+    }
+
+    /// Handles a `Gesture` from a touch screen or gamepad.
+    pub fn apply_gesture(&mut self, gesture: Gesture) {
+        match gesture {
+            Gesture::Pan(x, y) => self.pan_by(x, y),
+            Gesture::Zoom { factor, center } => self.zoom_at(factor, center),
+            Gesture::Select(x, y) => {
+                // Behave exactly like a click there.
+                self.update_mouse(x, y);
+                self.on_mouse_release();
+            }
+            Gesture::Deselect => self.board.selected_creature.deselect(),
+        }
+    }
+
+    /// Moves the board along by `x` and `y` pixels.
+    pub fn pan_by(&mut self, x: f64, y: f64) {
+        self.change_precise_x(-x / self.tile_width);
+        self.change_precise_y(-y / self.tile_width);
+    }
+
+    /// Zooms in by `factor` (or out if it's less than 1), the board stays put under `center`.
+    pub fn zoom_at(&mut self, factor: f64, center: (f64, f64)) {
+        let board_x = self.precise_x + center.0 / self.tile_width;
+        let board_y = self.precise_y + center.1 / self.tile_width;
+
+        let (width, height) = self.window_size;
+        // Never zoom out further than the board is large.
+        let min_tile_width = (width / self.max_x as f64)
+            .max(height / self.max_y as f64)
+            .max(MIN_TILE_WIDTH);
+        self.tile_width = (self.tile_width * factor)
+            .max(min_tile_width)
+            .min(MAX_TILE_WIDTH);
+
+        self.tiles_on_width = ((width / self.tile_width).ceil() as usize).min(self.max_x);
+        self.tiles_on_height = ((height / self.tile_width).ceil() as usize).min(self.max_y);
+
+        self.set_precise_x(board_x - center.0 / self.tile_width);
+        self.set_precise_y(board_y - center.1 / self.tile_width);
+    }
+
+    /// Cycles through the terraforming tools, ending with terraforming turned off.
+    pub fn switch_terraform_tool(&mut self) {
+        use self::TerraformTool::*;
+
+        self.terraform = match self.terraform {
+            Option::None => Some(Water),
+            Some(Water) => Some(Barren),
+            Some(Barren) => Some(Fertile),
+            Some(Fertile) => Some(Obstacle),
+            Some(Obstacle) => Option::None,
+        };
+    }
+
+    pub fn change_brush_radius(&mut self, change: f64) {
+        self.brush.radius = (self.brush.radius + change).max(0.0).min(20.0);
+    }
+
+    /// Returns the tiles the brush would paint if the user clicked right now.
+    pub fn get_brush_footprint(&self) -> Vec<BoardCoordinate> {
+        match self.mouse.into_board_coordinate(
+            self.get_precise_x(),
+            self.get_precise_y(),
+            self.get_tile_size(),
+            self.board.get_board_size(),
+        ) {
+            Some(center) => self
+                .brush
+                .get_footprint(center, self.board.get_board_size()),
+            Option::None => Vec::new(),
+        }
+    }
+
+    fn paint_terrain(&mut self) {
+        let tool = match self.terraform {
+            Some(ref tool) => tool.clone(),
+            Option::None => return,
+        };
+
+        if let Some(center) = self.mouse.into_board_coordinate(
+            self.get_precise_x(),
+            self.get_precise_y(),
+            self.get_tile_size(),
+            self.board.get_board_size(),
+        ) {
+            self.edit_history
+                .paint(&mut self.board.terrain, &self.brush, center, |tile| {
+                    tool.paint(tile)
+                });
+        }
+    }
+
+    pub fn undo_terrain_edit(&mut self) {
+        self.edit_history.undo(&mut self.board.terrain);
+    }
+
+    pub fn redo_terrain_edit(&mut self) {
+        self.edit_history.redo(&mut self.board.terrain);
+    }
+
+    pub fn toggle_species_colors(&mut self) {
+        self.show_species = !self.show_species;
+    }
+
+    /// Starts following the lineage of the selected creature, or stops following a lineage.
+    pub fn toggle_lineage(&mut self) {
+        if self.lineage.is_some() {
+            self.lineage = Option::None;
+            return;
+        }
+
+        let founder = match self.board.selected_creature.get_primary() {
+            Some(id) => id,
+            Option::None => return,
+        };
+
+        self.lineage = Some(LineageTracker::new(&mut self.board, founder));
+    }
+
+    /// Keeps the lineage up to date, call this after every update of the board.
+    pub fn update_lineage(&mut self) -> Option<SuccessionEvent> {
+        match self.lineage {
+            Some(ref mut lineage) => lineage.update(&mut self.board),
+            Option::None => Option::None,
+        }
+    }
+
+    /// Writes the weights of the selected creature's brain to `brain_<name>_<id>.npz`, returns the file name.
+    pub fn export_selected_brain(&self) -> Result<Option<String>, Box<std::error::Error>> {
+        use lib_evolvim::brain::export::write_npz;
+        use lib_evolvim::brain::ExportWeights;
+
+        let c_rc = match self.board.get_selected_creature() {
+            Some(c) => c,
+            Option::None => return Ok(Option::None),
+        };
+        let c = c_rc.borrow();
+
+        let path = format!("brain_{}_{}.npz", c.get_name(), c.get_id());
+        write_npz(&c.brain.get_arrays(), std::fs::File::create(&path)?)?;
+
+        Ok(Some(path))
+    }
+
+    /// Shows `replay` instead of the board from now on.
+    pub fn start_replay(&mut self, replay: Replayer<BufReader<File>>) {
+        let (width, height) = replay.get_current().get_board_size();
+        self.max_x = width;
+        self.max_y = height;
+
+        self.replay = Some(replay);
+    }
+
+    /// Plays the next frame of the replay, returns false once it's over.
+    pub fn step_replay(&mut self) -> Result<bool, EvolvimError> {
+        match self.replay {
+            Some(ref mut replay) => Ok(replay.next_frame()?.is_some()),
+            Option::None => Ok(false),
+        }
+    }
+
+    pub fn toggle_sensor_view(&mut self) {
+        self.show_sensors = !self.show_sensors;
+    }
+
     pub fn switch_display_mode(&mut self) {
         use self::DisplayMode::*;
 
@@ -126,6 +394,11 @@ impl View {
         return self.tile_width;
     }
 
+    /// Returns the width and height of the area the board is drawn on, in pixels.
+    pub fn get_window_size(&self) -> (f64, f64) {
+        self.window_size
+    }
+
     pub fn get_x(&self) -> usize {
         return self.precise_x.floor().max(0.0) as usize;
     }
@@ -171,23 +444,32 @@ impl View {
 
 impl View {
     pub fn prepare_for_drawing(&mut self) {
+        if let Some(ref replay) = self.replay {
+            let snapshot = replay.get_current();
+            let selected = snapshot
+                .get_selected()
+                .and_then(|id| snapshot.get_creature_by_id(id))
+                .map(|c| (c.px, c.py));
+
+            if let Some(pos) = selected {
+                let tw = self.tiles_on_width;
+                let th = self.tiles_on_height;
+
+                self.set_precise_x(pos.0 - tw as f64 * 0.5);
+                self.set_precise_y(pos.1 - th as f64 * 0.5);
+            }
+
+            return;
+        }
+
         if self.mode == DisplayMode::Normal || self.mode == DisplayMode::Tiles {
-            let time = self.board.get_time();
             let x_range = self.get_x_range();
             let y_range = self.get_y_range();
 
-            self.board
-                .terrain
-                .update_all_at(time, &self.board.climate, x_range, y_range);
-            // self.board.terrain.update_all(time, &self.board.climate);
-
-            if self.board.selected_creature.0.is_some() {
-                let pos = {
-                    let c = &self.board.selected_creature.0.as_ref().unwrap();
-                    let c = c.borrow();
+            self.board.prepare_for_drawing_at(x_range, y_range);
 
-                    c.get_position()
-                };
+            if let Some(c) = self.board.get_selected_creature() {
+                let pos = c.borrow().get_position();
 
                 let tw = self.tiles_on_width;
                 let th = self.tiles_on_height;
@@ -206,6 +488,20 @@ impl View {
     {
         use self::DisplayMode::*;
 
+        if let Some(ref replay) = self.replay {
+            if self.mode != None {
+                draw_snapshot(
+                    replay.get_current(),
+                    self.mode == Normal,
+                    context,
+                    graphics,
+                    &self,
+                );
+            }
+
+            return;
+        }
+
         match self.mode {
             Normal => {
                 draw_terrain(&self.board.terrain, context, graphics, glyphs, &self);
@@ -213,18 +509,44 @@ impl View {
                 let y_range = self.get_y_range();
                 let x_range = self.get_x_range();
 
+                // Eggs lie on the ground, below the creatures.
+                for egg in self
+                    .board
+                    .soft_bodies_in_positions
+                    .get_eggs_in(x_range.clone(), y_range.clone())
+                {
+                    draw_egg(&egg, context, graphics, &self);
+                }
+
                 for c in self
                     .board
                     .soft_bodies_in_positions
                     .get_soft_bodies_in(x_range, y_range)
                 {
-                    draw_creature(&c.borrow(), context, graphics, &self);
+                    let creature = c.borrow();
+
+                    if let Some(ref lineage) = self.lineage {
+                        if lineage.is_member(creature.get_id()) {
+                            draw_lineage_highlight(&creature, context, graphics, &self);
+                        }
+                    }
+
+                    if self.board.selected_creature.is_selected(creature.get_id()) {
+                        draw_selection_highlight(&creature, context, graphics, &self);
+                    }
+
+                    draw_creature(&creature, context, graphics, &self);
                 }
 
-                if let Some(ref c) = self.board.selected_creature.0 {
+                if let Some(c) = self.board.get_selected_creature() {
                     let creature = c.borrow();
 
                     draw_details_creature(&creature, context, graphics, glyphs, &self);
+
+                    if self.show_sensors {
+                        let readings = self.board.get_sensor_readings(&c);
+                        draw_sensor_view(&readings, context, graphics, glyphs, &self);
+                    }
                 }
             }
             Tiles => {
@@ -232,6 +554,66 @@ impl View {
             }
             None => {}
         }
+
+        if self.browser.open && self.mode != None {
+            self.browser.draw(
+                &self.board,
+                self.get_window_size(),
+                context,
+                graphics,
+                glyphs,
+            );
+        }
+
+        if let Some(ref tool) = self.terraform {
+            if self.mode != None {
+                draw_brush_preview(
+                    &self.get_brush_footprint(),
+                    tool.get_preview_color(),
+                    context,
+                    graphics,
+                    &self,
+                );
+            }
+        }
+    }
+}
+
+/// The different ways the user can change the terrain.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TerraformTool {
+    /// Floods tiles.
+    Water,
+    /// Turns tiles into land without any fertility.
+    Barren,
+    /// Turns tiles into fully fertile land.
+    Fertile,
+    /// Turns tiles into obstacles creatures can't enter.
+    Obstacle,
+}
+
+impl TerraformTool {
+    /// Returns the new tile that should replace `tile`.
+    pub fn paint(&self, tile: &Tile) -> Tile {
+        use self::TerraformTool::*;
+
+        match self {
+            Water => Tile::new_water(1.0),
+            Barren => Tile::new(0.0, tile.get_food_type()),
+            Fertile => Tile::new(1.0, tile.get_food_type()),
+            Obstacle => Tile::Obstacle,
+        }
+    }
+
+    pub fn get_preview_color(&self) -> Color {
+        use self::TerraformTool::*;
+
+        match self {
+            Water => [0.0, 0.0, 1.0, 0.4],
+            Barren => [1.0, 1.0, 1.0, 0.4],
+            Fertile => [0.0, 1.0, 0.0, 0.4],
+            Obstacle => [0.5, 0.5, 0.5, 0.4],
+        }
     }
 }
 
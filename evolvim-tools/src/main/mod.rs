@@ -4,9 +4,10 @@ extern crate piston_window;
 
 mod graphics;
 
-use self::graphics::View;
+use self::graphics::{Gamepad, TouchPhase, TouchTracker, View};
 use clap::{App, Arg};
-use lib_evolvim::Board;
+use lib_evolvim::replay::{Recorder, Replayer};
+use lib_evolvim::{Board, Simulation};
 use piston_window::*;
 
 // type BrainType = lib_evolvim::neat::NeatBrain;
@@ -41,6 +42,21 @@ fn main() {
                 .requires("input")
                 .help("Saves to the input file when done"),
         )
+        .arg(
+            Arg::with_name("record")
+                .long("record")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Record everything that happens to FILE so it can be watched again with --replay"),
+        )
+        .arg(
+            Arg::with_name("replay")
+                .long("replay")
+                .value_name("FILE")
+                .takes_value(true)
+                .conflicts_with_all(&["input", "output", "record"])
+                .help("Watch a run recorded with --record instead of simulating"),
+        )
         .get_matches();
 
     let mut view = View::default();
@@ -58,7 +74,16 @@ fn main() {
     view.board.update(0.001);
     view.board.terrain.update_all(time, &view.board.climate);
 
-    let mut playspeed = 1;
+    if let Some(filename) = matches.value_of("replay") {
+        view.start_replay(Replayer::open(filename).unwrap());
+    }
+    let mut recorder = matches
+        .value_of("record")
+        .map(|filename| Recorder::create(filename, &view.board).unwrap());
+
+    let mut simulation = Simulation::default();
+    let mut touch = TouchTracker::default();
+    let mut gamepad = Gamepad::default();
 
     let mut window: PistonWindow = WindowSettings::new("Hello Piston!", [1000, 900])
         .exit_on_esc(true)
@@ -74,9 +99,51 @@ fn main() {
 
     while let Some(event) = window.next() {
         // Render
-        event.update(|_args| {
-            for _i in 0..playspeed {
-                view.board.update(0.001);
+        event.update(|args| {
+            for _i in 0..simulation.take_updates(args.dt) {
+                if view.replay.is_some() {
+                    match view.step_replay() {
+                        Ok(true) => continue,
+                        Ok(false) => {}
+                        Err(e) => eprintln!("Could not play the replay: {}", e),
+                    }
+                    // The replay is over, stop here.
+                    simulation.set_speed(0.0);
+                    break;
+                }
+
+                if view.board.is_paused() {
+                    break;
+                }
+
+                view.board.update(simulation.get_time_step());
+                if let Some(ref mut recorder) = recorder {
+                    recorder.record(&view.board).unwrap();
+                }
+
+                if let Some(event) = view.update_lineage() {
+                    let name = |id| match view.board.get_name_of(id) {
+                        Some(name) => format!("{} ({})", name, id),
+                        None => id.to_string(),
+                    };
+                    match event.successor {
+                        Some(id) => println!(
+                            "Year {:.3}: {} died, following {} now.",
+                            event.time,
+                            name(event.deceased),
+                            name(id)
+                        ),
+                        None => println!(
+                            "Year {:.3}: {} died, the lineage died out with it.",
+                            event.time,
+                            name(event.deceased)
+                        ),
+                    }
+                }
+            }
+
+            for gesture in gamepad.get_gestures(args.dt, view.get_window_size()) {
+                view.apply_gesture(gesture);
             }
         });
 
@@ -91,24 +158,64 @@ fn main() {
         // Match some events
         event.mouse_relative(|x, y| view.on_mouse_move(x, y));
         event.mouse_cursor(|x, y| view.update_mouse(x, y));
+        event.mouse_scroll(|x, y| view.on_mouse_scroll(x, y));
+
+        // Touch screens and gamepads
+        event.touch(|args| {
+            let phase = match args.touch {
+                Touch::Start => TouchPhase::Start,
+                Touch::Move => TouchPhase::Move,
+                Touch::End => TouchPhase::End,
+                Touch::Cancel => TouchPhase::Cancel,
+            };
+            // Touch positions are normalized to [0, 1].
+            let (width, height) = view.get_window_size();
+            let pos = (args.x * width, args.y * height);
+
+            if let Some(gesture) = touch.on_touch(args.id, pos, phase) {
+                view.apply_gesture(gesture);
+            }
+        });
+        event.controller_axis(|args| gamepad.on_axis(args.axis, args.position));
+
+        if let Some(Button::Keyboard(Key::LShift)) = event.release_args() {
+            view.multi_select = false;
+        }
 
         // Match some button presses
         if let Some(button) = event.press_args() {
-            use Button::Keyboard;
+            use Button::{Controller, Keyboard};
 
             match button {
-                Keyboard(Key::Up) => {
-                    if playspeed > 0 {
-                        playspeed *= 2;
-                    } else {
-                        playspeed = 1;
+                Controller(c) => {
+                    if let Some(gesture) = gamepad.on_button(c.button, view.get_window_size()) {
+                        view.apply_gesture(gesture);
                     }
                 }
+                Keyboard(Key::Up) => {
+                    let speed = simulation.get_speed();
+                    simulation.set_speed(if speed > 0.0 { speed * 2.0 } else { 1.0 });
+                }
                 Keyboard(Key::Down) => {
-                    if playspeed > 1 {
-                        playspeed /= 2;
+                    let speed = simulation.get_speed();
+                    simulation.set_speed(if speed > 1.0 { speed / 2.0 } else { 0.0 });
+                }
+                Keyboard(Key::LShift) => {
+                    view.multi_select = true;
+                }
+                Keyboard(Key::Space) => {
+                    if view.board.is_paused() {
+                        view.board.resume();
                     } else {
-                        playspeed = 0;
+                        view.board.pause();
+                    }
+                }
+                Keyboard(Key::N) => {
+                    if view.board.is_paused() && view.replay.is_none() {
+                        view.board.step_once(simulation.get_time_step());
+                        if let Some(ref mut recorder) = recorder {
+                            recorder.record(&view.board).unwrap();
+                        }
                     }
                 }
                 Keyboard(Key::D) => {
@@ -123,6 +230,44 @@ fn main() {
                 Keyboard(Key::Q) => {
                     view.board.selected_creature.deselect();
                 }
+                Keyboard(Key::P) => {
+                    view.browser.toggle();
+                }
+                Keyboard(Key::M) => {
+                    view.browser.next_metric();
+                }
+                Keyboard(Key::G) => {
+                    view.board.select_best(view.browser.metric);
+                }
+                Keyboard(Key::C) => {
+                    view.toggle_sensor_view();
+                }
+                Keyboard(Key::L) => {
+                    view.toggle_lineage();
+                }
+                Keyboard(Key::V) => {
+                    view.toggle_species_colors();
+                }
+                Keyboard(Key::E) => match view.export_selected_brain() {
+                    Ok(Some(path)) => println!("Exported the brain to {}.", path),
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Could not export the brain: {}", e),
+                },
+                Keyboard(Key::T) => {
+                    view.switch_terraform_tool();
+                }
+                Keyboard(Key::LeftBracket) => {
+                    view.change_brush_radius(-1.0);
+                }
+                Keyboard(Key::RightBracket) => {
+                    view.change_brush_radius(1.0);
+                }
+                Keyboard(Key::Z) => {
+                    view.undo_terrain_edit();
+                }
+                Keyboard(Key::Y) => {
+                    view.redo_terrain_edit();
+                }
                 // Keyboard(Key::S) => {
                 //     view.board.save_to("test.bin").unwrap();
                 // }
@@ -151,12 +296,29 @@ fn main() {
             }
         }
 
-        window.set_title(format!(
-            "Population size: {}, year: {:.2}, season: {}.",
-            view.board.get_population_size(),
-            view.board.get_time(),
-            view.board.get_season()
-        ));
+        match view.replay {
+            Some(ref replay) => window.set_title(format!(
+                "Replay, population size: {}, year: {:.2}, frame: {}.",
+                replay.get_current().get_population(),
+                replay.get_current().get_time(),
+                replay.get_frame()
+            )),
+            None => window.set_title(format!(
+                "Population size: {}, year: {:.3}, season: {}{}.",
+                view.board.get_population_size(),
+                view.board.get_time(),
+                view.board.get_season(),
+                if view.board.is_paused() {
+                    " (paused)"
+                } else {
+                    ""
+                }
+            )),
+        }
+    }
+
+    if let Some(recorder) = recorder {
+        recorder.finish().unwrap();
     }
 
     if let Some(filename) = output_file {